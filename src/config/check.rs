@@ -0,0 +1,132 @@
+use std::path::Path;
+
+use super::{resolve_default_tab, theme::UiConfig, ConfigFile, ImageMethodFile, Tabs};
+use crate::shared::dependencies::UEBERZUGPP;
+
+/// Loads the config at `path` and returns a list of human readable problems found in it. An empty list means the
+/// config is valid.
+#[allow(dead_code)]
+pub fn check_file(path: &Path) -> Vec<String> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(err) => return vec![format!("Failed to read config file '{}': {err}", path.display())],
+    };
+    let config_dir = path.parent().unwrap_or(Path::new("."));
+
+    check_str(&content, config_dir)
+}
+
+fn check_str(content: &str, config_dir: &Path) -> Vec<String> {
+    match ron::de::from_str::<ConfigFile>(content) {
+        Ok(config) => config.check(config_dir),
+        Err(err) => vec![format!("Failed to parse config: {err}")],
+    }
+}
+
+impl ConfigFile {
+    /// Validates everything that can fail when converting the config, unlike [`ConfigFile::into_config`] all
+    /// problems are reported instead of only the first one
+    pub fn check(&self, config_dir: &Path) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if let Err(err) = self.read_theme(config_dir).and_then(UiConfig::try_from) {
+            problems.push(format!("Invalid theme: {err:#}"));
+        }
+
+        match Tabs::try_from(self.tabs.clone()) {
+            Ok(tabs) => {
+                if let Some(Err(err)) = self.default_tab.as_ref().map(|name| resolve_default_tab(name, &tabs)) {
+                    problems.push(format!("Invalid default_tab: {err:#}"));
+                }
+            }
+            Err(err) => problems.push(format!("Invalid tabs: {err:#}")),
+        }
+
+        problems.extend(
+            self.keybinds
+                .conflicts
+                .iter()
+                .map(|conflict| format!("Keybind conflict: {conflict}")),
+        );
+
+        let image_method = self.image_method.unwrap_or(self.album_art.method);
+        if matches!(
+            image_method,
+            ImageMethodFile::UeberzugWayland | ImageMethodFile::UeberzugX11
+        ) && !UEBERZUGPP.installed
+        {
+            problems.push(format!(
+                "Image method {image_method:?} requires ueberzugpp which was not found"
+            ));
+        }
+
+        problems
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::check_str;
+
+    #[test]
+    fn default_config_is_valid() {
+        let problems = check_str("()", Path::new("/nonexistent"));
+
+        assert!(problems.is_empty(), "{problems:?}");
+    }
+
+    #[test]
+    fn unknown_keybind_is_reported() {
+        let problems = check_str(r#"(keybinds: (global: {"<X-q>": Quit}))"#, Path::new("/nonexistent"));
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].contains("Invalid key"), "{problems:?}");
+    }
+
+    #[test]
+    fn duplicate_keybind_is_reported() {
+        let problems = check_str(
+            r#"(keybinds: (global: {"q": Quit, "q": Stop}))"#,
+            Path::new("/nonexistent"),
+        );
+
+        assert_eq!(
+            problems,
+            vec![
+                "Keybind conflict: Key 'q' in global keybinds is bound to both Quit and Stop, only Stop will be used"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_default_tab_is_reported() {
+        let problems = check_str(r#"(default_tab: Some("Nope"))"#, Path::new("/nonexistent"));
+
+        assert_eq!(
+            problems,
+            vec!["Invalid default_tab: Default tab 'Nope' is not one of the configured tabs".to_string()]
+        );
+    }
+
+    #[test]
+    fn unknown_color_is_reported() {
+        let dir = std::env::temp_dir().join(format!("rmpc_config_check_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("themes")).unwrap();
+        let theme = include_str!("../../docs/src/content/docs/next/assets/example_theme.ron").replacen(
+            "background_color: None",
+            r#"background_color: Some("notacolor")"#,
+            1,
+        );
+        std::fs::write(dir.join("themes").join("bad.ron"), theme).unwrap();
+
+        let problems = check_str(r#"(theme: Some("bad"))"#, &dir);
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(problems.len(), 1);
+        assert!(problems[0].starts_with("Invalid theme"), "{problems:?}");
+        assert!(problems[0].contains("notacolor"), "{problems:?}");
+    }
+}
@@ -23,12 +23,16 @@ pub enum Command {
         /// If provided, print the current config instead of the default one.
         #[arg(short, long, default_value = "false")]
         current: bool,
+        #[command(subcommand)]
+        action: Option<ConfigCommand>,
     },
     /// Prints the default theme. Can be used to bootstrap your theme file.
     Theme {
         /// If provided, print the current theme instead of the default one.
         #[arg(short, long, default_value = "false")]
         current: bool,
+        #[command(subcommand)]
+        action: Option<ThemeCommand>,
     },
     /// Index the lyrics dir and display result, meant only for debugging purposes
     LyricsIndex,
@@ -124,13 +128,20 @@ pub enum Command {
     /// List MPD decoder plugins
     Decoders,
     /// Prints various information like the playback status
-    Status,
+    Status {
+        /// Format of the printed status
+        #[arg(short, long, value_enum, default_value_t)]
+        output: OutputFormat,
+    },
     /// Prints info about the current song.
     /// If --path specified, prints information about the song at the given path instead.
     /// If --path is specified multiple times, prints an array containing all the songs.
     Song {
         #[arg(short, long)]
         path: Option<Vec<String>>,
+        /// Format of the printed song
+        #[arg(short, long, value_enum, default_value_t)]
+        output: OutputFormat,
     },
     /// Mounts supported storage to MPD
     Mount { name: String, path: String },
@@ -140,6 +151,24 @@ pub enum Command {
     ListMounts,
 }
 
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+#[clap(rename_all = "lower")]
+pub enum ConfigCommand {
+    /// Validates the config file and its theme and prints all problems found. Exits with a non-zero code if
+    /// the config is invalid.
+    Check,
+}
+
+#[derive(Subcommand, Clone, Debug, PartialEq)]
+#[clap(rename_all = "lower")]
+pub enum ThemeCommand {
+    /// Renders a preview of the given theme file with made up songs and exits. Does not connect to MPD.
+    Preview {
+        /// Path to the theme file
+        file: PathBuf,
+    },
+}
+
 #[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
 pub enum OnOff {
     /// Enable
@@ -148,6 +177,15 @@ pub enum OnOff {
     Off,
 }
 
+#[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq, Default)]
+pub enum OutputFormat {
+    /// Structured JSON, meant for scripts
+    #[default]
+    Json,
+    /// Single human readable line
+    Text,
+}
+
 #[derive(Parser, ValueEnum, Copy, Clone, Debug, PartialEq)]
 pub enum OnOffOneshot {
     /// Enable
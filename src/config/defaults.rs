@@ -1,8 +1,15 @@
 #![allow(dead_code)]
+use super::IdleSubsystemFile;
+
 pub fn default_column_widths() -> Vec<u16> {
     vec![20, 38, 42]
 }
 
+#[allow(clippy::unnecessary_wraps)]
+pub fn default_tag_editor() -> Option<Vec<String>> {
+    Some(vec!["kid3".to_owned()])
+}
+
 pub fn default_false() -> bool {
     false
 }
@@ -15,10 +22,55 @@ pub fn default_volume_step() -> u8 {
     5
 }
 
+/// Every subsystem rmpc reacts to, the rest only wakes it up for nothing
+pub fn default_idle_subsystems() -> Vec<IdleSubsystemFile> {
+    vec![
+        IdleSubsystemFile::Player,
+        IdleSubsystemFile::Mixer,
+        IdleSubsystemFile::Options,
+        IdleSubsystemFile::Playlist,
+        IdleSubsystemFile::Database,
+        IdleSubsystemFile::Update,
+        IdleSubsystemFile::StoredPlaylist,
+        IdleSubsystemFile::Output,
+        IdleSubsystemFile::Partition,
+        IdleSubsystemFile::Sticker,
+    ]
+}
+
+pub fn default_history_size() -> usize {
+    100
+}
+
+pub fn default_recently_added_days() -> u32 {
+    30
+}
+
+pub fn default_seek_step_secs() -> u32 {
+    5
+}
+
+pub fn default_seek_acceleration_window_ms() -> u64 {
+    500
+}
+
+/// Acceleration is opt in, a factor of 1 keeps the seek step constant
+pub fn default_seek_acceleration_factor() -> u32 {
+    1
+}
+
+pub fn default_seek_acceleration_max_step_secs() -> u32 {
+    60
+}
+
 pub fn default_scrolloff() -> usize {
     0
 }
 
+pub fn default_scroll_amount() -> usize {
+    1
+}
+
 #[allow(clippy::unnecessary_wraps)]
 pub fn default_progress_update_interval_ms() -> Option<u64> {
     Some(1000)
@@ -39,3 +91,7 @@ pub fn mpd_port() -> String {
 pub fn disabled_album_art_protos() -> Vec<String> {
     ["http://", "https://"].into_iter().map(|p| p.to_owned()).collect()
 }
+
+pub fn album_art_max_file_size_bytes() -> usize {
+    10 * 1024 * 1024
+}
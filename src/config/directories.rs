@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Default, Clone)]
+pub struct Directories {
+    pub confirm_on_dir: DirConfirmAction,
+    pub confirm_on_song: SongConfirmAction,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DirectoriesFile {
+    #[serde(default)]
+    pub confirm_on_dir: DirConfirmAction,
+    #[serde(default)]
+    pub confirm_on_song: SongConfirmAction,
+}
+
+/// What the `Confirm` action does on a directory in the Directories pane
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DirConfirmAction {
+    #[default]
+    Descend,
+    AddAndDescend,
+}
+
+/// What the `Confirm` action does on a song in the Directories pane
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum SongConfirmAction {
+    Add,
+    #[default]
+    AddAndPlay,
+    ReplaceAndPlay,
+}
+
+impl From<DirectoriesFile> for Directories {
+    fn from(value: DirectoriesFile) -> Self {
+        Self {
+            confirm_on_dir: value.confirm_on_dir,
+            confirm_on_song: value.confirm_on_song,
+        }
+    }
+}
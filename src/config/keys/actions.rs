@@ -14,18 +14,35 @@ pub enum GlobalAction {
     ShowCurrentSongInfo,
     ShowOutputs,
     ShowDecoders,
+    ShowPartitions,
+    ShowPlaybackSettings,
+    ShowHistory,
+    ShowFavorites,
+    ToggleFavorite,
+    CycleImageMethod,
+    ReloadAlbumArt,
+    ToggleBorders,
     NextTrack,
     PreviousTrack,
     Stop,
+    StopAndClear,
     ToggleRepeat,
+    ToggleRepeatOne,
+    RepeatQueue,
     ToggleSingle,
     ToggleRandom,
     ToggleConsume,
+    CycleReplayGainMode,
     TogglePause,
     VolumeUp,
     VolumeDown,
+    ToggleMute,
+    SetVolume,
+    TogglePreviewPlay,
+    PlayRandomSong,
     SeekForward,
     SeekBack,
+    SeekToPercent(u8),
     CommandMode,
     NextTab,
     PreviousTab,
@@ -47,18 +64,35 @@ pub enum GlobalActionFile {
     ShowCurrentSongInfo,
     ShowOutputs,
     ShowDecoders,
+    ShowPartitions,
+    ShowPlaybackSettings,
+    ShowHistory,
+    ShowFavorites,
+    ToggleFavorite,
+    CycleImageMethod,
+    ReloadAlbumArt,
+    ToggleBorders,
     NextTrack,
     PreviousTrack,
     Stop,
+    StopAndClear,
     ToggleRepeat,
+    ToggleRepeatOne,
+    RepeatQueue,
     ToggleSingle,
     ToggleRandom,
     ToggleConsume,
+    CycleReplayGainMode,
     TogglePause,
     VolumeUp,
     VolumeDown,
+    ToggleMute,
+    SetVolume,
+    TogglePreviewPlay,
+    PlayRandomSong,
     SeekForward,
     SeekBack,
+    SeekToPercent(u8),
     NextTab,
     PreviousTab,
     SwitchToTab(String),
@@ -85,6 +119,14 @@ impl From<GlobalActionFile> for GlobalAction {
             GlobalActionFile::Quit => GlobalAction::Quit,
             GlobalActionFile::ShowOutputs => GlobalAction::ShowOutputs,
             GlobalActionFile::ShowDecoders => GlobalAction::ShowDecoders,
+            GlobalActionFile::ShowPartitions => GlobalAction::ShowPartitions,
+            GlobalActionFile::ShowPlaybackSettings => GlobalAction::ShowPlaybackSettings,
+            GlobalActionFile::ShowHistory => GlobalAction::ShowHistory,
+            GlobalActionFile::ShowFavorites => GlobalAction::ShowFavorites,
+            GlobalActionFile::ToggleFavorite => GlobalAction::ToggleFavorite,
+            GlobalActionFile::CycleImageMethod => GlobalAction::CycleImageMethod,
+            GlobalActionFile::ReloadAlbumArt => GlobalAction::ReloadAlbumArt,
+            GlobalActionFile::ToggleBorders => GlobalAction::ToggleBorders,
             GlobalActionFile::ShowCurrentSongInfo => GlobalAction::ShowCurrentSongInfo,
             GlobalActionFile::CommandMode => GlobalAction::CommandMode,
             GlobalActionFile::Command { command, description } => GlobalAction::Command {
@@ -95,17 +137,26 @@ impl From<GlobalActionFile> for GlobalAction {
             GlobalActionFile::NextTrack => GlobalAction::NextTrack,
             GlobalActionFile::PreviousTrack => GlobalAction::PreviousTrack,
             GlobalActionFile::Stop => GlobalAction::Stop,
+            GlobalActionFile::StopAndClear => GlobalAction::StopAndClear,
             GlobalActionFile::ToggleRepeat => GlobalAction::ToggleRepeat,
+            GlobalActionFile::ToggleRepeatOne => GlobalAction::ToggleRepeatOne,
+            GlobalActionFile::RepeatQueue => GlobalAction::RepeatQueue,
             GlobalActionFile::ToggleRandom => GlobalAction::ToggleRandom,
             GlobalActionFile::ToggleSingle => GlobalAction::ToggleSingle,
             GlobalActionFile::TogglePause => GlobalAction::TogglePause,
             GlobalActionFile::SeekForward => GlobalAction::SeekForward,
             GlobalActionFile::SeekBack => GlobalAction::SeekBack,
+            GlobalActionFile::SeekToPercent(percent) => GlobalAction::SeekToPercent(percent.min(100)),
             GlobalActionFile::VolumeDown => GlobalAction::VolumeDown,
             GlobalActionFile::VolumeUp => GlobalAction::VolumeUp,
+            GlobalActionFile::ToggleMute => GlobalAction::ToggleMute,
+            GlobalActionFile::SetVolume => GlobalAction::SetVolume,
+            GlobalActionFile::TogglePreviewPlay => GlobalAction::TogglePreviewPlay,
+            GlobalActionFile::PlayRandomSong => GlobalAction::PlayRandomSong,
             GlobalActionFile::PreviousTab => GlobalAction::PreviousTab,
             GlobalActionFile::NextTab => GlobalAction::NextTab,
             GlobalActionFile::ToggleConsume => GlobalAction::ToggleConsume,
+            GlobalActionFile::CycleReplayGainMode => GlobalAction::CycleReplayGainMode,
             GlobalActionFile::SwitchToTab(name) => GlobalAction::SwitchToTab(name.into()),
             GlobalActionFile::QueueTab => GlobalAction::SwitchToTab("Queue".into()),
             GlobalActionFile::DirectoriesTab => GlobalAction::SwitchToTab("Directories".into()),
@@ -131,21 +182,40 @@ impl ToDescription for GlobalAction {
             GlobalAction::Quit => "Exit rmpc",
             GlobalAction::ShowOutputs => "Show MPD outputs config",
             GlobalAction::ShowDecoders => "Show MPD decoder plugins",
+            GlobalAction::ShowPartitions => "Show MPD partitions and switch between them",
+            GlobalAction::ShowPlaybackSettings => "Show and change crossfade and mixramp settings",
+            GlobalAction::ShowHistory => "Show songs played during this session and add them to the queue again",
+            GlobalAction::ShowFavorites => "Show songs marked as favorite and add them to the queue",
+            GlobalAction::ToggleFavorite => "Mark or unmark the selected song, or the current song, as favorite",
+            GlobalAction::CycleImageMethod => "Switch to the next album art rendering method, useful for debugging",
+            GlobalAction::ReloadAlbumArt => "Download the album art of the current song again, bypassing the cache",
+            GlobalAction::ToggleBorders => "Toggle drawing of borders for a less cluttered UI",
             GlobalAction::ShowCurrentSongInfo => "Show metadata of the currently playing song in a modal popup",
             GlobalAction::ToggleRepeat => "Toggle repeat",
+            GlobalAction::ToggleRepeatOne => {
+                "Loop the current song by turning on both repeat and single, or turn both off"
+            }
+            GlobalAction::RepeatQueue => "Play the queue a given number of times, then stop",
             GlobalAction::ToggleSingle => {
                 "Whether to stop playing after single track or repeat track/playlist when repeat is on"
             }
             GlobalAction::ToggleRandom => "Toggles random playback",
             GlobalAction::ToggleConsume => "Remove song from the queue after playing",
+            GlobalAction::CycleReplayGainMode => "Cycle replay gain mode between off, track, album and auto",
             GlobalAction::TogglePause => "Pause/Unpause playback",
-            GlobalAction::Stop => "Stop playback",
+            GlobalAction::Stop => "Stop playback and keep the queue",
+            GlobalAction::StopAndClear => "Stop playback and clear the queue",
             GlobalAction::VolumeUp => "Raise volume",
             GlobalAction::VolumeDown => "Lower volume",
+            GlobalAction::ToggleMute => "Mute, or restore the volume from before muting",
+            GlobalAction::SetVolume => "Set volume to an exact value",
+            GlobalAction::TogglePreviewPlay => "Toggle playing a short snippet of the selected song while browsing",
+            GlobalAction::PlayRandomSong => "Play a random song from the whole music database",
             GlobalAction::NextTrack => "Play next track in the queue",
             GlobalAction::PreviousTrack => "Play previous track in the queue",
             GlobalAction::SeekForward => "Seek currently playing track forwards",
             GlobalAction::SeekBack => "Seek currently playing track backwards",
+            GlobalAction::SeekToPercent(percent) => format!("Seek to {percent}% of the currently playing track").leak(),
             GlobalAction::NextTab => "Switch to next tab",
             GlobalAction::PreviousTab => "Switch to previous tab",
             GlobalAction::SwitchToTab(TabName("Queue")) => "Switch directly to Queue tab",
@@ -194,40 +264,58 @@ impl ToDescription for AlbumsActions {
 // Artists actions
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, Clone)]
-pub enum ArtistsActionsFile {}
+pub enum ArtistsActionsFile {
+    AddDiscography,
+}
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum ArtistsActions {}
+pub enum ArtistsActions {
+    AddDiscography,
+}
 
 impl ToDescription for ArtistsActions {
     fn to_description(&self) -> &str {
-        ""
+        match self {
+            ArtistsActions::AddDiscography => "Add all songs of the artist under cursor to the queue",
+        }
     }
 }
 
 impl From<ArtistsActionsFile> for ArtistsActions {
-    fn from(_value: ArtistsActionsFile) -> Self {
-        unreachable!()
+    fn from(value: ArtistsActionsFile) -> Self {
+        match value {
+            ArtistsActionsFile::AddDiscography => ArtistsActions::AddDiscography,
+        }
     }
 }
 
 // Directories actions
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, Clone)]
-pub enum DirectoriesActionsFile {}
+pub enum DirectoriesActionsFile {
+    AddFromCursor,
+}
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum DirectoriesActions {}
+pub enum DirectoriesActions {
+    AddFromCursor,
+}
 
 impl ToDescription for DirectoriesActions {
     fn to_description(&self) -> &str {
-        ""
+        match self {
+            DirectoriesActions::AddFromCursor => {
+                "Add the song under cursor and all songs after it in the current directory to the queue"
+            }
+        }
     }
 }
 
 impl From<DirectoriesActionsFile> for DirectoriesActions {
-    fn from(_value: DirectoriesActionsFile) -> Self {
-        unreachable!()
+    fn from(value: DirectoriesActionsFile) -> Self {
+        match value {
+            DirectoriesActionsFile::AddFromCursor => DirectoriesActions::AddFromCursor,
+        }
     }
 }
 
@@ -278,6 +366,19 @@ pub enum QueueActionsFile {
     AddToPlaylist,
     ShowInfo,
     JumpToCurrent,
+    JumpToNextAlbum,
+    JumpToPreviousAlbum,
+    EditTags,
+    MoveToTop,
+    MoveToBottom,
+    MoveUpBy(usize),
+    MoveDownBy(usize),
+    Swap,
+    ShuffleSelected,
+    Prioritize,
+    ToggleFollowPlayback,
+    CycleSort,
+    ToggleSortDirection,
 }
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
@@ -289,6 +390,19 @@ pub enum QueueActions {
     AddToPlaylist,
     ShowInfo,
     JumpToCurrent,
+    JumpToNextAlbum,
+    JumpToPreviousAlbum,
+    EditTags,
+    MoveToTop,
+    MoveToBottom,
+    MoveUpBy(usize),
+    MoveDownBy(usize),
+    Swap,
+    ShuffleSelected,
+    Prioritize,
+    ToggleFollowPlayback,
+    CycleSort,
+    ToggleSortDirection,
 }
 
 impl From<QueueActionsFile> for QueueActions {
@@ -301,6 +415,19 @@ impl From<QueueActionsFile> for QueueActions {
             QueueActionsFile::AddToPlaylist => QueueActions::AddToPlaylist,
             QueueActionsFile::ShowInfo => QueueActions::ShowInfo,
             QueueActionsFile::JumpToCurrent => QueueActions::JumpToCurrent,
+            QueueActionsFile::JumpToNextAlbum => QueueActions::JumpToNextAlbum,
+            QueueActionsFile::JumpToPreviousAlbum => QueueActions::JumpToPreviousAlbum,
+            QueueActionsFile::EditTags => QueueActions::EditTags,
+            QueueActionsFile::MoveToTop => QueueActions::MoveToTop,
+            QueueActionsFile::MoveToBottom => QueueActions::MoveToBottom,
+            QueueActionsFile::MoveUpBy(count) => QueueActions::MoveUpBy(count),
+            QueueActionsFile::MoveDownBy(count) => QueueActions::MoveDownBy(count),
+            QueueActionsFile::Swap => QueueActions::Swap,
+            QueueActionsFile::ShuffleSelected => QueueActions::ShuffleSelected,
+            QueueActionsFile::Prioritize => QueueActions::Prioritize,
+            QueueActionsFile::ToggleFollowPlayback => QueueActions::ToggleFollowPlayback,
+            QueueActionsFile::CycleSort => QueueActions::CycleSort,
+            QueueActionsFile::ToggleSortDirection => QueueActions::ToggleSortDirection,
         }
     }
 }
@@ -315,6 +442,27 @@ impl ToDescription for QueueActions {
             QueueActions::AddToPlaylist => "Add song under cursor to an existing playlist",
             QueueActions::ShowInfo => "Show metadata of the song under cursor in a modal popup",
             QueueActions::JumpToCurrent => "Moves the cursor in Queue table to the currently playing song",
+            QueueActions::JumpToNextAlbum => "Move the cursor to the first song of the next album in the queue",
+            QueueActions::JumpToPreviousAlbum => {
+                "Move the cursor to the first song of the album under cursor, or of the previous album"
+            }
+            QueueActions::EditTags => "Override a tag of the song under cursor for as long as it is queued",
+            QueueActions::MoveToTop => "Move song under cursor or selected songs to the top of the queue",
+            QueueActions::MoveToBottom => "Move song under cursor or selected songs to the bottom of the queue",
+            QueueActions::MoveUpBy(_) => "Move song under cursor up by the given number of positions",
+            QueueActions::MoveDownBy(_) => "Move song under cursor down by the given number of positions",
+            QueueActions::Swap => "Swap positions of the two selected songs",
+            QueueActions::ShuffleSelected => "Shuffle the selected songs, the selection has to be contiguous",
+            QueueActions::Prioritize => {
+                "Toggle highest priority of the song under cursor or selected songs, they are played first in random mode"
+            }
+            QueueActions::ToggleFollowPlayback => {
+                "Toggle keeping the currently playing song in view as playback advances"
+            }
+            QueueActions::CycleSort => {
+                "Cycle the column the queue is displayed sorted by, the queue itself is not reordered"
+            }
+            QueueActions::ToggleSortDirection => "Toggle between ascending and descending sort of the queue",
         }
     }
 }
@@ -335,6 +483,8 @@ pub enum CommonActionFile {
     MoveUp,
     DownHalf,
     UpHalf,
+    PageDown,
+    PageUp,
     Top,
     Bottom,
     EnterSearch,
@@ -342,6 +492,7 @@ pub enum CommonActionFile {
     PreviousResult,
     Select,
     InvertSelection,
+    SelectAll,
     Add,
     Delete,
     Rename,
@@ -349,6 +500,12 @@ pub enum CommonActionFile {
     Confirm,
     FocusInput,
     AddAll,
+    CopyFilePath,
+    OpenInTagEditor,
+    GoToParent,
+    GoToRoot,
+    IncreaseRating,
+    DecreaseRating,
 }
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
@@ -365,6 +522,8 @@ pub enum CommonAction {
     MoveUp,
     DownHalf,
     UpHalf,
+    PageDown,
+    PageUp,
     Top,
     Bottom,
     EnterSearch,
@@ -372,6 +531,7 @@ pub enum CommonAction {
     PreviousResult,
     Select,
     InvertSelection,
+    SelectAll,
     Add,
     Delete,
     Rename,
@@ -379,6 +539,12 @@ pub enum CommonAction {
     Confirm,
     FocusInput,
     AddAll,
+    CopyFilePath,
+    OpenInTagEditor,
+    GoToParent,
+    GoToRoot,
+    IncreaseRating,
+    DecreaseRating,
 }
 
 impl ToDescription for CommonAction {
@@ -388,6 +554,8 @@ impl ToDescription for CommonAction {
             CommonAction::Down => "Go down",
             CommonAction::UpHalf => "Jump by half a screen up",
             CommonAction::DownHalf => "Jump by half a screen down",
+            CommonAction::PageUp => "Jump by a full screen up",
+            CommonAction::PageDown => "Jump by a full screen down",
             CommonAction::MoveUp => "Move current item up, for example song in a queue",
             CommonAction::MoveDown => "Move current item down, for example song in a queue",
             CommonAction::Right => "Go right",
@@ -399,8 +567,15 @@ impl ToDescription for CommonAction {
             CommonAction::PreviousResult => "When a filter is active, jump to the previous result",
             CommonAction::Select => "Mark current item as selected in the browser, useful for example when you want to add multiple songs to a playlist",
             CommonAction::InvertSelection => "Inverts the current selected items",
+            CommonAction::SelectAll => "Select all items, or all items matching the current filter. Unselects them if they are all selected already",
             CommonAction::Add => "Add item to queue",
             CommonAction::AddAll => "Add all items to queue",
+            CommonAction::CopyFilePath => "Copy path of the song under cursor to clipboard",
+            CommonAction::OpenInTagEditor => "Open the song under cursor in the configured tag editor",
+            CommonAction::GoToParent => "Go up one level in the browsers",
+            CommonAction::GoToRoot => "Go all the way up to the top level in the browsers",
+            CommonAction::IncreaseRating => "Increase the rating of the song under cursor by one star",
+            CommonAction::DecreaseRating => "Decrease the rating of the song under cursor by one star",
             CommonAction::Delete => "Delete. For example a playlist, song from a playlist or wipe the current queue",
             CommonAction::Rename => "Rename. Currently only for playlists",
             CommonAction::Close => "Close/Stop whatever action is currently going on. Cancel filter, close a modal, etc.",
@@ -421,6 +596,8 @@ impl From<CommonActionFile> for CommonAction {
             CommonActionFile::Down => CommonAction::Down,
             CommonActionFile::UpHalf => CommonAction::UpHalf,
             CommonActionFile::DownHalf => CommonAction::DownHalf,
+            CommonActionFile::PageUp => CommonAction::PageUp,
+            CommonActionFile::PageDown => CommonAction::PageDown,
             CommonActionFile::MoveUp => CommonAction::MoveUp,
             CommonActionFile::MoveDown => CommonAction::MoveDown,
             CommonActionFile::Right => CommonAction::Right,
@@ -432,6 +609,7 @@ impl From<CommonActionFile> for CommonAction {
             CommonActionFile::PreviousResult => CommonAction::PreviousResult,
             CommonActionFile::Select => CommonAction::Select,
             CommonActionFile::InvertSelection => CommonAction::InvertSelection,
+            CommonActionFile::SelectAll => CommonAction::SelectAll,
             CommonActionFile::Add => CommonAction::Add,
             CommonActionFile::Delete => CommonAction::Delete,
             CommonActionFile::Rename => CommonAction::Rename,
@@ -439,6 +617,12 @@ impl From<CommonActionFile> for CommonAction {
             CommonActionFile::Confirm => CommonAction::Confirm,
             CommonActionFile::FocusInput => CommonAction::FocusInput,
             CommonActionFile::AddAll => CommonAction::AddAll,
+            CommonActionFile::CopyFilePath => CommonAction::CopyFilePath,
+            CommonActionFile::OpenInTagEditor => CommonAction::OpenInTagEditor,
+            CommonActionFile::GoToParent => CommonAction::GoToParent,
+            CommonActionFile::GoToRoot => CommonAction::GoToRoot,
+            CommonActionFile::IncreaseRating => CommonAction::IncreaseRating,
+            CommonActionFile::DecreaseRating => CommonAction::DecreaseRating,
             CommonActionFile::PaneUp => CommonAction::PaneUp,
             CommonActionFile::PaneDown => CommonAction::PaneDown,
             CommonActionFile::PaneLeft => CommonAction::PaneLeft,
@@ -467,20 +651,52 @@ impl From<PlaylistsActionsFile> for PlaylistsActions {
     }
 }
 
+// Search actions
+
 #[derive(Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq, Hash, Clone)]
-pub enum SearchActionsFile {}
+pub enum SearchActionsFile {
+    SaveAsPlaylist,
+    ReplaceQueue,
+    FillTimeBudget,
+    RefineResults,
+    CopyMpdCommand,
+}
 
 #[derive(Debug, Display, PartialEq, Eq, Hash, Clone, Copy)]
-pub enum SearchActions {}
+pub enum SearchActions {
+    SaveAsPlaylist,
+    ReplaceQueue,
+    FillTimeBudget,
+    RefineResults,
+    CopyMpdCommand,
+}
 
 impl ToDescription for SearchActions {
     fn to_description(&self) -> &str {
-        ""
+        match self {
+            SearchActions::SaveAsPlaylist => "Save current search results as a playlist",
+            SearchActions::ReplaceQueue => "Replace the queue with all songs matching the current search",
+            SearchActions::FillTimeBudget => {
+                "Add random songs matching the current search, or the whole library when it is empty, which fit into the given number of minutes"
+            }
+            SearchActions::RefineResults => {
+                "Narrow the search results down to the ones matching the current filter, without a new search"
+            }
+            SearchActions::CopyMpdCommand => {
+                "Copy the raw MPD command of the current search, for debugging and scripting"
+            }
+        }
     }
 }
 
 impl From<SearchActionsFile> for SearchActions {
-    fn from(_value: SearchActionsFile) -> Self {
-        unreachable!()
+    fn from(value: SearchActionsFile) -> Self {
+        match value {
+            SearchActionsFile::SaveAsPlaylist => SearchActions::SaveAsPlaylist,
+            SearchActionsFile::ReplaceQueue => SearchActions::ReplaceQueue,
+            SearchActionsFile::FillTimeBudget => SearchActions::FillTimeBudget,
+            SearchActionsFile::RefineResults => SearchActions::RefineResults,
+            SearchActionsFile::CopyMpdCommand => SearchActions::CopyMpdCommand,
+        }
     }
 }
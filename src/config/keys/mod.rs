@@ -1,11 +1,12 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, fmt::Debug};
 
 use actions::{
     AlbumsActionsFile, ArtistsActionsFile, CommonActionFile, DirectoriesActionsFile, GlobalActionFile,
-    PlaylistsActionsFile, QueueActionsFile,
+    PlaylistsActionsFile, QueueActionsFile, SearchActionsFile,
 };
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, Map};
 
 #[cfg(debug_assertions)]
 pub use actions::LogsActions;
@@ -36,21 +37,114 @@ pub struct KeyConfig {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(from = "KeyConfigFileRaw")]
 pub struct KeyConfigFile {
     #[serde(default)]
     pub global: HashMap<Key, GlobalActionFile>,
     #[serde(default)]
     pub navigation: HashMap<Key, CommonActionFile>,
     // pub albums: HashMap<AlbumsActions, Vec<Key>>,
-    // pub artists: HashMap<ArtistsActions, Vec<Key>>,
-    // pub directories: HashMap<DirectoriesActions, Vec<Key>>,
+    #[serde(default)]
+    pub artists: HashMap<Key, ArtistsActionsFile>,
+    #[serde(default)]
+    pub directories: HashMap<Key, DirectoriesActionsFile>,
     // pub playlists: HashMap<PlaylistsActions, Vec<Key>>,
-    // pub search: HashMap<SearchActions, Vec<Key>>,
     #[cfg(debug_assertions)]
     #[serde(default)]
     pub logs: HashMap<Key, LogsActionsFile>,
     #[serde(default)]
     pub queue: HashMap<Key, QueueActionsFile>,
+    #[serde(default)]
+    pub search: HashMap<Key, SearchActionsFile>,
+    /// Keys which were bound to more than one action in the same scope. Only the last binding is kept.
+    #[serde(skip)]
+    pub conflicts: Vec<KeyConflict>,
+}
+
+/// Same as [`KeyConfigFile`] but keeps every binding, including duplicate keys, so that conflicts can be reported
+/// instead of silently letting the last binding win
+#[serde_as]
+#[derive(Deserialize)]
+struct KeyConfigFileRaw {
+    #[serde(default)]
+    #[serde_as(as = "Map<_, _>")]
+    global: Vec<(Key, GlobalActionFile)>,
+    #[serde(default)]
+    #[serde_as(as = "Map<_, _>")]
+    navigation: Vec<(Key, CommonActionFile)>,
+    #[serde(default)]
+    #[serde_as(as = "Map<_, _>")]
+    artists: Vec<(Key, ArtistsActionsFile)>,
+    #[serde(default)]
+    #[serde_as(as = "Map<_, _>")]
+    directories: Vec<(Key, DirectoriesActionsFile)>,
+    #[cfg(debug_assertions)]
+    #[serde(default)]
+    #[serde_as(as = "Map<_, _>")]
+    logs: Vec<(Key, LogsActionsFile)>,
+    #[serde(default)]
+    #[serde_as(as = "Map<_, _>")]
+    queue: Vec<(Key, QueueActionsFile)>,
+    #[serde(default)]
+    #[serde_as(as = "Map<_, _>")]
+    search: Vec<(Key, SearchActionsFile)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyConflict {
+    pub scope: &'static str,
+    pub key: Key,
+    pub first: String,
+    pub second: String,
+}
+
+impl std::fmt::Display for KeyConflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Key '{}' in {} keybinds is bound to both {} and {}, only {} will be used",
+            self.key, self.scope, self.first, self.second, self.second
+        )
+    }
+}
+
+fn collect_bindings<A: Debug + PartialEq>(
+    scope: &'static str,
+    bindings: Vec<(Key, A)>,
+    conflicts: &mut Vec<KeyConflict>,
+) -> HashMap<Key, A> {
+    let mut result = HashMap::with_capacity(bindings.len());
+    for (key, action) in bindings {
+        if let Some(previous) = result.get(&key) {
+            if *previous != action {
+                conflicts.push(KeyConflict {
+                    scope,
+                    key: key.clone(),
+                    first: format!("{previous:?}"),
+                    second: format!("{action:?}"),
+                });
+            }
+        }
+        result.insert(key, action);
+    }
+    result
+}
+
+impl From<KeyConfigFileRaw> for KeyConfigFile {
+    fn from(value: KeyConfigFileRaw) -> Self {
+        let mut conflicts = Vec::new();
+        Self {
+            global: collect_bindings("global", value.global, &mut conflicts),
+            navigation: collect_bindings("navigation", value.navigation, &mut conflicts),
+            artists: collect_bindings("artists", value.artists, &mut conflicts),
+            directories: collect_bindings("directories", value.directories, &mut conflicts),
+            #[cfg(debug_assertions)]
+            logs: collect_bindings("logs", value.logs, &mut conflicts),
+            queue: collect_bindings("queue", value.queue, &mut conflicts),
+            search: collect_bindings("search", value.search, &mut conflicts),
+            conflicts,
+        }
+    }
 }
 
 impl Default for KeyConfigFile {
@@ -68,6 +162,7 @@ impl Default for KeyConfigFile {
         #[cfg(debug_assertions)]
         use LogsActionsFile as L;
         use QueueActionsFile as Q;
+        use SearchActionsFile as S;
         Self {
             global: HashMap::from([
                 (Key { key: K::Char('q'), modifiers: M::NONE  }, G::Quit),
@@ -76,9 +171,11 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('I'), modifiers: M::SHIFT }, G::ShowCurrentSongInfo),
                 (Key { key: K::Char('O'), modifiers: M::SHIFT }, G::ShowOutputs),
                 (Key { key: K::Char('P'), modifiers: M::SHIFT }, G::ShowDecoders),
+                (Key { key: K::Char('H'), modifiers: M::SHIFT }, G::ShowHistory),
                 (Key { key: K::Char('>'), modifiers: M::NONE  }, G::NextTrack),
                 (Key { key: K::Char('<'), modifiers: M::NONE  }, G::PreviousTrack),
                 (Key { key: K::Char('s'), modifiers: M::NONE  }, G::Stop),
+                (Key { key: K::Char('S'), modifiers: M::SHIFT }, G::StopAndClear),
                 (Key { key: K::Char('z'), modifiers: M::NONE  }, G::ToggleRepeat),
                 (Key { key: K::Char('x'), modifiers: M::NONE  }, G::ToggleRandom),
                 (Key { key: K::Char('c'), modifiers: M::NONE  }, G::ToggleConsume),
@@ -88,6 +185,8 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('b'), modifiers: M::NONE  }, G::SeekBack),
                 (Key { key: K::Char(','), modifiers: M::NONE  }, G::VolumeDown),
                 (Key { key: K::Char('.'), modifiers: M::NONE  }, G::VolumeUp),
+                (Key { key: K::Char('m'), modifiers: M::NONE  }, G::ToggleMute),
+                (Key { key: K::Char('M'), modifiers: M::SHIFT }, G::SetVolume),
                 (Key { key: K::BackTab,   modifiers: M::SHIFT }, G::PreviousTab),
                 (Key { key: K::Tab,       modifiers: M::NONE  }, G::NextTab),
                 (Key { key: K::Char('1'), modifiers: M::NONE  }, G::SwitchToTab("Queue".to_string())),
@@ -107,6 +206,9 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Down,      modifiers: M::NONE    }, C::Down),
                 (Key { key: K::Right,     modifiers: M::NONE    }, C::Right),
                 (Key { key: K::Char('h'), modifiers: M::NONE    }, C::Left),
+                (Key { key: K::Backspace, modifiers: M::NONE    }, C::GoToParent),
+                (Key { key: K::Char(']'), modifiers: M::NONE    }, C::IncreaseRating),
+                (Key { key: K::Char('['), modifiers: M::NONE    }, C::DecreaseRating),
                 (Key { key: K::Char('k'), modifiers: M::CONTROL }, C::PaneUp),
                 (Key { key: K::Char('j'), modifiers: M::CONTROL }, C::PaneDown),
                 (Key { key: K::Char('l'), modifiers: M::CONTROL }, C::PaneRight),
@@ -117,13 +219,20 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('u'), modifiers: M::CONTROL }, C::UpHalf),
                 (Key { key: K::Char('G'), modifiers: M::SHIFT   }, C::Bottom),
                 (Key { key: K::Char('g'), modifiers: M::NONE    }, C::Top),
+                (Key { key: K::Home,      modifiers: M::NONE    }, C::Top),
+                (Key { key: K::End,       modifiers: M::NONE    }, C::Bottom),
+                (Key { key: K::PageUp,    modifiers: M::NONE    }, C::PageUp),
+                (Key { key: K::PageDown,  modifiers: M::NONE    }, C::PageDown),
                 (Key { key: K::Char('/'), modifiers: M::NONE    }, C::EnterSearch),
                 (Key { key: K::Char('n'), modifiers: M::NONE    }, C::NextResult),
                 (Key { key: K::Char('N'), modifiers: M::SHIFT   }, C::PreviousResult),
                 (Key { key: K::Char(' '), modifiers: M::NONE    }, C::Select),
                 (Key { key: K::Char(' '), modifiers: M::CONTROL }, C::InvertSelection),
+                (Key { key: K::Char('V'), modifiers: M::SHIFT   }, C::SelectAll),
                 (Key { key: K::Char('a'), modifiers: M::NONE    }, C::Add),
                 (Key { key: K::Char('A'), modifiers: M::SHIFT   }, C::AddAll),
+                (Key { key: K::Char('y'), modifiers: M::NONE    }, C::CopyFilePath),
+                (Key { key: K::Char('E'), modifiers: M::SHIFT   }, C::OpenInTagEditor),
                 (Key { key: K::Char('D'), modifiers: M::SHIFT   }, C::Delete),
                 (Key { key: K::Char('r'), modifiers: M::NONE    }, C::Rename),
                 (Key { key: K::Char('c'), modifiers: M::CONTROL }, C::Close),
@@ -133,10 +242,8 @@ impl Default for KeyConfigFile {
             ]),
             // albums: HashMap::from([
             // ]),
-            // artists: HashMap::from([
-            // ]),
-            // directories: HashMap::from([
-            // ]),
+            artists: HashMap::new(),
+            directories: HashMap::new(),
             // playlists: HashMap::from([
             // ]),
             #[cfg(debug_assertions)]
@@ -152,7 +259,23 @@ impl Default for KeyConfigFile {
                 (Key { key: K::Char('a'), modifiers: M::NONE    }, Q::AddToPlaylist),
                 (Key { key: K::Char('i'), modifiers: M::NONE    }, Q::ShowInfo),
                 (Key { key: K::Char('C'), modifiers: M::SHIFT   }, Q::JumpToCurrent),
+                (Key { key: K::Char('e'), modifiers: M::NONE    }, Q::EditTags),
+                (Key { key: K::Char('T'), modifiers: M::SHIFT   }, Q::MoveToTop),
+                (Key { key: K::Char('B'), modifiers: M::SHIFT   }, Q::MoveToBottom),
+                (Key { key: K::Char('X'), modifiers: M::SHIFT   }, Q::Swap),
+                (Key { key: K::Char('Z'), modifiers: M::SHIFT   }, Q::ShuffleSelected),
+                (Key { key: K::Char('F'), modifiers: M::SHIFT   }, Q::ToggleFollowPlayback),
+                (Key { key: K::Char('p'), modifiers: M::CONTROL }, Q::Prioritize),
+                (Key { key: K::Char('o'), modifiers: M::NONE    }, Q::CycleSort),
+                (Key { key: K::Char('R'), modifiers: M::SHIFT   }, Q::ToggleSortDirection),
+            ]),
+            search: HashMap::from([
+                (Key { key: K::Char('s'), modifiers: M::CONTROL }, S::SaveAsPlaylist),
+                (Key { key: K::Char('R'), modifiers: M::SHIFT   }, S::ReplaceQueue),
+                (Key { key: K::Char('T'), modifiers: M::SHIFT   }, S::FillTimeBudget),
+                (Key { key: K::Char('F'), modifiers: M::SHIFT   }, S::RefineResults),
             ]),
+            conflicts: Vec::new(),
         }
     }
 }
@@ -163,14 +286,12 @@ impl From<KeyConfigFile> for KeyConfig {
             global: value.global.into_iter().map(|(k, v)| (k, v.into())).collect(),
             navigation: value.navigation.into_iter().map(|(k, v)| (k, v.into())).collect(),
             // albums: invert_map(value.albums),
-            // artists: invert_map(value.artists),
-            // directories: invert_map(value.directories),
             // playlists: invert_map(value.playlists),
             albums: HashMap::new(),
-            artists: HashMap::new(),
-            directories: HashMap::new(),
+            artists: value.artists.into_iter().map(|(k, v)| (k, v.into())).collect(),
+            directories: value.directories.into_iter().map(|(k, v)| (k, v.into())).collect(),
             playlists: HashMap::new(),
-            search: HashMap::new(),
+            search: value.search.into_iter().map(|(k, v)| (k, v.into())).collect(),
             #[cfg(debug_assertions)]
             logs: value.logs.into_iter().map(|(k, v)| (k, v.into())).collect(),
             queue: value.queue.into_iter().map(|(k, v)| (k, v.into())).collect(),
@@ -202,8 +323,11 @@ mod tests {
     #[cfg(debug_assertions)]
     use crate::config::keys::LogsActionsFile;
     use crate::config::keys::{
-        actions::{CommonActionFile, GlobalActionFile, QueueActionsFile},
-        CommonAction, GlobalAction, QueueActions,
+        actions::{
+            ArtistsActionsFile, CommonActionFile, DirectoriesActionsFile, GlobalActionFile, QueueActionsFile,
+            SearchActionsFile,
+        },
+        ArtistsActions, CommonAction, DirectoriesActions, GlobalAction, QueueActions, SearchActions,
     };
 
     use super::{Key, KeyConfig, KeyConfigFile};
@@ -218,9 +342,11 @@ mod tests {
             logs: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, LogsActionsFile::Clear)]),
             queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActionsFile::Play),
                                   (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }, QueueActionsFile::Save)]),
+            search: HashMap::from([(Key { key: KeyCode::Char('s'), modifiers: KeyModifiers::CONTROL, }, SearchActionsFile::SaveAsPlaylist)]),
+            conflicts: Vec::new(),
             // albums: HashMap::from([]),
-            // artists: HashMap::from([]),
-            // directories: HashMap::from([]),
+            artists: HashMap::from([(Key { key: KeyCode::Char('d'), modifiers: KeyModifiers::NONE, }, ArtistsActionsFile::AddDiscography)]),
+            directories: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::SHIFT, }, DirectoriesActionsFile::AddFromCursor)]),
             // playlists: HashMap::from([]),
             navigation: HashMap::from([
                 (Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, CommonActionFile::Up),
@@ -234,10 +360,10 @@ mod tests {
             queue: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL, }, QueueActions::Play),
                                   (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT, }, QueueActions::Save)]),
             albums: HashMap::from([]),
-            artists: HashMap::from([]),
-            directories: HashMap::from([]),
+            artists: HashMap::from([(Key { key: KeyCode::Char('d'), modifiers: KeyModifiers::NONE, }, ArtistsActions::AddDiscography)]),
+            directories: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::SHIFT, }, DirectoriesActions::AddFromCursor)]),
             playlists: HashMap::from([]),
-            search: HashMap::from([]),
+            search: HashMap::from([(Key { key: KeyCode::Char('s'), modifiers: KeyModifiers::CONTROL, }, SearchActions::SaveAsPlaylist)]),
             navigation: HashMap::from([(Key { key: KeyCode::Char('a'), modifiers: KeyModifiers::CONTROL }, CommonAction::Up),
                                        (Key { key: KeyCode::Char('b'), modifiers: KeyModifiers::SHIFT }, CommonAction::Up)]),
         };
@@ -247,4 +373,24 @@ mod tests {
 
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn duplicate_binding_is_reported_as_conflict() {
+        let input = r#"(global: {"q": Quit, "s": Stop, "q": NextTrack}, queue: {"d": Delete, "d": Delete})"#;
+
+        let result: KeyConfigFile = ron::de::from_str(input).unwrap();
+
+        assert_eq!(result.conflicts.len(), 1);
+        assert_eq!(
+            result.conflicts[0].to_string(),
+            "Key 'q' in global keybinds is bound to both Quit and NextTrack, only NextTrack will be used"
+        );
+        assert_eq!(
+            result.global.get(&Key {
+                key: KeyCode::Char('q'),
+                modifiers: KeyModifiers::NONE
+            }),
+            Some(&GlobalActionFile::NextTrack)
+        );
+    }
 }
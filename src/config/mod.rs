@@ -7,23 +7,30 @@ use anyhow::Result;
 use artists::{Artists, ArtistsFile};
 use clap::Parser;
 use cli::{Args, OnOff, OnOffOneshot};
+use directories::{Directories, DirectoriesFile};
 use itertools::Itertools;
 use rustix::path::Arg;
-use search::SearchFile;
+use search::{FilterKindFile, SearchFile};
+use seek::{SeekAcceleration, SeekAccelerationFile};
 use serde::{Deserialize, Serialize};
 use strum::Display;
-use tabs::{Tabs, TabsFile};
+use tabs::{TabName, Tabs, TabsFile};
 use utils::tilde_expand;
 
 pub mod address;
 pub mod artists;
+pub mod check;
 pub mod cli;
 mod defaults;
+pub mod directories;
 pub mod keys;
-mod search;
+pub mod search;
+pub mod seek;
 pub mod tabs;
 pub mod theme;
 
+use crate::mpd::commands::IdleEvent;
+use crate::mpd::mpd_client::FilterKind;
 use crate::shared::image;
 use crate::shared::image::ImageProtocol;
 use crate::shared::macros::status_warn;
@@ -44,6 +51,7 @@ pub enum ImageMethodFile {
     UeberzugX11,
     Iterm2,
     Sixel,
+    Block,
     None,
     #[default]
     Auto,
@@ -56,11 +64,85 @@ pub enum ImageMethod {
     UeberzugX11,
     Iterm2,
     Sixel,
+    Block,
     None,
     #[default]
     Unsupported,
 }
 
+impl ImageMethod {
+    /// Method to switch to when cycling through the album art methods at runtime
+    pub fn next(self) -> Self {
+        match self {
+            ImageMethod::Kitty => ImageMethod::Sixel,
+            ImageMethod::Sixel => ImageMethod::Iterm2,
+            ImageMethod::Iterm2 => ImageMethod::UeberzugWayland,
+            ImageMethod::UeberzugWayland => ImageMethod::UeberzugX11,
+            ImageMethod::UeberzugX11 => ImageMethod::Block,
+            ImageMethod::Block => ImageMethod::None,
+            ImageMethod::None | ImageMethod::Unsupported => ImageMethod::Kitty,
+        }
+    }
+}
+
+/// MPD subsystem whose changes rmpc is notified about
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum IdleSubsystemFile {
+    Player,
+    Mixer,
+    Playlist,
+    Options,
+    Database,
+    Update,
+    StoredPlaylist,
+    Output,
+    Partition,
+    Sticker,
+    Subscription,
+    Message,
+    Neighbor,
+    Mount,
+}
+
+impl From<IdleSubsystemFile> for IdleEvent {
+    fn from(value: IdleSubsystemFile) -> Self {
+        match value {
+            IdleSubsystemFile::Player => IdleEvent::Player,
+            IdleSubsystemFile::Mixer => IdleEvent::Mixer,
+            IdleSubsystemFile::Playlist => IdleEvent::Playlist,
+            IdleSubsystemFile::Options => IdleEvent::Options,
+            IdleSubsystemFile::Database => IdleEvent::Database,
+            IdleSubsystemFile::Update => IdleEvent::Update,
+            IdleSubsystemFile::StoredPlaylist => IdleEvent::StoredPlaylist,
+            IdleSubsystemFile::Output => IdleEvent::Output,
+            IdleSubsystemFile::Partition => IdleEvent::Partition,
+            IdleSubsystemFile::Sticker => IdleEvent::Sticker,
+            IdleSubsystemFile::Subscription => IdleEvent::Subscription,
+            IdleSubsystemFile::Message => IdleEvent::Message,
+            IdleSubsystemFile::Neighbor => IdleEvent::Neighbor,
+            IdleSubsystemFile::Mount => IdleEvent::Mount,
+        }
+    }
+}
+
+/// Where to tell the user about songs added to the queue from the browsers
+#[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum AddFeedback {
+    #[default]
+    StatusLine,
+    Notification,
+    Silent,
+}
+
+/// What a second press of the `Close` key shortly after the first one does
+#[derive(Default, Display, Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DoubleEscapeAction {
+    #[default]
+    None,
+    GoBack,
+    GoToQueue,
+}
+
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
 pub struct Size {
     pub width: u16,
@@ -77,27 +159,59 @@ impl Default for Size {
 }
 
 #[derive(Debug, Default, Clone)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct Config {
     pub address: MpdAddress<'static>,
     pub password: Option<MpdPassword<'static>>,
     pub cache_dir: Option<&'static str>,
     pub lyrics_dir: Option<&'static str>,
+    pub music_directory: Option<&'static str>,
     pub volume_step: u8,
     pub scrolloff: usize,
     pub wrap_navigation: bool,
+    pub scroll_amount: usize,
+    pub reverse_scroll: bool,
+    pub seek_step_secs: u32,
+    pub seek_acceleration: SeekAcceleration,
     pub keybinds: KeyConfig,
     pub enable_mouse: bool,
     pub status_update_interval_ms: Option<u64>,
+    pub progress_update_while_paused: bool,
     pub select_current_song_on_change: bool,
+    pub follow_playback: bool,
+    pub confirm_destructive_actions: bool,
+    pub wrap_next: bool,
+    pub random_song_replaces_queue: bool,
+    pub add_feedback: AddFeedback,
+    pub autoplay_on_add: bool,
+    pub double_escape_action: DoubleEscapeAction,
+    pub queue_size_warning: Option<usize>,
+    pub history_size: usize,
+    pub screensaver_timeout_secs: Option<u64>,
     pub theme: UiConfig,
     pub album_art: AlbumArtConfig,
     pub on_song_change: Option<&'static [&'static str]>,
+    pub notify_on_song_change: bool,
+    pub record_last_played: bool,
+    pub tag_editor: Option<&'static [&'static str]>,
+    pub enabled_tags: Option<&'static [&'static str]>,
+    /// Subsystems the idle connection listens to, empty means all of them
+    pub idle_subsystems: &'static [IdleEvent],
     pub search: Search,
+    pub browser_filter_mode: FilterKind,
     pub artists: Artists,
+    pub directories: Directories,
+    /// Load the whole database once so the directories pane does not have to ask MPD for every directory
+    pub preload_library: bool,
+    /// How many days back the `RecentlyAdded` pane looks for added songs
+    pub recently_added_days: u32,
     pub tabs: Tabs,
+    pub default_tab: Option<TabName>,
+    pub now_playing_socket: Option<&'static str>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct ConfigFile {
     #[serde(default = "defaults::mpd_address")]
     pub address: String,
@@ -108,6 +222,8 @@ pub struct ConfigFile {
     #[serde(default)]
     lyrics_dir: Option<String>,
     #[serde(default)]
+    music_directory: Option<String>,
+    #[serde(default)]
     pub theme: Option<String>,
     #[serde(default = "defaults::default_volume_step")]
     volume_step: u8,
@@ -115,10 +231,40 @@ pub struct ConfigFile {
     scrolloff: usize,
     #[serde(default = "defaults::default_false")]
     wrap_navigation: bool,
+    #[serde(default = "defaults::default_scroll_amount")]
+    scroll_amount: usize,
+    #[serde(default = "defaults::default_false")]
+    reverse_scroll: bool,
+    #[serde(default = "defaults::default_seek_step_secs")]
+    seek_step_secs: u32,
+    #[serde(default)]
+    seek_acceleration: SeekAccelerationFile,
     #[serde(default = "defaults::default_progress_update_interval_ms")]
     status_update_interval_ms: Option<u64>,
     #[serde(default = "defaults::default_false")]
+    progress_update_while_paused: bool,
+    #[serde(default = "defaults::default_false")]
     select_current_song_on_change: bool,
+    #[serde(default = "defaults::default_false")]
+    follow_playback: bool,
+    #[serde(default = "defaults::default_true")]
+    confirm_destructive_actions: bool,
+    #[serde(default = "defaults::default_false")]
+    wrap_next: bool,
+    #[serde(default = "defaults::default_false")]
+    random_song_replaces_queue: bool,
+    #[serde(default)]
+    add_feedback: AddFeedback,
+    #[serde(default = "defaults::default_false")]
+    autoplay_on_add: bool,
+    #[serde(default)]
+    double_escape_action: DoubleEscapeAction,
+    #[serde(default)]
+    queue_size_warning: Option<usize>,
+    #[serde(default = "defaults::default_history_size")]
+    history_size: usize,
+    #[serde(default)]
+    screensaver_timeout_secs: Option<u64>,
     #[serde(default = "defaults::default_true")]
     enable_mouse: bool,
     #[serde(default)]
@@ -131,11 +277,33 @@ pub struct ConfigFile {
     pub album_art: AlbumArtConfigFile,
     #[serde(default)]
     on_song_change: Option<Vec<String>>,
+    #[serde(default = "defaults::default_false")]
+    notify_on_song_change: bool,
+    #[serde(default = "defaults::default_false")]
+    record_last_played: bool,
+    #[serde(default = "defaults::default_tag_editor")]
+    tag_editor: Option<Vec<String>>,
+    #[serde(default)]
+    enabled_tags: Option<Vec<String>>,
+    #[serde(default = "defaults::default_idle_subsystems")]
+    idle_subsystems: Vec<IdleSubsystemFile>,
+    #[serde(default)]
+    now_playing_socket: Option<String>,
     #[serde(default)]
     search: SearchFile,
     #[serde(default)]
+    browser_filter_mode: FilterKindFile,
+    #[serde(default)]
     artists: ArtistsFile,
     #[serde(default)]
+    directories: DirectoriesFile,
+    #[serde(default = "defaults::default_false")]
+    preload_library: bool,
+    #[serde(default = "defaults::default_recently_added_days")]
+    recently_added_days: u32,
+    #[serde(default)]
+    default_tab: Option<String>,
+    #[serde(default)]
     tabs: TabsFile,
 }
 
@@ -147,6 +315,10 @@ pub struct AlbumArtConfigFile {
     pub max_size_px: Size,
     #[serde(default = "defaults::disabled_album_art_protos")]
     pub disabled_protocols: Vec<String>,
+    #[serde(default)]
+    pub refresh_on_player_event: bool,
+    #[serde(default = "defaults::album_art_max_file_size_bytes")]
+    pub max_file_size_bytes: usize,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -154,6 +326,10 @@ pub struct AlbumArtConfig {
     pub method: ImageMethod,
     pub max_size_px: Size,
     pub disabled_protocols: Vec<&'static str>,
+    /// Fetch the album art again on every player state change, not only when the song changes
+    pub refresh_on_player_event: bool,
+    /// Album art larger than this is not read from MPD at all to avoid buffering huge images in memory
+    pub max_file_size_bytes: usize,
 }
 
 impl Default for ConfigFile {
@@ -163,24 +339,53 @@ impl Default for ConfigFile {
             keybinds: KeyConfigFile::default(),
             volume_step: 5,
             scrolloff: 0,
+            scroll_amount: 1,
+            reverse_scroll: false,
+            seek_step_secs: defaults::default_seek_step_secs(),
+            seek_acceleration: SeekAccelerationFile::default(),
             status_update_interval_ms: Some(1000),
+            progress_update_while_paused: false,
             theme: None,
             cache_dir: None,
             lyrics_dir: None,
+            music_directory: None,
             image_method: None,
             select_current_song_on_change: false,
+            follow_playback: false,
+            confirm_destructive_actions: true,
+            wrap_next: false,
+            random_song_replaces_queue: false,
+            add_feedback: AddFeedback::default(),
+            autoplay_on_add: false,
+            double_escape_action: DoubleEscapeAction::default(),
+            queue_size_warning: None,
+            history_size: defaults::default_history_size(),
+            screensaver_timeout_secs: None,
             album_art_max_size_px: Size::default(),
             album_art: AlbumArtConfigFile {
                 disabled_protocols: defaults::disabled_album_art_protos(),
+                refresh_on_player_event: false,
+                max_file_size_bytes: defaults::album_art_max_file_size_bytes(),
                 ..Default::default()
             },
             on_song_change: None,
+            notify_on_song_change: false,
+            record_last_played: false,
+            tag_editor: defaults::default_tag_editor(),
+            enabled_tags: None,
+            idle_subsystems: defaults::default_idle_subsystems(),
+            now_playing_socket: None,
             search: SearchFile::default(),
+            browser_filter_mode: FilterKindFile::default(),
+            default_tab: None,
             tabs: TabsFile::default(),
             enable_mouse: true,
             wrap_navigation: false,
             password: None,
             artists: ArtistsFile::default(),
+            directories: DirectoriesFile::default(),
+            preload_library: false,
+            recently_added_days: defaults::default_recently_added_days(),
         }
     }
 }
@@ -228,6 +433,35 @@ impl ConfigFile {
             .unwrap_or_default()
             .try_into()?;
 
+        let tabs: Tabs = self.tabs.try_into()?;
+        let default_tab = self
+            .default_tab
+            .map(|name| resolve_default_tab(&name, &tabs))
+            .transpose()?;
+
+        if self.tag_editor.as_ref().is_some_and(Vec::is_empty) {
+            anyhow::bail!("tag_editor has to contain at least the program to run, use None to disable it");
+        }
+
+        for conflict in &self.keybinds.conflicts {
+            status_warn!("{conflict}");
+        }
+
+        let seek_step_secs = if self.seek_step_secs == 0 {
+            let default = defaults::default_seek_step_secs();
+            status_warn!("seek_step_secs has to be greater than 0, using the default of {default} seconds");
+            default
+        } else {
+            self.seek_step_secs
+        };
+
+        let browser_filter_mode = if self.browser_filter_mode == FilterKindFile::Regex {
+            status_warn!("Regex is not supported by browser_filter_mode, using Contains instead");
+            FilterKind::Contains
+        } else {
+            self.browser_filter_mode.into()
+        };
+
         let size = self.album_art.max_size_px;
         let (address, password) = MpdAddress::resolve(address_cli, password_cli, self.address, self.password);
         let mut config = Config {
@@ -244,18 +478,50 @@ impl ConfigFile {
                 }
                 .leak() as &'static _
             }),
+            music_directory: self.music_directory.map(|v| {
+                let v = tilde_expand(&v);
+                if v.ends_with('/') {
+                    v.into_owned()
+                } else {
+                    format!("{v}/")
+                }
+                .leak() as &'static _
+            }),
             address,
             password,
             volume_step: self.volume_step,
             scrolloff: self.scrolloff,
             wrap_navigation: self.wrap_navigation,
+            scroll_amount: self.scroll_amount.max(1),
+            reverse_scroll: self.reverse_scroll,
+            seek_step_secs,
+            seek_acceleration: self.seek_acceleration.into(),
             status_update_interval_ms: self.status_update_interval_ms.map(|v| v.max(100)),
+            progress_update_while_paused: self.progress_update_while_paused,
             enable_mouse: self.enable_mouse,
             keybinds: self.keybinds.into(),
             select_current_song_on_change: self.select_current_song_on_change,
+            follow_playback: self.follow_playback,
+            confirm_destructive_actions: self.confirm_destructive_actions,
+            wrap_next: self.wrap_next,
+            random_song_replaces_queue: self.random_song_replaces_queue,
+            add_feedback: self.add_feedback,
+            autoplay_on_add: self.autoplay_on_add,
+            double_escape_action: self.double_escape_action,
+            queue_size_warning: self.queue_size_warning,
+            history_size: self.history_size,
+            screensaver_timeout_secs: self.screensaver_timeout_secs,
             search: self.search.into(),
+            browser_filter_mode,
             artists: self.artists.into(),
-            tabs: self.tabs.try_into()?,
+            directories: self.directories.into(),
+            preload_library: self.preload_library,
+            recently_added_days: self.recently_added_days,
+            tabs,
+            default_tab,
+            now_playing_socket: self
+                .now_playing_socket
+                .map(|v| tilde_expand(&v).into_owned().leak() as &'static _),
             album_art: AlbumArtConfig {
                 method: ImageMethod::default(),
                 max_size_px: Size {
@@ -268,6 +534,8 @@ impl ConfigFile {
                     .into_iter()
                     .map(|proto| proto.leak() as &'static _)
                     .collect(),
+                refresh_on_player_event: self.album_art.refresh_on_player_event,
+                max_file_size_bytes: self.album_art.max_file_size_bytes,
             },
             on_song_change: self.on_song_change.map(|arr| {
                 arr.into_iter()
@@ -275,6 +543,18 @@ impl ConfigFile {
                     .collect_vec()
                     .leak() as &'static [_]
             }),
+            notify_on_song_change: self.notify_on_song_change,
+            record_last_played: self.record_last_played,
+            tag_editor: self.tag_editor.map(|arr| {
+                arr.into_iter()
+                    .map(|v| tilde_expand(&v).into_owned().leak() as &'static str)
+                    .collect_vec()
+                    .leak() as &'static [_]
+            }),
+            enabled_tags: self
+                .enabled_tags
+                .map(|arr| arr.into_iter().map(|v| v.leak() as &'static str).collect_vec().leak() as &'static [_]),
+            idle_subsystems: self.idle_subsystems.into_iter().map(Into::into).collect_vec().leak(),
         };
 
         if is_cli {
@@ -294,6 +574,7 @@ impl ConfigFile {
             ImageMethodFile::UeberzugX11 if image::is_ueberzug_x11_supported() => ImageMethod::UeberzugX11,
             ImageMethodFile::UeberzugX11 => ImageMethod::Unsupported,
             ImageMethodFile::Sixel => ImageMethod::Sixel,
+            ImageMethodFile::Block => ImageMethod::Block,
             ImageMethodFile::None => ImageMethod::None,
             ImageMethodFile::Auto => match image::determine_image_support(is_tmux)? {
                 ImageProtocol::Kitty => ImageMethod::Kitty,
@@ -301,6 +582,7 @@ impl ConfigFile {
                 ImageProtocol::UeberzugX11 => ImageMethod::UeberzugX11,
                 ImageProtocol::Iterm2 => ImageMethod::Iterm2,
                 ImageProtocol::Sixel => ImageMethod::Sixel,
+                ImageProtocol::Block => ImageMethod::Block,
                 ImageProtocol::None => ImageMethod::Unsupported,
             },
         };
@@ -316,7 +598,8 @@ impl ConfigFile {
             | ImageMethod::UeberzugWayland
             | ImageMethod::UeberzugX11
             | ImageMethod::Iterm2
-            | ImageMethod::Sixel => {
+            | ImageMethod::Sixel
+            | ImageMethod::Block => {
                 log::debug!(resolved:? = config.album_art.method, requested:? = self.album_art.method, is_tmux; "Image method resolved");
             }
         }
@@ -362,6 +645,14 @@ impl<T> Leak for T {
     }
 }
 
+fn resolve_default_tab(name: &str, tabs: &Tabs) -> Result<TabName> {
+    tabs.names
+        .iter()
+        .find(|tab| tab.0 == name)
+        .copied()
+        .with_context(|| format!("Default tab '{name}' is not one of the configured tabs"))
+}
+
 pub mod utils {
     use std::borrow::Cow;
     use std::path::MAIN_SEPARATOR;
@@ -466,6 +757,36 @@ mod tests {
         assert_eq!(config, f);
     }
 
+    #[test]
+    fn default_tab_must_be_configured() {
+        let config = ConfigFile {
+            default_tab: Some("Nonexistent".to_string()),
+            ..Default::default()
+        };
+
+        assert!(config.into_config(None, None, None, true).is_err());
+    }
+
+    #[test]
+    fn empty_tag_editor_is_rejected() {
+        let config = ConfigFile {
+            tag_editor: Some(Vec::new()),
+            ..Default::default()
+        };
+
+        assert!(config.into_config(None, None, None, true).is_err());
+    }
+
+    #[test]
+    fn zero_seek_step_falls_back_to_default() {
+        let config = ConfigFile {
+            seek_step_secs: 0,
+            ..Default::default()
+        };
+
+        assert_eq!(config.into_config(None, None, None, true).unwrap().seek_step_secs, 5);
+    }
+
     #[test]
     fn example_theme_equals_default() {
         let theme = UiConfigFile::default();
@@ -8,6 +8,8 @@ pub struct Search {
     pub case_sensitive: bool,
     pub mode: FilterKind,
     pub tags: &'static [SearchableTag],
+    /// Value of the tag whose input is focused when the Search pane is shown
+    pub default_tag: Option<&'static str>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -15,6 +17,8 @@ pub struct SearchFile {
     case_sensitive: bool,
     mode: FilterKindFile,
     tags: Vec<SearchableTagFile>,
+    #[serde(default)]
+    default_tag: Option<String>,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -50,6 +54,7 @@ impl From<SearchFile> for Search {
                     .collect_vec()
             }
             .leak(),
+            default_tag: value.default_tag.map(|tag| &*tag.leak()),
         }
     }
 }
@@ -90,17 +95,19 @@ impl Default for SearchFile {
                 },
             ]
             .to_vec(),
+            default_tag: None,
         }
     }
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize, PartialEq, Eq)]
-enum FilterKindFile {
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub(super) enum FilterKindFile {
     Exact,
     StartsWith,
     #[default]
     Contains,
     Regex,
+    Fuzzy,
 }
 
 impl From<FilterKindFile> for FilterKind {
@@ -110,6 +117,7 @@ impl From<FilterKindFile> for FilterKind {
             FilterKindFile::StartsWith => FilterKind::StartsWith,
             FilterKindFile::Contains => FilterKind::Contains,
             FilterKindFile::Regex => FilterKind::Regex,
+            FilterKindFile::Fuzzy => FilterKind::Fuzzy,
         }
     }
 }
@@ -0,0 +1,48 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use super::defaults;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SeekAcceleration {
+    pub window: Duration,
+    pub factor: u32,
+    pub max_step_secs: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SeekAccelerationFile {
+    #[serde(default = "defaults::default_seek_acceleration_window_ms")]
+    pub window_ms: u64,
+    #[serde(default = "defaults::default_seek_acceleration_factor")]
+    pub factor: u32,
+    #[serde(default = "defaults::default_seek_acceleration_max_step_secs")]
+    pub max_step_secs: u32,
+}
+
+impl Default for SeekAccelerationFile {
+    fn default() -> Self {
+        Self {
+            window_ms: defaults::default_seek_acceleration_window_ms(),
+            factor: defaults::default_seek_acceleration_factor(),
+            max_step_secs: defaults::default_seek_acceleration_max_step_secs(),
+        }
+    }
+}
+
+impl Default for SeekAcceleration {
+    fn default() -> Self {
+        SeekAccelerationFile::default().into()
+    }
+}
+
+impl From<SeekAccelerationFile> for SeekAcceleration {
+    fn from(value: SeekAccelerationFile) -> Self {
+        Self {
+            window: Duration::from_millis(value.window_ms),
+            factor: value.factor.max(1),
+            max_step_secs: value.max_step_secs,
+        }
+    }
+}
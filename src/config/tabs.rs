@@ -37,6 +37,7 @@ enum PaneTypeFile {
     Albums,
     AlbumArtists,
     Playlists,
+    RecentlyAdded,
     Search,
     AlbumArt,
     Lyrics,
@@ -52,6 +53,7 @@ pub enum PaneType {
     AlbumArtists,
     Albums,
     Playlists,
+    RecentlyAdded,
     Search,
     AlbumArt,
     Lyrics,
@@ -74,6 +76,7 @@ impl From<&PaneTypeFile> for PaneType {
             PaneTypeFile::AlbumArtists => PaneType::AlbumArtists,
             PaneTypeFile::Albums => PaneType::Albums,
             PaneTypeFile::Playlists => PaneType::Playlists,
+            PaneTypeFile::RecentlyAdded => PaneType::RecentlyAdded,
             PaneTypeFile::Search => PaneType::Search,
             PaneTypeFile::AlbumArt => PaneType::AlbumArt,
             PaneTypeFile::Lyrics => PaneType::Lyrics,
@@ -18,6 +18,19 @@ pub struct HeaderConfigRow {
 #[derive(Debug, Default, Clone, Copy)]
 pub struct HeaderConfig {
     pub rows: &'static [HeaderConfigRow],
+    pub overflow: HeaderOverflow,
+}
+
+/// What to do with header text which does not fit into its area
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum HeaderOverflow {
+    /// Text is simply cut off at the edge of the area
+    #[default]
+    Off,
+    /// Text is truncated and ends with the ellipsis symbol
+    Ellipsis,
+    /// Text scrolls horizontally, advancing by one column every rendered frame
+    Marquee,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -30,11 +43,14 @@ pub struct HeaderConfigRowFile {
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct HeaderConfigFile {
     pub(super) rows: Vec<HeaderConfigRowFile>,
+    #[serde(default)]
+    pub(super) overflow: HeaderOverflow,
 }
 
 impl Default for HeaderConfigFile {
     fn default() -> Self {
         Self {
+            overflow: HeaderOverflow::Off,
             rows: vec![
                 HeaderConfigRowFile {
                     left: vec![
@@ -177,6 +193,7 @@ impl Default for HeaderConfigFile {
                                 bg: None,
                                 modifiers: None,
                             }),
+                            oneshot_style: None,
                         })),
                         style: Some(StyleFile {
                             fg: Some("dark_gray".to_string()),
@@ -191,38 +208,43 @@ impl Default for HeaderConfigFile {
     }
 }
 
-impl TryFrom<HeaderConfigFile> for HeaderConfig {
+impl TryFrom<HeaderConfigRowFile> for HeaderConfigRow {
     type Error = anyhow::Error;
 
-    fn try_from(value: HeaderConfigFile) -> Result<Self, Self::Error> {
-        let rows: Vec<_> = value
-            .rows
+    fn try_from(row: HeaderConfigRowFile) -> Result<Self, Self::Error> {
+        let left = row
+            .left
             .into_iter()
-            .map(|row| -> Result<_> {
-                let left = row
-                    .left
-                    .into_iter()
-                    .map(TryInto::<&'static Property<'static, PropertyKind>>::try_into)
-                    .collect::<Result<Vec<_>>>()?;
-                let center = row
-                    .center
-                    .into_iter()
-                    .map(TryInto::<&'static Property<'static, PropertyKind>>::try_into)
-                    .collect::<Result<Vec<_>>>()?;
-                let right = row
-                    .right
-                    .into_iter()
-                    .map(TryInto::<&'static Property<'static, PropertyKind>>::try_into)
-                    .collect::<Result<Vec<_>>>()?;
+            .map(TryInto::<&'static Property<'static, PropertyKind>>::try_into)
+            .collect::<Result<Vec<_>>>()?;
+        let center = row
+            .center
+            .into_iter()
+            .map(TryInto::<&'static Property<'static, PropertyKind>>::try_into)
+            .collect::<Result<Vec<_>>>()?;
+        let right = row
+            .right
+            .into_iter()
+            .map(TryInto::<&'static Property<'static, PropertyKind>>::try_into)
+            .collect::<Result<Vec<_>>>()?;
 
-                Ok(HeaderConfigRow {
-                    left: left.leak(),
-                    center: center.leak(),
-                    right: right.leak(),
-                })
-            })
-            .try_collect()?;
+        Ok(HeaderConfigRow {
+            left: left.leak(),
+            center: center.leak(),
+            right: right.leak(),
+        })
+    }
+}
+
+impl TryFrom<HeaderConfigFile> for HeaderConfig {
+    type Error = anyhow::Error;
+
+    fn try_from(value: HeaderConfigFile) -> Result<Self, Self::Error> {
+        let rows: Vec<HeaderConfigRow> = value.rows.into_iter().map(TryInto::try_into).try_collect()?;
 
-        Ok(Self { rows: rows.leak() })
+        Ok(Self {
+            rows: rows.leak(),
+            overflow: value.overflow,
+        })
     }
 }
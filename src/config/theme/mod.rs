@@ -4,7 +4,7 @@ use properties::{SongFormat, SongFormatFile};
 use ratatui::style::{Color, Style};
 
 use self::{
-    header::{HeaderConfig, HeaderConfigFile},
+    header::{HeaderConfig, HeaderConfigFile, HeaderConfigRowFile},
     progress_bar::{ProgressBarConfig, ProgressBarConfigFile},
     queue_table::{QueueTableColumns, QueueTableColumnsFile},
     scrollbar::{ScrollbarConfig, ScrollbarConfigFile},
@@ -12,20 +12,23 @@ use self::{
 };
 
 mod header;
-mod progress_bar;
+pub mod progress_bar;
 pub mod properties;
 mod queue_table;
 mod scrollbar;
 mod style;
 
+pub use self::header::{HeaderConfigRow, HeaderOverflow};
 pub use self::queue_table::{PercentOrLength, SongTableColumn};
 pub use style::{ConfigColor, StyleFile};
 
 use super::defaults;
+use crate::mpd::commands::Song;
 
 const DEFAULT_ART: &[u8; 58599] = include_bytes!("../../../assets/default.jpg");
 
 #[derive(Default, Clone, Copy)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct UiConfig {
     pub draw_borders: bool,
     pub background_color: Option<Color>,
@@ -35,6 +38,8 @@ pub struct UiConfig {
     pub borders_style: Style,
     pub highlighted_item_style: Style,
     pub current_item_style: Style,
+    pub hovered_item_style: Style,
+    pub current_song_style: Style,
     pub highlight_border_style: Style,
     pub column_widths: [u16; 3],
     pub browser_song_format: SongFormat,
@@ -43,18 +48,36 @@ pub struct UiConfig {
     pub tab_bar: TabBar,
     pub scrollbar: ScrollbarConfig,
     pub show_song_table_header: bool,
+    pub show_queue_position: bool,
+    pub show_breadcrumbs: bool,
     pub song_table_format: &'static [SongTableColumn],
     pub header: HeaderConfig,
+    /// Shown in the bottom bar instead of the progress bar when there is no message to show
+    pub status_line: Option<HeaderConfigRow>,
     pub default_album_art: &'static [u8],
+    pub content_padding: Padding,
+    pub section_gap: u16,
+    pub tag_styles: &'static [TagStyle],
+}
+
+impl UiConfig {
+    /// Style of the first configured tag style which matches the song
+    pub fn tag_style(&self, song: &Song) -> Option<Style> {
+        self.tag_styles
+            .iter()
+            .find(|tag_style| tag_style.matches(song))
+            .map(|tag_style| tag_style.style)
+    }
 }
 
 impl std::fmt::Debug for UiConfig {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "UiConfig {{ draw_borders: {}, background_color: {:?}, header_background_color: {:?}, background_color_modal: {:?}, borders_style: {:?}, highlighted_item_style: {:?}, current_item_style: {:?}, highlight_border_style: {:?}, tab_bar: {:?}, column_widths: {:?}, symbols: {:?}, progress_bar: {:?}, scrollbar: {:?}, show_song_table_header: {}, song_table_format: {:?}, header: {:?}, default_album_art: [u8; {}] }}", self.draw_borders, self.background_color, self.header_background_color, self.modal_background_color, self.borders_style, self.highlighted_item_style, self.current_item_style, self.highlight_border_style, self.tab_bar, self.column_widths, self.symbols, self.progress_bar, self.scrollbar, self.show_song_table_header, self.song_table_format, self.header, self.default_album_art.len())
+        write!(f, "UiConfig {{ draw_borders: {}, background_color: {:?}, header_background_color: {:?}, background_color_modal: {:?}, borders_style: {:?}, highlighted_item_style: {:?}, current_item_style: {:?}, hovered_item_style: {:?}, current_song_style: {:?}, highlight_border_style: {:?}, tab_bar: {:?}, column_widths: {:?}, symbols: {:?}, progress_bar: {:?}, scrollbar: {:?}, show_song_table_header: {}, show_queue_position: {}, show_breadcrumbs: {}, song_table_format: {:?}, header: {:?}, status_line: {:?}, default_album_art: [u8; {}], content_padding: {:?}, section_gap: {}, tag_styles: {:?} }}", self.draw_borders, self.background_color, self.header_background_color, self.modal_background_color, self.borders_style, self.highlighted_item_style, self.current_item_style, self.hovered_item_style, self.current_song_style, self.highlight_border_style, self.tab_bar, self.column_widths, self.symbols, self.progress_bar, self.scrollbar, self.show_song_table_header, self.show_queue_position, self.show_breadcrumbs, self.song_table_format, self.header, self.status_line, self.default_album_art.len(), self.content_padding, self.section_gap, self.tag_styles)
     }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct UiConfigFile {
     #[serde(default = "defaults::default_true")]
     pub(super) draw_borders: bool,
@@ -73,11 +96,27 @@ pub struct UiConfigFile {
     pub(super) borders_style: Option<StyleFile>,
     pub(super) highlighted_item_style: Option<StyleFile>,
     pub(super) current_item_style: Option<StyleFile>,
+    #[serde(default = "default_hovered_item_style")]
+    pub(super) hovered_item_style: Option<StyleFile>,
+    #[serde(default)]
+    pub(super) current_song_style: Option<StyleFile>,
     pub(super) highlight_border_style: Option<StyleFile>,
     pub(super) show_song_table_header: bool,
+    #[serde(default)]
+    pub(super) show_queue_position: bool,
+    #[serde(default = "defaults::default_true")]
+    pub(super) show_breadcrumbs: bool,
     pub(super) song_table_format: QueueTableColumnsFile,
     pub(super) header: HeaderConfigFile,
+    #[serde(default)]
+    pub(super) status_line: Option<HeaderConfigRowFile>,
     pub(super) default_album_art_path: Option<String>,
+    #[serde(default)]
+    pub(super) content_padding: Padding,
+    #[serde(default)]
+    pub(super) section_gap: u16,
+    #[serde(default)]
+    pub(super) tag_styles: Vec<TagStyleFile>,
 }
 
 impl Default for UiConfigFile {
@@ -85,11 +124,17 @@ impl Default for UiConfigFile {
         Self {
             default_album_art_path: None,
             draw_borders: true,
+            content_padding: Padding::default(),
+            section_gap: 0,
+            tag_styles: Vec::new(),
             background_color: None,
             text_color: None,
             header_background_color: None,
             show_song_table_header: true,
+            show_queue_position: false,
+            show_breadcrumbs: true,
             header: HeaderConfigFile::default(),
+            status_line: None,
             modal_background_color: None,
             borders_style: Some(StyleFile {
                 fg: Some("blue".to_string()),
@@ -106,6 +151,8 @@ impl Default for UiConfigFile {
                 bg: Some("blue".to_string()),
                 modifiers: Some(Modifiers::Bold),
             }),
+            hovered_item_style: default_hovered_item_style(),
+            current_song_style: None,
             highlight_border_style: Some(StyleFile {
                 fg: Some("blue".to_string()),
                 bg: None,
@@ -132,6 +179,8 @@ impl Default for UiConfigFile {
                 dir: "D".to_owned(),
                 marker: "M".to_owned(),
                 ellipsis: Some("...".to_owned()),
+                current_song: None,
+                favorite: default_favorite_symbol(),
             },
             song_table_format: QueueTableColumnsFile::default(),
             browser_song_format: SongFormatFile::default(),
@@ -139,6 +188,63 @@ impl Default for UiConfigFile {
     }
 }
 
+fn default_favorite_symbol() -> String {
+    "♥".to_owned()
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn default_hovered_item_style() -> Option<StyleFile> {
+    Some(StyleFile {
+        fg: None,
+        bg: None,
+        modifiers: Some(Modifiers::Underlined),
+    })
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TagStyleFile {
+    pub(super) tag: String,
+    pub(super) value: String,
+    pub(super) style: StyleFile,
+}
+
+/// Style of songs whose `tag` has the given value, ie. to color songs of a genre
+#[derive(Debug, Clone, Copy)]
+pub struct TagStyle {
+    pub tag: &'static str,
+    pub value: &'static str,
+    pub style: Style,
+}
+
+impl TagStyle {
+    fn matches(&self, song: &Song) -> bool {
+        song.metadata
+            .get(self.tag)
+            .is_some_and(|value| value.eq_ignore_ascii_case(self.value))
+    }
+}
+
+impl TryFrom<TagStyleFile> for TagStyle {
+    type Error = anyhow::Error;
+
+    fn try_from(value: TagStyleFile) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tag: value.tag.to_lowercase().leak(),
+            value: value.value.leak(),
+            style: value.style.to_config_or(None, None)?,
+        })
+    }
+}
+
+/// Empty space in columns/rows left on each side of an area
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone, Copy)]
+pub struct Padding {
+    #[serde(default)]
+    pub horizontal: u16,
+    #[serde(default)]
+    pub vertical: u16,
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TabBarFile {
     pub(super) enabled: Option<bool>,
@@ -159,6 +265,10 @@ pub struct SymbolsFile {
     pub(super) dir: String,
     pub(super) marker: String,
     pub(super) ellipsis: Option<String>,
+    #[serde(default)]
+    pub(super) current_song: Option<String>,
+    #[serde(default = "default_favorite_symbol")]
+    pub(super) favorite: String,
 }
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -167,6 +277,8 @@ pub struct SymbolsConfig {
     pub dir: &'static str,
     pub marker: &'static str,
     pub ellipsis: &'static str,
+    pub current_song: Option<&'static str>,
+    pub favorite: &'static str,
 }
 
 impl From<SymbolsFile> for SymbolsConfig {
@@ -176,6 +288,8 @@ impl From<SymbolsFile> for SymbolsConfig {
             dir: value.dir.leak(),
             marker: value.marker.leak(),
             ellipsis: value.ellipsis.unwrap_or_else(|| "...".to_string()).leak(),
+            current_song: value.current_song.map(|v| v.leak() as &'static str),
+            favorite: value.favorite.leak(),
         }
     }
 }
@@ -188,6 +302,7 @@ impl TryFrom<UiConfigFile> for UiConfig {
         let bg_color = StringColor(value.background_color).to_color()?;
         let header_bg_color = StringColor(value.header_background_color).to_color()?.or(bg_color);
         let fallback_border_fg = Color::White;
+        let highlighted_item_style = value.highlighted_item_style.to_config_or(Some(Color::Blue), None)?;
 
         Ok(Self {
             background_color: bg_color,
@@ -196,16 +311,22 @@ impl TryFrom<UiConfigFile> for UiConfig {
             text_color: StringColor(value.text_color).to_color()?,
             header_background_color: header_bg_color,
             borders_style: value.borders_style.to_config_or(Some(fallback_border_fg), None)?,
-            highlighted_item_style: value.highlighted_item_style.to_config_or(Some(Color::Blue), None)?,
+            highlighted_item_style,
+            current_song_style: value
+                .current_song_style
+                .map_or(Ok(highlighted_item_style), |style| style.to_config_or(None, None))?,
             highlight_border_style: value.highlight_border_style.to_config_or(Some(Color::Blue), None)?,
             symbols: value.symbols.into(),
             show_song_table_header: value.show_song_table_header,
+            show_queue_position: value.show_queue_position,
+            show_breadcrumbs: value.show_breadcrumbs,
             scrollbar: value.scrollbar.into_config(fallback_border_fg)?,
             progress_bar: value.progress_bar.into_config()?,
             song_table_format: TryInto::<QueueTableColumns>::try_into(value.song_table_format)?
                 .0
                 .leak(),
             header: value.header.try_into()?,
+            status_line: value.status_line.map(TryInto::try_into).transpose()?,
             column_widths: [
                 value.browser_column_widths[0],
                 value.browser_column_widths[1],
@@ -222,12 +343,86 @@ impl TryFrom<UiConfigFile> for UiConfig {
             current_item_style: value
                 .current_item_style
                 .to_config_or(Some(Color::Black), Some(Color::Blue))?,
+            hovered_item_style: value.hovered_item_style.to_config_or(None, None)?,
             default_album_art: value
                 .default_album_art_path
-                .map_or(Ok(DEFAULT_ART as &'static [u8]), |path| -> Result<_> {
-                    Ok(std::fs::read(path)?.leak())
-                })?,
+                .and_then(|path| read_album_art(&path))
+                .map_or(DEFAULT_ART as &'static [u8], |data| &*data.leak()),
             browser_song_format: TryInto::<SongFormat>::try_into(value.browser_song_format)?,
+            content_padding: value.content_padding,
+            section_gap: value.section_gap,
+            tag_styles: value
+                .tag_styles
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<Vec<TagStyle>>>()?
+                .leak(),
         })
     }
 }
+
+/// Reads the image at `path` and checks that it can be decoded. Problems are only logged so that the built-in image
+/// is used instead of failing to start.
+fn read_album_art(path: &str) -> Option<Vec<u8>> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(err) => {
+            log::warn!(err:?, path; "Failed to read default album art, using the built-in one");
+            return None;
+        }
+    };
+    if let Err(err) = image::load_from_memory(&data) {
+        log::warn!(err:?, path; "Failed to decode default album art, using the built-in one");
+        return None;
+    }
+
+    Some(data)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::{UiConfig, UiConfigFile, DEFAULT_ART};
+
+    fn config_with_album_art(name: &str, data: Option<&[u8]>) -> UiConfig {
+        let path = std::env::temp_dir().join(format!("rmpc_default_art_{name}_{}", std::process::id()));
+        if let Some(data) = data {
+            std::fs::write(&path, data).unwrap();
+        }
+
+        let config = UiConfigFile {
+            default_album_art_path: Some(path.to_string_lossy().into_owned()),
+            ..Default::default()
+        }
+        .try_into();
+        // The file is only needed while the config is built, remove it before the result is checked
+        let _ = std::fs::remove_file(&path);
+
+        config.unwrap()
+    }
+
+    #[test]
+    fn default_album_art_is_read_from_path() {
+        let mut png = Vec::new();
+        image::DynamicImage::new_rgb8(1, 1)
+            .write_to(&mut Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+
+        assert_eq!(config_with_album_art("valid", Some(&png)).default_album_art, png);
+    }
+
+    #[test]
+    fn missing_default_album_art_falls_back_to_built_in() {
+        assert_eq!(config_with_album_art("missing", None).default_album_art, DEFAULT_ART);
+    }
+
+    #[test]
+    fn undecodable_default_album_art_falls_back_to_built_in() {
+        assert_eq!(
+            config_with_album_art("invalid", Some(b"not an image")).default_album_art,
+            DEFAULT_ART
+        );
+    }
+}
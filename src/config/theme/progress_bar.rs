@@ -19,6 +19,28 @@ pub struct ProgressBarConfig {
     /// Thumb at the end of the elapsed part of the progress bar
     /// Fall sback to blue for foreground and black for background
     pub thumb_style: Style,
+    pub position: ProgressBarPosition,
+}
+
+/// Where the progress bar is shown
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ProgressBarPosition {
+    /// In the bottom bar, hidden while a status message or key hints are shown
+    #[default]
+    Bottom,
+    /// On its own row directly below the header
+    Header,
+    Both,
+}
+
+impl ProgressBarPosition {
+    pub fn in_header(self) -> bool {
+        matches!(self, ProgressBarPosition::Header | ProgressBarPosition::Both)
+    }
+
+    pub fn in_bottom(self) -> bool {
+        matches!(self, ProgressBarPosition::Bottom | ProgressBarPosition::Both)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -27,6 +49,8 @@ pub struct ProgressBarConfigFile {
     pub(super) track_style: Option<StyleFile>,
     pub(super) elapsed_style: Option<StyleFile>,
     pub(super) thumb_style: Option<StyleFile>,
+    #[serde(default)]
+    pub(super) position: ProgressBarPosition,
 }
 
 impl Default for ProgressBarConfigFile {
@@ -48,6 +72,7 @@ impl Default for ProgressBarConfigFile {
                 bg: Some("#1e2030".to_string()),
                 modifiers: None,
             }),
+            position: ProgressBarPosition::default(),
         }
     }
 }
@@ -63,6 +88,7 @@ impl ProgressBarConfigFile {
             elapsed_style: self.elapsed_style.to_config_or(Some(Color::Blue), None)?,
             thumb_style: self.thumb_style.to_config_or(Some(Color::Blue), None)?,
             track_style: self.track_style.to_config_or(Some(Color::Black), None)?,
+            position: self.position,
         })
     }
 }
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use itertools::Itertools;
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 use strum::Display;
@@ -18,6 +18,7 @@ pub enum SongPropertyFile {
     Album,
     Duration,
     Track,
+    Added,
     Other(String),
 }
 
@@ -30,6 +31,8 @@ pub enum SongProperty {
     Album,
     Duration,
     Track,
+    /// Time since the song file was last modified, for example `2d ago`
+    Added,
     Other(&'static str),
 }
 
@@ -45,6 +48,7 @@ pub enum StatusPropertyFile {
     Duration,
     Crossfade,
     Bitrate,
+    Output,
 }
 
 #[derive(Debug, Clone, Display)]
@@ -59,6 +63,7 @@ pub enum StatusProperty {
     Duration,
     Crossfade,
     Bitrate,
+    Output,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -109,8 +114,16 @@ pub enum WidgetPropertyFile {
     States {
         active_style: Option<StyleFile>,
         separator_style: Option<StyleFile>,
+        oneshot_style: Option<StyleFile>,
     },
     Volume,
+    Quality {
+        high_style: Option<StyleFile>,
+        medium_style: Option<StyleFile>,
+        low_style: Option<StyleFile>,
+        high_bitrate: Option<u32>,
+        low_bitrate: Option<u32>,
+    },
 }
 
 #[derive(Debug, Display, Clone, Copy)]
@@ -118,8 +131,16 @@ pub enum WidgetProperty {
     States {
         active_style: Style,
         separator_style: Style,
+        oneshot_style: Style,
     },
     Volume,
+    Quality {
+        high_style: Style,
+        medium_style: Style,
+        low_style: Style,
+        high_bitrate: u32,
+        low_bitrate: u32,
+    },
 }
 
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq)]
@@ -141,6 +162,7 @@ impl TryFrom<SongPropertyFile> for SongProperty {
             SongPropertyFile::Album => SongProperty::Album,
             SongPropertyFile::Duration => SongProperty::Duration,
             SongPropertyFile::Track => SongProperty::Track,
+            SongPropertyFile::Added => SongProperty::Added,
             SongPropertyFile::Other(name) => SongProperty::Other(name.leak()),
         })
     }
@@ -171,6 +193,7 @@ impl TryFrom<StatusPropertyFile> for StatusProperty {
             StatusPropertyFile::Single => StatusProperty::Single,
             StatusPropertyFile::Bitrate => StatusProperty::Bitrate,
             StatusPropertyFile::Crossfade => StatusProperty::Crossfade,
+            StatusPropertyFile::Output => StatusProperty::Output,
         })
     }
 }
@@ -199,9 +222,30 @@ impl TryFrom<PropertyFile<PropertyKindFile>> for Property<'static, PropertyKind>
                     PropertyKindFile::Widget(WidgetPropertyFile::States {
                         active_style,
                         separator_style,
-                    }) => PropertyKind::Widget(WidgetProperty::States {
-                        active_style: active_style.to_config_or(Some(Color::White), None)?,
-                        separator_style: separator_style.to_config_or(Some(Color::White), None)?,
+                        oneshot_style,
+                    }) => {
+                        let active_style = active_style.to_config_or(Some(Color::White), None)?;
+                        PropertyKind::Widget(WidgetProperty::States {
+                            active_style,
+                            separator_style: separator_style.to_config_or(Some(Color::White), None)?,
+                            oneshot_style: match oneshot_style {
+                                Some(style) => style.to_config_or(None, None)?,
+                                None => active_style.add_modifier(Modifier::REVERSED),
+                            },
+                        })
+                    }
+                    PropertyKindFile::Widget(WidgetPropertyFile::Quality {
+                        high_style,
+                        medium_style,
+                        low_style,
+                        high_bitrate,
+                        low_bitrate,
+                    }) => PropertyKind::Widget(WidgetProperty::Quality {
+                        high_style: high_style.to_config_or(Some(Color::Green), None)?,
+                        medium_style: medium_style.to_config_or(Some(Color::Yellow), None)?,
+                        low_style: low_style.to_config_or(Some(Color::Red), None)?,
+                        high_bitrate: high_bitrate.unwrap_or(700),
+                        low_bitrate: low_bitrate.unwrap_or(192),
                     }),
                 }),
                 PropertyKindFileOrText::Group(group) => {
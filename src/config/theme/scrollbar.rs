@@ -18,6 +18,26 @@ pub struct ScrollbarConfig {
     pub ends_style: Style,
     // Falls back to blue for foreground and default color for background
     pub thumb_style: Style,
+    pub visibility: ScrollbarVisibility,
+}
+
+#[derive(Debug, Default, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ScrollbarVisibility {
+    #[default]
+    Always,
+    Never,
+    /// Only shown when the content does not fit into the viewport
+    Auto,
+}
+
+impl ScrollbarVisibility {
+    pub fn is_visible(self, content_len: Option<usize>, viewport_len: Option<usize>) -> bool {
+        match self {
+            ScrollbarVisibility::Always => true,
+            ScrollbarVisibility::Never => false,
+            ScrollbarVisibility::Auto => content_len.unwrap_or(0) > viewport_len.unwrap_or(0),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
@@ -26,6 +46,8 @@ pub struct ScrollbarConfigFile {
     pub(super) track_style: Option<StyleFile>,
     pub(super) ends_style: Option<StyleFile>,
     pub(super) thumb_style: Option<StyleFile>,
+    #[serde(default)]
+    pub(super) visibility: ScrollbarVisibility,
 }
 
 impl Default for ScrollbarConfigFile {
@@ -47,6 +69,7 @@ impl Default for ScrollbarConfigFile {
                 bg: None,
                 modifiers: None,
             }),
+            visibility: ScrollbarVisibility::default(),
         }
     }
 }
@@ -63,6 +86,7 @@ impl ScrollbarConfigFile {
             ends_style: self.ends_style.to_config_or(Some(fallback_color), None)?,
             thumb_style: self.thumb_style.to_config_or(Some(Color::Blue), None)?,
             track_style: self.track_style.to_config_or(Some(fallback_color), None)?,
+            visibility: self.visibility,
         })
     }
 }
@@ -70,7 +94,11 @@ impl ScrollbarConfigFile {
 #[cfg(test)]
 #[allow(clippy::unwrap_used, clippy::needless_pass_by_value)]
 mod tests {
-    use crate::config::theme::{scrollbar::ScrollbarConfigFile, style::Modifiers, Style, StyleFile};
+    use crate::config::theme::{
+        scrollbar::{ScrollbarConfigFile, ScrollbarVisibility},
+        style::Modifiers,
+        Style, StyleFile,
+    };
     use ratatui::style::{Color as RC, Modifier as RM};
     use test_case::test_case;
 
@@ -86,6 +114,20 @@ mod tests {
         assert_eq!(result, ["a".to_owned(), "b".to_owned(), "c".to_owned(), "d".to_owned()]);
     }
 
+    #[test_case(ScrollbarVisibility::Always, Some(5),  Some(10), true  ; "always shown")]
+    #[test_case(ScrollbarVisibility::Never,  Some(20), Some(10), false ; "never shown")]
+    #[test_case(ScrollbarVisibility::Auto,   Some(5),  Some(10), false ; "auto omitted when content fits")]
+    #[test_case(ScrollbarVisibility::Auto,   Some(10), Some(10), false ; "auto omitted when content fills viewport")]
+    #[test_case(ScrollbarVisibility::Auto,   Some(11), Some(10), true  ; "auto shown when content overflows")]
+    fn visibility(
+        visibility: ScrollbarVisibility,
+        content_len: Option<usize>,
+        viewport_len: Option<usize>,
+        expected: bool,
+    ) {
+        assert_eq!(visibility.is_visible(content_len, viewport_len), expected);
+    }
+
     #[test_case(None,         None,         Style::default().fg(RC::Blue)                ; "uses default colors")]
     #[test_case(Some("none"), Some("none"), Style::default().fg(RC::Blue)                ; "uses default colors when whole value is None")]
     #[test_case(Some("red"),  Some("blue"), Style::default().fg(RC::Red).bg(RC::Blue)    ; "correctly maps provided colors")]
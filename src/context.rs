@@ -5,10 +5,12 @@ use crate::{
     mpd::{
         client::Client,
         commands::{Song, State, Status},
-        mpd_client::MpdClient,
+        mpd_client::{MpdClient, Tag},
     },
     shared::{
         events::ClientRequest,
+        ext::mpd_client::MpdClientExt,
+        favorites::{Favorites, FAVORITE_STICKER},
         lrc::{Lrc, LrcIndex},
         macros::status_warn,
         mpd_query::MpdQuerySync,
@@ -21,14 +23,34 @@ use crossbeam::channel::{bounded, SendError, Sender};
 
 pub struct AppContext {
     pub config: &'static Config,
+    /// Whether borders are drawn, starts out as `theme.draw_borders` and is flipped by the `ToggleBorders` action
+    pub draw_borders: bool,
     pub status: Status,
     pub queue: Vec<Song>,
     pub supported_commands: HashSet<String>,
+    pub supported_tags: HashSet<String>,
     pub app_event_sender: Sender<AppEvent>,
     pub work_sender: Sender<WorkRequest>,
     pub client_request_sender: Sender<ClientRequest>,
     pub needs_render: Cell<bool>,
     pub lrc_index: LrcIndex,
+    pub favorites: Favorites,
+}
+
+/// Tags provided by the server. Empty when they cannot be listed, every tag is then assumed to be supported, see
+/// [`AppContext::supports_tag`].
+pub fn fetch_supported_tags(client: &mut impl MpdClient) -> HashSet<String> {
+    match client.tag_types() {
+        Ok(tags) => {
+            let supported_tags: HashSet<String> = tags.into_iter().collect();
+            log::info!(supported_tags:? = supported_tags; "Supported tags by server");
+            supported_tags
+        }
+        Err(err) => {
+            status_warn!(error:? = err; "Failed to list tags supported by MPD, assuming all tags are supported");
+            HashSet::new()
+        }
+    }
 }
 
 #[bon]
@@ -40,29 +62,53 @@ impl AppContext {
         work_sender: Sender<WorkRequest>,
         client_request_sender: Sender<ClientRequest>,
     ) -> Result<Self> {
-        let status = client.get_status()?;
+        let mut status = client.get_status()?;
+        status.replay_gain_mode = client
+            .replay_gain_status()
+            .inspect_err(|err| log::warn!(error:? = err; "Failed to read replay gain mode"))
+            .ok();
+        status.output = client
+            .enabled_outputs()
+            .inspect_err(|err| log::warn!(error:? = err; "Failed to read enabled outputs"))
+            .ok();
         let queue = client.playlist_info()?.unwrap_or_default();
         let supported_commands: HashSet<String> = client.commands()?.0.into_iter().collect();
 
         log::info!(supported_commands:? = supported_commands; "Supported commands by server");
+        let supported_tags = fetch_supported_tags(client);
 
         if !supported_commands.contains("albumart") || !supported_commands.contains("readpicture") {
             config.album_art.method = ImageMethod::None;
             status_warn!("Album art is disabled because it is not supported by MPD");
         }
 
+        let favorites = if supported_commands.contains("sticker") {
+            match client.sticker_find(FAVORITE_STICKER) {
+                Ok(files) => Favorites::new(files.into_iter().collect()),
+                Err(err) => {
+                    log::warn!(error:? = err; "Failed to load favorite songs");
+                    Favorites::default()
+                }
+            }
+        } else {
+            Favorites::default()
+        };
+
         log::info!(config:? = config; "Resolved config");
 
         Ok(Self {
             lrc_index: LrcIndex::default(),
+            draw_borders: config.theme.draw_borders,
             config: config.leak(),
             status,
             queue,
             supported_commands,
+            supported_tags,
             app_event_sender,
             work_sender,
             client_request_sender,
             needs_render: Cell::new(false),
+            favorites,
         })
     }
 
@@ -131,6 +177,15 @@ impl AppContext {
         }
     }
 
+    /// Whether the server provides the tag. Tags are assumed to be supported if the server did not report any.
+    pub fn supports_tag(&self, tag: Tag) -> bool {
+        self.supported_tags.is_empty()
+            || self
+                .supported_tags
+                .iter()
+                .any(|supported| supported.eq_ignore_ascii_case(tag.as_str()))
+    }
+
     pub fn find_current_song_in_queue(&self) -> Option<(usize, &Song)> {
         if self.status.state == State::Stop {
             return None;
@@ -3,6 +3,7 @@ use drop_guard::{ClientDropGuard, DropGuard};
 use std::{
     collections::VecDeque,
     io::{self, Write},
+    sync::{Arc, Mutex},
     thread::Builder,
 };
 
@@ -16,21 +17,54 @@ use crate::shared::{
     macros::try_skip,
 };
 use crate::{
-    mpd::{client::Client, commands::idle::IdleEvent, mpd_client::MpdClient},
+    mpd::{
+        client::{Client, TcpOrUnixStream},
+        commands::idle::IdleEvent,
+        mpd_client::MpdClient,
+    },
     shared::macros::try_break,
 };
 
+/// Shared handle to the socket currently used by the client task. Lets other threads forcefully close the
+/// connection, which unblocks any pending read and makes the client task reconnect.
+#[derive(Clone, Default)]
+pub struct ConnectionHandle(Arc<Mutex<Option<TcpOrUnixStream>>>);
+
+impl ConnectionHandle {
+    fn set(&self, stream: Option<TcpOrUnixStream>) {
+        *self.0.lock().expect("Connection handle lock not to be poisoned") = stream;
+    }
+
+    pub fn force_reconnect(&self) -> io::Result<()> {
+        match self
+            .0
+            .lock()
+            .expect("Connection handle lock not to be poisoned")
+            .as_ref()
+        {
+            Some(stream) => stream.shutdown(),
+            None => Ok(()),
+        }
+    }
+}
+
 pub fn init(
     client_rx: Receiver<ClientRequest>,
     event_tx: Sender<AppEvent>,
     client: Client<'static>,
+    connection: ConnectionHandle,
 ) -> io::Result<std::thread::JoinHandle<()>> {
     std::thread::Builder::new()
         .name("client task".to_owned())
-        .spawn(move || client_task(&client_rx, &event_tx, client))
+        .spawn(move || client_task(&client_rx, &event_tx, client, &connection))
 }
 
-fn client_task(client_rx: &Receiver<ClientRequest>, event_tx: &Sender<AppEvent>, client: Client<'_>) {
+fn client_task(
+    client_rx: &Receiver<ClientRequest>,
+    event_tx: &Sender<AppEvent>,
+    client: Client<'_>,
+    connection: &ConnectionHandle,
+) {
     let (req2idle_tx, req2idle_rx) = &bounded::<Client<'_>>(0);
     let (idle2req_tx, idle2req_rx) = &bounded::<Client<'_>>(0);
     let (idle_entered_tx, idle_entered_rx) = &bounded::<()>(0);
@@ -62,6 +96,7 @@ fn client_task(client_rx: &Receiver<ClientRequest>, event_tx: &Sender<AppEvent>,
 
             if is_client_ok {
                 let mut client_write = client.stream.try_clone().expect("Client write clone to succeed");
+                connection.set(client.stream.try_clone().ok());
 
                 let idle = Builder::new()
                     .name("idle".to_string())
@@ -185,6 +220,7 @@ fn client_task(client_rx: &Receiver<ClientRequest>, event_tx: &Sender<AppEvent>,
 
                 idle.join().expect("idle thread not to panic");
                 work.join().expect("work thread not to panic");
+                connection.set(None);
             } else {
                 client_return_tx.send(client).expect("To be able to return the client");
             }
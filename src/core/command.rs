@@ -3,16 +3,22 @@ use itertools::Itertools;
 use std::{io::Write, path::PathBuf};
 
 use crate::{
-    config::{cli::Command, Config},
+    config::{
+        cli::{Command, OutputFormat},
+        Config,
+    },
     context::AppContext,
     mpd::{
         client::Client,
-        commands::{volume::Bound, IdleEvent},
+        commands::{status::OnOffOneshot, volume::Bound, IdleEvent, Song, Status},
         mpd_client::{Filter, MpdClient, Tag},
     },
     shared::{
+        events::ClientRequest,
+        ext::{duration::DurationExt, mpd_client::MpdClientExt},
         lrc::LrcIndex,
-        macros::{status_error, status_info},
+        macros::{status_error, status_info, status_warn},
+        mpd_query::MpdCommand,
         ytdlp::YtDlp,
     },
 };
@@ -108,21 +114,27 @@ impl Command {
             Command::ToggleOutput { id } => Ok(Box::new(move |client| Ok(client.toggle_output(id)?))),
             Command::EnableOutput { id } => Ok(Box::new(move |client| Ok(client.enable_output(id)?))),
             Command::DisableOutput { id } => Ok(Box::new(move |client| Ok(client.disable_output(id)?))),
-            Command::Status => Ok(Box::new(|client| {
-                println!("{}", serde_json::ser::to_string(&client.get_status()?)?);
+            Command::Status { output } => Ok(Box::new(move |client| {
+                println!("{}", format_status(&client.get_status()?, output)?);
                 Ok(())
             })),
-            Command::Song { path: Some(paths) } if paths.len() == 1 => Ok(Box::new(move |client| {
+            Command::Song {
+                path: Some(paths),
+                output,
+            } if paths.len() == 1 => Ok(Box::new(move |client| {
                 let path = &paths[0];
                 if let Some(song) = client.find_one(&[Filter::new(Tag::File, path.as_str())])? {
-                    println!("{}", serde_json::ser::to_string(&song)?);
+                    println!("{}", format_song(&song, output)?);
                     Ok(())
                 } else {
                     println!("Song with path '{path}' not found.");
                     std::process::exit(1);
                 }
             })),
-            Command::Song { path: Some(paths) } => Ok(Box::new(move |client| {
+            Command::Song {
+                path: Some(paths),
+                output,
+            } => Ok(Box::new(move |client| {
                 let mut songs = Vec::new();
                 for path in &paths {
                     if let Some(song) = client.find_one(&[Filter::new(Tag::File, path.as_str())])? {
@@ -132,13 +144,20 @@ impl Command {
                         std::process::exit(1);
                     }
                 }
-                println!("{}", serde_json::ser::to_string(&songs)?);
+                match output {
+                    OutputFormat::Json => println!("{}", serde_json::ser::to_string(&songs)?),
+                    OutputFormat::Text => {
+                        for song in &songs {
+                            println!("{}", format_song(song, output)?);
+                        }
+                    }
+                }
                 Ok(())
             })),
-            Command::Song { path: None } => Ok(Box::new(|client| {
+            Command::Song { path: None, output } => Ok(Box::new(move |client| {
                 let current_song = client.get_current_song()?;
                 if let Some(song) = current_song {
-                    println!("{}", serde_json::ser::to_string(&song)?);
+                    println!("{}", format_song(&song, output)?);
                     Ok(())
                 } else {
                     std::process::exit(1);
@@ -155,7 +174,7 @@ impl Command {
                     std::process::exit(3);
                 };
 
-                let album_art = client.find_album_art(&song.file)?;
+                let album_art = client.find_album_art(&song.file, config.album_art.max_file_size_bytes)?;
 
                 let Some(album_art) = album_art else {
                     std::process::exit(2);
@@ -179,6 +198,42 @@ impl Command {
     }
 }
 
+fn format_status(status: &Status, output: OutputFormat) -> Result<String> {
+    Ok(match output {
+        OutputFormat::Json => serde_json::ser::to_string(status)?,
+        OutputFormat::Text => {
+            let on_off = |value: bool| if value { "on" } else { "off" };
+            let oneshot = |value: OnOffOneshot| match value {
+                OnOffOneshot::On => "on",
+                OnOffOneshot::Off => "off",
+                OnOffOneshot::Oneshot => "oneshot",
+            };
+            format!(
+                "{} | {}/{} | volume: {}% | repeat: {} | random: {} | single: {} | consume: {}",
+                status.state.as_ref(),
+                status.elapsed.to_string(),
+                status.duration.to_string(),
+                status.volume.value(),
+                on_off(status.repeat),
+                on_off(status.random),
+                oneshot(status.single),
+                oneshot(status.consume),
+            )
+        }
+    })
+}
+
+fn format_song(song: &Song, output: OutputFormat) -> Result<String> {
+    Ok(match output {
+        OutputFormat::Json => serde_json::ser::to_string(song)?,
+        OutputFormat::Text => match (song.artist(), song.title()) {
+            (Some(artist), Some(title)) => format!("{artist} - {title}"),
+            (None, Some(title)) => title.clone(),
+            _ => song.file.clone(),
+        },
+    })
+}
+
 pub fn run_external_blocking<'a, E>(command: &[&str], envs: E) -> Result<()>
 where
     E: IntoIterator<Item = (&'a str, &'a str)> + std::fmt::Debug,
@@ -226,6 +281,109 @@ pub fn run_external<'a: 'static, K: Into<String>, V: Into<String>>(command: &'a
     });
 }
 
+/// Opens the song in the tag editor in the background and asks MPD to rescan it once the editor exits so that the
+/// new tags show up. The absolute path of the song is passed to the editor as the last argument.
+pub fn run_tag_editor(command: &'static [&'static str], song: &Song, context: &AppContext) {
+    if song.is_stream() {
+        status_warn!("Tags of streams cannot be edited");
+        return;
+    }
+    let Some(music_directory) = context.config.music_directory else {
+        status_warn!("Set music_directory in the config to open songs in the tag editor");
+        return;
+    };
+
+    let path = song.resolved_path(Some(music_directory));
+    let file = song.file.clone();
+    let client_request_sender = context.client_request_sender.clone();
+    std::thread::spawn(move || {
+        let command = command
+            .iter()
+            .copied()
+            .chain(std::iter::once(path.as_str()))
+            .collect_vec();
+        if let Err(err) = run_external_blocking(&command, std::iter::empty()) {
+            status_error!("{}", err);
+            return;
+        }
+
+        let update = MpdCommand {
+            callback: Box::new(move |client| {
+                client.update(Some(&file))?;
+                status_info!("Updating tags of '{file}'");
+                Ok(())
+            }),
+        };
+        if let Err(err) = client_request_sender.send(ClientRequest::Command(update)) {
+            log::error!(error:? = err; "Failed to request update after editing tags");
+        }
+    });
+}
+
+/// Name of the sticker holding the 0-5 star rating of a song, shared with other MPD clients
+pub const RATING_STICKER: &str = "rating";
+const MAX_RATING: u8 = 5;
+
+/// Changes the rating of the song by `delta` stars. A rating of zero removes the sticker altogether.
+pub fn change_rating(song: &Song, delta: i8, context: &AppContext) {
+    if song.is_stream() {
+        status_warn!("Streams cannot be rated");
+        return;
+    }
+    if !context.supported_commands.contains("sticker") {
+        status_warn!("Ratings require the sticker database to be enabled in MPD");
+        return;
+    }
+
+    let file = song.file.clone();
+    let title = song.title_str().to_owned();
+    context.command(move |client| {
+        let current = client.sticker_get(&file, RATING_STICKER)?;
+        let rating = changed_rating(current.as_deref(), delta);
+        if rating == 0 {
+            if current.is_some() {
+                client.sticker_delete(&file, RATING_STICKER)?;
+            }
+        } else {
+            client.sticker_set(&file, RATING_STICKER, &rating.to_string())?;
+        }
+        status_info!("Rating of '{title}' set to {rating}/{MAX_RATING}");
+        Ok(())
+    });
+}
+
+/// Marks the song as favorite with a sticker, or removes the mark when it already is one
+pub fn toggle_favorite(song: &Song, context: &AppContext) {
+    if song.is_stream() {
+        status_warn!("Streams cannot be marked as favorite");
+        return;
+    }
+    if !context.supported_commands.contains("sticker") {
+        status_warn!("Favorites require the sticker database to be enabled in MPD");
+        return;
+    }
+
+    let file = song.file.clone();
+    let title = song.title_str().to_owned();
+    context.command(move |client| {
+        if client.toggle_favorite(&file)? {
+            status_info!("'{title}' added to favorites");
+        } else {
+            status_info!("'{title}' removed from favorites");
+        }
+        Ok(())
+    });
+}
+
+/// Ratings outside of the 0-5 range, for example set by other clients, are clamped before being changed
+fn changed_rating(current: Option<&str>, delta: i8) -> u8 {
+    let current = current
+        .and_then(|v| v.trim().parse::<u8>().ok())
+        .unwrap_or(0)
+        .min(MAX_RATING);
+    current.saturating_add_signed(delta).min(MAX_RATING)
+}
+
 pub fn create_env<'a>(
     context: &AppContext,
     selected_songs_paths: impl IntoIterator<Item = &'a str>,
@@ -255,3 +413,140 @@ pub fn create_env<'a>(
 
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use crossbeam::channel::unbounded;
+    use itertools::Itertools;
+    use rstest::rstest;
+
+    use super::{changed_rating, format_song, format_status, run_tag_editor};
+    use crate::{
+        config::{cli::OutputFormat, Leak},
+        context::AppContext,
+        mpd::commands::{status::OnOffOneshot, Song, State, Status, Volume},
+        shared::events::ClientRequest,
+        tests::fixtures::app_context,
+    };
+
+    fn song() -> Song {
+        Song {
+            id: 5,
+            file: "artist/album/song.flac".to_owned(),
+            duration: Some(Duration::from_secs(125)),
+            metadata: HashMap::from([
+                ("title".to_owned(), "song".to_owned()),
+                ("artist".to_owned(), "artist".to_owned()),
+            ]),
+        }
+    }
+
+    fn status() -> Status {
+        Status {
+            volume: Volume::new(80),
+            state: State::Play,
+            repeat: true,
+            single: OnOffOneshot::Oneshot,
+            elapsed: Duration::from_secs(65),
+            duration: Duration::from_secs(125),
+            songid: Some(5),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn song_as_json() {
+        let result: serde_json::Value =
+            serde_json::from_str(&format_song(&song(), OutputFormat::Json).unwrap()).unwrap();
+
+        assert_eq!(result["file"], "artist/album/song.flac");
+        assert_eq!(result["id"], 5);
+        assert_eq!(result["metadata"]["title"], "song");
+        assert_eq!(result["metadata"]["artist"], "artist");
+    }
+
+    #[test]
+    fn status_as_json() {
+        let result: serde_json::Value =
+            serde_json::from_str(&format_status(&status(), OutputFormat::Json).unwrap()).unwrap();
+
+        assert_eq!(result["state"], "Play");
+        assert_eq!(result["volume"], 80);
+        assert_eq!(result["repeat"], true);
+        assert_eq!(result["songid"], 5);
+    }
+
+    #[test]
+    fn song_as_text() {
+        assert_eq!(format_song(&song(), OutputFormat::Text).unwrap(), "artist - song");
+    }
+
+    #[test]
+    fn status_as_text() {
+        assert_eq!(
+            format_status(&status(), OutputFormat::Text).unwrap(),
+            "Playing | 1:05/2:05 | volume: 80% | repeat: on | random: off | single: oneshot | consume: off"
+        );
+    }
+
+    #[rstest]
+    fn tag_editor_receives_absolute_path_and_update_follows(mut app_context: AppContext) {
+        let out = std::env::temp_dir().join(format!("rmpc_tag_editor_test_{}", std::process::id()));
+        let mut config = app_context.config.clone();
+        config.music_directory = Some("/music/");
+        app_context.config = config.leak();
+        let (tx, rx) = unbounded();
+        app_context.client_request_sender = tx;
+        let command = [
+            "sh".to_owned(),
+            "-c".to_owned(),
+            format!("printf %s \"$1\" > '{}'", out.display()),
+        ]
+        .into_iter()
+        .chain(std::iter::once("sh".to_owned()))
+        .map(|v| v.leak() as &'static str)
+        .collect_vec()
+        .leak();
+
+        run_tag_editor(command, &song(), &app_context);
+
+        let request = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert!(matches!(request, ClientRequest::Command(_)));
+        assert_eq!(std::fs::read_to_string(&out).unwrap(), "/music/artist/album/song.flac");
+        std::fs::remove_file(out).unwrap();
+    }
+
+    #[rstest]
+    fn tag_editor_is_not_run_for_streams(mut app_context: AppContext) {
+        let mut config = app_context.config.clone();
+        config.music_directory = Some("/music/");
+        app_context.config = config.leak();
+        let (tx, rx) = unbounded();
+        app_context.client_request_sender = tx;
+        let stream = Song {
+            file: "https://radio.example.com/stream.mp3".to_owned(),
+            ..Default::default()
+        };
+
+        run_tag_editor(&["false"], &stream, &app_context);
+
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+    }
+
+    #[test]
+    fn rating_changes_within_bounds() {
+        assert_eq!(changed_rating(None, 1), 1);
+        assert_eq!(changed_rating(Some("3"), 1), 4);
+        assert_eq!(changed_rating(Some("3"), -1), 2);
+        assert_eq!(changed_rating(Some("5"), 1), 5);
+        assert_eq!(changed_rating(None, -1), 0);
+    }
+
+    #[test]
+    fn rating_from_other_clients_is_clamped() {
+        assert_eq!(changed_rating(Some("10"), -1), 4);
+        assert_eq!(changed_rating(Some("not a number"), 1), 1);
+    }
+}
@@ -0,0 +1,51 @@
+/// Holds back refreshes of the browsers while MPD is updating its database. Listing the database during a scan
+/// shows only the part which was scanned so far, so the refresh is done once the update finishes instead.
+#[derive(Debug, Default)]
+pub struct DatabaseRefresh {
+    pending: bool,
+}
+
+impl DatabaseRefresh {
+    /// Called when MPD reports a changed database. Returns whether the UI should be refreshed right away.
+    pub fn on_database_changed(&mut self, updating: bool) -> bool {
+        if updating {
+            self.pending = true;
+            return false;
+        }
+        self.pending = false;
+        true
+    }
+
+    /// Called with every new status. Returns whether a deferred refresh should be done now because the update
+    /// has finished.
+    pub fn on_status(&mut self, updating: bool) -> bool {
+        if self.pending && !updating {
+            self.pending = false;
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DatabaseRefresh;
+
+    #[test]
+    fn refresh_is_deferred_until_update_finishes() {
+        let mut refresh = DatabaseRefresh::default();
+
+        assert!(!refresh.on_database_changed(true));
+        assert!(!refresh.on_status(true));
+        assert!(refresh.on_status(false));
+        assert!(!refresh.on_status(false));
+    }
+
+    #[test]
+    fn refresh_is_immediate_without_update() {
+        let mut refresh = DatabaseRefresh::default();
+
+        assert!(refresh.on_database_changed(false));
+        assert!(!refresh.on_status(false));
+    }
+}
@@ -1,4 +1,9 @@
-use std::{collections::HashSet, io::Stdout, ops::Sub, time::Duration};
+use std::{
+    collections::HashSet,
+    io::Stdout,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use crossbeam::channel::{Receiver, RecvTimeoutError};
 use itertools::Itertools;
@@ -9,46 +14,55 @@ use ratatui::{
 };
 
 use crate::{
-    context::AppContext,
+    context::{fetch_supported_tags, AppContext},
     mpd::{
         commands::{IdleEvent, State},
         mpd_client::MpdClient,
     },
     shared::{
+        color_support::COLOR_SUPPORT,
         events::{AppEvent, WorkDone},
-        ext::{duration::DurationExt, error::ErrorExt},
+        ext::{duration::DurationExt, error::ErrorExt, mpd_client::MpdClientExt},
+        favorites::{Favorites, FAVORITE_STICKER},
         macros::{status_error, status_warn, try_skip},
         mpd_query::MpdQueryResult,
+        notification,
     },
-    ui::{KeyHandleResult, Ui, UiEvent},
+    ui::{modals::partitions::REFRESH_PARTITIONS, KeyHandleResult, Ui, UiEvent},
 };
 
 use super::{
     command::{create_env, run_external},
-    update_loop::UpdateLoop,
+    db_refresh::DatabaseRefresh,
+    now_playing::{NowPlaying, NowPlayingServer},
+    update_loop::{self, UpdateLoop},
 };
 
 pub const EXTERNAL_COMMAND: &str = "external_command";
 pub const GLOBAL_STATUS_UPDATE: &str = "global_status_update";
 pub const GLOBAL_VOLUME_UPDATE: &str = "global_volume_update";
 pub const GLOBAL_QUEUE_UPDATE: &str = "global_queue_update";
+pub const GLOBAL_FAVORITES_UPDATE: &str = "global_favorites_update";
+pub const GLOBAL_TAGS_UPDATE: &str = "global_tags_update";
 
 pub fn init(
     context: AppContext,
     event_rx: Receiver<AppEvent>,
     update_loop: UpdateLoop,
     terminal: Terminal<CrosstermBackend<Stdout>>,
+    last_render: Arc<Mutex<Instant>>,
 ) -> std::io::Result<std::thread::JoinHandle<Terminal<CrosstermBackend<Stdout>>>> {
     std::thread::Builder::new()
         .name("main".to_owned())
-        .spawn(move || main_task(context, event_rx, update_loop, terminal))
+        .spawn(move || main_task(context, event_rx, update_loop, terminal, &last_render))
 }
 
-fn main_task<B: Backend + std::io::Write>(
+fn main_task<B: Backend>(
     mut context: AppContext,
     event_rx: Receiver<AppEvent>,
     mut render_loop: UpdateLoop,
     mut terminal: Terminal<B>,
+    shared_last_render: &Mutex<Instant>,
 ) -> Terminal<B> {
     let size = terminal.size().expect("To be able to get terminal size");
     let area = Rect::new(0, 0, size.width, size.height);
@@ -57,19 +71,32 @@ fn main_task<B: Backend + std::io::Write>(
     let mut render_wanted = false;
     let max_fps = 30f64;
     let min_frame_duration = Duration::from_secs_f64(1f64 / max_fps);
-    let mut last_render = std::time::Instant::now().sub(Duration::from_secs(10));
+    let mut last_render = Instant::now()
+        .checked_sub(Duration::from_secs(10))
+        .unwrap_or_else(Instant::now);
     let mut additional_evs = HashSet::new();
+    let mut db_refresh = DatabaseRefresh::default();
     let mut connected = true;
     ui.before_show(area, &mut context)
         .expect("Initial render init to succeed");
+    let now_playing = context
+        .config
+        .now_playing_socket
+        .and_then(|path| match NowPlayingServer::init(path) {
+            Ok(server) => Some(server),
+            Err(err) => {
+                status_error!(error:? = err; "Failed to start now playing socket: '{}'", err.to_status());
+                None
+            }
+        });
 
     loop {
-        let now = std::time::Instant::now();
+        let now = Instant::now();
 
         let event = if render_wanted {
             match event_receiver.recv_timeout(
                 min_frame_duration
-                    .checked_sub(now - last_render)
+                    .checked_sub(now.saturating_duration_since(last_render))
                     .unwrap_or(Duration::ZERO),
             ) {
                 Ok(v) => Some(v),
@@ -96,6 +123,13 @@ fn main_task<B: Backend + std::io::Write>(
                         render_wanted = true;
                     }
                 },
+                AppEvent::Shutdown => {
+                    log::info!("Shutting down");
+                    if let Err(err) = ui.on_event(UiEvent::Exit, &mut context) {
+                        log::error!(error:? = err; "UI failed to handle exit event");
+                    }
+                    break;
+                }
                 AppEvent::UserMouseInput(ev) => match ui.handle_mouse_event(ev, &mut context) {
                     Ok(()) => {}
                     Err(err) => {
@@ -103,6 +137,11 @@ fn main_task<B: Backend + std::io::Write>(
                         render_wanted = true;
                     }
                 },
+                AppEvent::UserIdle => {
+                    if let Err(err) = ui.show_screensaver(&mut context) {
+                        log::error!(error:? = err; "UI failed to show screensaver");
+                    }
+                }
                 AppEvent::Status(message, level) => {
                     ui.display_message(message, level);
                     render_wanted = true;
@@ -115,6 +154,12 @@ fn main_task<B: Backend + std::io::Write>(
                 AppEvent::IdleEvent(event) => {
                     handle_idle_event(event, &context, &mut additional_evs);
                     for ev in additional_evs.drain() {
+                        if ev == UiEvent::Database
+                            && !db_refresh.on_database_changed(context.status.updating_db.is_some())
+                        {
+                            log::debug!("Database changed during an update, deferring refresh");
+                            continue;
+                        }
                         if let Err(err) = ui.on_event(ev, &mut context) {
                             status_error!(error:? = err, event:?; "UI failed to handle idle event, event: '{:?}', error: '{}'", event, err.to_status());
                         }
@@ -135,27 +180,36 @@ fn main_task<B: Backend + std::io::Write>(
                         (GLOBAL_STATUS_UPDATE, None, MpdQueryResult::Status(status)) => {
                             let current_song_id = context.find_current_song_in_queue().map(|(_, song)| song.id);
                             let current_status = context.status.state;
-                            context.status = status;
+                            let replay_gain_mode = context.status.replay_gain_mode;
+                            let output = context.status.output.take();
+                            context.status = *status;
+                            // Only refreshed on options and output changes
+                            context.status.replay_gain_mode = context.status.replay_gain_mode.or(replay_gain_mode);
+                            context.status.output = context.status.output.take().or(output);
+                            ui.on_volume_changed(&context);
+                            if db_refresh.on_status(context.status.updating_db.is_some()) {
+                                if let Err(err) = ui.on_event(UiEvent::Database, &mut context) {
+                                    status_error!(error:? = err; "UI failed to handle database event, error: '{}'", err.to_status());
+                                }
+                            }
                             let mut song_changed = false;
 
-                            match context.status.state {
-                                State::Play => {
-                                    if current_status != context.status.state {
-                                        try_skip!(render_loop.start(), "Failed to start render loop");
-                                    }
+                            if current_status != context.status.state {
+                                if update_loop::is_needed(
+                                    context.status.state,
+                                    context.config.progress_update_while_paused,
+                                ) {
+                                    try_skip!(render_loop.start(), "Failed to start render loop");
+                                } else {
+                                    try_skip!(render_loop.stop(), "Failed to stop render loop");
                                 }
-                                State::Pause => {
-                                    if current_status != context.status.state {
-                                        try_skip!(render_loop.stop(), "Failed to stop render loop");
-                                    }
-                                }
-                                State::Stop => {
-                                    song_changed = true;
-                                    if current_status != context.status.state {
-                                        try_skip!(render_loop.stop(), "Failed to stop render loop");
-                                    }
+                                if let Err(err) = ui.on_player_state_changed(&mut context) {
+                                    log::error!(error:? = err; "UI failed to show screensaver");
                                 }
                             }
+                            if context.status.state == State::Stop {
+                                song_changed = true;
+                            }
 
                             if let Some((_, song)) = context.find_current_song_in_queue() {
                                 if Some(song.id) != current_song_id {
@@ -176,6 +230,9 @@ fn main_task<B: Backend + std::io::Write>(
                                             .collect_vec();
                                         run_external(command, env);
                                     }
+                                    if context.config.notify_on_song_change {
+                                        try_skip!(notification::notify_song(song), "Failed to send song notification");
+                                    }
                                     song_changed = true;
                                 }
                             }
@@ -184,16 +241,26 @@ fn main_task<B: Backend + std::io::Write>(
                                     status_error!(error:? = err; "UI failed to handle idle event, error: '{}'", err.to_status());
                                 }
                             }
+                            publish_now_playing(now_playing.as_ref(), &context);
                             render_wanted = true;
                         }
                         ("global_volume_update", None, MpdQueryResult::Volume(volume)) => {
                             context.status.volume = volume;
+                            ui.on_volume_changed(&context);
+                            publish_now_playing(now_playing.as_ref(), &context);
                             render_wanted = true;
                         }
                         ("global_queue_update", None, MpdQueryResult::Queue(queue)) => {
                             context.queue = queue.unwrap_or_default();
                             render_wanted = true;
                         }
+                        (GLOBAL_FAVORITES_UPDATE, None, MpdQueryResult::Favorites(files)) => {
+                            context.favorites = Favorites::new(files);
+                            render_wanted = true;
+                        }
+                        (GLOBAL_TAGS_UPDATE, None, MpdQueryResult::SupportedTags(tags)) => {
+                            context.supported_tags = tags;
+                        }
                         (EXTERNAL_COMMAND, None, MpdQueryResult::ExternalCommand(command, songs)) => {
                             let songs = songs.iter().map(|s| s.file.as_str());
                             run_external(command, create_env(&context, songs));
@@ -226,6 +293,16 @@ fn main_task<B: Backend + std::io::Write>(
                     for ev in [IdleEvent::Player, IdleEvent::Playlist, IdleEvent::Options] {
                         handle_idle_event(ev, &context, &mut additional_evs);
                     }
+                    // The server might have been restarted with a different configuration
+                    context
+                        .query()
+                        .id(GLOBAL_TAGS_UPDATE)
+                        .replace_id(GLOBAL_TAGS_UPDATE)
+                        .query(|client| Ok(MpdQueryResult::SupportedTags(fetch_supported_tags(client))));
+                    // Favorites might have changed while disconnected
+                    if context.supported_commands.contains("sticker") {
+                        handle_idle_event(IdleEvent::Sticker, &context, &mut additional_evs);
+                    }
                     if let Err(err) = ui.on_event(UiEvent::Reconnected, &mut context) {
                         log::error!(error:? = err, event:?; "UI failed to handle resize event");
                     }
@@ -245,7 +322,7 @@ fn main_task<B: Backend + std::io::Write>(
             }
         }
         if render_wanted {
-            let till_next_frame = min_frame_duration.saturating_sub(now.duration_since(last_render));
+            let till_next_frame = min_frame_duration.saturating_sub(now.saturating_duration_since(last_render));
             if till_next_frame != Duration::ZERO {
                 continue;
             }
@@ -254,6 +331,7 @@ fn main_task<B: Backend + std::io::Write>(
                     if let Err(err) = ui.render(frame, &mut context) {
                         log::error!(error:? = err; "Failed to render a frame");
                     };
+                    COLOR_SUPPORT.downsample_buffer(frame.buffer_mut());
                 })
                 .expect("Expected render to succeed");
 
@@ -261,11 +339,26 @@ fn main_task<B: Backend + std::io::Write>(
             last_render = now;
             render_wanted = false;
         }
+        if !render_wanted {
+            // Either a frame was just rendered or there is nothing to render, let the watchdog know that
+            // the loop is still alive
+            *shared_last_render.lock().expect("Last render lock not to be poisoned") = Instant::now();
+        }
     }
 
     terminal
 }
 
+fn publish_now_playing(server: Option<&NowPlayingServer>, context: &AppContext) {
+    let Some(server) = server else {
+        return;
+    };
+    let song = context.find_current_song_in_queue().map(|(_, song)| song);
+    if let Err(err) = server.publish(&NowPlaying::new(&context.status, song)) {
+        log::error!(error:? = err; "Failed to publish now playing state");
+    }
+}
+
 fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut HashSet<UiEvent>) {
     match event {
         IdleEvent::Mixer if context.supported_commands.contains("getvol") => {
@@ -280,21 +373,25 @@ fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut
                 .query()
                 .id(GLOBAL_STATUS_UPDATE)
                 .replace_id("status")
-                .query(move |client| Ok(MpdQueryResult::Status(client.get_status()?)));
+                .query(move |client| Ok(MpdQueryResult::Status(Box::new(client.get_status()?))));
         }
         IdleEvent::Options => {
             context
                 .query()
                 .id(GLOBAL_STATUS_UPDATE)
                 .replace_id("status")
-                .query(move |client| Ok(MpdQueryResult::Status(client.get_status()?)));
+                .query(move |client| {
+                    let mut status = client.get_status()?;
+                    status.replay_gain_mode = Some(client.replay_gain_status()?);
+                    Ok(MpdQueryResult::Status(Box::new(status)))
+                });
         }
-        IdleEvent::Player => {
+        IdleEvent::Player | IdleEvent::Update => {
             context
                 .query()
                 .id(GLOBAL_STATUS_UPDATE)
                 .replace_id("status")
-                .query(move |client| Ok(MpdQueryResult::Status(client.get_status()?)));
+                .query(move |client| Ok(MpdQueryResult::Status(Box::new(client.get_status()?))));
         }
         IdleEvent::Playlist => {
             context
@@ -303,16 +400,39 @@ fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut
                 .replace_id("playlist")
                 .query(move |client| Ok(MpdQueryResult::Queue(client.playlist_info()?)));
         }
+        IdleEvent::Output => {
+            context
+                .query()
+                .id(GLOBAL_STATUS_UPDATE)
+                .replace_id("output")
+                .query(move |client| {
+                    let mut status = client.get_status()?;
+                    status.output = Some(client.enabled_outputs()?);
+                    Ok(MpdQueryResult::Status(Box::new(status)))
+                });
+        }
         IdleEvent::StoredPlaylist => {}
         IdleEvent::Database => {}
-        IdleEvent::Update => {}
-        IdleEvent::Output
-        | IdleEvent::Partition
-        | IdleEvent::Sticker
-        | IdleEvent::Subscription
-        | IdleEvent::Message
-        | IdleEvent::Neighbor
-        | IdleEvent::Mount => {
+        // Panes showing ratings refresh them on the corresponding ui event
+        IdleEvent::Sticker => {
+            context
+                .query()
+                .id(GLOBAL_FAVORITES_UPDATE)
+                .replace_id("favorites")
+                .query(move |client| {
+                    Ok(MpdQueryResult::Favorites(
+                        client.sticker_find(FAVORITE_STICKER)?.into_iter().collect(),
+                    ))
+                });
+        }
+        IdleEvent::Partition => {
+            context
+                .query()
+                .id(REFRESH_PARTITIONS)
+                .replace_id(REFRESH_PARTITIONS)
+                .query(move |client| Ok(MpdQueryResult::Partitions(client.list_partitions()?.0)));
+        }
+        IdleEvent::Subscription | IdleEvent::Message | IdleEvent::Neighbor | IdleEvent::Mount => {
             log::warn!(event:?; "Received unhandled event");
         }
     };
@@ -321,3 +441,43 @@ fn handle_idle_event(event: IdleEvent, context: &AppContext, result_ui_evs: &mut
         result_ui_evs.insert(ev);
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        sync::Mutex,
+        time::{Duration, Instant},
+    };
+
+    use crossbeam::channel::unbounded;
+    use ratatui::{backend::TestBackend, Terminal};
+    use rstest::rstest;
+
+    use super::main_task;
+    use crate::{
+        context::AppContext, core::update_loop::UpdateLoop, shared::events::AppEvent, tests::fixtures::app_context,
+    };
+
+    #[rstest]
+    fn shutdown_event_exits_the_event_loop(app_context: AppContext) {
+        let (event_tx, event_rx) = unbounded();
+        let (done_tx, done_rx) = unbounded();
+        let render_loop = UpdateLoop::try_new(unbounded().0, None).unwrap();
+        let terminal = Terminal::new(TestBackend::new(100, 100)).unwrap();
+        event_tx.send(AppEvent::Shutdown).unwrap();
+
+        std::thread::spawn(move || {
+            main_task(
+                app_context,
+                event_rx,
+                render_loop,
+                terminal,
+                &Mutex::new(Instant::now()),
+            );
+            done_tx.send(()).unwrap();
+        });
+
+        assert!(done_rx.recv_timeout(Duration::from_secs(5)).is_ok());
+    }
+}
@@ -1,20 +1,33 @@
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crossbeam::channel::Sender;
 use crossterm::event::Event;
 
 use crate::shared::{events::AppEvent, mouse_event::MouseEventTracker};
 
-pub fn init(event_tx: Sender<AppEvent>) -> std::io::Result<std::thread::JoinHandle<()>> {
+pub fn init(
+    event_tx: Sender<AppEvent>,
+    idle_timeout: Option<Duration>,
+) -> std::io::Result<std::thread::JoinHandle<()>> {
     std::thread::Builder::new()
         .name("input".to_owned())
-        .spawn(move || input_poll_task(&event_tx))
+        .spawn(move || input_poll_task(&event_tx, idle_timeout))
 }
 
-fn input_poll_task(event_tx: &Sender<AppEvent>) {
+fn input_poll_task(event_tx: &Sender<AppEvent>, idle_timeout: Option<Duration>) {
     let mut mouse_event_tracker = MouseEventTracker::default();
+    let mut inactivity_tracker = InactivityTracker::new(idle_timeout, Instant::now());
     loop {
-        match crossterm::event::poll(Duration::from_millis(250)) {
+        let poll_result = crossterm::event::poll(Duration::from_millis(250));
+        if matches!(poll_result, Ok(true)) {
+            inactivity_tracker.input(Instant::now());
+        } else if inactivity_tracker.check(Instant::now()) {
+            if let Err(err) = event_tx.send(AppEvent::UserIdle) {
+                log::error!(error:? = err; "Failed to send user idle event");
+            }
+        }
+
+        match poll_result {
             Ok(true) => match crossterm::event::read() {
                 Ok(Event::Mouse(mouse)) => {
                     if let Some(ev) = mouse_event_tracker.track_and_get(mouse) {
@@ -46,3 +59,77 @@ fn input_poll_task(event_tx: &Sender<AppEvent>) {
         }
     }
 }
+
+/// Keeps track of the time of the last user input and reports when no input was received for the configured timeout.
+#[derive(Debug)]
+struct InactivityTracker {
+    timeout: Option<Duration>,
+    last_input: Instant,
+    reported: bool,
+}
+
+impl InactivityTracker {
+    fn new(timeout: Option<Duration>, now: Instant) -> Self {
+        Self {
+            timeout,
+            last_input: now,
+            reported: false,
+        }
+    }
+
+    fn input(&mut self, now: Instant) {
+        self.last_input = now;
+        self.reported = false;
+    }
+
+    /// Returns true exactly once after the timeout elapsed without any input
+    fn check(&mut self, now: Instant) -> bool {
+        let Some(timeout) = self.timeout else {
+            return false;
+        };
+
+        if self.reported || now.duration_since(self.last_input) < timeout {
+            return false;
+        }
+
+        self.reported = true;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::InactivityTracker;
+
+    #[test]
+    fn reports_idle_after_timeout() {
+        let start = Instant::now();
+        let mut tracker = InactivityTracker::new(Some(Duration::from_secs(60)), start);
+
+        assert!(!tracker.check(start + Duration::from_secs(59)));
+        assert!(tracker.check(start + Duration::from_secs(60)));
+        assert!(!tracker.check(start + Duration::from_secs(61)));
+    }
+
+    #[test]
+    fn input_resets_timeout() {
+        let start = Instant::now();
+        let mut tracker = InactivityTracker::new(Some(Duration::from_secs(60)), start);
+
+        assert!(tracker.check(start + Duration::from_secs(60)));
+        tracker.input(start + Duration::from_secs(70));
+
+        assert!(!tracker.check(start + Duration::from_secs(100)));
+        assert!(tracker.check(start + Duration::from_secs(130)));
+    }
+
+    #[test]
+    fn never_reports_without_timeout() {
+        let start = Instant::now();
+        let mut tracker = InactivityTracker::new(None, start);
+
+        assert!(!tracker.check(start + Duration::from_secs(3600)));
+    }
+}
@@ -1,6 +1,10 @@
 pub mod client;
 pub mod command;
+pub mod db_refresh;
 pub mod event_loop;
 pub mod input;
+pub mod now_playing;
+pub mod signal;
 pub mod update_loop;
+pub mod watchdog;
 pub mod work;
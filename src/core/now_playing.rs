@@ -0,0 +1,243 @@
+use std::{
+    io::{ErrorKind, Write},
+    os::unix::{
+        fs::FileTypeExt,
+        net::{UnixListener, UnixStream},
+    },
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::{bail, Context, Result};
+use serde::Serialize;
+
+use crate::mpd::commands::{volume::Bound, Song, Status};
+
+const WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Serves the currently playing song over a unix socket so that external programs like status bars can subscribe
+/// to changes instead of polling MPD. Every connected client receives one JSON object per line whenever the
+/// song, playback state or volume changes, and the last known state right after connecting.
+#[derive(Debug)]
+pub struct NowPlayingServer {
+    path: PathBuf,
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    subscribers: Vec<UnixStream>,
+    last_frame: Option<String>,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct NowPlaying<'a> {
+    pub state: &'a str,
+    pub volume: u8,
+    pub file: Option<&'a str>,
+    pub title: Option<&'a str>,
+    pub artist: Option<&'a str>,
+    pub album: Option<&'a str>,
+    pub duration_secs: Option<u64>,
+}
+
+impl<'a> NowPlaying<'a> {
+    pub fn new(status: &'a Status, song: Option<&'a Song>) -> Self {
+        Self {
+            state: status.state.as_ref(),
+            volume: *status.volume.value(),
+            file: song.map(|s| s.file.as_str()),
+            title: song.and_then(|s| s.title()).map(String::as_str),
+            artist: song.and_then(|s| s.artist()).map(String::as_str),
+            album: song.and_then(|s| s.album()).map(String::as_str),
+            duration_secs: song.and_then(|s| s.duration).map(|d| d.as_secs()),
+        }
+    }
+}
+
+impl NowPlayingServer {
+    pub fn init(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        remove_stale_socket(&path)?;
+        let listener = UnixListener::bind(&path)
+            .with_context(|| format!("Failed to bind now playing socket at '{}'", path.display()))?;
+
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let thread_shared = Arc::clone(&shared);
+        std::thread::Builder::new()
+            .name("now_playing".to_owned())
+            .spawn(move || {
+                for stream in listener.incoming() {
+                    let mut stream = match stream {
+                        Ok(stream) => stream,
+                        Err(err) => {
+                            log::warn!(err:?; "Failed to accept now playing subscriber");
+                            continue;
+                        }
+                    };
+                    // Publishing happens on the main thread, a subscriber which stopped reading must not block it
+                    if let Err(err) = stream.set_write_timeout(Some(WRITE_TIMEOUT)) {
+                        log::warn!(err:?; "Failed to set write timeout on now playing subscriber");
+                        continue;
+                    }
+                    let mut shared = thread_shared.lock().expect("Now playing lock not to be poisoned");
+                    if let Some(frame) = &shared.last_frame {
+                        if stream.write_all(frame.as_bytes()).is_err() {
+                            continue;
+                        }
+                    }
+                    shared.subscribers.push(stream);
+                }
+            })?;
+
+        Ok(Self { path, shared })
+    }
+
+    /// Sends the state to all subscribers if it differs from the last sent one. Subscribers which can no longer
+    /// be written to are dropped.
+    pub fn publish(&self, now_playing: &NowPlaying) -> Result<()> {
+        let mut frame = serde_json::to_string(now_playing)?;
+        frame.push('\n');
+
+        let mut shared = self.shared.lock().expect("Now playing lock not to be poisoned");
+        if shared.last_frame.as_ref() == Some(&frame) {
+            return Ok(());
+        }
+        shared
+            .subscribers
+            .retain_mut(|subscriber| subscriber.write_all(frame.as_bytes()).is_ok());
+        shared.last_frame = Some(frame);
+
+        Ok(())
+    }
+
+    #[cfg(test)]
+    fn subscriber_count(&self) -> usize {
+        self.shared
+            .lock()
+            .expect("Now playing lock not to be poisoned")
+            .subscribers
+            .len()
+    }
+}
+
+/// A socket left over from a previous run which was not shut down cleanly would make the bind fail. Only a socket
+/// nobody listens on anymore is removed, anything else at the path is left alone.
+fn remove_stale_socket(path: &Path) -> Result<()> {
+    let metadata = match std::fs::symlink_metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(()),
+        Err(err) => {
+            return Err(err).with_context(|| format!("Failed to inspect now playing socket at '{}'", path.display()));
+        }
+    };
+    if !metadata.file_type().is_socket() {
+        bail!(
+            "Now playing socket path '{}' exists and is not a socket",
+            path.display()
+        );
+    }
+    if UnixStream::connect(path).is_ok() {
+        bail!("Now playing socket at '{}' is already in use", path.display());
+    }
+
+    std::fs::remove_file(path)
+        .with_context(|| format!("Failed to remove stale now playing socket at '{}'", path.display()))
+}
+
+impl Drop for NowPlayingServer {
+    fn drop(&mut self) {
+        if let Err(err) = std::fs::remove_file(&self.path) {
+            log::warn!(err:?; "Failed to remove now playing socket");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        io::{BufRead, BufReader},
+        os::unix::net::{UnixListener, UnixStream},
+        path::PathBuf,
+        time::{Duration, Instant},
+    };
+
+    use super::{NowPlaying, NowPlayingServer};
+    use crate::mpd::commands::{Song, State, Status};
+
+    fn socket_path(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("rmpc_now_playing_{name}_{}.sock", std::process::id()))
+    }
+
+    #[test]
+    fn stale_socket_is_replaced() {
+        let path = socket_path("stale");
+        drop(UnixListener::bind(&path).expect("socket to be bound"));
+        assert!(path.exists());
+
+        let server = NowPlayingServer::init(&path);
+
+        assert!(server.is_ok());
+    }
+
+    #[test]
+    fn socket_in_use_is_not_removed() {
+        let path = socket_path("in_use");
+        let _listener = UnixListener::bind(&path).expect("socket to be bound");
+
+        assert!(NowPlayingServer::init(&path).is_err());
+        assert!(UnixStream::connect(&path).is_ok());
+        std::fs::remove_file(&path).expect("socket to be removed");
+    }
+
+    #[test]
+    fn regular_file_is_not_removed() {
+        let path = socket_path("regular_file");
+        std::fs::write(&path, "keep me").expect("file to be written");
+
+        assert!(NowPlayingServer::init(&path).is_err());
+        assert_eq!(std::fs::read_to_string(&path).expect("file to be read"), "keep me");
+        std::fs::remove_file(&path).expect("file to be removed");
+    }
+
+    #[test]
+    fn song_change_is_written_to_subscriber() {
+        let path = socket_path("test");
+        let server = NowPlayingServer::init(&path).expect("server to start");
+        let stream = UnixStream::connect(&path).expect("client to connect");
+        stream
+            .set_read_timeout(Some(Duration::from_secs(5)))
+            .expect("timeout to be set");
+        let start = Instant::now();
+        while server.subscriber_count() == 0 && start.elapsed() < Duration::from_secs(5) {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+
+        let status = Status {
+            state: State::Play,
+            ..Default::default()
+        };
+        let song = Song {
+            file: "artist/album/song.flac".to_owned(),
+            duration: Some(Duration::from_secs(125)),
+            metadata: HashMap::from([
+                ("title".to_owned(), "song".to_owned()),
+                ("artist".to_owned(), "artist".to_owned()),
+            ]),
+            ..Default::default()
+        };
+        server
+            .publish(&NowPlaying::new(&status, Some(&song)))
+            .expect("publish to succeed");
+
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).expect("frame to be read");
+        assert_eq!(
+            line,
+            "{\"state\":\"Playing\",\"volume\":0,\"file\":\"artist/album/song.flac\",\"title\":\"song\",\
+             \"artist\":\"artist\",\"album\":null,\"duration_secs\":125}\n"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use std::sync::{atomic::AtomicBool, Arc};
+
+use crossbeam::channel::Sender;
+use signal_hook::{
+    consts::{SIGHUP, SIGINT, SIGTERM},
+    iterator::Signals,
+};
+
+use crate::shared::events::AppEvent;
+
+const SHUTDOWN_SIGNALS: [i32; 3] = [SIGTERM, SIGINT, SIGHUP];
+
+/// Requests a clean shutdown through the event loop when rmpc receives a termination signal. Receiving the
+/// signal a second time terminates rmpc immediately in case the clean shutdown got stuck.
+pub fn init(event_tx: Sender<AppEvent>) -> std::io::Result<std::thread::JoinHandle<()>> {
+    let terminate_now = Arc::new(AtomicBool::new(false));
+    for signal in SHUTDOWN_SIGNALS {
+        signal_hook::flag::register_conditional_shutdown(signal, 1, Arc::clone(&terminate_now))?;
+        signal_hook::flag::register(signal, Arc::clone(&terminate_now))?;
+    }
+
+    let mut signals = Signals::new(SHUTDOWN_SIGNALS)?;
+    std::thread::Builder::new().name("signals".to_owned()).spawn(move || {
+        for signal in signals.forever() {
+            log::info!(signal; "Received termination signal");
+            if let Err(err) = event_tx.send(AppEvent::Shutdown) {
+                log::error!(error:? = err; "Failed to send shutdown event");
+            }
+        }
+    })
+}
@@ -4,13 +4,23 @@ use std::time::Duration;
 use crossbeam::channel::{unbounded, Sender, TryRecvError};
 
 use crate::{
-    mpd::mpd_client::MpdClient,
+    mpd::{commands::State, mpd_client::MpdClient},
     shared::{
         events::ClientRequest,
         mpd_query::{MpdQuery, MpdQueryResult},
     },
 };
 
+/// Whether the status has to be polled in the given state to keep the progress up to date. There is no progress
+/// while stopped so the loop never runs then.
+pub fn is_needed(state: State, while_paused: bool) -> bool {
+    match state {
+        State::Play => true,
+        State::Pause => while_paused,
+        State::Stop => false,
+    }
+}
+
 enum LoopEvent {
     Start,
     Stop,
@@ -52,7 +62,7 @@ impl UpdateLoop {
                     id: "global_status_update",
                     target: None,
                     replace_id: None,
-                    callback: Box::new(move |client| Ok(MpdQueryResult::Status(client.get_status()?))),
+                    callback: Box::new(move |client| Ok(MpdQueryResult::Status(Box::new(client.get_status()?)))),
                 })) {
                     log::error!(error:? = err; "Failed to send status update request");
                 }
@@ -77,3 +87,21 @@ impl UpdateLoop {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::is_needed;
+    use crate::mpd::commands::State;
+
+    #[test_case(State::Play, false, true)]
+    #[test_case(State::Play, true, true)]
+    #[test_case(State::Pause, false, false)]
+    #[test_case(State::Pause, true, true)]
+    #[test_case(State::Stop, false, false)]
+    #[test_case(State::Stop, true, false)]
+    fn loop_runs_only_when_progress_changes(state: State, while_paused: bool, expected: bool) {
+        assert_eq!(is_needed(state, while_paused), expected);
+    }
+}
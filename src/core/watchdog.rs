@@ -0,0 +1,102 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant, SystemTime},
+};
+
+use crossbeam::channel::Receiver;
+
+use crate::shared::events::AppEvent;
+
+use super::client::ConnectionHandle;
+
+/// How long the main loop can go without rendering while there are events waiting to be processed
+const STALL_THRESHOLD: Duration = Duration::from_secs(5);
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+/// How much more the wall clock can advance than the monotonic clock between two checks before it is
+/// considered a resume from suspend
+const SUSPEND_THRESHOLD: Duration = Duration::from_secs(5);
+
+/// Watches the main loop and forces a reconnect to MPD when it stops processing events, which usually
+/// means it is stuck waiting on a client call to a dead socket. `last_render` is updated by the main loop
+/// whenever it renders a frame or has nothing left to render.
+///
+/// Also reconnects after the system resumes from suspend because the socket is often dead by then but takes
+/// a long time to error out. The monotonic clock does not advance while suspended, the wall clock does.
+pub fn init(
+    event_rx: Receiver<AppEvent>,
+    last_render: Arc<Mutex<Instant>>,
+    connection: ConnectionHandle,
+) -> std::io::Result<std::thread::JoinHandle<()>> {
+    std::thread::Builder::new().name("watchdog".to_owned()).spawn(move || {
+        let mut reported_render = None;
+        let mut last_check = (Instant::now(), SystemTime::now());
+        loop {
+            std::thread::sleep(CHECK_INTERVAL);
+
+            let now = (Instant::now(), SystemTime::now());
+            let monotonic = now.0.saturating_duration_since(last_check.0);
+            // Wall clock going backwards is a clock adjustment and not a suspend
+            let wall_clock = now.1.duration_since(last_check.1).unwrap_or_default();
+            last_check = now;
+            if is_resumed_from_suspend(monotonic, wall_clock, SUSPEND_THRESHOLD) {
+                log::info!(monotonic:?, wall_clock:?; "System resumed from suspend, reconnecting to MPD");
+                if let Err(err) = connection.force_reconnect() {
+                    log::error!(error:? = err; "Failed to close MPD connection");
+                }
+                continue;
+            }
+
+            let last_render = *last_render.lock().expect("Last render lock not to be poisoned");
+            let pending_events = event_rx.len();
+            if !is_stalled(last_render.elapsed(), pending_events, STALL_THRESHOLD) {
+                continue;
+            }
+            // Report each stall only once, the main loop renders again as soon as it recovers
+            if reported_render == Some(last_render) {
+                continue;
+            }
+            reported_render = Some(last_render);
+
+            log::warn!(since_last_render:? = last_render.elapsed(), pending_events; "Main loop appears to be stuck, forcing reconnect to MPD");
+            if let Err(err) = connection.force_reconnect() {
+                log::error!(error:? = err; "Failed to close MPD connection");
+            }
+        }
+    })
+}
+
+fn is_stalled(since_last_render: Duration, pending_events: usize, threshold: Duration) -> bool {
+    pending_events > 0 && since_last_render > threshold
+}
+
+fn is_resumed_from_suspend(monotonic: Duration, wall_clock: Duration, threshold: Duration) -> bool {
+    wall_clock.saturating_sub(monotonic) > threshold
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use test_case::test_case;
+
+    use super::{is_resumed_from_suspend, is_stalled};
+
+    const THRESHOLD: Duration = Duration::from_secs(5);
+
+    #[test_case(Duration::from_secs(6), 1, true; "pending events and no recent frame")]
+    #[test_case(Duration::from_secs(6), 0, false; "no pending events")]
+    #[test_case(Duration::from_secs(1), 10, false; "recent frame")]
+    #[test_case(Duration::from_secs(5), 1, false; "exactly at threshold")]
+    fn stall_detection(since_last_render: Duration, pending_events: usize, expected: bool) {
+        assert_eq!(is_stalled(since_last_render, pending_events, THRESHOLD), expected);
+    }
+
+    #[test_case(Duration::from_secs(1), Duration::from_secs(3600), true; "wall clock jumped over a suspend")]
+    #[test_case(Duration::from_secs(1), Duration::from_secs(1), false; "clocks in sync")]
+    #[test_case(Duration::from_secs(3), Duration::from_secs(3), false; "slow check without suspend")]
+    #[test_case(Duration::from_secs(1), Duration::from_secs(6), false; "exactly at threshold")]
+    #[test_case(Duration::from_secs(1), Duration::ZERO, false; "wall clock went backwards")]
+    fn suspend_detection(monotonic: Duration, wall_clock: Duration, expected: bool) {
+        assert_eq!(is_resumed_from_suspend(monotonic, wall_clock, THRESHOLD), expected);
+    }
+}
@@ -4,13 +4,16 @@ use std::path::PathBuf;
 use crossbeam::channel::{Receiver, Sender};
 
 use crate::{
-    config::Config,
+    config::{tabs::PaneType, Config},
+    mpd::{client::Client, mpd_client::MpdClient},
     shared::{
         events::{AppEvent, ClientRequest, WorkDone, WorkRequest},
+        library_tree::LibraryTree,
         lrc::LrcIndex,
         macros::try_skip,
-        mpd_query::MpdCommand,
+        mpd_query::{MpdCommand, MpdQueryResult},
     },
+    ui::panes::directories::PRELOAD_LIBRARY,
 };
 
 pub fn init(
@@ -48,5 +51,18 @@ fn handle_work_request(
             let index = LrcIndex::index(&PathBuf::from(lyrics_dir));
             Ok(WorkDone::LyricsIndexed { index })
         }
+        WorkRequest::PreloadLibrary => {
+            // Uses its own connection so that the dump does not hold up the commands on the shared one
+            let library = Client::init(config.address, config.password, "preload")
+                .and_then(|mut client| client.list_all_info(None))
+                .inspect_err(|err| log::error!(error:? = err; "Failed to preload the library"))
+                .ok()
+                .map(LibraryTree::new);
+            Ok(WorkDone::MpdCommandFinished {
+                id: PRELOAD_LIBRARY,
+                target: Some(PaneType::Directories),
+                data: MpdQueryResult::Library(library),
+            })
+        }
     }
 }
@@ -18,21 +18,25 @@
 use anyhow::Result;
 use config::ConfigFile;
 use context::AppContext;
-use core::update_loop::UpdateLoop;
+use core::update_loop::{self, UpdateLoop};
 use crossbeam::channel::unbounded;
 use log::info;
-use mpd::{client::Client, commands::State};
+use mpd::client::Client;
 use shared::{
     dependencies::DEPENDENCIES,
     events::{AppEvent, ClientRequest, WorkRequest},
     mpd_query::{MpdCommand, MpdQuery, MpdQueryResult},
 };
 use shared::{logging, tmux};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::shared::macros::try_ret;
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "bench")]
+    pub mod bench;
     pub mod fixtures;
 }
 
@@ -81,6 +85,11 @@ pub fn main_tui() -> Result<()> {
 
     let enable_mouse = config.enable_mouse;
     let terminal = try_ret!(ui::setup_terminal(enable_mouse), "Failed to setup terminal");
+    let terminal_guard = ui::TerminalGuard::new(move || {
+        if let Err(err) = ui::reset_terminal(enable_mouse) {
+            log::error!(error:? = err; "Failed to restore terminal");
+        }
+    });
     let tx_clone = event_tx.clone();
 
     let context = try_ret!(
@@ -88,15 +97,29 @@ pub fn main_tui() -> Result<()> {
         "Failed to create app context"
     );
 
+    // Done after the context is created so that the supported tags reflect the server and not the limit
+    try_ret!(
+        client.set_enabled_tags(context.config.enabled_tags),
+        "Failed to limit the tags sent by MPD"
+    );
+    client.set_idle_subsystems(context.config.idle_subsystems);
+
     let mut render_loop = UpdateLoop::try_new(client_tx.clone(), context.config.status_update_interval_ms)?;
-    if context.status.state == State::Play {
+    if update_loop::is_needed(context.status.state, context.config.progress_update_while_paused) {
         render_loop.start()?;
     }
 
-    core::client::init(client_rx.clone(), event_tx.clone(), client)?;
+    let connection = core::client::ConnectionHandle::default();
+    core::client::init(client_rx.clone(), event_tx.clone(), client, connection.clone())?;
     core::work::init(worker_rx.clone(), client_tx.clone(), event_tx.clone(), context.config)?;
-    core::input::init(event_tx.clone())?;
-    let event_loop_handle = core::event_loop::init(context, event_rx, render_loop, terminal)?;
+    core::input::init(
+        event_tx.clone(),
+        context.config.screensaver_timeout_secs.map(Duration::from_secs),
+    )?;
+    core::signal::init(event_tx.clone())?;
+    let last_render = Arc::new(Mutex::new(Instant::now()));
+    core::watchdog::init(event_rx.clone(), Arc::clone(&last_render), connection)?;
+    let event_loop_handle = core::event_loop::init(context, event_rx, render_loop, terminal, last_render)?;
 
     let original_hook = std::panic::take_hook();
     std::panic::set_hook(Box::new(move |panic| {
@@ -109,6 +132,7 @@ pub fn main_tui() -> Result<()> {
     info!("Application initialized successfully");
 
     let mut terminal = event_loop_handle.join().expect("event loop to not panic");
+    terminal_guard.disarm();
     try_ret!(
         ui::restore_terminal(&mut terminal, enable_mouse),
         "Terminal restore to succeed"
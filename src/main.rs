@@ -15,19 +15,22 @@
     clippy::redundant_closure_for_method_calls,
     unused_macros
 )]
-use core::update_loop::UpdateLoop;
+use core::update_loop::{self, UpdateLoop};
 use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 use anyhow::{Context, Result};
 use clap::Parser;
 use config::{
-    cli::{Args, Command},
+    check,
+    cli::{Args, Command, ConfigCommand, ThemeCommand},
     ConfigFile,
 };
 use context::AppContext;
 use crossbeam::channel::unbounded;
 use log::info;
-use mpd::{client::Client, commands::State};
+use mpd::client::Client;
 use rustix::path::Arg;
 use shared::{
     dependencies::{DEPENDENCIES, FFMPEG, FFPROBE, PYTHON3, PYTHON3MUTAGEN, UEBERZUGPP, YTDLP},
@@ -39,6 +42,7 @@ use shared::{env::ENV, logging, tmux};
 use crate::{
     config::Config,
     shared::macros::{status_warn, try_ret},
+    ui::theme_preview,
 };
 
 #[cfg(test)]
@@ -56,22 +60,43 @@ mod ui;
 fn main() -> Result<()> {
     let mut args = Args::parse();
     match args.command {
-        Some(Command::Config { current: false }) => {
+        Some(Command::Config {
+            action: Some(ConfigCommand::Check),
+            ..
+        }) => {
+            let problems = check::check_file(&args.config);
+            if problems.is_empty() {
+                println!("Config at '{}' is valid", args.config.to_string_lossy());
+            } else {
+                eprintln!("Found problems in config at '{}':", args.config.to_string_lossy());
+                for problem in &problems {
+                    eprintln!("  - {problem}");
+                }
+                std::process::exit(1);
+            }
+        }
+        Some(Command::Config { current: false, .. }) => {
             std::io::stdout().write_all(include_bytes!(
                 "../docs/src/content/docs/next/assets/example_config.ron"
             ))?;
         }
-        Some(Command::Theme { current: false }) => {
+        Some(Command::Theme {
+            action: Some(ThemeCommand::Preview { file }),
+            ..
+        }) => {
+            theme_preview::preview_file(&file)?;
+        }
+        Some(Command::Theme { current: false, .. }) => {
             std::io::stdout().write_all(include_bytes!("../docs/src/content/docs/next/assets/example_theme.ron"))?;
         }
-        Some(Command::Config { current: true }) => {
+        Some(Command::Config { current: true, .. }) => {
             let mut file = std::fs::File::open(&args.config)
                 .with_context(|| format!("Config file was not found at '{}'", args.config.to_string_lossy()))?;
             let mut config = String::new();
             file.read_to_string(&mut config)?;
             println!("{config}");
         }
-        Some(Command::Theme { current: true }) => {
+        Some(Command::Theme { current: true, .. }) => {
             let config_file = ConfigFile::read(&args.config)
                 .with_context(|| format!("Config file was not found at '{}'", args.config.to_string_lossy()))?;
             let config_dir = args
@@ -205,6 +230,11 @@ fn main() -> Result<()> {
 
             let enable_mouse = config.enable_mouse;
             let terminal = try_ret!(ui::setup_terminal(enable_mouse), "Failed to setup terminal");
+            let terminal_guard = ui::TerminalGuard::new(move || {
+                if let Err(err) = ui::reset_terminal(enable_mouse) {
+                    log::error!(error:? = err; "Failed to restore terminal");
+                }
+            });
             let tx_clone = event_tx.clone();
 
             let context = try_ret!(
@@ -212,15 +242,29 @@ fn main() -> Result<()> {
                 "Failed to create app context"
             );
 
+            // Done after the context is created so that the supported tags reflect the server and not the limit
+            try_ret!(
+                client.set_enabled_tags(context.config.enabled_tags),
+                "Failed to limit the tags sent by MPD"
+            );
+            client.set_idle_subsystems(context.config.idle_subsystems);
+
             let mut render_loop = UpdateLoop::try_new(client_tx.clone(), context.config.status_update_interval_ms)?;
-            if context.status.state == State::Play {
+            if update_loop::is_needed(context.status.state, context.config.progress_update_while_paused) {
                 render_loop.start()?;
             }
 
-            core::client::init(client_rx.clone(), event_tx.clone(), client)?;
+            let connection = core::client::ConnectionHandle::default();
+            core::client::init(client_rx.clone(), event_tx.clone(), client, connection.clone())?;
             core::work::init(worker_rx.clone(), client_tx.clone(), event_tx.clone(), context.config)?;
-            core::input::init(event_tx.clone())?;
-            let event_loop_handle = core::event_loop::init(context, event_rx, render_loop, terminal)?;
+            core::input::init(
+                event_tx.clone(),
+                context.config.screensaver_timeout_secs.map(Duration::from_secs),
+            )?;
+            core::signal::init(event_tx.clone())?;
+            let last_render = Arc::new(Mutex::new(Instant::now()));
+            core::watchdog::init(event_rx.clone(), Arc::clone(&last_render), connection)?;
+            let event_loop_handle = core::event_loop::init(context, event_rx, render_loop, terminal, last_render)?;
 
             let original_hook = std::panic::take_hook();
             std::panic::set_hook(Box::new(move |panic| {
@@ -233,6 +277,7 @@ fn main() -> Result<()> {
             info!("Application initialized successfully");
 
             let mut terminal = event_loop_handle.join().expect("event loop to not panic");
+            terminal_guard.disarm();
             try_ret!(
                 ui::restore_terminal(&mut terminal, enable_mouse),
                 "Terminal restore to succeed"
@@ -6,16 +6,18 @@ use std::{
 
 use crate::{
     config::{address::MpdPassword, MpdAddress},
-    mpd::mpd_client::MpdClient,
+    mpd::mpd_client::{idle_command, MpdClient},
     shared::macros::status_warn,
 };
 
 use super::{
+    commands::IdleEvent,
     errors::MpdError,
     proto_client::{ProtoClient, SocketClient},
     version::Version,
 };
 use anyhow::Result;
+use itertools::Itertools;
 use log::debug;
 
 type MpdResult<T> = Result<T, MpdError>;
@@ -32,6 +34,11 @@ pub struct Client<'name> {
     pub stream: TcpOrUnixStream,
     addr: MpdAddress<'name>,
     password: Option<MpdPassword<'name>>,
+    enabled_tags: Option<&'name [&'name str]>,
+    /// Sent to enter idle, lives as long as the idle response which borrows it
+    pub(super) idle_command: &'static str,
+    /// Partition switched to, entered again after reconnecting
+    pub(super) partition: Option<String>,
     pub version: Version,
 }
 
@@ -71,6 +78,13 @@ impl TcpOrUnixStream {
         Ok(())
     }
 
+    pub fn shutdown(&self) -> std::io::Result<()> {
+        match self {
+            TcpOrUnixStream::Unix(s) => s.shutdown(std::net::Shutdown::Both),
+            TcpOrUnixStream::Tcp(s) => s.shutdown(std::net::Shutdown::Both),
+        }
+    }
+
     pub fn try_clone(&self) -> std::io::Result<Self> {
         Ok(match self {
             TcpOrUnixStream::Unix(s) => TcpOrUnixStream::Unix(s.try_clone()?),
@@ -144,16 +158,14 @@ impl<'name> Client<'name> {
             stream,
             addr,
             password,
+            enabled_tags: None,
+            idle_command: "idle",
+            partition: None,
             version,
         };
 
-        if let Some(MpdPassword(password)) = password {
-            debug!("Used password auth to MPD");
-            client.password(password)?;
-        }
-
         // 2^18 seems to be max limit supported by MPD and higher values dont have any effect
-        client.binary_limit(2u64.pow(18))?;
+        client.configure(2u64.pow(18))?;
 
         Ok(client)
     }
@@ -186,14 +198,59 @@ impl<'name> Client<'name> {
 
         debug!(name = self.name, addr:? = self.addr, handshake = buf.trim(), version = version.to_string().as_str(); "MPD client initialized");
 
+        self.configure(1024 * 1024 * 5)?;
+
+        Ok(self)
+    }
+
+    /// Limits the tags MPD sends along with songs to the given ones, `None` means all tags are sent.
+    /// The limit is applied again whenever the connection is reestablished.
+    pub fn set_enabled_tags(&mut self, tags: Option<&'name [&'name str]>) -> MpdResult<()> {
+        self.enabled_tags = tags;
+        self.apply_enabled_tags()
+    }
+
+    /// Limits the subsystems the client is notified about when entering idle, empty means all of them
+    pub fn set_idle_subsystems(&mut self, subsystems: &[IdleEvent]) {
+        self.idle_command = idle_command(subsystems).leak();
+    }
+
+    /// Applies the connection settings, shared by the initial connection and reconnects so that
+    /// every connection behaves the same apart from the binary limit
+    fn configure(&mut self, binary_limit: u64) -> MpdResult<()> {
         if let Some(MpdPassword(password)) = self.password {
             debug!("Used password auth to MPD");
             self.password(password)?;
         }
 
-        self.binary_limit(1024 * 1024 * 5)?;
+        self.binary_limit(binary_limit)?;
 
-        Ok(self)
+        if let Some(partition) = self.partition.take() {
+            if let Err(err) = self.switch_partition(&partition) {
+                status_warn!(err:?; "Failed to switch back to partition '{partition}', using the default one");
+            }
+        }
+
+        self.apply_enabled_tags()
+    }
+
+    fn apply_enabled_tags(&mut self) -> MpdResult<()> {
+        let Some(enabled) = self.enabled_tags else {
+            return Ok(());
+        };
+
+        self.enable_all_tag_types()?;
+        let disabled = self
+            .tag_types()?
+            .into_iter()
+            .filter(|tag| !enabled.iter().any(|enabled| enabled.eq_ignore_ascii_case(tag)))
+            .collect_vec();
+        if disabled.is_empty() {
+            return Ok(());
+        }
+
+        debug!(name = self.name, disabled:?; "Disabling tag types");
+        self.disable_tag_types(&disabled.iter().map(String::as_str).collect_vec())
     }
 
     pub fn set_read_timeout(&mut self, timeout: Option<std::time::Duration>) -> std::io::Result<()> {
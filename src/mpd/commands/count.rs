@@ -0,0 +1,19 @@
+use crate::mpd::errors::MpdError;
+use crate::mpd::{FromMpd, LineHandled};
+
+/// Totals of the songs matched by `count`
+#[derive(Default, Debug, Clone, Copy)]
+pub struct Count {
+    pub songs: usize,
+}
+
+impl FromMpd for Count {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "songs" => self.songs = value.parse()?,
+            "playtime" => {}
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
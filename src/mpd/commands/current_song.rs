@@ -38,6 +38,26 @@ impl Song {
     pub fn album(&self) -> Option<&String> {
         self.metadata.get("album")
     }
+
+    /// Priority of the queued song in random mode, zero when MPD did not report any
+    pub fn priority(&self) -> u8 {
+        self.metadata
+            .get("prio")
+            .and_then(|prio| prio.parse().ok())
+            .unwrap_or_default()
+    }
+
+    pub fn is_stream(&self) -> bool {
+        self.file.contains("://")
+    }
+
+    /// Absolute path of the song if the music directory is known, streams are returned as their URL
+    pub fn resolved_path(&self, music_directory: Option<&str>) -> String {
+        match music_directory {
+            Some(dir) if !self.is_stream() => format!("{dir}{}", self.file),
+            _ => self.file.clone(),
+        }
+    }
 }
 
 impl FromMpd for Song {
@@ -56,3 +76,22 @@ impl FromMpd for Song {
         Ok(LineHandled::Yes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::Song;
+
+    #[test_case("artist/album/song.flac", Some("/music/"), "/music/artist/album/song.flac" ; "resolved against music directory")]
+    #[test_case("artist/album/song.flac", None, "artist/album/song.flac" ; "relative without music directory")]
+    #[test_case("https://radio.example.com/stream.mp3", Some("/music/"), "https://radio.example.com/stream.mp3" ; "stream url")]
+    fn resolved_path(file: &str, music_directory: Option<&str>, expected: &str) {
+        let song = Song {
+            file: file.to_owned(),
+            ..Default::default()
+        };
+
+        assert_eq!(song.resolved_path(music_directory), expected);
+    }
+}
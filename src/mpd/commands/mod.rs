@@ -1,3 +1,5 @@
+pub mod add_id;
+pub mod count;
 pub mod current_song;
 pub mod decoders;
 pub mod idle;
@@ -8,11 +10,17 @@ pub mod list_playlist;
 pub mod list_playlists;
 pub mod lsinfo;
 pub mod outputs;
+pub mod partitions;
 pub mod playlist_info;
+pub mod replay_gain;
 pub mod status;
+pub mod stickers;
+pub mod tag_types;
 pub mod update;
 pub mod volume;
 
+pub use self::add_id::AddId;
+pub use self::count::Count;
 pub use self::current_song::Song;
 pub use self::decoders::Decoder;
 pub use self::idle::IdleEvent;
@@ -21,7 +29,11 @@ pub use self::list_mounts::Mounts;
 pub use self::list_playlists::Playlist;
 pub use self::lsinfo::LsInfo;
 pub use self::outputs::Output;
+pub use self::partitions::Partitions;
+pub use self::replay_gain::ReplayGainMode;
 pub use self::status::State;
 pub use self::status::Status;
+pub use self::stickers::{StickerMatches, Stickers};
+pub use self::tag_types::TagTypes;
 pub use self::update::Update;
 pub use self::volume::Volume;
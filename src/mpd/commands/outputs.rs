@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::anyhow;
 use anyhow::Context;
 use derive_more::{AsMut, AsRef, Into, IntoIterator};
@@ -13,6 +15,9 @@ pub struct Output {
     pub id: u32,
     pub name: String,
     pub enabled: bool,
+    pub plugin: String,
+    /// Runtime settings of the output plugin, for example `dop` of the alsa plugin
+    pub attributes: BTreeMap<String, String>,
 }
 
 impl FromMpd for Outputs {
@@ -42,8 +47,62 @@ impl FromMpd for Output {
                 "1" => self.enabled = true,
                 _ => return Ok(LineHandled::No { value }),
             },
+            "plugin" => self.plugin = value,
+            "attribute" => {
+                let Some((name, attr_value)) = value.split_once('=') else {
+                    return Ok(LineHandled::No { value });
+                };
+                self.attributes.insert(name.to_owned(), attr_value.to_owned());
+            }
             _ => return Ok(LineHandled::No { value }),
         }
         Ok(LineHandled::Yes)
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{FromMpd, Outputs};
+
+    #[test]
+    fn can_parse_outputs_with_attributes() {
+        let input = r"outputid: 0
+outputname: My ALSA Device
+plugin: alsa
+outputenabled: 1
+attribute: allowed_formats=
+attribute: dop=0
+outputid: 1
+outputname: Stream
+plugin: httpd
+outputenabled: 0";
+
+        let mut result = Outputs::default();
+        for line in input.lines() {
+            let (key, value) = line.split_once(": ").unwrap();
+            result
+                .next_internal(key.to_lowercase().as_str(), value.to_owned())
+                .unwrap();
+        }
+
+        let [alsa, httpd] = result.0.as_slice() else {
+            panic!("Expected two outputs, got {:?}", result.0);
+        };
+        assert_eq!(alsa.id, 0);
+        assert_eq!(alsa.name, "My ALSA Device");
+        assert_eq!(alsa.plugin, "alsa");
+        assert!(alsa.enabled);
+        assert_eq!(
+            alsa.attributes
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect::<Vec<_>>(),
+            [("allowed_formats", ""), ("dop", "0")]
+        );
+        assert_eq!(httpd.id, 1);
+        assert_eq!(httpd.plugin, "httpd");
+        assert!(!httpd.enabled);
+        assert!(httpd.attributes.is_empty());
+    }
+}
@@ -0,0 +1,38 @@
+use crate::mpd::{errors::MpdError, FromMpd, LineHandled};
+
+/// Names of the partitions on the server, as returned by the `listpartitions` command
+#[derive(Debug, Default)]
+pub struct Partitions(pub Vec<String>);
+
+impl FromMpd for Partitions {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "partition" => self.0.push(value),
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{FromMpd, Partitions};
+
+    #[test]
+    fn can_parse_partitions() {
+        let input = r"partition: default
+partition: kitchen
+partition: living room";
+
+        let mut result = Partitions::default();
+        for line in input.lines() {
+            let (key, value) = line.split_once(": ").unwrap();
+            result
+                .next_internal(key.to_lowercase().as_str(), value.to_owned())
+                .unwrap();
+        }
+
+        assert_eq!(result.0, ["default", "kitchen", "living room"]);
+    }
+}
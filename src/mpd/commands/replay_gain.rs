@@ -0,0 +1,127 @@
+use anyhow::anyhow;
+use serde::Serialize;
+
+use crate::mpd::{errors::MpdError, FromMpd, LineHandled, ParseErrorExt};
+
+#[derive(Debug, Serialize, Default, PartialEq, Eq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum ReplayGainMode {
+    #[default]
+    Off,
+    Track,
+    Album,
+    Auto,
+}
+
+impl ReplayGainMode {
+    pub fn cycle(self) -> Self {
+        match self {
+            ReplayGainMode::Off => ReplayGainMode::Track,
+            ReplayGainMode::Track => ReplayGainMode::Album,
+            ReplayGainMode::Album => ReplayGainMode::Auto,
+            ReplayGainMode::Auto => ReplayGainMode::Off,
+        }
+    }
+
+    pub fn to_mpd_value(self) -> &'static str {
+        match self {
+            ReplayGainMode::Off => "off",
+            ReplayGainMode::Track => "track",
+            ReplayGainMode::Album => "album",
+            ReplayGainMode::Auto => "auto",
+        }
+    }
+}
+
+impl std::fmt::Display for ReplayGainMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                ReplayGainMode::Off => "Off",
+                ReplayGainMode::Track => "Track",
+                ReplayGainMode::Album => "Album",
+                ReplayGainMode::Auto => "Auto",
+            }
+        )
+    }
+}
+
+impl std::str::FromStr for ReplayGainMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(ReplayGainMode::Off),
+            "track" => Ok(ReplayGainMode::Track),
+            "album" => Ok(ReplayGainMode::Album),
+            "auto" => Ok(ReplayGainMode::Auto),
+            val => Err(anyhow!("Received unknown value for ReplayGainMode '{}'", val)),
+        }
+    }
+}
+
+/// Reply of the `replay_gain_status` command
+#[derive(Debug, Default)]
+pub struct ReplayGainStatus {
+    pub mode: ReplayGainMode,
+}
+
+impl FromMpd for ReplayGainStatus {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "replay_gain_mode" => self.mode = value.parse().logerr(key, &value)?,
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use test_case::test_case;
+
+    use super::{ReplayGainMode, ReplayGainStatus};
+    use crate::mpd::FromMpd;
+
+    #[test_case("replay_gain_mode: off", ReplayGainMode::Off)]
+    #[test_case("replay_gain_mode: track", ReplayGainMode::Track)]
+    #[test_case("replay_gain_mode: album", ReplayGainMode::Album)]
+    #[test_case("replay_gain_mode: auto", ReplayGainMode::Auto)]
+    fn can_parse_replay_gain_status(input: &str, expected: ReplayGainMode) {
+        let mut result = ReplayGainStatus::default();
+
+        result.next(input.to_owned()).unwrap();
+
+        assert_eq!(result.mode, expected);
+    }
+
+    #[test]
+    fn unknown_mode_is_an_error() {
+        let mut result = ReplayGainStatus::default();
+
+        assert!(result.next("replay_gain_mode: loud".to_owned()).is_err());
+    }
+
+    #[test]
+    fn cycles_through_all_modes() {
+        let mut mode = ReplayGainMode::Off;
+        let mut seen = Vec::new();
+        for _ in 0..4 {
+            mode = mode.cycle();
+            seen.push(mode);
+        }
+
+        assert_eq!(
+            seen,
+            [
+                ReplayGainMode::Track,
+                ReplayGainMode::Album,
+                ReplayGainMode::Auto,
+                ReplayGainMode::Off
+            ]
+        );
+    }
+}
@@ -1,11 +1,11 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use anyhow::anyhow;
 use serde::Serialize;
 
 use crate::mpd::{errors::MpdError, FromMpd, LineHandled, ParseErrorExt};
 
-use super::Volume;
+use super::{ReplayGainMode, Volume};
 
 #[derive(Debug, Serialize, Default, Clone)]
 pub struct Status {
@@ -15,22 +15,32 @@ pub struct Status {
     pub random: bool,
     pub single: OnOffOneshot,
     pub consume: OnOffOneshot,
-    pub playlist: Option<u32>,        // 31-bit unsigned integer, the playlist version number
-    pub playlistlength: u32,          // integer, the length of the playlist
-    pub state: State,                 // play, stop, or pause
-    pub song: Option<u32>,            // playlist song number of the current song stopped on or playing
-    pub songid: Option<u32>,          // playlist songid of the current song stopped on or playing
-    pub nextsong: Option<u32>,        // playlist song number of the next song to be played
-    pub nextsongid: Option<u32>,      // playlist songid of the next song to be played
-    pub elapsed: Duration, // Total time elapsed within the current song in seconds, but with higher resolution.
-    pub duration: Duration, // Duration of the current song in seconds.
-    pub bitrate: Option<u32>, // instantaneous bitrate in kbps
-    pub xfade: Option<u32>, // crossfade in seconds (see Cross-Fading)
-    pub mixrampdb: Option<String>, // mixramp threshold in dB
-    pub mixrampdelay: Option<String>, // mixrampdelay in seconds
+    pub playlist: Option<u32>,     // 31-bit unsigned integer, the playlist version number
+    pub playlistlength: u32,       // integer, the length of the playlist
+    pub state: State,              // play, stop, or pause
+    pub song: Option<u32>,         // playlist song number of the current song stopped on or playing
+    pub songid: Option<u32>,       // playlist songid of the current song stopped on or playing
+    pub nextsong: Option<u32>,     // playlist song number of the next song to be played
+    pub nextsongid: Option<u32>,   // playlist songid of the next song to be played
+    pub elapsed: Duration,         // Total time elapsed within the current song in seconds, but with higher resolution.
+    pub duration: Duration,        // Duration of the current song in seconds.
+    pub bitrate: Option<u32>,      // instantaneous bitrate in kbps
+    pub xfade: Option<u32>,        // crossfade in seconds (see Cross-Fading)
+    pub mixrampdb: Option<f32>,    // mixramp threshold in dB
+    pub mixrampdelay: Option<f32>, // mixrampdelay in seconds, None when mixramp is disabled
     pub audio: Option<String>, // The format emitted by the decoder plugin during playback, format: samplerate:bits:channels. See Global Audio Format for a detailed explanation.
     pub updating_db: Option<u32>, // job id
     pub error: Option<String>, // if there is an error, returns message here
+    /// Not part of the `status` reply, filled from `replay_gain_status`. None when it was not requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay_gain_mode: Option<ReplayGainMode>,
+    /// Not part of the `status` reply, names of the enabled outputs filled from `outputs`. None when it was not
+    /// requested.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<String>,
+    /// Fields added in newer MPD versions which rmpc does not know about yet
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub extra: HashMap<String, String>,
 }
 
 impl FromMpd for Status {
@@ -57,19 +67,133 @@ impl FromMpd for Status {
             "duration" => self.duration = Duration::from_secs_f32(value.parse().logerr(key, &value)?),
             "bitrate" if value != "0" => self.bitrate = Some(value.parse().logerr(key, &value)?),
             "xfade" => self.xfade = Some(value.parse().logerr(key, &value)?),
-            "mixrampdb" => self.mixrampdb = Some(value),
-            "mixrampdelay" => self.mixrampdelay = Some(value),
+            "mixrampdb" => self.mixrampdb = Some(value.parse().logerr(key, &value)?),
+            "mixrampdelay" if value == "nan" => self.mixrampdelay = None,
+            "mixrampdelay" => self.mixrampdelay = Some(value.parse().logerr(key, &value)?),
             "audio" => self.audio = Some(value),
             "updating_db" => self.updating_db = Some(value.parse().logerr(key, &value)?),
             "error" => self.error = Some(value),
             "bitrate" => self.bitrate = None,
             "time" => {} // deprecated
-            _ => return Ok(LineHandled::No { value }),
+            key => {
+                self.extra.insert(key.to_owned(), value);
+            }
         }
         Ok(LineHandled::Yes)
     }
 }
 
+impl Status {
+    /// Whether the current song is looped, ie. both repeat and single are on
+    pub fn is_repeat_one(&self) -> bool {
+        self.repeat && matches!(self.single, OnOffOneshot::On)
+    }
+
+    pub fn audio_format(&self) -> Option<AudioFormat> {
+        self.audio.as_ref().and_then(|audio| audio.parse().ok())
+    }
+
+    /// Rough quality of the currently playing `file`. Lossless codecs, recognized by the file extension, are always
+    /// high quality and lossy ones are classified by their bitrate. The decoded audio format is only used when the
+    /// codec is unknown because MPD decodes some lossy codecs to 24 bit, ie. mp3.
+    pub fn audio_quality(&self, file: Option<&str>, high_bitrate: u32, low_bitrate: u32) -> Option<AudioQuality> {
+        let format = self.audio_format();
+        if format.is_some_and(|format| format.sample_format == SampleFormat::Dsd) {
+            return Some(AudioQuality::High);
+        }
+
+        match file.and_then(Codec::from_uri) {
+            Some(Codec::Lossless) => return Some(AudioQuality::High),
+            Some(Codec::Lossy) => {}
+            None if format.is_some_and(AudioFormat::is_high_resolution) => return Some(AudioQuality::High),
+            None => {}
+        }
+
+        match self.bitrate {
+            Some(bitrate) if bitrate >= high_bitrate => Some(AudioQuality::High),
+            Some(bitrate) if bitrate >= low_bitrate => Some(AudioQuality::Medium),
+            Some(_) => Some(AudioQuality::Low),
+            None => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Codec {
+    Lossless,
+    Lossy,
+}
+
+impl Codec {
+    /// Codec of the file judged by its extension, `None` when the extension is unknown or ambiguous like `m4a`
+    fn from_uri(uri: &str) -> Option<Self> {
+        let extension = std::path::Path::new(uri).extension()?.to_str()?.to_lowercase();
+        match extension.as_str() {
+            "flac" | "wav" | "aif" | "aiff" | "ape" | "wv" | "tta" | "tak" | "dsf" | "dff" => Some(Self::Lossless),
+            "mp3" | "mp2" | "ogg" | "oga" | "opus" | "aac" | "wma" | "mpc" => Some(Self::Lossy),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AudioQuality {
+    High,
+    Medium,
+    Low,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum SampleFormat {
+    Bits(u8),
+    Float,
+    Dsd,
+}
+
+/// Audio format as reported by MPD, ie. `44100:16:2`, `48000:f:2` or `dsd64:2`
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct AudioFormat {
+    pub sample_rate: u32,
+    pub sample_format: SampleFormat,
+    pub channels: u8,
+}
+
+impl AudioFormat {
+    fn is_high_resolution(self) -> bool {
+        match self.sample_format {
+            SampleFormat::Bits(bits) => bits >= 24 || self.sample_rate > 48000,
+            SampleFormat::Float => false,
+            SampleFormat::Dsd => true,
+        }
+    }
+}
+
+impl std::str::FromStr for AudioFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<_> = s.split(':').collect();
+        match parts.as_slice() {
+            [rate, channels] if rate.starts_with("dsd") => Ok(Self {
+                // DSD rates are multiples of 44.1 kHz, dsd64 is 64 * 44100 samples per second
+                sample_rate: rate.trim_start_matches("dsd").parse::<u32>()? * 44100,
+                sample_format: SampleFormat::Dsd,
+                channels: channels.parse()?,
+            }),
+            [rate, bits, channels] => Ok(Self {
+                sample_rate: rate.parse()?,
+                sample_format: match *bits {
+                    "f" => SampleFormat::Float,
+                    "dsd" => SampleFormat::Dsd,
+                    bits => SampleFormat::Bits(bits.parse()?),
+                },
+                channels: channels.parse()?,
+            }),
+            _ => Err(anyhow!("Invalid audio format: '{}'", s)),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Default, PartialEq, Clone, Copy, strum::AsRefStr)]
 pub enum State {
     #[strum(serialize = "Playing")]
@@ -171,3 +295,81 @@ impl std::str::FromStr for State {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use test_case::test_case;
+
+    use super::{AudioFormat, AudioQuality, SampleFormat, State, Status};
+    use crate::mpd::FromMpd;
+
+    #[test]
+    fn unknown_fields_are_collected() {
+        let mut status = Status::default();
+
+        for line in ["repeat: 1", "made_up_field: some value", "state: play"] {
+            status.next(line.to_owned()).unwrap();
+        }
+
+        assert!(status.repeat);
+        assert_eq!(status.state, State::Play);
+        assert_eq!(
+            status.extra.get("made_up_field").map(String::as_str),
+            Some("some value")
+        );
+    }
+
+    #[test_case("mixrampdelay: 2.5", Some(2.5))]
+    #[test_case("mixrampdelay: nan", None)]
+    fn mixramp_delay_is_parsed(line: &str, expected: Option<f32>) {
+        let mut status = Status::default();
+
+        for line in ["xfade: 5", "mixrampdb: -17.000000", line] {
+            status.next(line.to_owned()).unwrap();
+        }
+
+        assert_eq!(status.xfade, Some(5));
+        assert_eq!(status.mixrampdb, Some(-17.0));
+        assert_eq!(status.mixrampdelay, expected);
+    }
+
+    #[test_case("44100:16:2", 44100, SampleFormat::Bits(16), 2)]
+    #[test_case("48000:f:2", 48000, SampleFormat::Float, 2)]
+    #[test_case("96000:24:6", 96000, SampleFormat::Bits(24), 6)]
+    #[test_case("dsd64:2", 2_822_400, SampleFormat::Dsd, 2)]
+    fn audio_format_is_parsed(input: &str, sample_rate: u32, sample_format: SampleFormat, channels: u8) {
+        assert_eq!(
+            input.parse::<AudioFormat>().unwrap(),
+            AudioFormat {
+                sample_rate,
+                sample_format,
+                channels
+            }
+        );
+    }
+
+    #[test_case(Some("song.flac"), Some("44100:16:2"), Some(1011), Some(AudioQuality::High); "lossless")]
+    #[test_case(Some("song.FLAC"), Some("44100:16:2"), Some(550), Some(AudioQuality::High); "low bitrate lossless")]
+    #[test_case(Some("song.mp3"), Some("44100:24:2"), Some(320), Some(AudioQuality::Medium); "high bitrate lossy")]
+    #[test_case(Some("song.ogg"), Some("48000:f:2"), Some(96), Some(AudioQuality::Low); "low bitrate lossy")]
+    #[test_case(Some("song.dsf"), Some("dsd64:2"), None, Some(AudioQuality::High); "dsd")]
+    #[test_case(None, Some("96000:24:2"), None, Some(AudioQuality::High); "hi res of unknown codec")]
+    #[test_case(Some("song.m4a"), Some("44100:16:2"), Some(256), Some(AudioQuality::Medium); "ambiguous codec")]
+    #[test_case(Some("song.m4a"), Some("44100:16:2"), None, None; "unknown")]
+    #[test_case(None, None, None, None; "stopped")]
+    fn audio_quality_uses_codec_and_bitrate(
+        file: Option<&str>,
+        audio: Option<&str>,
+        bitrate: Option<u32>,
+        expected: Option<AudioQuality>,
+    ) {
+        let status = Status {
+            audio: audio.map(ToOwned::to_owned),
+            bitrate,
+            ..Default::default()
+        };
+
+        assert_eq!(status.audio_quality(file, 700, 192), expected);
+    }
+}
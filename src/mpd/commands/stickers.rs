@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+use crate::mpd::{errors::MpdError, FromMpd, LineHandled};
+
+/// Stickers attached to a song, as returned by the `sticker get` and `sticker list` commands
+#[derive(Debug, Default)]
+pub struct Stickers(pub HashMap<String, String>);
+
+impl FromMpd for Stickers {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "sticker" => {
+                let Some((name, value)) = value.split_once('=') else {
+                    return Err(MpdError::Parse(format!("Invalid sticker '{value}'")));
+                };
+                self.0.insert(name.to_owned(), value.to_owned());
+            }
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+/// Songs having a sticker, as returned by the `sticker find` command
+#[derive(Debug, Default)]
+pub struct StickerMatches(pub Vec<String>);
+
+impl FromMpd for StickerMatches {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "file" => self.0.push(value),
+            "sticker" => {}
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{FromMpd, StickerMatches, Stickers};
+
+    #[test]
+    fn can_parse_stickers() {
+        let input = r"sticker: rating=4
+sticker: note=a=b";
+
+        let mut result = Stickers::default();
+        for line in input.lines() {
+            let (key, value) = line.split_once(": ").unwrap();
+            result
+                .next_internal(key.to_lowercase().as_str(), value.to_owned())
+                .unwrap();
+        }
+
+        assert_eq!(result.0.get("rating").map(String::as_str), Some("4"));
+        assert_eq!(result.0.get("note").map(String::as_str), Some("a=b"));
+    }
+
+    #[test]
+    fn sticker_without_value_is_an_error() {
+        let mut result = Stickers::default();
+
+        assert!(result.next_internal("sticker", "rating".to_owned()).is_err());
+    }
+
+    #[test]
+    fn can_parse_sticker_matches() {
+        let input = r"file: dir/a.flac
+sticker: favorite=1
+file: dir/b.flac
+sticker: favorite=1";
+
+        let mut result = StickerMatches::default();
+        for line in input.lines() {
+            let (key, value) = line.split_once(": ").unwrap();
+            result
+                .next_internal(key.to_lowercase().as_str(), value.to_owned())
+                .unwrap();
+        }
+
+        assert_eq!(result.0, vec!["dir/a.flac".to_owned(), "dir/b.flac".to_owned()]);
+    }
+}
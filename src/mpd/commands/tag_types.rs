@@ -0,0 +1,40 @@
+use crate::mpd::{errors::MpdError, FromMpd, LineHandled};
+
+/// Tags the server knows about, as returned by the `tagtypes` command
+#[derive(Debug, Default)]
+pub struct TagTypes(pub Vec<String>);
+
+impl FromMpd for TagTypes {
+    fn next_internal(&mut self, key: &str, value: String) -> Result<LineHandled, MpdError> {
+        match key {
+            "tagtype" => self.0.push(value),
+            _ => return Ok(LineHandled::No { value }),
+        }
+        Ok(LineHandled::Yes)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{FromMpd, TagTypes};
+
+    #[test]
+    fn can_parse_tag_types() {
+        let input = r"tagtype: Artist
+tagtype: ArtistSort
+tagtype: Album
+tagtype: Title
+tagtype: Genre";
+
+        let mut result = TagTypes::default();
+        for line in input.lines() {
+            let (key, value) = line.split_once(": ").unwrap();
+            result
+                .next_internal(key.to_lowercase().as_str(), value.to_owned())
+                .unwrap();
+        }
+
+        assert_eq!(result.0, ["Artist", "ArtistSort", "Album", "Title", "Genre"]);
+    }
+}
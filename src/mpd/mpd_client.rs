@@ -5,6 +5,7 @@ use std::{
 
 use anyhow::Result;
 use derive_more::Deref;
+use itertools::Itertools;
 use strum::AsRefStr;
 
 use crate::shared::{ext::error::ErrorExt, macros::status_error};
@@ -12,8 +13,9 @@ use crate::shared::{ext::error::ErrorExt, macros::status_error};
 use super::{
     client::Client,
     commands::{
-        decoders::Decoders, list::MpdList, list_playlist::FileList, outputs::Outputs, status::OnOffOneshot,
-        volume::Bound, IdleEvent, ListFiles, LsInfo, Mounts, Playlist, Song, Status, Update, Volume,
+        decoders::Decoders, list::MpdList, list_playlist::FileList, outputs::Outputs, replay_gain::ReplayGainStatus,
+        status::OnOffOneshot, volume::Bound, AddId, Count, IdleEvent, ListFiles, LsInfo, Mounts, Partitions, Playlist,
+        ReplayGainMode, Song, Status, StickerMatches, Stickers, TagTypes, Update, Volume,
     },
     errors::{ErrorCode, MpdError, MpdFailureResponse},
     proto_client::{ProtoClient, SocketClient},
@@ -22,6 +24,9 @@ use super::{
 
 type MpdResult<T> = Result<T, MpdError>;
 
+/// Maximum number of commands sent to MPD in a single command list
+const COMMAND_LIST_CHUNK_SIZE: usize = 500;
+
 #[derive(AsRefStr, Debug)]
 #[allow(dead_code)]
 pub enum SaveMode {
@@ -67,6 +72,12 @@ pub trait MpdClient: Sized {
     fn binary_limit(&mut self, limit: u64) -> MpdResult<()>;
     fn password(&mut self, password: &str) -> MpdResult<()>;
     fn commands(&mut self) -> MpdResult<MpdList>;
+    /// Names of the tags the server supports, like `Artist` or `AlbumArtist`
+    fn tag_types(&mut self) -> MpdResult<Vec<String>>;
+    /// Stops the server from sending the given tags for this connection
+    fn disable_tag_types(&mut self, tags: &[&str]) -> MpdResult<()>;
+    /// Makes the server send all of its tags again for this connection
+    fn enable_all_tag_types(&mut self) -> MpdResult<()>;
     fn update(&mut self, path: Option<&str>) -> MpdResult<Update>;
     fn rescan(&mut self, path: Option<&str>) -> MpdResult<Update>;
     fn idle(&mut self, subsystem: Option<IdleEvent>) -> MpdResult<Vec<IdleEvent>>;
@@ -95,29 +106,77 @@ pub trait MpdClient: Sized {
     fn random(&mut self, enabled: bool) -> MpdResult<()>;
     fn single(&mut self, single: OnOffOneshot) -> MpdResult<()>;
     fn consume(&mut self, consume: OnOffOneshot) -> MpdResult<()>;
+    fn crossfade(&mut self, seconds: u32) -> MpdResult<()>;
+    fn mixramp_db(&mut self, db: f32) -> MpdResult<()>;
+    /// Disables mixramp when `seconds` is None
+    fn mixramp_delay(&mut self, seconds: Option<f32>) -> MpdResult<()>;
+    fn replay_gain_mode(&mut self, mode: ReplayGainMode) -> MpdResult<()>;
+    fn replay_gain_status(&mut self) -> MpdResult<ReplayGainMode>;
     // Mounts
     fn mount(&mut self, name: &str, path: &str) -> MpdResult<()>;
     fn unmount(&mut self, name: &str) -> MpdResult<()>;
     fn list_mounts(&mut self) -> MpdResult<Mounts>;
     // Current queue
     fn add(&mut self, path: &str) -> MpdResult<()>;
+    /// Adds the song at the given position in the queue and returns the id of the newly queued song. Relative
+    /// positions require MPD 0.23.
+    fn add_at(&mut self, path: &str, position: QueueMoveTarget) -> MpdResult<u32>;
+    fn add_multiple(&mut self, paths: &[&str]) -> MpdResult<()>;
     fn clear(&mut self) -> MpdResult<()>;
     fn delete_id(&mut self, id: u32) -> MpdResult<()>;
     fn delete_from_queue(&mut self, songs: SingleOrRange) -> MpdResult<()>;
     fn playlist_info(&mut self) -> MpdResult<Option<Vec<Song>>>;
+    /// Returns the queued song with the given id or `None` if there is no such song in the queue
+    fn playlist_id(&mut self, id: u32) -> MpdResult<Option<Song>>;
     fn find(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>>;
     fn search(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>>;
+    /// Same as [`Self::find`] but only the queue is searched
+    fn playlist_find(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>>;
+    /// Same as [`Self::search`] but only the queue is searched
+    fn playlist_search(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>>;
     fn move_in_queue(&mut self, from: SingleOrRange, to: QueueMoveTarget) -> MpdResult<()>;
     fn move_id(&mut self, id: u32, to: QueueMoveTarget) -> MpdResult<()>;
+    /// Swaps the positions of the songs at the two positions in the queue
+    fn swap(&mut self, pos1: usize, pos2: usize) -> MpdResult<()>;
+    /// Swaps the positions of the queued songs with the given ids
+    fn swap_id(&mut self, id1: u32, id2: u32) -> MpdResult<()>;
+    /// Shuffles the songs in the range, or the whole queue when no range is given
+    fn shuffle(&mut self, range: Option<SingleOrRange>) -> MpdResult<()>;
+    /// Sets the priority of the songs in the range. Songs with higher priority are played first in random mode.
+    fn set_priority(&mut self, songs: SingleOrRange, prio: u8) -> MpdResult<()>;
+    /// Same as [`Self::set_priority`] but for the queued song with the given id
+    fn set_priority_id(&mut self, id: u32, prio: u8) -> MpdResult<()>;
+    /// Limits playback of the queued song to the range in seconds, `None` plays the whole song again
+    fn set_song_range(&mut self, id: u32, range: Option<Range<f32>>) -> MpdResult<()>;
     fn find_one(&mut self, filter: &[Filter<'_>]) -> MpdResult<Option<Song>>;
-    fn find_add(&mut self, filter: &[Filter<'_>]) -> MpdResult<()>;
-    fn search_add(&mut self, filter: &[Filter<'_>]) -> MpdResult<()>;
+    /// Finds the songs with the given uris using batched command lists. Uris not in the database are skipped.
+    fn find_files(&mut self, uris: &[&str]) -> MpdResult<Vec<Song>>;
+    /// Finds all songs modified in the database since `since`, an ISO 8601 timestamp or seconds since the epoch
+    fn modified_since(&mut self, since: &str) -> MpdResult<Vec<Song>>;
+    /// Adds all songs matching the filter to the queue, at the given position if any. MPD older than 0.23.5
+    /// does not support the position so the songs are added to the end of the queue and moved afterwards.
+    fn find_add(&mut self, filter: &[Filter<'_>], position: Option<QueueMoveTarget>) -> MpdResult<()>;
+    /// Same as [`Self::find_add`] but case insensitive
+    fn search_add(&mut self, filter: &[Filter<'_>], position: Option<QueueMoveTarget>) -> MpdResult<()>;
     fn list_tag(&mut self, tag: Tag, filter: Option<&[Filter<'_>]>) -> MpdResult<MpdList>;
+    /// Counts the songs matching the filter without listing them
+    fn count(&mut self, filter: &[Filter<'_>]) -> MpdResult<Count>;
+    /// Counts the songs in the directory and all of its subdirectories, an empty path counts the whole database
+    fn count_in_dir(&mut self, path: &str) -> MpdResult<Count>;
+    /// Adds a tag value to the queued song with the given id. MPD only allows this for remote songs and the
+    /// change lasts only until the song is removed from the queue.
+    fn add_tag_id(&mut self, id: u32, tag: Tag, value: &str) -> MpdResult<()>;
+    /// Removes all values of the tag from the queued song with the given id
+    fn clear_tag_id(&mut self, id: u32, tag: Tag) -> MpdResult<()>;
     // Database
     fn lsinfo(&mut self, path: Option<&str>) -> MpdResult<LsInfo>;
+    /// Like [`MpdClient::lsinfo`] but recurses into every subdirectory
+    fn list_all_info(&mut self, path: Option<&str>) -> MpdResult<LsInfo>;
     fn list_files(&mut self, path: Option<&str>) -> MpdResult<ListFiles>;
-    fn read_picture(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>>;
-    fn albumart(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>>;
+    /// Images larger than `max_size` bytes are skipped and `None` is returned
+    fn read_picture(&mut self, path: &str, max_size: usize) -> MpdResult<Option<Vec<u8>>>;
+    /// Images larger than `max_size` bytes are skipped and `None` is returned
+    fn albumart(&mut self, path: &str, max_size: usize) -> MpdResult<Option<Vec<u8>>>;
     // Stored playlists
     fn list_playlists(&mut self) -> MpdResult<Vec<Playlist>>;
     fn list_playlist(&mut self, name: &str) -> MpdResult<FileList>;
@@ -129,18 +188,35 @@ pub trait MpdClient: Sized {
     fn move_in_playlist(&mut self, playlist_name: &str, range: &SingleOrRange, target_position: usize)
         -> MpdResult<()>;
     fn add_to_playlist(&mut self, playlist_name: &str, uri: &str, target_position: Option<usize>) -> MpdResult<()>;
+    /// Appends all `paths` to the playlist, creating it if it does not exist yet
+    fn add_multiple_to_playlist(&mut self, playlist_name: &str, paths: &[&str]) -> MpdResult<()>;
     fn save_queue_as_playlist(&mut self, name: &str, mode: Option<SaveMode>) -> MpdResult<()>;
     /// This function first invokes [`Self::albumart`].
     /// If no album art is fonud it invokes [`Self::read_picture`].
     /// If no art is still found, but no errors were encountered, None is returned.
-    fn find_album_art(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>>;
+    fn find_album_art(&mut self, path: &str, max_size: usize) -> MpdResult<Option<Vec<u8>>>;
     // Outputs
     fn outputs(&mut self) -> MpdResult<Outputs>;
     fn toggle_output(&mut self, id: u32) -> MpdResult<()>;
     fn enable_output(&mut self, id: u32) -> MpdResult<()>;
     fn disable_output(&mut self, id: u32) -> MpdResult<()>;
+    fn set_output_attribute(&mut self, id: u32, name: &str, value: &str) -> MpdResult<()>;
+    // Partitions
+    fn list_partitions(&mut self) -> MpdResult<Partitions>;
+    fn new_partition(&mut self, name: &str) -> MpdResult<()>;
+    fn delete_partition(&mut self, name: &str) -> MpdResult<()>;
+    /// Moves this connection to another partition, it has its own queue, player state and outputs
+    fn switch_partition(&mut self, name: &str) -> MpdResult<()>;
     // Decoders
     fn decoders(&mut self) -> MpdResult<Decoders>;
+    // Stickers
+    fn sticker_set(&mut self, uri: &str, name: &str, value: &str) -> MpdResult<()>;
+    /// Returns `None` when the song does not have the sticker
+    fn sticker_get(&mut self, uri: &str, name: &str) -> MpdResult<Option<String>>;
+    fn sticker_delete(&mut self, uri: &str, name: &str) -> MpdResult<()>;
+    fn sticker_list(&mut self, uri: &str) -> MpdResult<Stickers>;
+    /// Returns uris of all songs which have the sticker
+    fn sticker_find(&mut self, name: &str) -> MpdResult<Vec<String>>;
 }
 
 impl MpdClient for Client<'_> {
@@ -181,6 +257,21 @@ impl MpdClient for Client<'_> {
         self.send("commands").and_then(ProtoClient::read_response)
     }
 
+    fn tag_types(&mut self) -> MpdResult<Vec<String>> {
+        self.send("tagtypes")
+            .and_then(ProtoClient::read_response)
+            .map(|TagTypes(tags)| tags)
+    }
+
+    fn disable_tag_types(&mut self, tags: &[&str]) -> MpdResult<()> {
+        self.send(&disable_tag_types_command(tags))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn enable_all_tag_types(&mut self) -> MpdResult<()> {
+        self.send("tagtypes all").and_then(ProtoClient::read_ok)
+    }
+
     // Queries
     fn idle(&mut self, subsystem: Option<IdleEvent>) -> MpdResult<Vec<IdleEvent>> {
         if let Some(subsystem) = subsystem {
@@ -195,7 +286,7 @@ impl MpdClient for Client<'_> {
     where
         Self: SocketClient,
     {
-        self.send("idle")
+        self.send(self.idle_command)
     }
 
     fn noidle(&mut self) -> MpdResult<()> {
@@ -300,6 +391,32 @@ impl MpdClient for Client<'_> {
         }
     }
 
+    fn crossfade(&mut self, seconds: u32) -> MpdResult<()> {
+        self.send(&format!("crossfade {seconds}"))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn mixramp_db(&mut self, db: f32) -> MpdResult<()> {
+        self.send(&format!("mixrampdb {db}")).and_then(ProtoClient::read_ok)
+    }
+
+    fn mixramp_delay(&mut self, seconds: Option<f32>) -> MpdResult<()> {
+        let seconds = seconds.map_or_else(|| "nan".to_owned(), |seconds| seconds.to_string());
+        self.send(&format!("mixrampdelay {seconds}"))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn replay_gain_mode(&mut self, mode: ReplayGainMode) -> MpdResult<()> {
+        self.send(&format!("replay_gain_mode {}", mode.to_mpd_value()))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn replay_gain_status(&mut self) -> MpdResult<ReplayGainMode> {
+        self.send("replay_gain_status")
+            .and_then(ProtoClient::read_response)
+            .map(|ReplayGainStatus { mode }| mode)
+    }
+
     // Mounts
     fn mount(&mut self, name: &str, path: &str) -> MpdResult<()> {
         self.send(&format!("mount \"{name}\" \"{path}\""))
@@ -316,7 +433,25 @@ impl MpdClient for Client<'_> {
 
     // Current queue
     fn add(&mut self, path: &str) -> MpdResult<()> {
-        self.send(&format!("add \"{path}\"")).and_then(ProtoClient::read_ok)
+        self.send(&add_command(path)).and_then(ProtoClient::read_ok)
+    }
+
+    fn add_at(&mut self, path: &str, position: QueueMoveTarget) -> MpdResult<u32> {
+        self.send(&add_id_command(path, &position))
+            .and_then(ProtoClient::read_response::<AddId>)
+            .map(|added| added.id)
+    }
+
+    fn add_multiple(&mut self, paths: &[&str]) -> MpdResult<()> {
+        for chunk in paths.chunks(COMMAND_LIST_CHUNK_SIZE) {
+            let commands = chunk.iter().map(|path| add_command(path)).collect::<Vec<_>>();
+            self.send(&format!(
+                "command_list_begin\n{}\ncommand_list_end",
+                commands.join("\n")
+            ))
+            .and_then(ProtoClient::read_ok)?;
+        }
+        Ok(())
     }
 
     fn clear(&mut self) -> MpdResult<()> {
@@ -342,13 +477,58 @@ impl MpdClient for Client<'_> {
             .and_then(ProtoClient::read_ok)
     }
 
+    fn swap(&mut self, pos1: usize, pos2: usize) -> MpdResult<()> {
+        self.send(&swap_command(pos1, pos2)).and_then(ProtoClient::read_ok)
+    }
+
+    fn swap_id(&mut self, id1: u32, id2: u32) -> MpdResult<()> {
+        self.send(&swap_id_command(id1, id2)).and_then(ProtoClient::read_ok)
+    }
+
+    fn shuffle(&mut self, range: Option<SingleOrRange>) -> MpdResult<()> {
+        self.send(&shuffle_command(range.as_ref()))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn set_priority(&mut self, songs: SingleOrRange, prio: u8) -> MpdResult<()> {
+        self.send(&prio_command(&songs, prio)?).and_then(ProtoClient::read_ok)
+    }
+
+    fn set_priority_id(&mut self, id: u32, prio: u8) -> MpdResult<()> {
+        self.send(&prio_id_command(id, prio)).and_then(ProtoClient::read_ok)
+    }
+
+    fn set_song_range(&mut self, id: u32, range: Option<Range<f32>>) -> MpdResult<()> {
+        self.send(&range_id_command(id, range.as_ref())?)
+            .and_then(ProtoClient::read_ok)
+    }
+
     fn playlist_info(&mut self) -> MpdResult<Option<Vec<Song>>> {
         self.send("playlistinfo").and_then(ProtoClient::read_opt_response)
     }
 
+    fn playlist_id(&mut self, id: u32) -> MpdResult<Option<Song>> {
+        match self
+            .send(&format!("playlistid \"{id}\""))
+            .and_then(ProtoClient::read_opt_response)
+        {
+            Ok(song) => Ok(song),
+            Err(MpdError::Mpd(MpdFailureResponse {
+                code: ErrorCode::NoExist,
+                ..
+            })) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Search the database for songs matching FILTER
     fn find(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>> {
-        self.send(&format!("find \"({})\"", filter.to_query_str()))
+        self.send(&filter_command("find", filter))
+            .and_then(ProtoClient::read_response)
+    }
+
+    fn modified_since(&mut self, since: &str) -> MpdResult<Vec<Song>> {
+        self.send(&modified_since_command(since))
             .and_then(ProtoClient::read_response)
     }
 
@@ -358,18 +538,38 @@ impl MpdClient for Client<'_> {
         let query = filter.to_query_str();
         let query = query.as_str();
         log::debug!(query; "Searching for songs");
-        self.send(&format!("search \"({query})\""))
+        self.send(&filter_command("search", filter))
             .and_then(ProtoClient::read_response)
     }
 
-    /// Search the database for songs matching FILTER (see Filters) AND add them to queue.
-    /// Parameters have the same meaning as for find, except that search is not case sensitive.
-    fn search_add(&mut self, filter: &[Filter<'_>]) -> MpdResult<()> {
+    /// Search the queue for songs that match the FILTER exactly.
+    fn playlist_find(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>> {
+        self.send(&format!("playlistfind \"({})\"", filter.to_query_str()))
+            .and_then(ProtoClient::read_response)
+    }
+
+    /// Search the queue for songs matching FILTER. Parameters have the same meaning as for playlistfind, except
+    /// that search is not case sensitive.
+    fn playlist_search(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>> {
         let query = filter.to_query_str();
         let query = query.as_str();
-        log::debug!(query; "Searching for songs and adding them");
-        self.send(&format!("searchadd \"({query})\""))
-            .and_then(ProtoClient::read_ok)
+        log::debug!(query; "Searching for songs in the queue");
+        self.send(&format!("playlistsearch \"({query})\""))
+            .and_then(ProtoClient::read_response)
+    }
+
+    /// Search the database for songs matching FILTER (see Filters) AND add them to queue.
+    /// Parameters have the same meaning as for find, except that search is not case sensitive.
+    fn search_add(&mut self, filter: &[Filter<'_>], position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        log::debug!(query = filter.to_query_str().as_str(); "Searching for songs and adding them");
+        match position {
+            Some(position) if self.version < Version::new(0, 23, 5) => {
+                add_then_move(self, |client| client.search_add(filter, None), &position)
+            }
+            position => self
+                .send(&add_matching_command("searchadd", filter, position.as_ref()))
+                .and_then(ProtoClient::read_ok),
+        }
     }
 
     fn find_one(&mut self, filter: &[Filter<'_>]) -> MpdResult<Option<Song>> {
@@ -379,9 +579,33 @@ impl MpdClient for Client<'_> {
             .pop())
     }
 
-    fn find_add(&mut self, filter: &[Filter<'_>]) -> MpdResult<()> {
-        self.send(&format!("findadd \"({})\"", filter.to_query_str()))
-            .and_then(ProtoClient::read_ok)
+    fn find_files(&mut self, uris: &[&str]) -> MpdResult<Vec<Song>> {
+        let mut result = Vec::with_capacity(uris.len());
+        for chunk in uris.chunks(COMMAND_LIST_CHUNK_SIZE) {
+            let commands = chunk
+                .iter()
+                .map(|uri| filter_command("find", &[Filter::new(Tag::File, uri)]))
+                .collect::<Vec<_>>();
+            result.extend(
+                self.send(&format!(
+                    "command_list_begin\n{}\ncommand_list_end",
+                    commands.join("\n")
+                ))
+                .and_then(ProtoClient::read_response::<Vec<Song>>)?,
+            );
+        }
+        Ok(result)
+    }
+
+    fn find_add(&mut self, filter: &[Filter<'_>], position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        match position {
+            Some(position) if self.version < Version::new(0, 23, 5) => {
+                add_then_move(self, |client| client.find_add(filter, None), &position)
+            }
+            position => self
+                .send(&add_matching_command("findadd", filter, position.as_ref()))
+                .and_then(ProtoClient::read_ok),
+        }
     }
 
     fn list_tag(&mut self, tag: Tag, filter: Option<&[Filter<'_>]>) -> MpdResult<MpdList> {
@@ -393,6 +617,25 @@ impl MpdClient for Client<'_> {
         .and_then(ProtoClient::read_response)
     }
 
+    fn count(&mut self, filter: &[Filter<'_>]) -> MpdResult<Count> {
+        self.send(&filter_command("count", filter))
+            .and_then(ProtoClient::read_response)
+    }
+
+    fn count_in_dir(&mut self, path: &str) -> MpdResult<Count> {
+        self.send(&count_in_dir_command(path))
+            .and_then(ProtoClient::read_response)
+    }
+
+    fn add_tag_id(&mut self, id: u32, tag: Tag, value: &str) -> MpdResult<()> {
+        self.send(&add_tag_id_command(id, tag, value))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn clear_tag_id(&mut self, id: u32, tag: Tag) -> MpdResult<()> {
+        self.send(&clear_tag_id_command(id, tag)).and_then(ProtoClient::read_ok)
+    }
+
     // Database
     fn lsinfo(&mut self, path: Option<&str>) -> MpdResult<LsInfo> {
         Ok(if let Some(path) = path {
@@ -407,6 +650,18 @@ impl MpdClient for Client<'_> {
         //     Ok(self
     }
 
+    fn list_all_info(&mut self, path: Option<&str>) -> MpdResult<LsInfo> {
+        Ok(if let Some(path) = path {
+            self.send(&format!("listallinfo \"{path}\""))
+                .and_then(ProtoClient::read_opt_response)?
+                .unwrap_or_default()
+        } else {
+            self.send("listallinfo")
+                .and_then(ProtoClient::read_opt_response)?
+                .unwrap_or_default()
+        })
+    }
+
     fn list_files(&mut self, path: Option<&str>) -> MpdResult<ListFiles> {
         Ok(if let Some(path) = path {
             self.send(&format!("listfiles \"{path}\""))
@@ -475,6 +730,21 @@ impl MpdClient for Client<'_> {
         }
     }
 
+    fn add_multiple_to_playlist(&mut self, playlist_name: &str, paths: &[&str]) -> MpdResult<()> {
+        for chunk in paths.chunks(COMMAND_LIST_CHUNK_SIZE) {
+            let commands = chunk
+                .iter()
+                .map(|path| format!("playlistadd {} {}", quote_arg(playlist_name), quote_arg(path)))
+                .collect::<Vec<_>>();
+            self.send(&format!(
+                "command_list_begin\n{}\ncommand_list_end",
+                commands.join("\n")
+            ))
+            .and_then(ProtoClient::read_ok)?;
+        }
+        Ok(())
+    }
+
     fn rename_playlist(&mut self, name: &str, new_name: &str) -> MpdResult<()> {
         self.send(&format!("rename \"{name}\" \"{new_name}\""))
             .and_then(ProtoClient::read_ok)
@@ -494,24 +764,24 @@ impl MpdClient for Client<'_> {
         }
     }
 
-    fn read_picture(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>> {
+    fn read_picture(&mut self, path: &str, max_size: usize) -> MpdResult<Option<Vec<u8>>> {
         self.send(&format!("readpicture \"{path}\" 0"))
-            .and_then(ProtoClient::read_bin)
+            .and_then(|client| client.read_bin(max_size))
     }
 
-    fn albumart(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>> {
+    fn albumart(&mut self, path: &str, max_size: usize) -> MpdResult<Option<Vec<u8>>> {
         self.send(&format!("albumart \"{path}\" 0"))
-            .and_then(ProtoClient::read_bin)
+            .and_then(|client| client.read_bin(max_size))
     }
 
-    fn find_album_art(&mut self, path: &str) -> MpdResult<Option<Vec<u8>>> {
-        match self.albumart(path) {
+    fn find_album_art(&mut self, path: &str, max_size: usize) -> MpdResult<Option<Vec<u8>>> {
+        match self.albumart(path, max_size) {
             Ok(Some(v)) => Ok(Some(v)),
             Ok(None)
             | Err(MpdError::Mpd(MpdFailureResponse {
                 code: ErrorCode::NoExist,
                 ..
-            })) => match self.read_picture(path) {
+            })) => match self.read_picture(path, max_size) {
                 Ok(Some(p)) => Ok(Some(p)),
                 Ok(None) => {
                     log::debug!("No album art found, falling back to placeholder image");
@@ -553,13 +823,221 @@ impl MpdClient for Client<'_> {
         self.send(&format!("disableoutput {id}")).and_then(ProtoClient::read_ok)
     }
 
+    fn set_output_attribute(&mut self, id: u32, name: &str, value: &str) -> MpdResult<()> {
+        self.send(&format!("outputset {id} \"{}\" \"{}\"", name.escape(), value.escape()))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    // Partitions
+    fn list_partitions(&mut self) -> MpdResult<Partitions> {
+        self.send("listpartitions").and_then(ProtoClient::read_response)
+    }
+
+    fn new_partition(&mut self, name: &str) -> MpdResult<()> {
+        self.send(&format!("newpartition \"{}\"", name.escape()))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn delete_partition(&mut self, name: &str) -> MpdResult<()> {
+        self.send(&format!("delpartition \"{}\"", name.escape()))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn switch_partition(&mut self, name: &str) -> MpdResult<()> {
+        self.send(&format!("partition \"{}\"", name.escape()))
+            .and_then(ProtoClient::read_ok)?;
+        self.partition = Some(name.to_owned());
+        Ok(())
+    }
+
     // Decoders
     fn decoders(&mut self) -> MpdResult<Decoders> {
         self.send("decoders").and_then(ProtoClient::read_response)
     }
+
+    // Stickers
+    fn sticker_set(&mut self, uri: &str, name: &str, value: &str) -> MpdResult<()> {
+        self.send(&sticker_command("set", uri, Some(name), Some(value)))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn sticker_get(&mut self, uri: &str, name: &str) -> MpdResult<Option<String>> {
+        match self
+            .send(&sticker_command("get", uri, Some(name), None))
+            .and_then(ProtoClient::read_response::<Stickers>)
+        {
+            Ok(Stickers(mut stickers)) => Ok(stickers.remove(name)),
+            Err(MpdError::Mpd(MpdFailureResponse {
+                code: ErrorCode::NoExist,
+                ..
+            })) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn sticker_delete(&mut self, uri: &str, name: &str) -> MpdResult<()> {
+        self.send(&sticker_command("delete", uri, Some(name), None))
+            .and_then(ProtoClient::read_ok)
+    }
+
+    fn sticker_list(&mut self, uri: &str) -> MpdResult<Stickers> {
+        self.send(&sticker_command("list", uri, None, None))
+            .and_then(ProtoClient::read_response)
+    }
+
+    fn sticker_find(&mut self, name: &str) -> MpdResult<Vec<String>> {
+        match self
+            .send(&sticker_command("find", "", Some(name), None))
+            .and_then(ProtoClient::read_response::<StickerMatches>)
+        {
+            Ok(StickerMatches(files)) => Ok(files),
+            Err(MpdError::Mpd(MpdFailureResponse {
+                code: ErrorCode::NoExist,
+                ..
+            })) => Ok(Vec::new()),
+            Err(err) => Err(err),
+        }
+    }
 }
 
-#[derive(Debug)]
+/// Raw protocol command with the filter expression as its argument, ie. `find "((Album == 'album'))"`
+pub fn filter_command(command: &str, filter: &[Filter<'_>]) -> String {
+    format!("{command} \"({})\"", filter.to_query_str())
+}
+
+/// `modified-since` is not a tag comparison so it does not fit into [`Filter`]
+fn modified_since_command(since: &str) -> String {
+    format!("find \"(modified-since '{}')\"", since.escape())
+}
+
+/// `base` is not a tag comparison either. Every song has a file, which matches the whole database at the root.
+fn count_in_dir_command(path: &str) -> String {
+    match path.trim_matches('/') {
+        "" => "count \"(file != '')\"".to_owned(),
+        path => format!("count \"(base '{}')\"", path.escape()),
+    }
+}
+
+fn add_matching_command(command: &str, filter: &[Filter<'_>], position: Option<&QueueMoveTarget>) -> String {
+    match position {
+        Some(position) => format!("{} position {}", filter_command(command, filter), position.as_mpd_str()),
+        None => filter_command(command, filter),
+    }
+}
+
+fn add_tag_id_command(id: u32, tag: Tag, value: &str) -> String {
+    format!(
+        "addtagid \"{id}\" \"{}\" \"{}\"",
+        tag.as_str(),
+        value.replace('\\', r"\\").replace('"', "\\\"")
+    )
+}
+
+fn clear_tag_id_command(id: u32, tag: Tag) -> String {
+    format!("cleartagid \"{id}\" \"{}\"", tag.as_str())
+}
+
+fn swap_command(pos1: usize, pos2: usize) -> String {
+    format!("swap \"{pos1}\" \"{pos2}\"")
+}
+
+fn swap_id_command(id1: u32, id2: u32) -> String {
+    format!("swapid \"{id1}\" \"{id2}\"")
+}
+
+fn shuffle_command(range: Option<&SingleOrRange>) -> String {
+    match range {
+        Some(range) => format!("shuffle {}", range.as_mpd_range()),
+        None => "shuffle".to_owned(),
+    }
+}
+
+fn prio_command(songs: &SingleOrRange, prio: u8) -> MpdResult<String> {
+    if songs.end.is_some_and(|end| end <= songs.start) {
+        return Err(MpdError::Generic(format!(
+            "Invalid range {}:{} to set priority of",
+            songs.start,
+            songs.end.unwrap_or_default()
+        )));
+    }
+    Ok(format!("prio \"{prio}\" {}", songs.as_mpd_range()))
+}
+
+fn prio_id_command(id: u32, prio: u8) -> String {
+    format!("prioid \"{prio}\" \"{id}\"")
+}
+
+/// Floats are formatted by `Display` which always uses a dot as the decimal separator and omits a trailing `.0`
+fn range_id_command(id: u32, range: Option<&Range<f32>>) -> MpdResult<String> {
+    match range {
+        Some(range)
+            if [range.start, range.end]
+                .iter()
+                .any(|bound| !bound.is_finite() || *bound < 0.0) =>
+        {
+            Err(MpdError::Generic(format!(
+                "Invalid range {}:{} of the song to play",
+                range.start, range.end
+            )))
+        }
+        Some(range) => Ok(format!("rangeid \"{id}\" \"{}:{}\"", range.start, range.end)),
+        None => Ok(format!("rangeid \"{id}\" \":\"")),
+    }
+}
+
+/// Quotes a plain command argument, unlike [`StrExt::escape`] which is meant for values inside filter expressions
+fn quote_arg(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', r"\\").replace('"', "\\\""))
+}
+
+fn add_command(path: &str) -> String {
+    format!("add {}", quote_arg(path))
+}
+
+fn sticker_command(command: &str, uri: &str, name: Option<&str>, value: Option<&str>) -> String {
+    let mut result = format!("sticker {command} song {}", quote_arg(uri));
+    for arg in [name, value].into_iter().flatten() {
+        result.push(' ');
+        result.push_str(&quote_arg(arg));
+    }
+    result
+}
+
+pub(super) fn idle_command(subsystems: &[IdleEvent]) -> String {
+    subsystems.iter().fold(String::from("idle"), |mut acc, subsystem| {
+        acc.push(' ');
+        acc.push_str(&subsystem.to_string());
+        acc
+    })
+}
+
+fn disable_tag_types_command(tags: &[&str]) -> String {
+    let tags = tags.iter().map(|tag| format!("\"{tag}\"")).collect::<Vec<_>>();
+    format!("tagtypes disable {}", tags.join(" "))
+}
+
+/// Emulates adding songs at a position for servers which do not support it. Runs `add`, which appends songs to
+/// the end of the queue, and then moves the newly added songs to `position`.
+pub(crate) fn add_then_move<C: MpdClient>(
+    client: &mut C,
+    add: impl FnOnce(&mut C) -> MpdResult<()>,
+    position: &QueueMoveTarget,
+) -> MpdResult<()> {
+    let original_len = client.get_status()?.playlistlength as usize;
+    add(client)?;
+    let status = client.get_status()?;
+    let new_len = status.playlistlength as usize;
+
+    let is_relative = !matches!(position, QueueMoveTarget::Absolute(_));
+    if new_len <= original_len || (is_relative && status.song.is_none()) {
+        // Nothing was added or there is no current song to be relative to, songs stay at the end of the queue
+        return Ok(());
+    }
+
+    client.move_in_queue(SingleOrRange::range(original_len, new_len), position.clone())
+}
+
+#[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum QueueMoveTarget {
     /// relative to the currently playing song; e.g. +0 moves to right after the current song
@@ -644,7 +1122,7 @@ pub enum Tag {
 }
 
 impl Tag {
-    fn as_str(&self) -> &str {
+    pub fn as_str(&self) -> &str {
         match self {
             Tag::Any => "Any",
             Tag::Artist => "Artist",
@@ -658,6 +1136,12 @@ impl Tag {
     }
 }
 
+impl std::fmt::Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 #[allow(dead_code)]
 pub enum FilterKind {
@@ -666,6 +1150,9 @@ pub enum FilterKind {
     #[default]
     Contains,
     Regex,
+    /// Matches the characters of the value in order with anything in between. Browser panes also rank the
+    /// matches, MPD only gets the equivalent regex.
+    Fuzzy,
 }
 
 #[derive(Debug)]
@@ -704,12 +1191,54 @@ impl<'value> Filter<'value> {
         self
     }
 
+    /// Parses a filter typed in the browsers. Words in the form of `tag:value` filter by that tag and all other
+    /// words by [`Tag::Any`]. Input without any known tag is kept whole as a single [`Tag::Any`] filter.
+    pub fn parse_query(query: &'value str, kind: FilterKind) -> Vec<Self> {
+        fn tag_from_key(key: &str) -> Option<Tag> {
+            Some(match key.to_lowercase().as_str() {
+                "any" => Tag::Any,
+                "artist" => Tag::Artist,
+                "albumartist" => Tag::AlbumArtist,
+                "album" => Tag::Album,
+                "title" => Tag::Title,
+                "file" => Tag::File,
+                "genre" => Tag::Genre,
+                _ => return None,
+            })
+        }
+
+        let words = query
+            .split_whitespace()
+            .map(|word| match word.split_once(':') {
+                Some((key, value)) => match tag_from_key(key) {
+                    Some(tag) => (Some(tag), value),
+                    None => (None, word),
+                },
+                None => (None, word),
+            })
+            .collect_vec();
+
+        if words.iter().all(|(tag, _)| tag.is_none()) {
+            return vec![Self::new_with_kind(Tag::Any, query, kind)];
+        }
+
+        words
+            .into_iter()
+            .map(|(tag, value)| Self::new_with_kind(tag.unwrap_or(Tag::Any), value, kind))
+            .collect()
+    }
+
     fn to_query_str(&self) -> String {
         match self.kind {
             FilterKind::Exact => format!("{} == '{}'", self.tag.as_str(), self.value.escape()),
             FilterKind::StartsWith => format!("{} =~ '^{}'", self.tag.as_str(), self.value.escape()),
             FilterKind::Contains => format!("{} =~ '.*{}.*'", self.tag.as_str(), self.value.escape()),
             FilterKind::Regex => format!("{} =~ '{}'", self.tag.as_str(), self.value.escape()),
+            FilterKind::Fuzzy => format!(
+                "{} =~ '.*{}.*'",
+                self.tag.as_str(),
+                self.value.chars().join(".*").as_str().escape()
+            ),
         }
     }
 }
@@ -730,6 +1259,328 @@ impl FilterExt for &[Filter<'_>] {
     }
 }
 
+fn add_id_command(path: &str, position: &QueueMoveTarget) -> String {
+    format!("addid \"{path}\" {}", position.as_mpd_str())
+}
+
+#[cfg(test)]
+mod count_tests {
+    use test_case::test_case;
+
+    use super::count_in_dir_command;
+
+    #[test_case("", r#"count "(file != '')""#; "root")]
+    #[test_case("/", r#"count "(file != '')""#; "root with separator")]
+    #[test_case("artist/album", r#"count "(base 'artist/album')""#; "directory")]
+    fn counts_directory(path: &str, expected: &str) {
+        assert_eq!(count_in_dir_command(path), expected);
+    }
+}
+
+#[cfg(test)]
+mod add_id_tests {
+    use test_case::test_case;
+
+    use super::{add_id_command, QueueMoveTarget};
+
+    #[test_case(QueueMoveTarget::Absolute(5), r#"addid "dir/song.flac" 5"#; "absolute")]
+    #[test_case(QueueMoveTarget::RelativeAdd(0), r#"addid "dir/song.flac" +0"#; "right after current song")]
+    #[test_case(QueueMoveTarget::RelativeAdd(2), r#"addid "dir/song.flac" +2"#; "after current song")]
+    #[test_case(QueueMoveTarget::RelativeSub(0), r#"addid "dir/song.flac" -0"#; "right before current song")]
+    fn command_contains_position(position: QueueMoveTarget, expected: &str) {
+        assert_eq!(add_id_command("dir/song.flac", &position), expected);
+    }
+}
+
+#[cfg(test)]
+mod parse_query_tests {
+    use test_case::test_case;
+
+    use super::{Filter, FilterKind, Tag};
+
+    fn parsed(query: &str) -> Vec<(Tag, &str)> {
+        Filter::parse_query(query, FilterKind::Contains)
+            .into_iter()
+            .map(|filter| (filter.tag, filter.value))
+            .collect()
+    }
+
+    #[test_case("pink floyd"; "plain words")]
+    #[test_case("re:zero"; "unknown tag")]
+    #[test_case(""; "empty")]
+    fn input_without_tags_is_kept_whole(query: &str) {
+        assert_eq!(parsed(query), vec![(Tag::Any, query)]);
+    }
+
+    #[test]
+    fn tagged_and_bare_words_become_separate_filters() {
+        assert_eq!(
+            parsed("artist:pink Title:time live"),
+            vec![(Tag::Artist, "pink"), (Tag::Title, "time"), (Tag::Any, "live")]
+        );
+    }
+}
+
+#[cfg(test)]
+mod add_matching_tests {
+    use rstest::rstest;
+
+    use super::{add_matching_command, add_then_move, Filter, MpdClient, QueueMoveTarget, Tag};
+    use crate::tests::fixtures::mpd_client::{client, TestMpdClient};
+
+    #[test]
+    fn command_without_position() {
+        let result = add_matching_command("findadd", &[Filter::new(Tag::Album, "album")], None);
+
+        assert_eq!(result, r#"findadd "((Album == 'album'))""#);
+    }
+
+    #[test]
+    fn command_with_position() {
+        let result = add_matching_command(
+            "searchadd",
+            &[Filter::new(Tag::Album, "album")],
+            Some(&QueueMoveTarget::RelativeAdd(0)),
+        );
+
+        assert_eq!(result, r#"searchadd "((Album == 'album'))" position +0"#);
+    }
+
+    #[rstest]
+    fn fallback_moves_added_songs_after_current(mut client: TestMpdClient) {
+        client.queue = vec![50, 51, 52];
+        client.status.playlistlength = 3;
+        client.status.song = Some(0);
+
+        add_then_move(
+            &mut client,
+            |client| client.find_add(&[Filter::new(Tag::Artist, "artist_2")], None),
+            &QueueMoveTarget::RelativeAdd(0),
+        )
+        .expect("add to succeed");
+
+        let mut expected = vec![50];
+        expected.extend(30..40);
+        expected.extend([51, 52]);
+        assert_eq!(client.queue, expected);
+    }
+
+    #[rstest]
+    fn fallback_keeps_songs_at_end_without_current_song(mut client: TestMpdClient) {
+        client.queue = vec![50, 51, 52];
+        client.status.playlistlength = 3;
+
+        add_then_move(
+            &mut client,
+            |client| client.find_add(&[Filter::new(Tag::Artist, "artist_2")], None),
+            &QueueMoveTarget::RelativeAdd(0),
+        )
+        .expect("add to succeed");
+
+        let mut expected = vec![50, 51, 52];
+        expected.extend(30..40);
+        assert_eq!(client.queue, expected);
+    }
+}
+
+#[cfg(test)]
+mod tag_id_tests {
+    use super::{add_tag_id_command, clear_tag_id_command, Tag};
+
+    #[test]
+    fn add_tag_id() {
+        let result = add_tag_id_command(12, Tag::Title, r#"the "greatest" \ song"#);
+
+        assert_eq!(result, r#"addtagid "12" "Title" "the \"greatest\" \\ song""#);
+    }
+
+    #[test]
+    fn clear_tag_id() {
+        let result = clear_tag_id_command(12, Tag::Artist);
+
+        assert_eq!(result, r#"cleartagid "12" "Artist""#);
+    }
+}
+
+#[cfg(test)]
+mod swap_tests {
+    use super::{swap_command, swap_id_command};
+
+    #[test]
+    fn swap() {
+        assert_eq!(swap_command(3, 7), r#"swap "3" "7""#);
+    }
+
+    #[test]
+    fn swap_id() {
+        assert_eq!(swap_id_command(12, 40), r#"swapid "12" "40""#);
+    }
+}
+
+#[cfg(test)]
+mod shuffle_tests {
+    use super::{shuffle_command, SingleOrRange};
+
+    #[test]
+    fn shuffle_range() {
+        assert_eq!(shuffle_command(Some(&(3..=6).into())), r#"shuffle "3:7""#);
+    }
+
+    #[test]
+    fn shuffle_whole_queue() {
+        assert_eq!(shuffle_command(None::<&SingleOrRange>), "shuffle");
+    }
+}
+
+#[cfg(test)]
+mod prio_tests {
+    use super::{prio_command, prio_id_command, SingleOrRange};
+
+    #[test]
+    fn prio_range() {
+        assert_eq!(
+            prio_command(&(2..=4).into(), 255).expect("range to be valid"),
+            r#"prio "255" "2:5""#
+        );
+    }
+
+    #[test]
+    fn prio_single() {
+        assert_eq!(
+            prio_command(&SingleOrRange::single(7), 0).expect("range to be valid"),
+            r#"prio "0" "7""#
+        );
+    }
+
+    #[test]
+    fn prio_empty_range_is_rejected() {
+        assert!(prio_command(&SingleOrRange::range(5, 5), 10).is_err());
+    }
+
+    #[test]
+    fn prio_id() {
+        assert_eq!(prio_id_command(12, 128), r#"prioid "128" "12""#);
+    }
+}
+
+#[cfg(test)]
+mod range_id_tests {
+    use test_case::test_case;
+
+    use super::range_id_command;
+
+    #[test]
+    fn range_with_fraction() {
+        assert_eq!(
+            range_id_command(3, Some(&(30.5..90.0))).unwrap(),
+            r#"rangeid "3" "30.5:90""#
+        );
+    }
+
+    #[test]
+    fn range_cleared() {
+        assert_eq!(range_id_command(3, None).unwrap(), r#"rangeid "3" ":""#);
+    }
+
+    #[test_case(f32::NAN, 90.0; "nan start")]
+    #[test_case(30.0, f32::NAN; "nan end")]
+    #[test_case(-1.0, 90.0; "negative start")]
+    #[test_case(30.0, f32::INFINITY; "infinite end")]
+    fn invalid_range_is_rejected(start: f32, end: f32) {
+        assert!(range_id_command(3, Some(&(start..end))).is_err());
+    }
+}
+
+#[cfg(test)]
+mod add_tests {
+    use super::add_command;
+
+    #[test]
+    fn add_escapes_path() {
+        assert_eq!(
+            add_command(r#"dir/"quoted" back\slash don't.flac"#),
+            r#"add "dir/\"quoted\" back\\slash don't.flac""#
+        );
+    }
+}
+
+#[cfg(test)]
+mod sticker_tests {
+    use super::sticker_command;
+
+    #[test]
+    fn set() {
+        assert_eq!(
+            sticker_command("set", "dir/song.flac", Some("rating"), Some("4")),
+            r#"sticker set song "dir/song.flac" "rating" "4""#
+        );
+    }
+
+    #[test]
+    fn get() {
+        assert_eq!(
+            sticker_command("get", "dir/song.flac", Some("rating"), None),
+            r#"sticker get song "dir/song.flac" "rating""#
+        );
+    }
+
+    #[test]
+    fn list() {
+        assert_eq!(
+            sticker_command("list", "dir/song.flac", None, None),
+            r#"sticker list song "dir/song.flac""#
+        );
+    }
+
+    #[test]
+    fn find() {
+        assert_eq!(
+            sticker_command("find", "", Some("favorite"), None),
+            r#"sticker find song "" "favorite""#
+        );
+    }
+
+    #[test]
+    fn escapes_quotes() {
+        assert_eq!(
+            sticker_command("delete", r#"dir/"quoted" song.flac"#, Some("rating"), None),
+            r#"sticker delete song "dir/\"quoted\" song.flac" "rating""#
+        );
+    }
+}
+
+#[cfg(test)]
+mod idle_tests {
+    use super::idle_command;
+    use crate::mpd::commands::IdleEvent;
+
+    #[test]
+    fn all_subsystems_when_none_configured() {
+        assert_eq!(idle_command(&[]), "idle");
+    }
+
+    #[test]
+    fn configured_subsystems() {
+        assert_eq!(
+            idle_command(&[IdleEvent::Player, IdleEvent::StoredPlaylist, IdleEvent::Sticker]),
+            "idle player stored_playlist sticker"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tag_types_tests {
+    use super::disable_tag_types_command;
+
+    #[test]
+    fn disable_multiple() {
+        assert_eq!(
+            disable_tag_types_command(&["Genre", "MUSICBRAINZ_TRACKID"]),
+            r#"tagtypes disable "Genre" "MUSICBRAINZ_TRACKID""#
+        );
+    }
+}
+
 #[cfg(test)]
 mod strext_tests {
     use crate::mpd::mpd_client::StrExt;
@@ -794,6 +1645,13 @@ mod filter_tests {
         assert_eq!(input.to_query_str(), r"(Album =~ 'the greatest.*\\\\s+[A-Za-z]+$')");
     }
 
+    #[test]
+    fn fuzzy() {
+        let input: &[Filter<'_>] = &[Filter::new_with_kind(Tag::Album, "grt", FilterKind::Fuzzy)];
+
+        assert_eq!(input.to_query_str(), "(Album =~ '.*g.*r.*t.*')");
+    }
+
     #[test]
     fn multiple_values() {
         let input: &[Filter<'_>] = &[
@@ -807,3 +1665,16 @@ mod filter_tests {
         );
     }
 }
+
+#[cfg(test)]
+mod modified_since_tests {
+    use super::modified_since_command;
+
+    #[test]
+    fn builds_modified_since_filter() {
+        assert_eq!(
+            modified_since_command("2024-05-01T10:00:00Z"),
+            r#"find "(modified-since '2024-05-01T10:00:00Z')""#
+        );
+    }
+}
@@ -166,12 +166,18 @@ impl<'cmd, 'client, C: SocketClient> ProtoClient<'cmd, 'client, C> {
         }
     }
 
-    pub(super) fn read_bin(mut self) -> MpdResult<Option<Vec<u8>>> {
+    /// Reads the whole binary response in chunks. Responses larger than `max_size` bytes are abandoned after the
+    /// first chunk and `None` is returned so that huge files are never fully buffered.
+    pub(super) fn read_bin(mut self, max_size: usize) -> MpdResult<Option<Vec<u8>>> {
         let mut buf = Vec::new();
         // trim the 0 offset from the initial command because we substitute
         // an actual value here
         let command = self.command.trim_end_matches(" 0");
         let _ = match self.read_bin_inner(&mut buf) {
+            Ok(Some(v)) if v.size_total as usize > max_size => {
+                log::warn!(size = v.size_total, max_size, command; "Binary response is larger than the limit, skipping it");
+                return Ok(None);
+            }
             Ok(Some(v)) => Ok(Some(v)),
             Ok(None) => return Ok(None),
             Err(e) => {
@@ -193,6 +199,10 @@ impl<'cmd, 'client, C: SocketClient> ProtoClient<'cmd, 'client, C> {
             log::trace!(len = buf.len(), command = command.as_str(); "Requesting more binary data");
             self.execute(&command)?;
             match self.read_bin_inner(&mut buf) {
+                Ok(Some(_)) if buf.len() > max_size => {
+                    log::warn!(len = buf.len(), max_size, command = command.as_str(); "Binary response is larger than the limit, skipping it");
+                    return Ok(None);
+                }
                 Ok(Some(response)) => {
                     if buf.len() >= response.size_total as usize || response.bytes_read == 0 {
                         trace!( len = buf.len();"Finshed reading binary response");
@@ -645,5 +655,35 @@ mod tests {
                 }))
             );
         }
+
+        #[test]
+        fn reads_all_chunks() {
+            let bytes = &[1; 111];
+            let buf: &[u8] = b"size: 222\ntype: image/png\nbinary: 111\n";
+            let buf_end: &[u8] = b"\nOK\n";
+            let c = [buf, bytes, buf_end, buf, bytes, buf_end].concat();
+            let mut client = TestClient::new(&c);
+
+            let result = ProtoClient::new("albumart \"file\" 0", &mut client)
+                .unwrap()
+                .read_bin(222);
+
+            assert_eq!(result, Ok(Some([1; 222].to_vec())));
+        }
+
+        #[test]
+        fn aborts_when_declared_size_exceeds_limit() {
+            let bytes = &[1; 111];
+            let buf: &[u8] = b"size: 222\ntype: image/png\nbinary: 111\n";
+            let buf_end: &[u8] = b"\nOK\n";
+            let c = [buf, bytes, buf_end, buf, bytes, buf_end].concat();
+            let mut client = TestClient::new(&c);
+
+            let result = ProtoClient::new("albumart \"file\" 0", &mut client)
+                .unwrap()
+                .read_bin(221);
+
+            assert_eq!(result, Ok(None));
+        }
     }
 }
@@ -0,0 +1,85 @@
+use crate::{
+    config::AddFeedback,
+    core::command::run_external_blocking,
+    shared::macros::{status_error, status_info},
+};
+
+/// Message about songs added to the queue together with where it should be shown
+#[derive(Debug, PartialEq, Eq)]
+pub enum Feedback {
+    StatusLine(String),
+    Notification(String),
+}
+
+impl Feedback {
+    /// Feedback for `what` being added to the queue. `count` is the number of songs it contained and is only
+    /// mentioned when more than a single song was added.
+    pub fn added(mode: AddFeedback, what: &str, count: usize) -> Option<Self> {
+        let message = if count == 1 {
+            format!("{what} added to queue")
+        } else {
+            format!("{what} added to queue ({count} songs)")
+        };
+
+        match mode {
+            AddFeedback::StatusLine => Some(Self::StatusLine(message)),
+            AddFeedback::Notification => Some(Self::Notification(message)),
+            AddFeedback::Silent => None,
+        }
+    }
+
+    pub fn emit(self) {
+        match self {
+            Feedback::StatusLine(message) => status_info!("{message}"),
+            Feedback::Notification(message) => {
+                std::thread::spawn(move || {
+                    if let Err(err) = run_external_blocking(&["notify-send", "rmpc", &message], std::iter::empty()) {
+                        status_error!("Failed to send notification: {err}");
+                    }
+                });
+            }
+        }
+    }
+}
+
+pub fn report_added(mode: AddFeedback, what: &str, count: usize) {
+    if let Some(feedback) = Feedback::added(mode, what, count) {
+        feedback.emit();
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use rstest::rstest;
+
+    use super::Feedback;
+    use crate::{
+        config::AddFeedback,
+        mpd::mpd_client::{Filter, MpdClient, Tag},
+        shared::ext::mpd_client::MpdClientExt,
+        tests::fixtures::mpd_client::{client, TestMpdClient},
+    };
+
+    #[rstest]
+    #[case(AddFeedback::StatusLine, Some(Feedback::StatusLine("Album 'album_2' added to queue (20 songs)".to_owned())))]
+    #[case(AddFeedback::Notification, Some(Feedback::Notification("Album 'album_2' added to queue (20 songs)".to_owned())))]
+    #[case(AddFeedback::Silent, None)]
+    fn album_add_feedback(#[case] mode: AddFeedback, #[case] expected: Option<Feedback>, mut client: TestMpdClient) {
+        let count = client
+            .count_added(false, |client| {
+                client.find_add(&[Filter::new(Tag::Album, "album_2")], None)
+            })
+            .unwrap();
+
+        assert_eq!(Feedback::added(mode, "Album 'album_2'", count), expected);
+    }
+
+    #[test]
+    fn single_song_has_no_count() {
+        assert_eq!(
+            Feedback::added(AddFeedback::StatusLine, "'song' by 'artist'", 1),
+            Some(Feedback::StatusLine("'song' by 'artist' added to queue".to_owned()))
+        );
+    }
+}
@@ -0,0 +1,37 @@
+use std::io::Write;
+
+use anyhow::Result;
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use super::tmux::tmux_write;
+
+/// Copies the text to the system clipboard with the OSC 52 escape sequence. Works over ssh and inside tmux as long
+/// as the terminal supports it.
+pub fn copy(text: &str) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    write_osc52(&mut stdout, text)?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn write_osc52(w: &mut impl Write, text: &str) -> std::io::Result<()> {
+    tmux_write!(w, "\x1b]52;c;{}\x07", STANDARD.encode(text))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::write_osc52;
+
+    #[test]
+    fn text_is_base64_encoded_in_osc52_sequence() {
+        let mut buf = Vec::new();
+
+        write_osc52(&mut buf, "artist/album/song.flac").unwrap();
+
+        // Wrapped in tmux's passthrough when the tests run inside tmux
+        assert!(String::from_utf8(buf)
+            .unwrap()
+            .contains("]52;c;YXJ0aXN0L2FsYnVtL3NvbmcuZmxhYw==\x07"));
+    }
+}
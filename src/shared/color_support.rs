@@ -0,0 +1,199 @@
+use std::sync::LazyLock;
+
+use ratatui::{buffer::Buffer, style::Color};
+
+use super::env::ENV;
+
+pub static COLOR_SUPPORT: LazyLock<ColorSupport> =
+    LazyLock::new(|| ColorSupport::detect(ENV.var("COLORTERM").ok().as_deref(), ENV.var("TERM").ok().as_deref()));
+
+/// Levels of the 6x6x6 color cube in the 256 color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Default xterm values of the 16 basic colors, the actual values depend on the terminal's palette
+const BASIC_COLORS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSupport {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+}
+
+impl ColorSupport {
+    /// Guesses the color support from `COLORTERM` and the terminfo name in `TERM`. Assumes truecolor when
+    /// nothing is known so that colors are never downsampled needlessly.
+    pub fn detect(colorterm: Option<&str>, term: Option<&str>) -> Self {
+        if colorterm.is_some_and(|v| v == "truecolor" || v == "24bit") {
+            return Self::TrueColor;
+        }
+
+        match term {
+            Some(term) if term.ends_with("-direct") => Self::TrueColor,
+            Some(term) if term.contains("256color") => Self::Ansi256,
+            Some("linux" | "xterm" | "screen" | "vt100" | "vt220" | "ansi" | "cons25") => Self::Ansi16,
+            _ => Self::TrueColor,
+        }
+    }
+
+    /// Converts the color to the closest one the terminal is able to display
+    pub fn downsample(self, color: Color) -> Color {
+        match (self, color) {
+            (Self::TrueColor, color) => color,
+            (Self::Ansi256, Color::Rgb(r, g, b)) => Color::Indexed(nearest_indexed((r, g, b))),
+            (Self::Ansi16, Color::Rgb(r, g, b)) => nearest_basic((r, g, b)),
+            (Self::Ansi16, Color::Indexed(idx)) if idx >= 16 => nearest_basic(indexed_to_rgb(idx)),
+            (_, color) => color,
+        }
+    }
+
+    /// Downsamples the colors of every cell in the rendered frame right before it is sent to the terminal
+    pub fn downsample_buffer(self, buffer: &mut Buffer) {
+        if self == Self::TrueColor {
+            return;
+        }
+        for cell in &mut buffer.content {
+            cell.fg = self.downsample(cell.fg);
+            cell.bg = self.downsample(cell.bg);
+        }
+    }
+}
+
+fn distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let dr = u32::from(r1.abs_diff(r2));
+    let dg = u32::from(g1.abs_diff(g2));
+    let db = u32::from(b1.abs_diff(b2));
+    dr * dr + dg * dg + db * db
+}
+
+fn nearest_cube_level(value: u8) -> u8 {
+    (0u8..6)
+        .min_by_key(|idx| CUBE_LEVELS[usize::from(*idx)].abs_diff(value))
+        .unwrap_or_default()
+}
+
+/// Finds the closest entry in either the color cube or the grayscale ramp of the 256 color palette. The first 16
+/// colors are skipped because their values are up to the terminal's theme.
+fn nearest_indexed(rgb: (u8, u8, u8)) -> u8 {
+    let (r, g, b) = (
+        nearest_cube_level(rgb.0),
+        nearest_cube_level(rgb.1),
+        nearest_cube_level(rgb.2),
+    );
+    let cube_idx = 16 + 36 * r + 6 * g + b;
+
+    let average = (u16::from(rgb.0) + u16::from(rgb.1) + u16::from(rgb.2)) / 3;
+    let gray_step = u8::try_from((average.saturating_sub(3) / 10).min(23)).unwrap_or(23);
+    let gray_idx = 232 + gray_step;
+
+    if distance(rgb, indexed_to_rgb(gray_idx)) < distance(rgb, indexed_to_rgb(cube_idx)) {
+        gray_idx
+    } else {
+        cube_idx
+    }
+}
+
+fn nearest_basic(rgb: (u8, u8, u8)) -> Color {
+    BASIC_COLORS
+        .iter()
+        .min_by_key(|(_, value)| distance(rgb, *value))
+        .map_or(Color::Reset, |(color, _)| *color)
+}
+
+fn indexed_to_rgb(idx: u8) -> (u8, u8, u8) {
+    match idx {
+        0..16 => BASIC_COLORS[usize::from(idx)].1,
+        16..232 => {
+            let idx = idx - 16;
+            (
+                CUBE_LEVELS[usize::from(idx / 36)],
+                CUBE_LEVELS[usize::from(idx / 6 % 6)],
+                CUBE_LEVELS[usize::from(idx % 6)],
+            )
+        }
+        232.. => {
+            let value = 8 + (idx - 232) * 10;
+            (value, value, value)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{
+        buffer::Buffer,
+        layout::Rect,
+        style::{Color, Style},
+    };
+    use test_case::test_case;
+
+    use super::ColorSupport;
+
+    #[test_case(0xFF0000, Color::Indexed(196), Color::LightRed ; "red")]
+    #[test_case(0x808080, Color::Indexed(244), Color::DarkGray ; "gray")]
+    #[test_case(0x1E90FF, Color::Indexed(33), Color::LightBlue ; "dodger blue")]
+    #[test_case(0x000000, Color::Indexed(16), Color::Black ; "black")]
+    #[test_case(0xFAFAFA, Color::Indexed(231), Color::White ; "almost white")]
+    fn rgb_is_mapped_to_nearest_palette_entry(hex: u32, expected_256: Color, expected_16: Color) {
+        let color = Color::from_u32(hex);
+
+        assert_eq!(ColorSupport::Ansi256.downsample(color), expected_256);
+        assert_eq!(ColorSupport::Ansi16.downsample(color), expected_16);
+        assert_eq!(ColorSupport::TrueColor.downsample(color), color);
+    }
+
+    #[test]
+    fn indexed_color_is_mapped_to_basic_color() {
+        assert_eq!(ColorSupport::Ansi16.downsample(Color::Indexed(196)), Color::LightRed);
+        assert_eq!(ColorSupport::Ansi16.downsample(Color::Indexed(3)), Color::Indexed(3));
+        assert_eq!(
+            ColorSupport::Ansi256.downsample(Color::Indexed(196)),
+            Color::Indexed(196)
+        );
+    }
+
+    #[test]
+    fn buffer_colors_are_downsampled() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buffer.set_style(
+            buffer.area,
+            Style::default().fg(Color::Rgb(255, 0, 0)).bg(Color::Indexed(196)),
+        );
+
+        ColorSupport::Ansi16.downsample_buffer(&mut buffer);
+
+        assert!(buffer
+            .content
+            .iter()
+            .all(|cell| cell.fg == Color::LightRed && cell.bg == Color::LightRed));
+    }
+
+    #[test_case(Some("truecolor"), Some("xterm-256color"), ColorSupport::TrueColor)]
+    #[test_case(Some("24bit"), Some("linux"), ColorSupport::TrueColor)]
+    #[test_case(None, Some("xterm-256color"), ColorSupport::Ansi256)]
+    #[test_case(None, Some("tmux-256color"), ColorSupport::Ansi256)]
+    #[test_case(None, Some("xterm-direct"), ColorSupport::TrueColor)]
+    #[test_case(None, Some("linux"), ColorSupport::Ansi16)]
+    #[test_case(None, Some("xterm-kitty"), ColorSupport::TrueColor)]
+    #[test_case(None, None, ColorSupport::TrueColor)]
+    fn color_support_is_detected(colorterm: Option<&str>, term: Option<&str>, expected: ColorSupport) {
+        assert_eq!(ColorSupport::detect(colorterm, term), expected);
+    }
+}
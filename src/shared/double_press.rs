@@ -0,0 +1,60 @@
+use std::time::{Duration, Instant};
+
+/// Time in which the second press has to follow the first one to count as a double press
+pub const DOUBLE_PRESS_WINDOW: Duration = Duration::from_millis(400);
+
+/// Detects a key being pressed twice in quick succession. A double press is reported only once, a third press
+/// starts a new sequence.
+#[derive(Debug, Default)]
+pub struct DoublePress {
+    last: Option<Instant>,
+}
+
+impl DoublePress {
+    /// Registers a key press at `now` and returns true if it completes a double press
+    pub fn press(&mut self, now: Instant) -> bool {
+        match self.last.take() {
+            Some(at) if now.duration_since(at) <= DOUBLE_PRESS_WINDOW => true,
+            Some(_) | None => {
+                self.last = Some(now);
+                false
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::DoublePress;
+
+    #[test]
+    fn second_press_within_window_is_double() {
+        let mut double = DoublePress::default();
+        let start = Instant::now();
+
+        assert!(!double.press(start));
+        assert!(double.press(start + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn second_press_after_window_is_not_double() {
+        let mut double = DoublePress::default();
+        let start = Instant::now();
+
+        assert!(!double.press(start));
+        assert!(!double.press(start + Duration::from_secs(1)));
+        assert!(double.press(start + Duration::from_millis(1100)));
+    }
+
+    #[test]
+    fn third_press_starts_new_sequence() {
+        let mut double = DoublePress::default();
+        let start = Instant::now();
+
+        assert!(!double.press(start));
+        assert!(double.press(start + Duration::from_millis(100)));
+        assert!(!double.press(start + Duration::from_millis(200)));
+    }
+}
@@ -25,6 +25,7 @@ pub(crate) enum ClientRequest {
 pub(crate) enum WorkRequest {
     IndexLyrics { lyrics_dir: &'static str },
     Command(Command),
+    PreloadLibrary,
 }
 
 #[derive(Debug)]
@@ -45,6 +46,8 @@ pub(crate) enum WorkDone {
 pub(crate) enum AppEvent {
     UserKeyInput(KeyEvent),
     UserMouseInput(MouseEvent),
+    UserIdle,
+    Shutdown,
     Status(String, Level),
     Log(Vec<u8>),
     IdleEvent(IdleEvent),
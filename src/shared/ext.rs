@@ -28,8 +28,12 @@ pub mod error {
 }
 
 pub mod duration {
+    use chrono::{DateTime, Utc};
+
     pub trait DurationExt {
         fn to_string(&self) -> String;
+        /// Short representation using only the largest unit, for example `5m` or `2d`
+        fn humanize(&self) -> String;
     }
 
     impl DurationExt for std::time::Duration {
@@ -38,6 +42,50 @@ pub mod duration {
             let min = secs / 60;
             format!("{}:{:0>2}", min, secs - min * 60)
         }
+
+        fn humanize(&self) -> String {
+            let secs = self.as_secs();
+            match secs {
+                0..60 => format!("{secs}s"),
+                60..3600 => format!("{}m", secs / 60),
+                3600..86400 => format!("{}h", secs / 3600),
+                _ => format!("{}d", secs / 86400),
+            }
+        }
+    }
+
+    /// Formats the time elapsed between an RFC 3339 timestamp, as reported by MPD, and `now` into a relative
+    /// string such as `2d ago`. Returns `None` when the timestamp cannot be parsed.
+    pub fn humanize_since(timestamp: &str, now: DateTime<Utc>) -> Option<String> {
+        let timestamp = DateTime::parse_from_rfc3339(timestamp).ok()?;
+        match (now - timestamp.with_timezone(&Utc)).to_std() {
+            Ok(elapsed) => Some(format!("{} ago", elapsed.humanize())),
+            // Timestamps in the future are most likely caused by clock differences between rmpc and MPD
+            Err(_) => Some("just now".to_owned()),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use chrono::{DateTime, Utc};
+        use test_case::test_case;
+
+        use super::humanize_since;
+
+        #[test_case("2024-01-10T11:59:55Z", Some("5s ago"); "seconds")]
+        #[test_case("2024-01-10T11:58:00Z", Some("2m ago"); "minutes")]
+        #[test_case("2024-01-10T09:30:00Z", Some("2h ago"); "hours")]
+        #[test_case("2024-01-08T12:00:00Z", Some("2d ago"); "days")]
+        #[test_case("2023-01-10T12:00:00Z", Some("365d ago"); "year")]
+        #[test_case("2024-01-10T12:00:00Z", Some("0s ago"); "now")]
+        #[test_case("2024-01-10T12:05:00Z", Some("just now"); "future")]
+        #[test_case("2024-01-10T14:00:00+02:00", Some("0s ago"); "timezone offset")]
+        #[test_case("yesterday", None; "invalid")]
+        fn humanizes_time_since_timestamp(timestamp: &str, expected: Option<&str>) {
+            let now = "2024-01-10T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+            assert_eq!(humanize_since(timestamp, now).as_deref(), expected);
+        }
     }
 }
 
@@ -130,13 +178,60 @@ pub mod iter {
 }
 
 pub mod mpd_client {
-    use crate::mpd::{
-        errors::{ErrorCode, MpdError, MpdFailureResponse},
-        mpd_client::MpdClient,
+    use crate::{
+        mpd::{
+            commands::{Song, State},
+            errors::{ErrorCode, MpdError, MpdFailureResponse},
+            mpd_client::{Filter, MpdClient, Tag},
+        },
+        shared::{favorites::FAVORITE_STICKER, random::Rng},
     };
 
     pub trait MpdClientExt {
         fn play_last(&mut self, queue_len: usize) -> Result<(), MpdError>;
+        /// Stores `paths` as a playlist with the given name. Existing playlist with the same name is replaced
+        /// when `overwrite` is set, otherwise the songs are appended to it.
+        fn save_as_playlist(&mut self, name: &str, paths: &[&str], overwrite: bool) -> Result<(), MpdError>;
+        /// Plays the next song. When `wrap` is set and the last song of the queue is playing with repeat
+        /// off, playback starts again from the first song instead of stopping.
+        fn next_or_wrap(&mut self, wrap: bool) -> Result<(), MpdError>;
+        /// Picks a random song from the whole database and plays it immediately. The song is appended to the
+        /// queue, or replaces it when `replace` is set. Returns the picked file or `None` for an empty database.
+        fn play_random_song(&mut self, rng: &mut Rng, replace: bool) -> Result<Option<String>, MpdError>;
+        /// Runs `add` and returns how many songs it added to the queue. Playback is started afterwards as
+        /// described in [`MpdClientExt::add_and_autoplay`].
+        fn count_added(
+            &mut self,
+            autoplay: bool,
+            add: impl FnOnce(&mut Self) -> Result<(), MpdError>,
+        ) -> Result<usize, MpdError>
+        where
+            Self: Sized;
+        /// Whether the queue stays within `limit` songs after adding `count` more
+        fn fits_in_queue(&mut self, count: usize, limit: usize) -> Result<bool, MpdError>;
+        /// Stops playback and then empties the queue
+        fn stop_and_clear(&mut self) -> Result<(), MpdError>;
+        /// Runs `add` and, when `autoplay` is set and playback was stopped with an empty queue, starts playing the
+        /// added songs. Playing or paused playback and songs added to an existing queue are left alone.
+        fn add_and_autoplay<R, E: From<MpdError>>(
+            &mut self,
+            autoplay: bool,
+            add: impl FnOnce(&mut Self) -> Result<R, E>,
+        ) -> Result<R, E>
+        where
+            Self: Sized;
+        /// Adds all songs with `artist` as their album artist, or as their artist when there are none. Returns
+        /// how many songs were added.
+        fn add_discography(&mut self, artist: &str, autoplay: bool) -> Result<usize, MpdError>
+        where
+            Self: Sized;
+        /// Marks the song as favorite, or unmarks it when it already is one. Returns whether the song is a
+        /// favorite afterwards.
+        fn toggle_favorite(&mut self, uri: &str) -> Result<bool, MpdError>;
+        /// All songs marked as favorite. Stickers of songs no longer in the database are skipped.
+        fn favorite_songs(&mut self) -> Result<Vec<Song>, MpdError>;
+        /// Names of the enabled outputs separated by commas
+        fn enabled_outputs(&mut self) -> Result<String, MpdError>;
     }
 
     impl<T: MpdClient> MpdClientExt for T {
@@ -156,6 +251,368 @@ pub mod mpd_client {
             };
             Ok(())
         }
+
+        fn save_as_playlist(&mut self, name: &str, paths: &[&str], overwrite: bool) -> Result<(), MpdError> {
+            if overwrite {
+                match self.delete_playlist(name) {
+                    Ok(())
+                    | Err(MpdError::Mpd(MpdFailureResponse {
+                        code: ErrorCode::NoExist,
+                        ..
+                    })) => {}
+                    Err(err) => return Err(err),
+                }
+            }
+            self.add_multiple_to_playlist(name, paths)
+        }
+
+        fn next_or_wrap(&mut self, wrap: bool) -> Result<(), MpdError> {
+            if wrap {
+                let status = self.get_status()?;
+                if !status.repeat && status.song.is_some() && status.nextsongid.is_none() && status.playlistlength > 0 {
+                    return self.play_pos(0);
+                }
+            }
+            self.next()
+        }
+
+        fn play_random_song(&mut self, rng: &mut Rng, replace: bool) -> Result<Option<String>, MpdError> {
+            let files = self.list_tag(Tag::File, None)?.0;
+            let Some(file) = rng.index(files.len()).and_then(|idx| files.into_iter().nth(idx)) else {
+                return Ok(None);
+            };
+
+            let position = if replace {
+                self.clear()?;
+                0
+            } else {
+                self.get_status()?.playlistlength as usize
+            };
+            self.add(&file)?;
+            self.play_pos(position)?;
+
+            Ok(Some(file))
+        }
+
+        fn count_added(
+            &mut self,
+            autoplay: bool,
+            add: impl FnOnce(&mut Self) -> Result<(), MpdError>,
+        ) -> Result<usize, MpdError> {
+            self.add_and_autoplay(autoplay, |client| {
+                let before = client.get_status()?.playlistlength;
+                add(client)?;
+                let after = client.get_status()?.playlistlength;
+
+                Ok(after.saturating_sub(before) as usize)
+            })
+        }
+
+        fn fits_in_queue(&mut self, count: usize, limit: usize) -> Result<bool, MpdError> {
+            let queue_len = self.get_status()?.playlistlength as usize;
+            Ok(queue_len + count <= limit)
+        }
+
+        fn stop_and_clear(&mut self) -> Result<(), MpdError> {
+            self.stop()?;
+            self.clear()
+        }
+
+        fn add_and_autoplay<R, E: From<MpdError>>(
+            &mut self,
+            autoplay: bool,
+            add: impl FnOnce(&mut Self) -> Result<R, E>,
+        ) -> Result<R, E> {
+            if !autoplay {
+                return add(self);
+            }
+
+            let before = self.get_status()?;
+            let result = add(self)?;
+            if before.state != State::Stop || before.playlistlength != 0 {
+                return Ok(result);
+            }
+
+            if self.get_status()?.playlistlength > 0 {
+                self.play()?;
+            }
+
+            Ok(result)
+        }
+
+        fn add_discography(&mut self, artist: &str, autoplay: bool) -> Result<usize, MpdError> {
+            let count = self.count_added(autoplay, |client| {
+                client.find_add(&[Filter::new(Tag::AlbumArtist, artist)], None)
+            })?;
+            if count > 0 {
+                return Ok(count);
+            }
+
+            self.count_added(autoplay, |client| {
+                client.find_add(&[Filter::new(Tag::Artist, artist)], None)
+            })
+        }
+
+        fn toggle_favorite(&mut self, uri: &str) -> Result<bool, MpdError> {
+            if self.sticker_get(uri, FAVORITE_STICKER)?.is_some() {
+                self.sticker_delete(uri, FAVORITE_STICKER)?;
+                Ok(false)
+            } else {
+                self.sticker_set(uri, FAVORITE_STICKER, "1")?;
+                Ok(true)
+            }
+        }
+
+        fn favorite_songs(&mut self) -> Result<Vec<Song>, MpdError> {
+            let files = self.sticker_find(FAVORITE_STICKER)?;
+            self.find_files(&files.iter().map(String::as_str).collect::<Vec<_>>())
+        }
+
+        fn enabled_outputs(&mut self) -> Result<String, MpdError> {
+            Ok(self
+                .outputs()?
+                .0
+                .into_iter()
+                .filter(|output| output.enabled)
+                .map(|output| output.name)
+                .collect::<Vec<_>>()
+                .join(", "))
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use rstest::rstest;
+
+        use super::MpdClientExt;
+        use crate::{
+            mpd::{commands::State, errors::MpdError, mpd_client::MpdClient},
+            shared::{favorites::FAVORITE_STICKER, random::Rng},
+            tests::fixtures::mpd_client::{client, TestMpdClient},
+        };
+
+        #[rstest]
+        fn stop_and_clear_stops_before_clearing(mut client: TestMpdClient) {
+            client.queue = vec![0, 1, 2];
+
+            client.stop_and_clear().expect("stop and clear to succeed");
+
+            assert_eq!(client.call_order, ["stop", "clear"]);
+            assert!(client.queue.is_empty());
+        }
+
+        #[rstest]
+        #[case(true, State::Stop, vec![], true)]
+        #[case(false, State::Stop, vec![], false)]
+        #[case(true, State::Stop, vec![0], false)]
+        #[case(true, State::Play, vec![0], false)]
+        #[case(true, State::Pause, vec![0], false)]
+        fn add_and_autoplay_plays_only_when_enabled_stopped_and_empty(
+            mut client: TestMpdClient,
+            #[case] autoplay: bool,
+            #[case] state: State,
+            #[case] queue: Vec<usize>,
+            #[case] expected_play: bool,
+        ) {
+            client.status.playlistlength = queue.len().try_into().expect("queue length to fit into u32");
+            client.queue = queue;
+            client.status.state = state;
+
+            client
+                .add_and_autoplay(autoplay, |client| client.add("artist_1_album_1_file_0"))
+                .expect("add to succeed");
+
+            assert_eq!(client.call_order.contains(&"play"), expected_play);
+        }
+
+        #[rstest]
+        fn add_and_autoplay_does_not_play_when_nothing_was_added(mut client: TestMpdClient) {
+            client
+                .add_and_autoplay(true, |_| Ok::<_, MpdError>(()))
+                .expect("add to succeed");
+
+            assert!(client.call_order.is_empty());
+        }
+
+        #[rstest]
+        fn add_discography_prefers_album_artist(mut client: TestMpdClient) {
+            for song in client.songs.iter_mut().filter(|song| song.file.starts_with("artist_2")) {
+                song.metadata.insert("albumartist".to_owned(), "artist_1".to_owned());
+            }
+
+            let count = client.add_discography("artist_1", false).expect("add to succeed");
+
+            assert_eq!(count, 10);
+            assert_eq!(client.calls.get("find_add"), Some(&1));
+        }
+
+        #[rstest]
+        fn add_discography_falls_back_to_artist(mut client: TestMpdClient) {
+            let count = client.add_discography("artist_1", false).expect("add to succeed");
+
+            assert_eq!(count, 30);
+            assert_eq!(client.calls.get("find_add"), Some(&2));
+        }
+
+        #[rstest]
+        fn add_discography_of_unknown_artist_adds_nothing(mut client: TestMpdClient) {
+            let count = client.add_discography("nobody", false).expect("add to succeed");
+
+            assert_eq!(count, 0);
+            assert!(client.queue.is_empty());
+        }
+
+        #[rstest]
+        fn toggle_favorite_sets_and_removes_sticker(mut client: TestMpdClient) {
+            let file = "artist_1_album_1_file_0";
+
+            assert!(client.toggle_favorite(file).expect("toggle to succeed"));
+            assert_eq!(
+                client
+                    .sticker_get(file, FAVORITE_STICKER)
+                    .expect("get to succeed")
+                    .as_deref(),
+                Some("1")
+            );
+
+            assert!(!client.toggle_favorite(file).expect("toggle to succeed"));
+            assert_eq!(
+                client.sticker_get(file, FAVORITE_STICKER).expect("get to succeed"),
+                None
+            );
+        }
+
+        #[rstest]
+        fn favorite_songs_are_found_by_sticker(mut client: TestMpdClient) {
+            client
+                .toggle_favorite("artist_2_album_1_file_3")
+                .expect("toggle to succeed");
+            client
+                .toggle_favorite("artist_1_album_2_file_5")
+                .expect("toggle to succeed");
+            client
+                .sticker_set("artist_3_album_1_file_1", "rating", "4")
+                .expect("set to succeed");
+            client
+                .sticker_set("removed_file", FAVORITE_STICKER, "1")
+                .expect("set to succeed");
+
+            let songs = client.favorite_songs().expect("favorites to be found");
+
+            assert_eq!(
+                songs.iter().map(|song| song.file.as_str()).collect::<Vec<_>>(),
+                ["artist_1_album_2_file_5", "artist_2_album_1_file_3"]
+            );
+        }
+
+        #[rstest]
+        fn save_as_playlist_adds_songs_in_one_batch(mut client: TestMpdClient) {
+            let paths = ["artist_1_album_1_file_0", "artist_2_album_1_file_3"];
+
+            client
+                .save_as_playlist("new_playlist", &paths, false)
+                .expect("save to succeed");
+
+            let playlist = client.playlists.iter().find(|p| p.name == "new_playlist");
+            assert_eq!(playlist.map(|p| p.songs_indices.clone()), Some(vec![0, 33]));
+            assert_eq!(client.calls.get("add_multiple_to_playlist"), Some(&1));
+            assert_eq!(client.calls.get("delete_playlist"), None);
+        }
+
+        #[rstest]
+        fn save_as_playlist_overwrites_existing(mut client: TestMpdClient) {
+            let paths = ["artist_1_album_1_file_0"];
+
+            client
+                .save_as_playlist("playlist_2", &paths, true)
+                .expect("save to succeed");
+
+            let playlists = client
+                .playlists
+                .iter()
+                .filter(|p| p.name == "playlist_2")
+                .collect::<Vec<_>>();
+            assert_eq!(playlists.len(), 1);
+            assert_eq!(playlists[0].songs_indices, vec![0]);
+            assert_eq!(client.calls.get("delete_playlist"), Some(&1));
+            assert_eq!(client.calls.get("add_multiple_to_playlist"), Some(&1));
+        }
+
+        #[rstest]
+        #[case(true, Some(&1), None, Some(0))]
+        #[case(false, None, Some(&1), Some(2))]
+        fn next_at_end_of_queue(
+            mut client: TestMpdClient,
+            #[case] wrap: bool,
+            #[case] play_pos_calls: Option<&u32>,
+            #[case] next_calls: Option<&u32>,
+            #[case] expected_song: Option<u32>,
+        ) {
+            client.queue = vec![0, 1, 2];
+            client.status.playlistlength = 3;
+            client.current_song_idx = Some(2);
+            client.status.song = Some(2);
+            client.status.nextsongid = None;
+
+            client.next_or_wrap(wrap).expect("next to succeed");
+
+            assert_eq!(client.calls.get("play_pos"), play_pos_calls);
+            assert_eq!(client.calls.get("next"), next_calls);
+            assert_eq!(client.status.song, expected_song);
+        }
+
+        #[rstest]
+        fn next_or_wrap_skips_to_next_song_when_not_at_end(mut client: TestMpdClient) {
+            client.queue = vec![0, 1, 2];
+            client.current_song_idx = Some(1);
+            client.status.song = Some(1);
+            client.status.nextsongid = Some(2);
+
+            client.next_or_wrap(true).expect("next to succeed");
+
+            assert_eq!(client.calls.get("play_pos"), None);
+            assert_eq!(client.calls.get("next"), Some(&1));
+        }
+
+        #[rstest]
+        #[case(false, vec![0, 1, 2, 5], 3)]
+        #[case(true, vec![5], 0)]
+        fn play_random_song_plays_picked_file(
+            mut client: TestMpdClient,
+            #[case] replace: bool,
+            #[case] expected_queue: Vec<usize>,
+            #[case] expected_pos: usize,
+        ) {
+            client.songs.truncate(10);
+            client.queue = vec![0, 1, 2];
+            client.status.playlistlength = 3;
+            let expected_idx = Rng::with_seed(1234).index(10).expect("index to be generated");
+            assert_eq!(expected_idx, 5);
+
+            let file = client
+                .play_random_song(&mut Rng::with_seed(1234), replace)
+                .expect("play to succeed");
+
+            assert_eq!(file.as_deref(), Some(client.songs[5].file.as_str()));
+            assert_eq!(client.queue, expected_queue);
+            assert_eq!(client.current_song_idx, Some(expected_pos));
+        }
+
+        #[rstest]
+        #[case(20, 30, true)]
+        #[case(7, 10, true)]
+        #[case(8, 10, false)]
+        fn fits_in_queue_counts_songs_already_queued(
+            mut client: TestMpdClient,
+            #[case] count: usize,
+            #[case] limit: usize,
+            #[case] expected: bool,
+        ) {
+            client.queue = vec![0, 1, 2];
+            client.status.playlistlength = 3;
+
+            assert_eq!(client.fits_in_queue(count, limit).expect("status to succeed"), expected);
+        }
     }
 }
 
@@ -280,6 +737,9 @@ pub mod rect {
 
     pub trait ShrinkExt {
         fn shrink_from_top(self, amount: u16) -> Rect;
+        /// Shrinks the rect by the given padding on each side. Padding is clamped so that at least one column
+        /// and one row remain available.
+        fn pad(self, horizontal: u16, vertical: u16) -> Rect;
     }
 
     impl ShrinkExt for Rect {
@@ -288,5 +748,43 @@ pub mod rect {
             self.y = self.y.saturating_add(amount);
             self
         }
+
+        fn pad(mut self, horizontal: u16, vertical: u16) -> Rect {
+            let horizontal = horizontal.min(self.width.saturating_sub(1) / 2);
+            let vertical = vertical.min(self.height.saturating_sub(1) / 2);
+            self.x = self.x.saturating_add(horizontal);
+            self.y = self.y.saturating_add(vertical);
+            self.width = self.width.saturating_sub(horizontal * 2);
+            self.height = self.height.saturating_sub(vertical * 2);
+            self
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ratatui::layout::Rect;
+
+        use super::ShrinkExt;
+
+        #[test]
+        fn pad_shrinks_by_padding() {
+            let area = Rect::new(0, 3, 100, 40);
+
+            assert_eq!(area.pad(2, 1), Rect::new(2, 4, 96, 38));
+        }
+
+        #[test]
+        fn pad_without_padding() {
+            let area = Rect::new(5, 5, 10, 10);
+
+            assert_eq!(area.pad(0, 0), area);
+        }
+
+        #[test]
+        fn pad_is_clamped() {
+            let area = Rect::new(0, 0, 10, 5);
+
+            assert_eq!(area.pad(20, 20), Rect::new(4, 2, 2, 1));
+        }
     }
 }
@@ -0,0 +1,57 @@
+use std::{borrow::Cow, collections::HashSet, sync::Arc};
+
+/// Name of the sticker marking a song as favorite, its value is always `1`
+pub const FAVORITE_STICKER: &str = "favorite";
+/// Word in a filter which only keeps favorite songs
+pub const FAVORITES_FILTER: &str = "is:favorite";
+
+/// Uris of the favorite songs. Refreshed on startup, on reconnect and whenever the sticker database changes.
+/// Cheap to clone so widgets can keep the current set around for rendering.
+#[derive(Debug, Default, Clone)]
+pub struct Favorites(Arc<HashSet<String>>);
+
+impl Favorites {
+    pub fn new(files: HashSet<String>) -> Self {
+        Self(Arc::new(files))
+    }
+
+    pub fn contains(&self, file: &str) -> bool {
+        self.0.contains(file)
+    }
+}
+
+/// Removes [`FAVORITES_FILTER`] from the filter, returns whether it was present and the rest of the filter
+pub fn split_filter(filter: &str) -> (bool, Cow<'_, str>) {
+    if !filter
+        .split_whitespace()
+        .any(|word| word.eq_ignore_ascii_case(FAVORITES_FILTER))
+    {
+        return (false, Cow::Borrowed(filter));
+    }
+
+    let rest = filter
+        .split_whitespace()
+        .filter(|word| !word.eq_ignore_ascii_case(FAVORITES_FILTER))
+        .collect::<Vec<_>>()
+        .join(" ");
+    (true, Cow::Owned(rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_filter;
+
+    #[test]
+    fn filter_without_favorites_is_kept() {
+        assert_eq!(split_filter("artist:foo bar"), (false, "artist:foo bar".into()));
+    }
+
+    #[test]
+    fn favorites_are_removed_from_filter() {
+        assert_eq!(
+            split_filter("foo IS:favorite artist:bar"),
+            (true, "foo artist:bar".into())
+        );
+        assert_eq!(split_filter("is:favorite"), (true, "".into()));
+    }
+}
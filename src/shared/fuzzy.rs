@@ -0,0 +1,66 @@
+/// Bonus for a character matched right after the previous one
+const CONSECUTIVE_BONUS: u32 = 4;
+/// Bonus for a character matched at the start of a word
+const WORD_START_BONUS: u32 = 2;
+
+/// Scores how well `filter` matches `value` when its characters are looked up in order, ignoring case. Returns
+/// `None` when `value` does not contain all characters of `filter` in order. Higher is better, runs of consecutive
+/// characters and matches at word starts score more.
+pub fn score(value: &str, filter: &str) -> Option<u32> {
+    let mut filter = filter.chars().flat_map(char::to_lowercase).peekable();
+    let mut score = 0;
+    let mut previous: Option<char> = None;
+    let mut previous_matched = false;
+
+    for c in value.chars().flat_map(char::to_lowercase) {
+        let Some(wanted) = filter.peek() else {
+            break;
+        };
+        if c == *wanted {
+            filter.next();
+            score += 1;
+            if previous_matched {
+                score += CONSECUTIVE_BONUS;
+            }
+            if !previous.is_some_and(char::is_alphanumeric) {
+                score += WORD_START_BONUS;
+            }
+            previous_matched = true;
+        } else {
+            previous_matched = false;
+        }
+        previous = Some(c);
+    }
+
+    filter.peek().is_none().then_some(score)
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::score;
+
+    #[test_case("Pink Floyd", "pf")]
+    #[test_case("Pink Floyd", "PNKFLD")]
+    #[test_case("Pink Floyd", "")]
+    fn matches_subsequence(value: &str, filter: &str) {
+        assert!(score(value, filter).is_some());
+    }
+
+    #[test_case("Pink Floyd", "fp")]
+    #[test_case("Pink Floyd", "pinkk")]
+    fn rejects_characters_out_of_order(value: &str, filter: &str) {
+        assert_eq!(score(value, filter), None);
+    }
+
+    #[test]
+    fn consecutive_match_ranks_higher() {
+        assert!(score("The Wall", "wall") > score("Whatever All", "wall"));
+    }
+
+    #[test]
+    fn word_start_ranks_higher() {
+        assert!(score("Dark Side", "ds") > score("Odds", "ds"));
+    }
+}
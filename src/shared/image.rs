@@ -22,6 +22,7 @@ pub enum ImageProtocol {
     UeberzugX11,
     Iterm2,
     Sixel,
+    Block,
     #[default]
     None,
 }
@@ -203,6 +204,24 @@ pub fn get_image_area_size_px(area_width_col: u16, area_height_col: u16, max_siz
     Ok((w, h))
 }
 
+/// Size of a single terminal cell in pixels, `None` if the terminal does not report its size in pixels
+pub fn get_cell_size_px() -> Option<(u16, u16)> {
+    let size = crossterm::terminal::window_size().ok()?;
+    if size.width == 0 || size.height == 0 || size.columns == 0 || size.rows == 0 {
+        return None;
+    }
+
+    Some((size.width / size.columns, size.height / size.rows))
+}
+
+pub fn get_image_dimensions(image_data: &[u8]) -> Result<(u32, u32)> {
+    image::ImageReader::new(Cursor::new(image_data))
+        .with_guessed_format()
+        .context("Unable to guess image format")?
+        .into_dimensions()
+        .context("Unable to read image dimensions")
+}
+
 pub fn resize_image(image_data: &[u8], width_px: u16, hegiht_px: u16) -> Result<DynamicImage> {
     Ok(image::ImageReader::new(Cursor::new(image_data))
         .with_guessed_format()
@@ -1,7 +1,7 @@
 use crossterm::event::{KeyCode, KeyEvent as CKeyEvent};
 
 use crate::{
-    config::keys::{CommonAction, GlobalAction, QueueActions},
+    config::keys::{ArtistsActions, CommonAction, DirectoriesActions, GlobalAction, QueueActions, SearchActions},
     context::AppContext,
 };
 
@@ -47,6 +47,16 @@ impl KeyEvent {
         }
     }
 
+    /// Whether the key is bound to the given common action, does not mark the event as handled
+    pub fn is_common_action(&self, action: CommonAction, context: &AppContext) -> bool {
+        context
+            .config
+            .keybinds
+            .navigation
+            .get(&self.inner.into())
+            .is_some_and(|bound| *bound == action)
+    }
+
     pub fn as_global_action(&mut self, context: &AppContext) -> Option<GlobalAction> {
         if self.already_handled {
             None
@@ -80,4 +90,37 @@ impl KeyEvent {
             None
         }
     }
+
+    pub fn as_artists_action(&mut self, context: &AppContext) -> Option<ArtistsActions> {
+        if self.already_handled {
+            None
+        } else if let Some(action) = context.config.keybinds.artists.get(&self.inner.into()) {
+            self.already_handled = true;
+            Some(*action)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_directories_action(&mut self, context: &AppContext) -> Option<DirectoriesActions> {
+        if self.already_handled {
+            None
+        } else if let Some(action) = context.config.keybinds.directories.get(&self.inner.into()) {
+            self.already_handled = true;
+            Some(*action)
+        } else {
+            None
+        }
+    }
+
+    pub fn as_search_action(&mut self, context: &AppContext) -> Option<SearchActions> {
+        if self.already_handled {
+            None
+        } else if let Some(action) = context.config.keybinds.search.get(&self.inner.into()) {
+            self.already_handled = true;
+            Some(*action)
+        } else {
+            None
+        }
+    }
 }
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+
+use crate::{
+    mpd::commands::{lsinfo::LsInfoEntry, LsInfo},
+    ui::panes::browser::DirOrSong,
+};
+
+/// The whole music database as returned by `listallinfo`, grouped by directory so that the directories pane can be
+/// browsed without asking MPD for every level
+#[derive(Debug, Default)]
+pub(crate) struct LibraryTree {
+    /// Contents of every directory keyed by its path from the music root, the root itself is the empty string.
+    /// Contents are sorted the same way as the live `lsinfo` results.
+    dirs: HashMap<String, Vec<DirOrSong>>,
+}
+
+impl LibraryTree {
+    pub fn new(entries: LsInfo) -> Self {
+        let mut dirs: HashMap<String, Vec<DirOrSong>> = HashMap::new();
+        dirs.insert(String::new(), Vec::new());
+
+        for entry in entries {
+            let parent = match &entry {
+                LsInfoEntry::Dir(dir) => {
+                    dirs.entry(dir.full_path.clone()).or_default();
+                    parent_path(&dir.full_path)
+                }
+                LsInfoEntry::File(song) => parent_path(&song.file),
+                LsInfoEntry::Playlist(_) => continue,
+            }
+            .to_owned();
+
+            if let Some(item) = Option::<DirOrSong>::from(entry) {
+                dirs.entry(parent).or_default().push(item);
+            }
+        }
+
+        for items in dirs.values_mut() {
+            items.sort();
+        }
+
+        Self { dirs }
+    }
+
+    /// Contents of the directory at `path`, given as its segments from the music root
+    pub fn dir(&self, path: &[String]) -> Option<&[DirOrSong]> {
+        self.dirs.get(&path.join("/")).map(Vec::as_slice)
+    }
+}
+
+/// Tracks the `listallinfo` dump which runs on its own connection in the background. Database changes arriving while
+/// a dump runs are coalesced into a single reload once it finishes, and a failed dump disables preloading for the
+/// rest of the session instead of being retried on every change.
+#[derive(Debug, Default)]
+pub(crate) struct Preload {
+    running: bool,
+    stale: bool,
+    disabled: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum PreloadOutcome {
+    /// The loaded library is current and can be used
+    Loaded,
+    /// The database changed while loading, the result has to be dropped and a new dump was started
+    Stale,
+    /// Loading failed, preloading is disabled from now on
+    Failed,
+}
+
+impl Preload {
+    /// Returns whether a new dump should be started now
+    pub fn request(&mut self) -> bool {
+        if self.disabled {
+            return false;
+        }
+        if self.running {
+            self.stale = true;
+            return false;
+        }
+        self.running = true;
+        true
+    }
+
+    pub fn on_finished(&mut self, loaded: bool) -> PreloadOutcome {
+        self.running = false;
+        if !loaded {
+            self.disabled = true;
+            return PreloadOutcome::Failed;
+        }
+        if std::mem::take(&mut self.stale) {
+            self.running = true;
+            return PreloadOutcome::Stale;
+        }
+        PreloadOutcome::Loaded
+    }
+}
+
+fn parent_path(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{LibraryTree, Preload, PreloadOutcome};
+    use crate::{
+        mpd::commands::{
+            lsinfo::{Dir, LsInfoEntry},
+            LsInfo, Song,
+        },
+        ui::panes::browser::DirOrSong,
+    };
+
+    fn dir(full_path: &str) -> LsInfoEntry {
+        LsInfoEntry::Dir(Dir {
+            path: full_path.rsplit('/').next().unwrap_or_default().to_owned(),
+            full_path: full_path.to_owned(),
+            last_modified: String::new(),
+        })
+    }
+
+    fn song(file: &str, track: &str) -> LsInfoEntry {
+        LsInfoEntry::File(Song {
+            file: file.to_owned(),
+            metadata: HashMap::from([("track".to_owned(), track.to_owned())]),
+            ..Default::default()
+        })
+    }
+
+    fn files(items: &[DirOrSong]) -> Vec<&str> {
+        items
+            .iter()
+            .map(|item| match item {
+                DirOrSong::Dir { full_path, .. } => full_path.as_str(),
+                DirOrSong::Song(song) => song.file.as_str(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn groups_entries_by_directory() {
+        let tree = LibraryTree::new(LsInfo(vec![
+            song("loose.mp3", "1"),
+            dir("artist"),
+            dir("artist/album"),
+            song("artist/album/02.mp3", "2"),
+            song("artist/album/01.mp3", "1"),
+            dir("artist/empty"),
+        ]));
+
+        assert_eq!(files(tree.dir(&[]).unwrap()), ["artist", "loose.mp3"]);
+        assert_eq!(
+            files(tree.dir(&["artist".to_owned()]).unwrap()),
+            ["artist/album", "artist/empty"]
+        );
+        assert_eq!(
+            files(tree.dir(&["artist".to_owned(), "album".to_owned()]).unwrap()),
+            ["artist/album/01.mp3", "artist/album/02.mp3"]
+        );
+        assert!(tree.dir(&["artist".to_owned(), "empty".to_owned()]).unwrap().is_empty());
+        assert!(tree.dir(&["missing".to_owned()]).is_none());
+    }
+
+    #[test]
+    fn changes_during_preload_are_coalesced_into_one_reload() {
+        let mut preload = Preload::default();
+
+        assert!(preload.request());
+        assert!(!preload.request());
+        assert!(!preload.request());
+
+        assert_eq!(preload.on_finished(true), PreloadOutcome::Stale);
+        assert_eq!(preload.on_finished(true), PreloadOutcome::Loaded);
+        assert!(preload.request());
+    }
+
+    #[test]
+    fn failed_preload_is_not_retried() {
+        let mut preload = Preload::default();
+
+        assert!(preload.request());
+        assert!(!preload.request());
+
+        assert_eq!(preload.on_finished(false), PreloadOutcome::Failed);
+        assert!(!preload.request());
+    }
+}
@@ -1,16 +1,31 @@
+pub mod add_feedback;
+pub mod clipboard;
+pub mod color_support;
 pub mod dependencies;
+pub mod double_press;
 pub mod env;
 pub mod events;
 pub mod ext;
+pub mod favorites;
+pub mod fuzzy;
 pub mod geometry;
 pub mod id;
 pub mod image;
 pub mod key_event;
+pub mod library_tree;
 pub mod logging;
 pub mod lrc;
 pub mod macros;
 pub mod mouse_event;
 pub mod mpd_query;
+pub mod mute;
+pub mod notification;
 pub mod percent;
+pub mod play_history;
+pub mod preview_play;
+pub mod queue_repeat;
+pub mod random;
+pub mod seek_acceleration;
+pub mod time_budget;
 pub mod tmux;
 pub mod ytdlp;
@@ -1,6 +1,6 @@
 use std::time::{Duration, Instant};
 
-use crossterm::event::{MouseButton, MouseEvent as CTMouseEvent, MouseEventKind as CTMouseEventKind};
+use crossterm::event::{KeyModifiers, MouseButton, MouseEvent as CTMouseEvent, MouseEventKind as CTMouseEventKind};
 use ratatui::layout::Position;
 
 // maybe make the timout configurable?
@@ -9,10 +9,23 @@ const DOUBLE_CLICK_TIMEOUT: Duration = Duration::from_millis(500);
 #[derive(Debug, Default, Clone, Copy)]
 pub struct MouseEventTracker {
     last_left_click: Option<TimedMouseEvent>,
+    last_position: Option<Position>,
 }
 
 impl MouseEventTracker {
+    /// Events with shift held are left alone so they can be used for the native text selection of the terminal.
+    /// Most terminals do not even report them while the mouse is captured but some do.
     pub fn track_and_get(&mut self, event: CTMouseEvent) -> Option<MouseEvent> {
+        if event.modifiers.contains(KeyModifiers::SHIFT) {
+            return None;
+        }
+
+        let position = Position::new(event.column, event.row);
+        if matches!(event.kind, CTMouseEventKind::Moved) && self.last_position == Some(position) {
+            return None;
+        }
+        self.last_position = Some(position);
+
         self.crossterm_ev_to_mouse_event(event).inspect(|ev| match ev.kind {
             MouseEventKind::LeftClick => {
                 self.last_left_click = (*ev).into();
@@ -66,7 +79,11 @@ impl MouseEventTracker {
             }),
             CTMouseEventKind::Up(_) => None,
             CTMouseEventKind::Drag(_) => None,
-            CTMouseEventKind::Moved => None,
+            CTMouseEventKind::Moved => Some(MouseEvent {
+                x,
+                y,
+                kind: MouseEventKind::Move,
+            }),
             CTMouseEventKind::ScrollLeft => None,
             CTMouseEventKind::ScrollRight => None,
         }
@@ -88,6 +105,7 @@ pub enum MouseEventKind {
     RightClick,
     ScrollDown,
     ScrollUp,
+    Move,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -126,3 +144,42 @@ impl From<MouseEvent> for Position {
         Self { x: value.x, y: value.y }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent as CTMouseEvent, MouseEventKind as CTMouseEventKind};
+    use test_case::test_case;
+
+    use super::{MouseEventKind, MouseEventTracker};
+
+    fn event(kind: CTMouseEventKind, modifiers: KeyModifiers) -> CTMouseEvent {
+        CTMouseEvent {
+            kind,
+            column: 5,
+            row: 7,
+            modifiers,
+        }
+    }
+
+    #[test_case(CTMouseEventKind::Down(MouseButton::Left))]
+    #[test_case(CTMouseEventKind::Down(MouseButton::Right))]
+    #[test_case(CTMouseEventKind::ScrollDown)]
+    #[test_case(CTMouseEventKind::Moved)]
+    fn shift_held_events_are_ignored(kind: CTMouseEventKind) {
+        let mut tracker = MouseEventTracker::default();
+
+        assert!(tracker.track_and_get(event(kind, KeyModifiers::SHIFT)).is_none());
+        assert!(tracker.track_and_get(event(kind, KeyModifiers::NONE)).is_some());
+    }
+
+    #[test]
+    fn shift_held_click_does_not_count_towards_double_click() {
+        let mut tracker = MouseEventTracker::default();
+        let click = CTMouseEventKind::Down(MouseButton::Left);
+
+        tracker.track_and_get(event(click, KeyModifiers::SHIFT));
+        let result = tracker.track_and_get(event(click, KeyModifiers::NONE));
+
+        assert!(matches!(result.map(|ev| ev.kind), Some(MouseEventKind::LeftClick)));
+    }
+}
@@ -1,4 +1,4 @@
-use std::any::Any;
+use std::{any::Any, collections::HashSet, sync::Mutex};
 
 use crate::{
     config::tabs::PaneType,
@@ -6,6 +6,7 @@ use crate::{
         client::Client,
         commands::{Decoder, Output, Song, Status, Volume},
     },
+    shared::library_tree::LibraryTree,
     ui::panes::browser::DirOrSong,
 };
 use anyhow::Result;
@@ -71,12 +72,36 @@ pub(crate) enum MpdQueryResult {
         playlists: Vec<String>,
         song_file: String,
     },
-    AlbumArt(Option<Vec<u8>>),
-    Status(Status),
+    SaveAsPlaylist {
+        name: String,
+        files: Vec<String>,
+        exists: bool,
+    },
+    /// The add would grow the queue past `queue_size_warning`, it is taken out and run once the user confirms
+    /// it. Wrapped in a mutex because app events and modals have to be `Sync`.
+    ConfirmQueueSize(Mutex<Option<MpdCommand>>),
+    RepeatQueue {
+        times: u32,
+    },
+    LastPlayed {
+        file: String,
+        timestamp: Option<String>,
+    },
+    AlbumArt {
+        /// Song the art was searched for, the result is dropped if the song is not current anymore
+        song_uri: String,
+        data: Option<Vec<u8>>,
+    },
+    Status(Box<Status>),
     Queue(Option<Vec<Song>>),
+    Favorites(HashSet<String>),
+    SupportedTags(HashSet<String>),
     Volume(Volume),
     Outputs(Vec<Output>),
+    Partitions(Vec<String>),
     Decoders(Vec<Decoder>),
+    /// `None` when the library could not be loaded
+    Library(Option<LibraryTree>),
     ExternalCommand(&'static [&'static str], Vec<Song>),
     Any(Box<dyn Any + Send + Sync>),
 }
@@ -0,0 +1,78 @@
+/// MPD has no native mute so muting is emulated by setting the volume to zero and remembering the previous volume
+/// until the next toggle.
+#[derive(Debug, Default)]
+pub struct MuteState {
+    saved_volume: Option<u8>,
+}
+
+impl MuteState {
+    /// Toggles mute based on the `current` volume and returns the volume which should be set
+    pub fn toggle(&mut self, current: u8) -> u8 {
+        match self.saved_volume.take() {
+            Some(saved) if current == 0 => saved,
+            // Volume was changed by something else while muted, treat it as unmuted and mute again
+            Some(_) | None => {
+                self.saved_volume = Some(current);
+                0
+            }
+        }
+    }
+
+    /// Forgets the saved volume when the volume was changed from elsewhere while muted
+    pub fn on_volume_changed(&mut self, current: u8) {
+        if current != 0 {
+            self.saved_volume = None;
+        }
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.saved_volume.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MuteState;
+
+    #[test]
+    fn mute_saves_and_unmute_restores_volume() {
+        let mut state = MuteState::default();
+
+        assert_eq!(state.toggle(65), 0);
+        assert!(state.is_muted());
+        assert_eq!(state.toggle(0), 65);
+        assert!(!state.is_muted());
+    }
+
+    #[test]
+    fn mixer_update_with_muted_volume_keeps_saved_volume() {
+        let mut state = MuteState::default();
+        state.toggle(65);
+
+        state.on_volume_changed(0);
+
+        assert!(state.is_muted());
+        assert_eq!(state.toggle(0), 65);
+    }
+
+    #[test]
+    fn external_volume_change_while_muted_unmutes() {
+        let mut state = MuteState::default();
+        state.toggle(65);
+
+        state.on_volume_changed(30);
+
+        assert!(!state.is_muted());
+        assert_eq!(state.toggle(30), 0);
+        assert_eq!(state.toggle(0), 30);
+    }
+
+    #[test]
+    fn toggle_after_missed_external_change_mutes_again() {
+        let mut state = MuteState::default();
+        state.toggle(65);
+
+        assert_eq!(state.toggle(40), 0);
+        assert_eq!(state.toggle(0), 40);
+    }
+}
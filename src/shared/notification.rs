@@ -0,0 +1,73 @@
+use std::io::Write;
+
+use anyhow::Result;
+
+use crate::mpd::commands::Song;
+
+use super::tmux::tmux_write;
+
+/// Shows a desktop notification about the song with the OSC 9 escape sequence. Does not need any external program
+/// but the terminal has to support it, otherwise the sequence is ignored.
+pub fn notify_song(song: &Song) -> Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    write_osc9(&mut stdout, &song_message(song))?;
+    stdout.flush()?;
+    Ok(())
+}
+
+fn song_message(song: &Song) -> String {
+    let title = song.title().map_or(song.file.as_str(), String::as_str);
+    match song.artist() {
+        Some(artist) => format!("{artist} - {title}"),
+        None => title.to_owned(),
+    }
+}
+
+fn write_osc9(w: &mut impl Write, message: &str) -> std::io::Result<()> {
+    // Control characters would terminate the sequence early
+    let message: String = message.chars().filter(|c| !c.is_control()).collect();
+    tmux_write!(w, "\x1b]9;{message}\x07")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{song_message, write_osc9};
+    use crate::mpd::commands::Song;
+
+    #[test]
+    fn message_contains_artist_and_title() {
+        let song = Song {
+            file: "artist/album/song.flac".to_owned(),
+            metadata: HashMap::from([
+                ("title".to_owned(), "Song".to_owned()),
+                ("artist".to_owned(), "Artist".to_owned()),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(song_message(&song), "Artist - Song");
+    }
+
+    #[test]
+    fn message_falls_back_to_file_without_title() {
+        let song = Song {
+            file: "artist/album/song.flac".to_owned(),
+            ..Default::default()
+        };
+
+        assert_eq!(song_message(&song), "artist/album/song.flac");
+    }
+
+    #[test]
+    fn control_characters_are_removed_from_osc9_sequence() {
+        let mut buf = Vec::new();
+
+        write_osc9(&mut buf, "Artist - Song\x07\x1b").unwrap();
+
+        // Wrapped in tmux's passthrough when the tests run inside tmux
+        assert!(String::from_utf8(buf).unwrap().contains("]9;Artist - Song\x07"));
+    }
+}
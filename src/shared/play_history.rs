@@ -0,0 +1,223 @@
+use std::{collections::VecDeque, fmt::Display};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use crate::{
+    context::AppContext,
+    mpd::{
+        commands::{Song, State},
+        errors::MpdError,
+        mpd_client::MpdClient,
+    },
+};
+
+/// Sticker holding the time the song last started playing as an RFC 3339 timestamp
+pub const LAST_PLAYED_STICKER: &str = "lastplayed";
+
+/// Songs played during this session, oldest first. MPD does not keep any play history so it is recorded on the
+/// client whenever the current song changes.
+#[derive(Debug, Default)]
+pub struct PlayHistory {
+    entries: VecDeque<HistoryEntry>,
+    capacity: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryEntry {
+    pub file: String,
+    pub label: String,
+}
+
+impl HistoryEntry {
+    pub fn add_to_queue(&self, client: &mut impl MpdClient) -> Result<(), MpdError> {
+        client.add(&self.file)
+    }
+}
+
+impl Display for HistoryEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.label)
+    }
+}
+
+impl From<&Song> for HistoryEntry {
+    fn from(song: &Song) -> Self {
+        let label = match (song.artist(), song.title()) {
+            (Some(artist), Some(title)) => format!("{artist} - {title}"),
+            (None, Some(title)) => title.to_owned(),
+            _ => song.file.clone(),
+        };
+
+        Self {
+            file: song.file.clone(),
+            label,
+        }
+    }
+}
+
+impl PlayHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    /// Records the current song. Repeats of the last recorded song, for example when it is restarted or when
+    /// single mode is on with repeat, are not recorded again.
+    pub fn on_song_change(&mut self, context: &AppContext) {
+        if let Some((_, song)) = context.find_current_song_in_queue() {
+            if context.config.record_last_played
+                && context.status.state != State::Stop
+                && !song.is_stream()
+                && context.supported_commands.contains("sticker")
+            {
+                let file = song.file.clone();
+                context.command(move |client| {
+                    if let Err(err) = record_last_played(client, &file, Utc::now()) {
+                        log::warn!(error:? = err, file = file.as_str(); "Failed to record last played time");
+                    }
+                    Ok(())
+                });
+            }
+            self.push(song.into());
+        }
+    }
+
+    fn push(&mut self, entry: HistoryEntry) {
+        if self.capacity == 0 || self.entries.back().is_some_and(|last| last.file == entry.file) {
+            return;
+        }
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Recorded songs, most recently played first
+    pub fn recent(&self) -> Vec<HistoryEntry> {
+        self.entries.iter().rev().cloned().collect()
+    }
+}
+
+pub fn record_last_played(client: &mut impl MpdClient, file: &str, now: DateTime<Utc>) -> Result<(), MpdError> {
+    client.sticker_set(
+        file,
+        LAST_PLAYED_STICKER,
+        &now.to_rfc3339_opts(SecondsFormat::Secs, true),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::{DateTime, Utc};
+    use crossbeam::channel::{Receiver, Sender};
+    use rstest::rstest;
+
+    use super::{record_last_played, PlayHistory, LAST_PLAYED_STICKER};
+    use crate::{
+        config::Leak,
+        context::AppContext,
+        mpd::commands::{Song, State},
+        shared::events::{ClientRequest, WorkRequest},
+        tests::fixtures::{
+            app_context, client_request_channel,
+            mpd_client::{client, TestMpdClient},
+            work_request_channel,
+        },
+    };
+
+    fn play(context: &mut AppContext, history: &mut PlayHistory, id: u32) {
+        context.status.songid = Some(id);
+        history.on_song_change(context);
+    }
+
+    #[rstest]
+    fn song_changes_are_recorded(mut app_context: AppContext) {
+        app_context.status.state = State::Play;
+        app_context.queue = (0..4)
+            .map(|id| Song {
+                id,
+                file: format!("song_{id}.flac"),
+                metadata: HashMap::from([
+                    ("artist".to_owned(), "artist".to_owned()),
+                    ("title".to_owned(), format!("title {id}")),
+                ]),
+                ..Default::default()
+            })
+            .collect();
+        let mut history = PlayHistory::new(3);
+
+        for id in [0, 1, 1, 2, 3] {
+            play(&mut app_context, &mut history, id);
+        }
+
+        let recent = history.recent();
+        assert_eq!(
+            recent.iter().map(|entry| entry.file.as_str()).collect::<Vec<_>>(),
+            vec!["song_3.flac", "song_2.flac", "song_1.flac"]
+        );
+        assert_eq!(recent[0].label, "artist - title 3");
+    }
+
+    #[rstest]
+    fn re_adding_adds_the_file(mut app_context: AppContext, mut client: TestMpdClient) {
+        app_context.status.state = State::Play;
+        app_context.queue = vec![Song {
+            id: 7,
+            file: client.songs[5].file.clone(),
+            ..Default::default()
+        }];
+        let mut history = PlayHistory::new(10);
+        play(&mut app_context, &mut history, 7);
+
+        history.recent()[0].add_to_queue(&mut client).unwrap();
+
+        assert_eq!(client.queue, vec![5]);
+    }
+
+    #[rstest]
+    fn last_played_is_stored_as_timestamp(mut client: TestMpdClient) {
+        let now = "2024-01-10T12:00:00Z".parse::<DateTime<Utc>>().unwrap();
+
+        record_last_played(&mut client, "song.flac", now).unwrap();
+
+        assert_eq!(
+            client.stickers["song.flac"][LAST_PLAYED_STICKER],
+            "2024-01-10T12:00:00Z"
+        );
+    }
+
+    #[rstest]
+    #[case(true, State::Play, true)]
+    #[case(false, State::Play, false)]
+    #[case(true, State::Stop, false)]
+    fn last_played_is_recorded_only_when_enabled_and_playing(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+        #[case] enabled: bool,
+        #[case] state: State,
+        #[case] recorded: bool,
+    ) {
+        let rx = client_request_channel.1.clone();
+        let mut app_context = app_context(work_request_channel, client_request_channel);
+        let mut config = app_context.config.clone();
+        config.record_last_played = enabled;
+        app_context.config = config.leak();
+        app_context.supported_commands.insert("sticker".to_owned());
+        app_context.status.state = state;
+        app_context.queue = vec![Song {
+            id: 1,
+            file: "song.flac".to_owned(),
+            ..Default::default()
+        }];
+        let mut history = PlayHistory::new(10);
+
+        play(&mut app_context, &mut history, 1);
+
+        assert_eq!(matches!(rx.try_recv(), Ok(ClientRequest::Command(_))), recorded);
+    }
+}
@@ -0,0 +1,524 @@
+use std::{
+    sync::{Arc, Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use crossbeam::channel::{unbounded, RecvTimeoutError, Sender};
+
+use crate::{
+    context::AppContext,
+    mpd::{
+        client::Client,
+        commands::{Song, State},
+        mpd_client::{MpdClient, QueueMoveTarget, ValueChange},
+    },
+    shared::{events::ClientRequest, macros::status_info, mpd_query::MpdCommand},
+};
+
+/// Position in the song at which the snippet starts playing
+const SNIPPET_START: Duration = Duration::from_secs(30);
+/// How long the snippet plays before playback is stopped
+const SNIPPET_LENGTH: Duration = Duration::from_secs(15);
+/// How long the selection has to stay on a song before its snippet starts, so that scrolling through a list does
+/// not start a snippet for every song on the way
+const SELECTION_DEBOUNCE: Duration = Duration::from_millis(300);
+/// Part of a song played while auditioning it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Snippet {
+    start: Duration,
+    length: Duration,
+}
+
+impl Snippet {
+    /// Songs too short for the whole snippet are played from a point where it still fits, or from the beginning.
+    /// Songs with unknown duration start at the beginning.
+    fn new(duration: Option<Duration>, start: Duration, length: Duration) -> Self {
+        match duration {
+            Some(duration) => {
+                let start = start.min(duration.saturating_sub(length));
+                Self {
+                    start,
+                    length: length.min(duration.saturating_sub(start)),
+                }
+            }
+            None => Self {
+                start: Duration::ZERO,
+                length,
+            },
+        }
+    }
+}
+
+/// Playback to return to once preview play is turned off
+#[derive(Debug, Clone, Copy)]
+struct Playback {
+    state: State,
+    song_id: Option<u32>,
+    elapsed: Duration,
+}
+
+/// Song added to the queue for a snippet
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Added {
+    id: u32,
+    file: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Start { file: String, snippet: Snippet },
+    Stop,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum TimerMessage {
+    Selected { file: String, snippet: Snippet },
+    Cancel,
+}
+
+/// Decides when snippets start and stop. Only one step is pending at a time so a new selection replaces both a
+/// snippet which did not start yet and the stop of the one playing. The current time is passed in by the caller.
+#[derive(Debug, Default)]
+struct Schedule {
+    next: Option<(Instant, Step)>,
+}
+
+impl Schedule {
+    fn select(&mut self, file: String, snippet: Snippet, now: Instant) {
+        self.next = Some((now + SELECTION_DEBOUNCE, Step::Start { file, snippet }));
+    }
+
+    fn cancel(&mut self) {
+        self.next = None;
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.next.as_ref().map(|(at, _)| *at)
+    }
+
+    /// Returns the step which is due at `now`, starting a snippet schedules its stop
+    fn due(&mut self, now: Instant) -> Option<Step> {
+        match self.next.take() {
+            Some((at, step)) if at <= now => {
+                if let Step::Start { snippet, .. } = &step {
+                    self.next = Some((now + snippet.length, Step::Stop));
+                }
+                Some(step)
+            }
+            next => {
+                self.next = next;
+                None
+            }
+        }
+    }
+}
+
+/// Auditions songs by playing a short snippet of the selected song. The snippet song is appended to the queue
+/// and removed again when the next song is selected or when the mode is turned off, after which the playback from
+/// before is restored.
+#[derive(Debug)]
+pub struct PreviewPlay {
+    restore: Option<Playback>,
+    current_file: Option<String>,
+    /// Selection changes for the timer thread which starts and stops the snippets. The thread is started when
+    /// preview play is turned on for the first time.
+    timer: Option<Sender<TimerMessage>>,
+    /// Songs added to the queue for the snippets
+    added: Arc<Mutex<Vec<Added>>>,
+    snippet_start: Duration,
+    snippet_length: Duration,
+}
+
+impl Default for PreviewPlay {
+    fn default() -> Self {
+        Self {
+            restore: None,
+            current_file: None,
+            timer: None,
+            added: Arc::default(),
+            snippet_start: SNIPPET_START,
+            snippet_length: SNIPPET_LENGTH,
+        }
+    }
+}
+
+impl PreviewPlay {
+    pub fn is_active(&self) -> bool {
+        self.restore.is_some()
+    }
+
+    /// Turns preview play on and remembers the current playback, or turns it off and restores that playback
+    pub fn toggle(&mut self, context: &AppContext) {
+        if let Some(playback) = self.restore.take() {
+            self.send(TimerMessage::Cancel);
+            self.stop(playback, context);
+            status_info!("Preview play off");
+        } else {
+            if self.timer.is_none() {
+                match spawn_timer(context.client_request_sender.clone(), Arc::clone(&self.added)) {
+                    Ok(timer) => self.timer = Some(timer),
+                    Err(err) => log::error!(error:? = err; "Failed to start preview play timer"),
+                }
+            }
+            self.restore = Some(Playback {
+                state: context.status.state,
+                song_id: context.status.songid,
+                elapsed: context.status.elapsed,
+            });
+            status_info!("Preview play on, selecting a song plays a snippet of it");
+        }
+    }
+
+    /// Plays a snippet of the selected song unless it is already playing
+    pub fn on_selected(&mut self, song: &Song) {
+        if !self.is_active() || song.is_stream() || self.current_file.as_ref() == Some(&song.file) {
+            return;
+        }
+        self.current_file = Some(song.file.clone());
+
+        self.send(TimerMessage::Selected {
+            file: song.file.clone(),
+            snippet: Snippet::new(song.duration, self.snippet_start, self.snippet_length),
+        });
+    }
+
+    fn send(&self, message: TimerMessage) {
+        if let Some(timer) = &self.timer {
+            if let Err(err) = timer.send(message) {
+                log::error!(error:? = err; "Failed to send message to preview play timer");
+            }
+        }
+    }
+
+    fn stop(&mut self, playback: Playback, context: &AppContext) {
+        self.current_file = None;
+
+        let added = Arc::clone(&self.added);
+        context.command(move |client| {
+            remove_added(client, &added);
+            match playback {
+                Playback {
+                    state: state @ (State::Play | State::Pause),
+                    song_id: Some(id),
+                    elapsed,
+                } => {
+                    client.play_id(id)?;
+                    client.seek_current(ValueChange::Set(elapsed.as_secs().try_into()?))?;
+                    if state == State::Pause {
+                        client.pause()?;
+                    }
+                }
+                Playback { .. } => client.stop()?,
+            }
+            Ok(())
+        });
+    }
+}
+
+/// Runs the [`Schedule`] and sends the due steps to MPD. The thread exits once [`PreviewPlay`] is dropped.
+fn spawn_timer(
+    client_request_sender: Sender<ClientRequest>,
+    added: Arc<Mutex<Vec<Added>>>,
+) -> std::io::Result<Sender<TimerMessage>> {
+    let (tx, rx) = unbounded();
+    std::thread::Builder::new()
+        .name("preview_play".to_owned())
+        .spawn(move || {
+            let mut schedule = Schedule::default();
+            loop {
+                let message = match schedule.deadline() {
+                    Some(deadline) => rx.recv_deadline(deadline),
+                    None => rx.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                };
+                match message {
+                    Ok(TimerMessage::Selected { file, snippet }) => schedule.select(file, snippet, Instant::now()),
+                    Ok(TimerMessage::Cancel) => schedule.cancel(),
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                while let Some(step) = schedule.due(Instant::now()) {
+                    let added = Arc::clone(&added);
+                    let callback: Box<dyn FnOnce(&mut Client<'_>) -> Result<()> + Send> = match step {
+                        Step::Start { file, snippet } => {
+                            Box::new(move |client| start_snippet(client, &file, snippet, &added))
+                        }
+                        Step::Stop => Box::new(move |client| stop_snippet(client, &added)),
+                    };
+                    if let Err(err) = client_request_sender.send(ClientRequest::Command(MpdCommand { callback })) {
+                        log::error!(error:? = err; "Failed to send preview snippet command");
+                    }
+                }
+            }
+        })?;
+
+    Ok(tx)
+}
+
+fn start_snippet(client: &mut impl MpdClient, file: &str, snippet: Snippet, added: &Mutex<Vec<Added>>) -> Result<()> {
+    remove_added(client, added);
+    let position = client.get_status()?.playlistlength as usize;
+    let id = client.add_at(file, QueueMoveTarget::Absolute(position))?;
+    added.lock().unwrap_or_else(PoisonError::into_inner).push(Added {
+        id,
+        file: file.to_owned(),
+    });
+    client.play_id(id)?;
+    if !snippet.start.is_zero() {
+        client.seek_current(ValueChange::Set(snippet.start.as_secs().try_into()?))?;
+    }
+    Ok(())
+}
+
+/// Stops the snippet unless the user started playing something else in the meantime
+fn stop_snippet(client: &mut impl MpdClient, added: &Mutex<Vec<Added>>) -> Result<()> {
+    let snippet_id = added
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .last()
+        .map(|added| added.id);
+    if snippet_id.is_some() && client.get_status()?.songid == snippet_id {
+        client.stop()?;
+    }
+    Ok(())
+}
+
+/// Removes the snippet songs from the queue. They might have been removed by the user already so songs which are
+/// no longer in the queue are skipped and errors are only logged.
+fn remove_added(client: &mut impl MpdClient, added: &Mutex<Vec<Added>>) {
+    let added = std::mem::take(&mut *added.lock().unwrap_or_else(PoisonError::into_inner));
+    for Added { id, file } in added {
+        match client.playlist_id(id) {
+            Ok(Some(song)) if song.file == file => {
+                if let Err(err) = client.delete_id(id) {
+                    log::debug!(error:? = err, id; "Failed to remove preview snippet song from the queue");
+                }
+            }
+            Ok(_) => log::debug!(id; "Preview snippet song is no longer in the queue"),
+            Err(err) => log::debug!(error:? = err, id; "Failed to look up preview snippet song in the queue"),
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        sync::Mutex,
+        time::{Duration, Instant},
+    };
+
+    use crossbeam::channel::{unbounded, Receiver, Sender};
+    use rstest::rstest;
+    use test_case::test_case;
+
+    use super::{
+        remove_added, stop_snippet, Added, PreviewPlay, Schedule, Snippet, Step, TimerMessage, SELECTION_DEBOUNCE,
+        SNIPPET_LENGTH, SNIPPET_START,
+    };
+    use crate::{
+        mpd::commands::{Song, State},
+        shared::events::{ClientRequest, WorkRequest},
+        tests::fixtures::{
+            app_context, client_request_channel,
+            mpd_client::{client, TestMpdClient},
+            work_request_channel,
+        },
+    };
+
+    const fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    #[test_case(Some(secs(240)), secs(30), secs(15); "long song")]
+    #[test_case(Some(secs(40)), secs(25), secs(15); "snippet would overrun the end")]
+    #[test_case(Some(secs(10)), secs(0), secs(10); "shorter than the snippet")]
+    #[test_case(None, secs(0), secs(15); "unknown duration")]
+    fn snippet_timing(duration: Option<Duration>, start: Duration, length: Duration) {
+        assert_eq!(
+            Snippet::new(duration, SNIPPET_START, SNIPPET_LENGTH),
+            Snippet { start, length }
+        );
+    }
+
+    fn snippet() -> Snippet {
+        Snippet {
+            start: SNIPPET_START,
+            length: SNIPPET_LENGTH,
+        }
+    }
+
+    fn start(file: &str) -> Option<Step> {
+        Some(Step::Start {
+            file: file.to_owned(),
+            snippet: snippet(),
+        })
+    }
+
+    #[test]
+    fn selection_changes_are_debounced() {
+        let mut schedule = Schedule::default();
+        let now = Instant::now();
+
+        schedule.select("a.mp3".to_owned(), snippet(), now);
+        schedule.select("b.mp3".to_owned(), snippet(), now + Duration::from_millis(100));
+
+        assert_eq!(schedule.due(now + SELECTION_DEBOUNCE), None);
+        assert_eq!(
+            schedule.due(now + Duration::from_millis(100) + SELECTION_DEBOUNCE),
+            start("b.mp3")
+        );
+    }
+
+    #[test]
+    fn snippet_is_stopped_after_its_length() {
+        let mut schedule = Schedule::default();
+        let now = Instant::now();
+        schedule.select("a.mp3".to_owned(), snippet(), now);
+        let started = now + SELECTION_DEBOUNCE;
+        assert_eq!(schedule.due(started), start("a.mp3"));
+
+        assert_eq!(schedule.due(started + SNIPPET_LENGTH - Duration::from_millis(1)), None);
+        assert_eq!(schedule.due(started + SNIPPET_LENGTH), Some(Step::Stop));
+        assert_eq!(schedule.deadline(), None);
+    }
+
+    #[test]
+    fn selecting_another_song_cancels_pending_stop() {
+        let mut schedule = Schedule::default();
+        let now = Instant::now();
+        schedule.select("a.mp3".to_owned(), snippet(), now);
+        let started = now + SELECTION_DEBOUNCE;
+        assert_eq!(schedule.due(started), start("a.mp3"));
+
+        schedule.select("b.mp3".to_owned(), snippet(), started + secs(1));
+
+        let b_started = started + secs(1) + SELECTION_DEBOUNCE;
+        assert_eq!(schedule.due(b_started), start("b.mp3"));
+        assert_eq!(schedule.deadline(), Some(b_started + SNIPPET_LENGTH));
+    }
+
+    #[test]
+    fn cancel_drops_pending_step() {
+        let mut schedule = Schedule::default();
+        let now = Instant::now();
+        schedule.select("a.mp3".to_owned(), snippet(), now);
+
+        schedule.cancel();
+
+        assert_eq!(schedule.deadline(), None);
+        assert_eq!(schedule.due(now + SELECTION_DEBOUNCE), None);
+    }
+
+    fn song(file: &str) -> Song {
+        Song {
+            file: file.to_owned(),
+            duration: Some(secs(240)),
+            ..Default::default()
+        }
+    }
+
+    fn with_timer() -> (PreviewPlay, Receiver<TimerMessage>) {
+        let (tx, rx) = unbounded();
+        (
+            PreviewPlay {
+                timer: Some(tx),
+                ..Default::default()
+            },
+            rx,
+        )
+    }
+
+    #[rstest]
+    fn reselecting_the_same_song_does_not_restart_it(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let app_context = app_context(work_request_channel, client_request_channel);
+        let (mut preview, timer) = with_timer();
+        preview.toggle(&app_context);
+
+        preview.on_selected(&song("a.mp3"));
+        preview.on_selected(&song("a.mp3"));
+
+        assert!(matches!(timer.try_recv(), Ok(TimerMessage::Selected { file, .. }) if file == "a.mp3"));
+        assert!(timer.try_recv().is_err());
+    }
+
+    #[rstest]
+    fn turning_off_cancels_pending_snippet_and_restores_playback(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let rx = client_request_channel.1.clone();
+        let app_context = app_context(work_request_channel, client_request_channel);
+        let (mut preview, timer) = with_timer();
+        preview.toggle(&app_context);
+        preview.on_selected(&song("a.mp3"));
+
+        preview.toggle(&app_context);
+
+        assert!(!preview.is_active());
+        assert!(matches!(timer.try_recv(), Ok(TimerMessage::Selected { .. })));
+        assert_eq!(timer.try_recv(), Ok(TimerMessage::Cancel));
+        assert!(matches!(rx.try_recv(), Ok(ClientRequest::Command(_))));
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn does_nothing_when_turned_off() {
+        let (mut preview, timer) = with_timer();
+
+        preview.on_selected(&song("a.mp3"));
+
+        assert!(timer.try_recv().is_err());
+    }
+
+    fn added(client: &TestMpdClient, idx: usize) -> Added {
+        Added {
+            id: client.songs[idx].id,
+            file: client.songs[idx].file.clone(),
+        }
+    }
+
+    #[rstest]
+    fn snippet_is_stopped_while_it_plays(mut client: TestMpdClient) {
+        client.queue = vec![3];
+        client.status.state = State::Play;
+        client.status.songid = Some(client.songs[3].id);
+        let added = Mutex::new(vec![added(&client, 3)]);
+
+        stop_snippet(&mut client, &added).unwrap();
+
+        assert_eq!(client.call_order, ["stop"]);
+    }
+
+    #[rstest]
+    fn other_song_is_not_stopped(mut client: TestMpdClient) {
+        client.queue = vec![3, 5];
+        client.status.state = State::Play;
+        client.status.songid = Some(client.songs[5].id);
+        let added = Mutex::new(vec![added(&client, 3)]);
+
+        stop_snippet(&mut client, &added).unwrap();
+
+        assert!(client.call_order.is_empty());
+    }
+
+    #[rstest]
+    fn only_snippet_songs_still_in_queue_are_removed(mut client: TestMpdClient) {
+        client.queue = vec![3, 5];
+        let replaced = Added {
+            id: client.songs[5].id,
+            file: "other.mp3".to_owned(),
+        };
+        let added = Mutex::new(vec![added(&client, 3), replaced]);
+
+        remove_added(&mut client, &added);
+
+        assert_eq!(client.queue, [5]);
+        assert_eq!(client.call_order, ["delete_id"]);
+        assert!(added.lock().unwrap().is_empty());
+    }
+}
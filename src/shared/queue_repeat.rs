@@ -0,0 +1,97 @@
+/// MPD can only repeat the queue forever so playing it a limited number of times is tracked on the client. Each
+/// time playback wraps from the last song of the queue back to the first one counts as one loop and repeat is turned
+/// off once the last loop starts. Loops cannot be told apart in random playback so nothing is counted while it is on.
+#[derive(Debug, Default)]
+pub struct QueueRepeat {
+    remaining: Option<u32>,
+    last_position: Option<u32>,
+}
+
+impl QueueRepeat {
+    /// Plays the queue `times` times in total, counting the current pass
+    pub fn start(&mut self, times: u32, position: Option<u32>) {
+        self.remaining = times.checked_sub(1).filter(|remaining| *remaining > 0);
+        self.last_position = position;
+    }
+
+    /// Loops left after the current one
+    pub fn remaining(&self) -> Option<u32> {
+        self.remaining
+    }
+
+    /// Called whenever the current song changes. Returns whether repeat should be turned off because the last loop
+    /// has just started.
+    pub fn on_song_change(&mut self, position: Option<u32>, queue_len: u32, repeat: bool, random: bool) -> bool {
+        let last_position = std::mem::replace(&mut self.last_position, position);
+        let Some(remaining) = self.remaining else {
+            return false;
+        };
+        if !repeat {
+            // Repeat was turned off by something else, nothing left to count
+            self.remaining = None;
+            return false;
+        }
+
+        let wrapped = !random && position == Some(0) && last_position.is_some_and(|last| last + 1 == queue_len);
+        if !wrapped {
+            return false;
+        }
+
+        self.remaining = Some(remaining - 1).filter(|remaining| *remaining > 0);
+        self.remaining.is_none()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueueRepeat;
+
+    #[test]
+    fn wrap_around_decrements_remaining_loops() {
+        let mut repeat = QueueRepeat::default();
+        repeat.start(3, Some(1));
+        assert_eq!(repeat.remaining(), Some(2));
+
+        assert!(!repeat.on_song_change(Some(2), 3, true, false));
+        assert_eq!(repeat.remaining(), Some(2));
+
+        assert!(!repeat.on_song_change(Some(0), 3, true, false));
+        assert_eq!(repeat.remaining(), Some(1));
+
+        assert!(!repeat.on_song_change(Some(1), 3, true, false));
+        assert!(!repeat.on_song_change(Some(2), 3, true, false));
+        assert!(repeat.on_song_change(Some(0), 3, true, false));
+        assert_eq!(repeat.remaining(), None);
+    }
+
+    #[test]
+    fn jumping_to_first_song_is_not_a_wrap() {
+        let mut repeat = QueueRepeat::default();
+        repeat.start(2, Some(1));
+
+        assert!(!repeat.on_song_change(Some(0), 3, true, false));
+
+        assert_eq!(repeat.remaining(), Some(1));
+    }
+
+    #[test]
+    fn random_playback_is_not_counted() {
+        let mut repeat = QueueRepeat::default();
+        repeat.start(2, Some(1));
+
+        assert!(!repeat.on_song_change(Some(2), 3, true, true));
+        assert!(!repeat.on_song_change(Some(0), 3, true, true));
+
+        assert_eq!(repeat.remaining(), Some(1));
+    }
+
+    #[test]
+    fn disabling_repeat_cancels_counting() {
+        let mut repeat = QueueRepeat::default();
+        repeat.start(2, Some(2));
+
+        assert!(!repeat.on_song_change(Some(0), 3, false, false));
+
+        assert_eq!(repeat.remaining(), None);
+    }
+}
@@ -0,0 +1,64 @@
+use std::{
+    hash::{BuildHasher, RandomState},
+    time::SystemTime,
+};
+
+/// Small splitmix64 based generator. Not suitable for anything security related, but good enough for picking
+/// random songs without pulling in a dependency.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn with_seed(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// Seeds the generator from the randomly keyed std hasher and the current time
+    pub fn from_entropy() -> Self {
+        Self::with_seed(RandomState::new().hash_one(SystemTime::now()))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a random index lower than `len`, or `None` when `len` is zero
+    pub fn index(&mut self, len: usize) -> Option<usize> {
+        if len == 0 {
+            return None;
+        }
+        #[allow(clippy::cast_possible_truncation)]
+        Some((self.next_u64() % len as u64) as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn same_seed_gives_same_sequence() {
+        let mut a = Rng::with_seed(42);
+        let mut b = Rng::with_seed(42);
+
+        for _ in 0..10 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn index_is_within_bounds() {
+        let mut rng = Rng::with_seed(7);
+
+        assert_eq!(rng.index(0), None);
+        for _ in 0..100 {
+            assert!(rng.index(13).is_some_and(|i| i < 13));
+        }
+    }
+}
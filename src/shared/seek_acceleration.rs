@@ -0,0 +1,121 @@
+use std::time::Instant;
+
+use crate::config::seek::SeekAcceleration;
+
+/// Number of consecutive repeats after which the seek step grows by another factor
+const REPEATS_PER_STEP: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeekDirection {
+    Forward,
+    Back,
+}
+
+/// Tracks consecutive seek key presses so that holding the key down seeks in increasingly larger steps.
+/// Terminals do not report key releases so the sequence resets after a gap longer than the configured window
+/// instead.
+#[derive(Debug, Default)]
+pub struct SeekAccelerator {
+    last: Option<(SeekDirection, Instant)>,
+    repeats: u32,
+}
+
+impl SeekAccelerator {
+    /// Registers a seek key press at `now` and returns the number of seconds to seek by
+    pub fn next_step(
+        &mut self,
+        direction: SeekDirection,
+        now: Instant,
+        base_step: u32,
+        config: &SeekAcceleration,
+    ) -> u32 {
+        let is_repeat = self
+            .last
+            .is_some_and(|(last_direction, at)| last_direction == direction && now.duration_since(at) <= config.window);
+        self.repeats = if is_repeat { self.repeats.saturating_add(1) } else { 0 };
+        self.last = Some((direction, now));
+
+        let multiplier = config.factor.saturating_pow(self.repeats / REPEATS_PER_STEP);
+        base_step
+            .saturating_mul(multiplier)
+            .min(config.max_step_secs)
+            .max(base_step)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{SeekAccelerator, SeekDirection};
+    use crate::config::seek::SeekAcceleration;
+
+    fn config() -> SeekAcceleration {
+        SeekAcceleration {
+            window: Duration::from_millis(500),
+            factor: 2,
+            max_step_secs: 60,
+        }
+    }
+
+    #[test]
+    fn step_grows_geometrically_up_to_cap() {
+        let config = config();
+        let mut accelerator = SeekAccelerator::default();
+        let start = Instant::now();
+
+        let steps: Vec<u32> = (0..24)
+            .map(|i| {
+                accelerator.next_step(
+                    SeekDirection::Forward,
+                    start + Duration::from_millis(30 * i),
+                    5,
+                    &config,
+                )
+            })
+            .collect();
+
+        assert_eq!(
+            steps,
+            [5, 5, 5, 5, 10, 10, 10, 10, 20, 20, 20, 20, 40, 40, 40, 40, 60, 60, 60, 60, 60, 60, 60, 60]
+        );
+    }
+
+    #[test]
+    fn step_resets_after_gap() {
+        let config = config();
+        let mut accelerator = SeekAccelerator::default();
+        let start = Instant::now();
+        for i in 0..8 {
+            accelerator.next_step(
+                SeekDirection::Forward,
+                start + Duration::from_millis(30 * i),
+                5,
+                &config,
+            );
+        }
+
+        let result = accelerator.next_step(SeekDirection::Forward, start + Duration::from_secs(2), 5, &config);
+
+        assert_eq!(result, 5);
+    }
+
+    #[test]
+    fn step_resets_when_direction_changes() {
+        let config = config();
+        let mut accelerator = SeekAccelerator::default();
+        let start = Instant::now();
+        for i in 0..8 {
+            accelerator.next_step(
+                SeekDirection::Forward,
+                start + Duration::from_millis(30 * i),
+                5,
+                &config,
+            );
+        }
+
+        let result = accelerator.next_step(SeekDirection::Back, start + Duration::from_millis(270), 5, &config);
+
+        assert_eq!(result, 5);
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    hash::{BuildHasher, RandomState},
+    time::Duration,
+};
+
+use crate::mpd::commands::Song;
+
+/// Greedily picks songs in the given order whose durations sum up as close to `budget` as possible without
+/// exceeding it. Songs without a known duration are skipped. Returns the picked songs and their total duration.
+pub fn fill(songs: impl IntoIterator<Item = Song>, budget: Duration) -> (Vec<Song>, Duration) {
+    let mut total = Duration::ZERO;
+    let picked = songs
+        .into_iter()
+        .filter(|song| {
+            let Some(duration) = song.duration.filter(|d| !d.is_zero()) else {
+                return false;
+            };
+            if total + duration > budget {
+                return false;
+            }
+            total += duration;
+            true
+        })
+        .collect();
+
+    (picked, total)
+}
+
+/// Shuffles the songs into a random order which differs between runs
+pub fn shuffle(songs: &mut [Song]) {
+    let state = RandomState::new();
+    songs.sort_by_cached_key(|song| state.hash_one(&song.file));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use itertools::Itertools;
+
+    use super::fill;
+    use crate::mpd::commands::Song;
+
+    fn song(file: &str, secs: Option<u64>) -> Song {
+        Song {
+            file: file.to_owned(),
+            duration: secs.map(Duration::from_secs),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn stays_under_budget() {
+        let songs = (1..=20).map(|i| song(&i.to_string(), Some(i * 37))).collect_vec();
+        let budget = Duration::from_secs(60 * 10);
+
+        let (picked, total) = fill(songs, budget);
+
+        assert!(total <= budget);
+        assert_eq!(total, picked.iter().filter_map(|s| s.duration).sum::<Duration>());
+    }
+
+    #[test]
+    fn skips_songs_which_do_not_fit_and_continues() {
+        let songs = vec![song("a", Some(200)), song("b", Some(300)), song("c", Some(100))];
+
+        let (picked, total) = fill(songs, Duration::from_secs(300));
+
+        assert_eq!(picked.iter().map(|s| s.file.as_str()).collect_vec(), ["a", "c"]);
+        assert_eq!(total, Duration::from_secs(300));
+    }
+
+    #[test]
+    fn skips_songs_without_duration() {
+        let songs = vec![song("a", None), song("b", Some(0)), song("c", Some(100))];
+
+        let (picked, total) = fill(songs, Duration::from_secs(300));
+
+        assert_eq!(picked.iter().map(|s| s.file.as_str()).collect_vec(), ["c"]);
+        assert_eq!(total, Duration::from_secs(100));
+    }
+
+    #[test]
+    fn nothing_fits() {
+        let songs = vec![song("a", Some(400))];
+
+        let (picked, total) = fill(songs, Duration::from_secs(300));
+
+        assert!(picked.is_empty());
+        assert_eq!(total, Duration::ZERO);
+    }
+}
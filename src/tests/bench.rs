@@ -0,0 +1,76 @@
+//! Headless render benchmark. Drives the whole UI against a [`TestBackend`] with a large synthetic state and
+//! reports frame times. Only compiled with the `bench` feature.
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crossbeam::channel::unbounded;
+use ratatui::{backend::TestBackend, Terminal};
+
+use crate::{
+    mpd::commands::{Song, State},
+    tests::fixtures::app_context,
+    ui::Ui,
+};
+
+const QUEUE_LEN: u32 = 10_000;
+const WARMUP_FRAMES: usize = 5;
+const MEASURED_FRAMES: usize = 50;
+
+fn synthetic_queue(len: u32) -> Vec<Song> {
+    (0..len)
+        .map(|id| Song {
+            id,
+            file: format!("artist {}/album {}/{id:05} song.flac", id / 1000, id / 10),
+            duration: Some(Duration::from_secs(180 + u64::from(id % 120))),
+            metadata: HashMap::from([
+                ("title".to_owned(), format!("Song number {id}")),
+                ("artist".to_owned(), format!("Artist {}", id / 1000)),
+                ("album".to_owned(), format!("Album {}", id / 10)),
+            ]),
+        })
+        .collect()
+}
+
+#[test]
+#[allow(clippy::unwrap_used, clippy::cast_precision_loss)]
+fn bench_render_large_queue() {
+    let mut context = app_context(unbounded(), unbounded());
+    context.queue = synthetic_queue(QUEUE_LEN);
+    context.status.state = State::Play;
+    context.status.songid = Some(QUEUE_LEN / 2);
+    context.status.duration = Duration::from_secs(240);
+    context.status.elapsed = Duration::from_secs(60);
+
+    let mut ui = Ui::new(&context).unwrap();
+    let mut terminal = Terminal::new(TestBackend::new(200, 60)).unwrap();
+
+    let mut render = |context: &mut _| {
+        let mut result = Ok(());
+        terminal.draw(|frame| result = ui.render(frame, context)).unwrap();
+        result.unwrap();
+    };
+
+    for _ in 0..WARMUP_FRAMES {
+        render(&mut context);
+    }
+
+    let mut frame_times = Vec::with_capacity(MEASURED_FRAMES);
+    for _ in 0..MEASURED_FRAMES {
+        let start = Instant::now();
+        render(&mut context);
+        frame_times.push(start.elapsed());
+    }
+    frame_times.sort();
+
+    let total: Duration = frame_times.iter().sum();
+    let mean = total / MEASURED_FRAMES as u32;
+    let median = frame_times[MEASURED_FRAMES / 2];
+    let p95 = frame_times[MEASURED_FRAMES * 95 / 100];
+    let max = frame_times[MEASURED_FRAMES - 1];
+    println!(
+        "rendered {MEASURED_FRAMES} frames with {QUEUE_LEN} songs in queue: mean {mean:?}, median {median:?}, p95 {p95:?}, max {max:?}, {:.1} fps",
+        MEASURED_FRAMES as f64 / total.as_secs_f64()
+    );
+}
@@ -10,6 +10,7 @@ use crate::{
     mpd::commands::Status,
     shared::{
         events::{ClientRequest, WorkRequest},
+        favorites::Favorites,
         lrc::LrcIndex,
     },
 };
@@ -46,13 +47,16 @@ pub fn app_context(
     AppContext {
         status: Status::default(),
         config,
+        draw_borders: config.theme.draw_borders,
         queue: Vec::default(),
         app_event_sender: chan1.0,
         work_sender: work_request_channel.0.clone(),
         client_request_sender: client_request_channel.0.clone(),
         supported_commands: HashSet::new(),
+        supported_tags: HashSet::new(),
         needs_render: Cell::new(false),
         lrc_index: LrcIndex::default(),
+        favorites: Favorites::default(),
     }
 }
 
@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     io::{BufRead, BufReader, Cursor},
-    ops::AddAssign,
+    ops::{AddAssign, Range},
     time::Duration,
 };
 
@@ -10,8 +10,8 @@ use rstest::fixture;
 
 use crate::mpd::{
     commands::{
-        list::MpdList, list_playlist::FileList, status::OnOffOneshot, volume::Bound, IdleEvent, ListFiles, LsInfo,
-        Playlist, Song, Status, Update, Volume,
+        list::MpdList, list_playlist::FileList, lsinfo::LsInfoEntry, status::OnOffOneshot, volume::Bound, Count,
+        IdleEvent, ListFiles, LsInfo, Playlist, ReplayGainMode, Song, Status, Stickers, Update, Volume,
     },
     errors::MpdError,
     mpd_client::{Filter, MpdClient, QueueMoveTarget, SaveMode, SingleOrRange, Tag, ValueChange},
@@ -71,6 +71,8 @@ pub fn client() -> TestMpdClient {
         volume: Volume::new(100),
         status: Status::default(),
         calls: HashMap::default(),
+        call_order: Vec::new(),
+        stickers: HashMap::default(),
         rx: BufReader::new(Box::new(Cursor::new(String::new()))),
     }
 }
@@ -88,10 +90,28 @@ pub struct TestMpdClient {
     pub volume: Volume,
     pub status: Status,
     pub calls: HashMap<String, u32>,
+    /// Commands which change playback or the queue, in the order they were called
+    pub call_order: Vec<&'static str>,
+    /// Stickers by song uri
+    pub stickers: HashMap<String, HashMap<String, String>>,
     pub rx: BufReader<Box<dyn BufRead>>,
 }
 
 impl TestMpdClient {
+    fn add_found_to_queue(&mut self, found: &[Song]) {
+        let indices = found
+            .iter()
+            .filter_map(|song| self.songs.iter().position(|s| s.file == song.file))
+            .collect_vec();
+        self.queue.extend(indices);
+        self.sync_playlistlength();
+    }
+
+    /// Keeps the reported queue length in sync after the queue was changed through the client
+    fn sync_playlistlength(&mut self) {
+        self.status.playlistlength = self.queue.len().try_into().expect("queue length to fit into u32");
+    }
+
     pub fn set_read_content(&mut self, content: Box<dyn BufRead>) {
         self.rx = BufReader::new(Box::new(content));
     }
@@ -120,6 +140,18 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn tag_types(&mut self) -> MpdResult<Vec<String>> {
+        todo!("Not yet implemented")
+    }
+
+    fn disable_tag_types(&mut self, _tags: &[&str]) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn enable_all_tag_types(&mut self) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn update(&mut self, _path: Option<&str>) -> MpdResult<Update> {
         todo!("Not yet implemented")
     }
@@ -192,6 +224,7 @@ impl MpdClient for TestMpdClient {
     }
 
     fn next(&mut self) -> MpdResult<()> {
+        self.calls.entry("next".to_string()).or_default().add_assign(1);
         self.current_song_idx = self.current_song_idx.map(|idx| (idx + 1) % self.queue.len());
         Ok(())
     }
@@ -205,8 +238,10 @@ impl MpdClient for TestMpdClient {
     }
 
     fn play_pos(&mut self, pos: usize) -> MpdResult<()> {
+        self.calls.entry("play_pos".to_string()).or_default().add_assign(1);
         if (pos) < self.queue.len() {
             self.current_song_idx = Some(pos);
+            self.status.song = u32::try_from(pos).ok();
             self.status.state = crate::mpd::commands::State::Play;
             Ok(())
         } else {
@@ -215,6 +250,7 @@ impl MpdClient for TestMpdClient {
     }
 
     fn play(&mut self) -> MpdResult<()> {
+        self.call_order.push("play");
         self.status.state = crate::mpd::commands::State::Play;
         Ok(())
     }
@@ -230,6 +266,7 @@ impl MpdClient for TestMpdClient {
     }
 
     fn stop(&mut self) -> MpdResult<()> {
+        self.call_order.push("stop");
         self.status.state = crate::mpd::commands::State::Stop;
         Ok(())
     }
@@ -258,6 +295,30 @@ impl MpdClient for TestMpdClient {
         Ok(())
     }
 
+    fn crossfade(&mut self, seconds: u32) -> MpdResult<()> {
+        self.status.xfade = Some(seconds);
+        Ok(())
+    }
+
+    fn mixramp_db(&mut self, db: f32) -> MpdResult<()> {
+        self.status.mixrampdb = Some(db);
+        Ok(())
+    }
+
+    fn mixramp_delay(&mut self, seconds: Option<f32>) -> MpdResult<()> {
+        self.status.mixrampdelay = seconds;
+        Ok(())
+    }
+
+    fn replay_gain_mode(&mut self, mode: ReplayGainMode) -> MpdResult<()> {
+        self.status.replay_gain_mode = Some(mode);
+        Ok(())
+    }
+
+    fn replay_gain_status(&mut self) -> MpdResult<ReplayGainMode> {
+        Ok(self.status.replay_gain_mode.unwrap_or_default())
+    }
+
     fn mount(&mut self, _name: &str, _path: &str) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
@@ -270,19 +331,49 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
-    fn add(&mut self, _path: &str) -> MpdResult<()> {
+    fn add(&mut self, path: &str) -> MpdResult<()> {
+        match self.songs.iter().position(|s| s.file == path) {
+            Some(idx) => {
+                self.queue.push(idx);
+                self.sync_playlistlength();
+                Ok(())
+            }
+            None => Err(MpdError::Generic("No such song".to_string())),
+        }
+    }
+
+    fn add_at(&mut self, _path: &str, _position: QueueMoveTarget) -> MpdResult<u32> {
         todo!("Not yet implemented")
     }
 
+    fn add_multiple(&mut self, paths: &[&str]) -> MpdResult<()> {
+        self.calls.entry("add_multiple".to_string()).or_default().add_assign(1);
+        for path in paths {
+            self.add(path)?;
+        }
+        Ok(())
+    }
+
     fn clear(&mut self) -> MpdResult<()> {
-        self.songs.clear();
+        self.calls.entry("clear".to_string()).or_default().add_assign(1);
+        self.call_order.push("clear");
+        self.queue.clear();
+        self.sync_playlistlength();
         self.current_song_idx = None;
         self.status.state = crate::mpd::commands::State::Stop;
         Ok(())
     }
 
-    fn delete_id(&mut self, _id: u32) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn delete_id(&mut self, id: u32) -> MpdResult<()> {
+        self.call_order.push("delete_id");
+        match self.queue.iter().position(|idx| self.songs[*idx].id == id) {
+            Some(pos) => {
+                self.queue.remove(pos);
+                self.sync_playlistlength();
+                Ok(())
+            }
+            None => Err(MpdError::Generic(format!("Song with id {id} is not in the queue"))),
+        }
     }
 
     fn delete_from_queue(&mut self, _songs: SingleOrRange) -> MpdResult<()> {
@@ -295,6 +386,15 @@ impl MpdClient for TestMpdClient {
         ))
     }
 
+    fn playlist_id(&mut self, id: u32) -> MpdResult<Option<Song>> {
+        Ok(self
+            .queue
+            .iter()
+            .map(|idx| &self.songs[*idx])
+            .find(|song| song.id == id)
+            .cloned())
+    }
+
     /// `FilterKind` not implemented, everything is treated as Contains
     fn find(&mut self, filter: &[Filter<'_>]) -> MpdResult<Vec<Song>> {
         Ok(self
@@ -382,32 +482,131 @@ impl MpdClient for TestMpdClient {
             .collect())
     }
 
-    fn move_in_queue(&mut self, _from: SingleOrRange, _to: QueueMoveTarget) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn move_in_queue(&mut self, from: SingleOrRange, to: QueueMoveTarget) -> MpdResult<()> {
+        let end = from.end.unwrap_or(from.start + 1);
+        let moved = self.queue.drain(from.start..end).collect_vec();
+        let target = match to {
+            QueueMoveTarget::Absolute(pos) => pos,
+            QueueMoveTarget::RelativeAdd(offset) => {
+                self.status
+                    .song
+                    .ok_or(MpdError::Generic("No current song".to_string()))? as usize
+                    + 1
+                    + offset
+            }
+            QueueMoveTarget::RelativeSub(offset) => (self
+                .status
+                .song
+                .ok_or(MpdError::Generic("No current song".to_string()))?
+                as usize)
+                .saturating_sub(offset),
+        };
+        self.queue.splice(target..target, moved);
+        Ok(())
     }
 
     fn move_id(&mut self, _id: u32, _to: QueueMoveTarget) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
+    fn swap(&mut self, pos1: usize, pos2: usize) -> MpdResult<()> {
+        self.queue.swap(pos1, pos2);
+        Ok(())
+    }
+
+    fn swap_id(&mut self, _id1: u32, _id2: u32) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn shuffle(&mut self, _range: Option<SingleOrRange>) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn set_priority(&mut self, _songs: SingleOrRange, _prio: u8) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn set_priority_id(&mut self, _id: u32, _prio: u8) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn set_song_range(&mut self, _id: u32, _range: Option<Range<f32>>) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn playlist_find(&mut self, _filter: &[Filter<'_>]) -> MpdResult<Vec<Song>> {
+        todo!("Not yet implemented")
+    }
+
+    fn playlist_search(&mut self, _filter: &[Filter<'_>]) -> MpdResult<Vec<Song>> {
+        todo!("Not yet implemented")
+    }
+
     fn find_one(&mut self, filter: &[Filter<'_>]) -> MpdResult<Option<Song>> {
         let mut res = self.find(filter)?;
         if res.len() > 1 {
             Err(MpdError::Generic("More than one song found".to_string()))
         } else {
-            Ok(Some(res.remove(0)))
+            Ok(res.pop())
         }
     }
 
-    fn find_add(&mut self, _filter: &[Filter<'_>]) -> MpdResult<()> {
+    fn find_files(&mut self, uris: &[&str]) -> MpdResult<Vec<Song>> {
+        Ok(self
+            .songs
+            .iter()
+            .filter(|song| uris.contains(&song.file.as_str()))
+            .cloned()
+            .collect())
+    }
+
+    fn modified_since(&mut self, _since: &str) -> MpdResult<Vec<Song>> {
         todo!("Not yet implemented")
     }
 
-    fn search_add(&mut self, _filter: &[Filter<'_>]) -> MpdResult<()> {
+    fn find_add(&mut self, filter: &[Filter<'_>], _position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        self.calls.entry("find_add".to_string()).or_default().add_assign(1);
+        let found = self.find(filter)?;
+        self.add_found_to_queue(&found);
+        Ok(())
+    }
+
+    fn search_add(&mut self, filter: &[Filter<'_>], _position: Option<QueueMoveTarget>) -> MpdResult<()> {
+        self.calls.entry("search_add".to_string()).or_default().add_assign(1);
+        let found = self.search(filter)?;
+        self.add_found_to_queue(&found);
+        Ok(())
+    }
+
+    fn count(&mut self, filter: &[Filter<'_>]) -> MpdResult<Count> {
+        Ok(Count {
+            songs: self.find(filter)?.len(),
+        })
+    }
+
+    fn count_in_dir(&mut self, path: &str) -> MpdResult<Count> {
+        let path = path.trim_matches('/');
+        Ok(Count {
+            songs: self
+                .songs
+                .iter()
+                .filter(|s| path.is_empty() || s.file.starts_with(&format!("{path}/")))
+                .count(),
+        })
+    }
+
+    fn list_tag(&mut self, tag: Tag, _filter: Option<&[Filter<'_>]>) -> MpdResult<MpdList> {
+        match tag {
+            Tag::File => Ok(self.songs.iter().map(|s| s.file.clone()).collect_vec().into()),
+            _ => todo!("Not yet implemented"),
+        }
+    }
+
+    fn add_tag_id(&mut self, _id: u32, _tag: Tag, _value: &str) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
-    fn list_tag(&mut self, _tag: Tag, _filter: Option<&[Filter<'_>]>) -> MpdResult<MpdList> {
+    fn clear_tag_id(&mut self, _id: u32, _tag: Tag) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
@@ -415,15 +614,19 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn list_all_info(&mut self, _path: Option<&str>) -> MpdResult<LsInfo> {
+        Ok(LsInfo(self.songs.iter().cloned().map(LsInfoEntry::File).collect()))
+    }
+
     fn list_files(&mut self, _path: Option<&str>) -> MpdResult<ListFiles> {
         todo!("Not yet implemented")
     }
 
-    fn read_picture(&mut self, _path: &str) -> MpdResult<Option<Vec<u8>>> {
+    fn read_picture(&mut self, _path: &str, _max_size: usize) -> MpdResult<Option<Vec<u8>>> {
         todo!("Not yet implemented")
     }
 
-    fn albumart(&mut self, _path: &str) -> MpdResult<Option<Vec<u8>>> {
+    fn albumart(&mut self, _path: &str, _max_size: usize) -> MpdResult<Option<Vec<u8>>> {
         todo!("Not yet implemented")
     }
 
@@ -478,8 +681,13 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
-    fn delete_playlist(&mut self, _name: &str) -> MpdResult<()> {
-        todo!("Not yet implemented")
+    fn delete_playlist(&mut self, name: &str) -> MpdResult<()> {
+        self.calls
+            .entry("delete_playlist".to_string())
+            .or_default()
+            .add_assign(1);
+        self.playlists.retain(|p| p.name != name);
+        Ok(())
     }
 
     fn delete_from_playlist(&mut self, _playlist_name: &str, _songs: &SingleOrRange) -> MpdResult<()> {
@@ -499,11 +707,30 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn add_multiple_to_playlist(&mut self, playlist_name: &str, paths: &[&str]) -> MpdResult<()> {
+        self.calls
+            .entry("add_multiple_to_playlist".to_string())
+            .or_default()
+            .add_assign(1);
+        let indices = paths
+            .iter()
+            .filter_map(|path| self.songs.iter().position(|s| s.file == *path))
+            .collect_vec();
+        match self.playlists.iter_mut().find(|p| p.name == playlist_name) {
+            Some(playlist) => playlist.songs_indices.extend(indices),
+            None => self.playlists.push(TestPlaylist {
+                name: playlist_name.to_owned(),
+                songs_indices: indices,
+            }),
+        }
+        Ok(())
+    }
+
     fn save_queue_as_playlist(&mut self, _name: &str, _mode: Option<SaveMode>) -> MpdResult<()> {
         todo!("Not yet implemented")
     }
 
-    fn find_album_art(&mut self, _path: &str) -> MpdResult<Option<Vec<u8>>> {
+    fn find_album_art(&mut self, _path: &str, _max_size: usize) -> MpdResult<Option<Vec<u8>>> {
         self.calls
             .entry("find_album_art".to_string())
             .or_default()
@@ -527,9 +754,62 @@ impl MpdClient for TestMpdClient {
         todo!("Not yet implemented")
     }
 
+    fn set_output_attribute(&mut self, _id: u32, _name: &str, _value: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn list_partitions(&mut self) -> MpdResult<crate::mpd::commands::Partitions> {
+        todo!("Not yet implemented")
+    }
+
+    fn new_partition(&mut self, _name: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn delete_partition(&mut self, _name: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
+    fn switch_partition(&mut self, _name: &str) -> MpdResult<()> {
+        todo!("Not yet implemented")
+    }
+
     fn decoders(&mut self) -> MpdResult<crate::mpd::commands::decoders::Decoders> {
         todo!("Not yet implemented")
     }
+
+    fn sticker_set(&mut self, uri: &str, name: &str, value: &str) -> MpdResult<()> {
+        self.stickers
+            .entry(uri.to_owned())
+            .or_default()
+            .insert(name.to_owned(), value.to_owned());
+        Ok(())
+    }
+
+    fn sticker_get(&mut self, uri: &str, name: &str) -> MpdResult<Option<String>> {
+        Ok(self.stickers.get(uri).and_then(|stickers| stickers.get(name)).cloned())
+    }
+
+    fn sticker_delete(&mut self, uri: &str, name: &str) -> MpdResult<()> {
+        if let Some(stickers) = self.stickers.get_mut(uri) {
+            stickers.remove(name);
+        }
+        Ok(())
+    }
+
+    fn sticker_list(&mut self, uri: &str) -> MpdResult<Stickers> {
+        Ok(Stickers(self.stickers.get(uri).cloned().unwrap_or_default()))
+    }
+
+    fn sticker_find(&mut self, name: &str) -> MpdResult<Vec<String>> {
+        Ok(self
+            .stickers
+            .iter()
+            .filter(|(_, stickers)| stickers.contains_key(name))
+            .map(|(uri, _)| uri.clone())
+            .sorted()
+            .collect())
+    }
 }
 
 impl SocketClient for TestMpdClient {
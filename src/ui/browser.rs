@@ -1,25 +1,101 @@
-use anyhow::Result;
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
 use crossterm::event::KeyCode;
 use itertools::Itertools;
-use ratatui::prelude::Rect;
+use ratatui::{layout::Position, prelude::Rect};
 
 use crate::{
     config::keys::{CommonAction, GlobalAction},
     context::AppContext,
-    core::event_loop::EXTERNAL_COMMAND,
-    mpd::{client::Client, commands::Song},
+    core::{
+        command::{change_rating, run_tag_editor},
+        event_loop::EXTERNAL_COMMAND,
+    },
+    mpd::{
+        client::Client,
+        commands::Song,
+        mpd_client::{Filter, MpdClient, QueueMoveTarget, SingleOrRange, Tag},
+    },
     shared::{
+        add_feedback::report_added,
+        clipboard,
+        events::AppEvent,
+        ext::mpd_client::MpdClientExt,
         key_event::KeyEvent,
+        macros::{modal, status_info, status_warn},
         mouse_event::{MouseEvent, MouseEventKind},
     },
-    MpdQueryResult,
+    MpdCommand, MpdQueryResult,
 };
 
 use super::{
     dirstack::{DirStack, DirStackItem},
+    key_hints::{self, KeyHint},
+    modals::context_menu::{ContextMenuEntry, ContextMenuModal},
     panes::Pane,
+    UiAppEvent, ADD_TO_PLAYLIST, CONFIRM_QUEUE_SIZE,
 };
 
+/// Entries of the context menu opened by right clicking an item. Songs additionally offer adding to a playlist
+/// and jumping to their album.
+pub(in crate::ui) fn context_menu_entries(song: Option<&Song>) -> Vec<ContextMenuEntry> {
+    let mut entries = vec![
+        ContextMenuEntry::Add,
+        ContextMenuEntry::AddNext,
+        ContextMenuEntry::ReplaceAndPlay,
+    ];
+    if let Some(song) = song {
+        entries.push(ContextMenuEntry::AddToPlaylist);
+        if song.album().is_some() {
+            entries.push(ContextMenuEntry::GoToAlbum);
+        }
+    }
+    entries
+}
+
+/// Runs `add` unless the songs it adds, as counted by `count`, would grow the queue past the configured
+/// `queue_size_warning`. The user is asked for confirmation first in that case.
+pub(in crate::ui) fn add_within_limit(
+    context: &AppContext,
+    count: impl FnOnce(&mut Client<'_>) -> Result<usize> + Send + 'static,
+    add: impl FnOnce(&mut Client<'_>) -> Result<()> + Send + 'static,
+) {
+    let Some(limit) = context.config.queue_size_warning else {
+        context.command(add);
+        return;
+    };
+
+    context.query().id(CONFIRM_QUEUE_SIZE).query(move |client| {
+        let count = count(client)?;
+        if client.fits_in_queue(count, limit)? {
+            add(client)?;
+            return Ok(MpdQueryResult::Any(Box::new(())));
+        }
+        Ok(MpdQueryResult::ConfirmQueueSize(Mutex::new(Some(MpdCommand {
+            callback: Box::new(add),
+        }))))
+    });
+}
+
+/// Adds the files to the queue as one batch, see [`add_within_limit`]
+pub(in crate::ui) fn add_files(files: Vec<String>, what: &'static str, context: &AppContext) {
+    let feedback = context.config.add_feedback;
+    let autoplay = context.config.autoplay_on_add;
+    let count = files.len();
+    add_within_limit(
+        context,
+        move |_| Ok(count),
+        move |client| {
+            client.add_and_autoplay(autoplay, |client| {
+                client.add_multiple(&files.iter().map(String::as_str).collect_vec())
+            })?;
+            report_added(feedback, what, files.len());
+            Ok(())
+        },
+    );
+}
+
 pub enum MoveDirection {
     Up,
     Down,
@@ -37,6 +113,11 @@ where
     fn is_filter_input_mode_active(&self) -> bool;
     fn next(&mut self, context: &AppContext) -> Result<()>;
     fn list_songs_in_item(&self, item: T) -> impl FnOnce(&mut Client<'_>) -> Result<Vec<Song>> + Send + 'static;
+    /// Filter matching exactly the songs in the item. When provided, the songs can be added to the queue by MPD
+    /// in a single command instead of listing them first.
+    fn filter_for_item(&self, _item: &T) -> Option<Vec<(Tag, String)>> {
+        None
+    }
     fn prepare_preview(&mut self, context: &AppContext) -> Result<()>;
     fn add(&self, item: &T, context: &AppContext) -> Result<()>;
     fn add_all(&self, context: &AppContext) -> Result<()>;
@@ -50,6 +131,33 @@ where
     fn move_selected(&mut self, direction: MoveDirection, context: &AppContext) -> Result<()> {
         Ok(())
     }
+    fn go_to_parent(&mut self, context: &AppContext) -> Result<()> {
+        self.stack_mut().pop();
+        self.stack_mut().clear_preview();
+        self.prepare_preview(context);
+        context.render()?;
+        Ok(())
+    }
+    fn go_to_root(&mut self, context: &AppContext) -> Result<()> {
+        if self.stack_mut().pop_to_root() {
+            self.stack_mut().clear_preview();
+            self.prepare_preview(context);
+            context.render()?;
+        }
+        Ok(())
+    }
+    fn browser_key_hints(&self, context: &AppContext) -> Option<Vec<KeyHint>> {
+        if self.is_filter_input_mode_active() {
+            Some(key_hints::filter_mode(context))
+        } else if !self.stack().current().marked().is_empty() {
+            Some(key_hints::selection_mode(context))
+        } else {
+            None
+        }
+    }
+    fn browser_selected_song(&self) -> Option<&Song> {
+        self.stack().current().selected().and_then(T::as_song)
+    }
     fn handle_filter_input(&mut self, event: &mut KeyEvent, context: &AppContext) -> Result<()> {
         if !self.is_filter_input_mode_active() {
             return Ok(());
@@ -59,7 +167,9 @@ where
         match event.as_common_action(context) {
             Some(CommonAction::Close) => {
                 self.set_filter_input_mode_active(false);
-                self.stack_mut().current_mut().set_filter(None, config);
+                self.stack_mut()
+                    .current_mut()
+                    .set_filter(None, config, &context.favorites);
                 self.prepare_preview(context);
             }
             Some(CommonAction::Confirm) => {
@@ -70,12 +180,16 @@ where
                 event.stop_propagation();
                 match event.code() {
                     KeyCode::Char(c) => {
-                        self.stack_mut().current_mut().push_filter(c, config);
-                        self.stack_mut().current_mut().jump_first_matching(config);
+                        self.stack_mut()
+                            .current_mut()
+                            .push_filter(c, config, &context.favorites);
+                        self.stack_mut()
+                            .current_mut()
+                            .jump_first_matching(config, &context.favorites);
                         self.prepare_preview(context);
                     }
                     KeyCode::Backspace => {
-                        self.stack_mut().current_mut().pop_filter(config);
+                        self.stack_mut().current_mut().pop_filter(config, &context.favorites);
                         context.render()?;
                     }
                     _ => {}
@@ -134,6 +248,16 @@ where
 
         let position = event.into();
         match event.kind {
+            MouseEventKind::Move => {
+                let hovered = if current_area.contains(position) {
+                    self.stack().current().state.get_at_y(current_area, event.y)
+                } else {
+                    None
+                };
+                if self.stack_mut().current_mut().state.set_hovered(hovered) {
+                    context.render()?;
+                }
+            }
             MouseEventKind::LeftClick | MouseEventKind::DoubleClick if prev_area.contains(position) => {
                 let clicked_row: usize = event.y.saturating_sub(prev_area.y).into();
                 let prev_stack = self.stack_mut().previous_mut();
@@ -176,9 +300,7 @@ where
                 }
             }
             MouseEventKind::LeftClick | MouseEventKind::DoubleClick if preview_area.contains(position) => {
-                let clicked_row: usize = event.y.saturating_sub(preview_area.y).into();
-                // Offset does not need to be accounted for since it is always scrolled all the way
-                // to the top when going deeper
+                let clicked_row = usize::from(event.y.saturating_sub(preview_area.y)) + self.stack().preview_offset();
                 let idx_to_select = self.stack().preview().and_then(|preview| {
                     if clicked_row < preview.len() {
                         Some(clicked_row)
@@ -194,20 +316,146 @@ where
 
                 self.prepare_preview(context);
             }
+            MouseEventKind::RightClick if current_area.contains(position) => {
+                let clicked_row: usize = event.y.saturating_sub(current_area.y).into();
+
+                if let Some(idx_to_select) = self.stack().current().state.get_at_rendered_row(clicked_row) {
+                    self.stack_mut()
+                        .current_mut()
+                        .select_idx(idx_to_select, context.config.scrolloff);
+                    self.prepare_preview(context);
+                    if let Some(item) = self.stack().current().selected() {
+                        self.open_context_menu(item.clone(), position, context)?;
+                    }
+                }
+            }
             MouseEventKind::ScrollUp if current_area.contains(position) => {
-                self.stack_mut().current_mut().prev(context.config.scrolloff, false);
+                self.stack_mut()
+                    .current_mut()
+                    .scroll_up(context.config.scroll_amount, context.config.scrolloff);
                 self.prepare_preview(context);
             }
             MouseEventKind::ScrollDown if current_area.contains(position) => {
-                self.stack_mut().current_mut().next(context.config.scrolloff, false);
+                self.stack_mut()
+                    .current_mut()
+                    .scroll_down(context.config.scroll_amount, context.config.scrolloff);
                 self.prepare_preview(context);
             }
+            MouseEventKind::ScrollUp if preview_area.contains(position) => {
+                self.stack_mut().scroll_preview_up(context.config.scroll_amount);
+                context.render()?;
+            }
+            MouseEventKind::ScrollDown if preview_area.contains(position) => {
+                self.stack_mut().scroll_preview_down(context.config.scroll_amount);
+                context.render()?;
+            }
             _ => {}
         };
 
         Ok(())
     }
 
+    fn open_context_menu(&self, item: T, position: Position, context: &AppContext) -> Result<()> {
+        let entries = context_menu_entries(item.as_song());
+        let album = item.as_song().and_then(Song::album).cloned();
+        let filter = self.filter_for_item(&item);
+        // The modal has to be Sync so the song listing closure is wrapped in a mutex
+        let mut list_songs = Mutex::new(Some(self.list_songs_in_item(item)));
+
+        modal!(
+            context,
+            ContextMenuModal::new(position)
+                .entries(entries)
+                .on_confirm(move |context, entry| {
+                    let Some(list_songs) = list_songs.get_mut().ok().and_then(Option::take) else {
+                        return Ok(());
+                    };
+
+                    let feedback = context.config.add_feedback;
+                    let autoplay = context.config.autoplay_on_add;
+                    match entry {
+                        ContextMenuEntry::Add => context.command(move |client| {
+                            let songs = (list_songs)(client)?;
+                            client.add_and_autoplay(autoplay, |client| {
+                                songs.iter().try_for_each(|song| client.add(&song.file))
+                            })?;
+                            report_added(feedback, "Selected item", songs.len());
+                            Ok(())
+                        }),
+                        ContextMenuEntry::AddNext if filter.is_some() => {
+                            let filter = filter.clone().unwrap_or_default();
+                            context.command(move |client| {
+                                let position = client.get_status()?.song.map(|_| QueueMoveTarget::RelativeAdd(0));
+                                client.find_add(
+                                    &filter.iter().map(|(tag, value)| Filter::new(*tag, value)).collect_vec(),
+                                    position,
+                                )?;
+                                status_info!("Songs added after the current song");
+                                Ok(())
+                            });
+                        }
+                        ContextMenuEntry::AddNext => context.command(move |client| {
+                            let songs = (list_songs)(client)?;
+                            let status = client.get_status()?;
+                            let queue_len = status.playlistlength as usize;
+                            for song in &songs {
+                                client.add(&song.file)?;
+                            }
+                            if status.song.is_some() && !songs.is_empty() {
+                                client.move_in_queue(
+                                    SingleOrRange::range(queue_len, queue_len + songs.len()),
+                                    QueueMoveTarget::RelativeAdd(0),
+                                )?;
+                            }
+                            status_info!("{} songs added after the current song", songs.len());
+                            Ok(())
+                        }),
+                        ContextMenuEntry::ReplaceAndPlay => context.command(move |client| {
+                            let songs = (list_songs)(client)?;
+                            client.clear()?;
+                            for song in &songs {
+                                client.add(&song.file)?;
+                            }
+                            if !songs.is_empty() {
+                                client.play_pos(0)?;
+                            }
+                            Ok(())
+                        }),
+                        ContextMenuEntry::AddToPlaylist => {
+                            context
+                                .query()
+                                .id(ADD_TO_PLAYLIST)
+                                .replace_id(ADD_TO_PLAYLIST)
+                                .query(move |client| {
+                                    let song_file = (list_songs)(client)?
+                                        .into_iter()
+                                        .next()
+                                        .map(|song| song.file)
+                                        .context("No song to add to playlist")?;
+                                    let playlists = client
+                                        .list_playlists()?
+                                        .into_iter()
+                                        .map(|v| v.name)
+                                        .sorted()
+                                        .collect_vec();
+                                    Ok(MpdQueryResult::AddToPlaylist { playlists, song_file })
+                                });
+                        }
+                        ContextMenuEntry::GoToAlbum => {
+                            if let Some(album) = album.clone() {
+                                context
+                                    .app_event_sender
+                                    .send(AppEvent::UiEvent(UiAppEvent::GoToAlbum(album)))?;
+                            }
+                        }
+                    }
+                    Ok(())
+                })
+        );
+
+        Ok(())
+    }
+
     fn handle_common_action(&mut self, event: &mut KeyEvent, context: &AppContext) -> Result<()> {
         let Some(action) = event.as_common_action(context) else {
             return Ok(());
@@ -249,6 +497,16 @@ where
                 self.prepare_preview(context);
                 context.render()?;
             }
+            CommonAction::PageDown => {
+                self.stack_mut().current_mut().next_viewport(context.config.scrolloff);
+                self.prepare_preview(context);
+                context.render()?;
+            }
+            CommonAction::PageUp => {
+                self.stack_mut().current_mut().prev_viewport(context.config.scrolloff);
+                self.prepare_preview(context);
+                context.render()?;
+            }
             CommonAction::Bottom => {
                 self.stack_mut().current_mut().last();
                 self.prepare_preview(context);
@@ -264,25 +522,31 @@ where
                 self.prepare_preview(context);
                 context.render()?;
             }
-            CommonAction::Left => {
-                self.stack_mut().pop();
-                self.stack_mut().clear_preview();
-                self.prepare_preview(context);
-                context.render()?;
+            CommonAction::Left | CommonAction::GoToParent => {
+                self.go_to_parent(context)?;
+            }
+            CommonAction::GoToRoot => {
+                self.go_to_root(context)?;
             }
             CommonAction::EnterSearch => {
                 self.set_filter_input_mode_active(true);
-                self.stack_mut().current_mut().set_filter(Some(String::new()), config);
+                self.stack_mut()
+                    .current_mut()
+                    .set_filter(Some(String::new()), config, &context.favorites);
 
                 context.render()?;
             }
             CommonAction::NextResult => {
-                self.stack_mut().current_mut().jump_next_matching(config);
+                self.stack_mut()
+                    .current_mut()
+                    .jump_next_matching(config, &context.favorites);
                 self.prepare_preview(context);
                 context.render()?;
             }
             CommonAction::PreviousResult => {
-                self.stack_mut().current_mut().jump_previous_matching(config);
+                self.stack_mut()
+                    .current_mut()
+                    .jump_previous_matching(config, &context.favorites);
                 self.prepare_preview(context);
                 context.render()?;
             }
@@ -291,6 +555,13 @@ where
 
                 context.render()?;
             }
+            CommonAction::SelectAll => {
+                self.stack_mut()
+                    .current_mut()
+                    .toggle_mark_all(context.config, &context.favorites);
+
+                context.render()?;
+            }
             CommonAction::Select => {
                 self.stack_mut().current_mut().toggle_mark_selected();
                 self.stack_mut()
@@ -300,10 +571,44 @@ where
                 context.render()?;
             }
             CommonAction::Add if !self.stack().current().marked().is_empty() => {
-                for idx in self.stack().current().marked() {
-                    let item = &self.stack().current().items[*idx];
-                    self.add(item, context)?;
-                }
+                let items = self
+                    .stack()
+                    .current()
+                    .marked()
+                    .iter()
+                    .map(|idx| self.stack().current().items[*idx].clone())
+                    .collect_vec();
+                let count_songs = items
+                    .iter()
+                    .map(|item| self.list_songs_in_item(item.clone()))
+                    .collect_vec();
+                let list_songs = items
+                    .into_iter()
+                    .map(|item| self.list_songs_in_item(item))
+                    .collect_vec();
+                let feedback = config.add_feedback;
+                let autoplay = config.autoplay_on_add;
+                add_within_limit(
+                    context,
+                    move |client| {
+                        let mut count = 0;
+                        for list in count_songs {
+                            count += (list)(client)?.len();
+                        }
+                        Ok(count)
+                    },
+                    move |client| {
+                        let mut songs = Vec::new();
+                        for list in list_songs {
+                            songs.extend((list)(client)?);
+                        }
+                        client.add_and_autoplay(autoplay, |client| {
+                            client.add_multiple(&songs.iter().map(|song| song.file.as_str()).collect_vec())
+                        })?;
+                        report_added(feedback, "Selected items", songs.len());
+                        Ok(())
+                    },
+                );
 
                 context.render()?;
             }
@@ -336,6 +641,32 @@ where
                     self.rename(item, context);
                 }
             }
+            CommonAction::CopyFilePath => {
+                if let Some(song) = self.stack().current().selected().and_then(T::as_song) {
+                    let path = song.resolved_path(config.music_directory);
+                    clipboard::copy(&path)?;
+                    status_info!("Copied '{path}' to clipboard");
+                }
+            }
+            CommonAction::OpenInTagEditor => {
+                let Some(command) = config.tag_editor else {
+                    status_warn!("No tag editor configured");
+                    return Ok(());
+                };
+                if let Some(song) = self.stack().current().selected().and_then(T::as_song) {
+                    run_tag_editor(command, song, context);
+                }
+            }
+            CommonAction::IncreaseRating => {
+                if let Some(song) = self.stack().current().selected().and_then(T::as_song) {
+                    change_rating(song, 1, context);
+                }
+            }
+            CommonAction::DecreaseRating => {
+                if let Some(song) = self.stack().current().selected().and_then(T::as_song) {
+                    change_rating(song, -1, context);
+                }
+            }
             CommonAction::FocusInput => {}
             CommonAction::Close => {}
             CommonAction::Confirm if self.stack().current().marked().is_empty() => {
@@ -352,3 +683,56 @@ where
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{
+        mpd::commands::Song,
+        ui::{dirstack::DirStackItem, modals::context_menu::ContextMenuEntry, panes::browser::DirOrSong},
+    };
+
+    use super::context_menu_entries;
+
+    #[test]
+    fn song_row_has_all_entries() {
+        let item = DirOrSong::Song(Song {
+            file: "artist/album/song.flac".to_owned(),
+            metadata: HashMap::from([("album".to_owned(), "album".to_owned())]),
+            ..Default::default()
+        });
+
+        let result = context_menu_entries(item.as_song());
+
+        assert_eq!(
+            result,
+            vec![
+                ContextMenuEntry::Add,
+                ContextMenuEntry::AddNext,
+                ContextMenuEntry::ReplaceAndPlay,
+                ContextMenuEntry::AddToPlaylist,
+                ContextMenuEntry::GoToAlbum,
+            ]
+        );
+    }
+
+    #[test]
+    fn dir_row_has_only_queue_entries() {
+        let item = DirOrSong::Dir {
+            name: "album".to_owned(),
+            full_path: "artist/album".to_owned(),
+        };
+
+        let result = context_menu_entries(item.as_song());
+
+        assert_eq!(
+            result,
+            vec![
+                ContextMenuEntry::Add,
+                ContextMenuEntry::AddNext,
+                ContextMenuEntry::ReplaceAndPlay,
+            ]
+        );
+    }
+}
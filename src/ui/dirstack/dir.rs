@@ -3,7 +3,10 @@ use std::collections::BTreeSet;
 use log::error;
 use ratatui::widgets::ListState;
 
-use crate::{config::Config, shared::macros::status_warn};
+use crate::{
+    config::Config,
+    shared::{favorites::Favorites, macros::status_warn},
+};
 
 use super::{state::DirState, DirStackItem};
 
@@ -57,37 +60,48 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
         self.filter.as_deref()
     }
 
-    pub fn set_filter(&mut self, value: Option<String>, config: &Config) {
+    pub fn set_filter(&mut self, value: Option<String>, config: &Config, favorites: &Favorites) {
         self.matched_item_count = if let Some(ref filter) = value {
-            self.items.iter().filter(|item| item.matches(config, filter)).count()
+            self.items
+                .iter()
+                .filter(|item| item.matches(config, favorites, filter))
+                .count()
         } else {
             0
         };
         self.filter = value;
     }
 
-    pub fn push_filter(&mut self, char: char, config: &Config) {
+    pub fn push_filter(&mut self, char: char, config: &Config, favorites: &Favorites) {
         if let Some(ref mut filter) = self.filter {
             filter.push(char);
-            self.matched_item_count = self.items.iter().filter(|item| item.matches(config, filter)).count();
+            self.matched_item_count = self
+                .items
+                .iter()
+                .filter(|item| item.matches(config, favorites, filter))
+                .count();
         }
     }
 
-    pub fn pop_filter(&mut self, config: &Config) {
+    pub fn pop_filter(&mut self, config: &Config, favorites: &Favorites) {
         if let Some(ref mut filter) = self.filter {
             filter.pop();
-            self.matched_item_count = self.items.iter().filter(|item| item.matches(config, filter)).count();
+            self.matched_item_count = self
+                .items
+                .iter()
+                .filter(|item| item.matches(config, favorites, filter))
+                .count();
         }
     }
 
-    pub fn to_list_items(&self, config: &crate::config::Config) -> Vec<T::Item> {
+    pub fn to_list_items(&self, config: &Config, favorites: &Favorites) -> Vec<T::Item> {
         let mut already_matched: u32 = 0;
         let current_item_idx = self.selected_with_idx().map(|(idx, _)| idx);
         self.items
             .iter()
             .enumerate()
             .map(|(i, item)| {
-                let matches = self.filter.as_ref().is_some_and(|v| item.matches(config, v));
+                let matches = self.filter.as_ref().is_some_and(|v| item.matches(config, favorites, v));
                 let is_current = current_item_idx.is_some_and(|idx| i == idx);
                 if matches {
                     already_matched = already_matched.saturating_add(1);
@@ -97,7 +111,7 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
                 } else {
                     None
                 };
-                item.to_list_item(config, self.marked().contains(&i), matches, content)
+                item.to_list_item(config, favorites, self.marked().contains(&i), matches, content)
             })
             .collect()
     }
@@ -146,6 +160,23 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
         self.state.invert_marked();
     }
 
+    /// Marks every item matching the current filter, or all items when no filter is set. Unmarks them
+    /// instead if they are all already marked.
+    pub fn toggle_mark_all(&mut self, config: &Config, favorites: &Favorites) {
+        let items = self
+            .items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| {
+                self.filter
+                    .as_ref()
+                    .map_or(true, |filter| item.matches(config, favorites, filter))
+            })
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        self.state.toggle_mark_all(items);
+    }
+
     pub fn toggle_mark_selected(&mut self) -> bool {
         if let Some(sel) = self.state.get_selected() {
             self.state.toggle_mark(sel)
@@ -194,6 +225,14 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
         self.state.prev(scrolloff, wrap);
     }
 
+    pub fn scroll_down(&mut self, amount: usize, scrolloff: usize) {
+        self.state.scroll_down(amount, scrolloff);
+    }
+
+    pub fn scroll_up(&mut self, amount: usize, scrolloff: usize) {
+        self.state.scroll_up(amount, scrolloff);
+    }
+
     pub fn select_idx(&mut self, idx: usize, scrolloff: usize) {
         self.state.select(Some(idx), scrolloff);
     }
@@ -206,6 +245,14 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
         self.state.prev_half_viewport(scrolloff);
     }
 
+    pub fn next_viewport(&mut self, scrolloff: usize) {
+        self.state.next_viewport(scrolloff);
+    }
+
+    pub fn prev_viewport(&mut self, scrolloff: usize) {
+        self.state.prev_viewport(scrolloff);
+    }
+
     pub fn last(&mut self) {
         self.state.last();
     }
@@ -214,7 +261,7 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
         self.state.first();
     }
 
-    pub fn jump_next_matching(&mut self, config: &Config) {
+    pub fn jump_next_matching(&mut self, config: &Config, favorites: &Favorites) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
@@ -227,14 +274,14 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
         let length = self.items.len();
         for i in selected + 1..length + selected {
             let i = i % length;
-            if self.items[i].matches(config, filter) {
+            if self.items[i].matches(config, favorites, filter) {
                 self.state.select(Some(i), config.scrolloff);
                 break;
             }
         }
     }
 
-    pub fn jump_previous_matching(&mut self, config: &Config) {
+    pub fn jump_previous_matching(&mut self, config: &Config, favorites: &Favorites) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
@@ -247,23 +294,25 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Dir<T> {
         let length = self.items.len();
         for i in (0..length).rev() {
             let i = (i + selected) % length;
-            if self.items[i].matches(config, filter) {
+            if self.items[i].matches(config, favorites, filter) {
                 self.state.select(Some(i), config.scrolloff);
                 break;
             }
         }
     }
 
-    pub fn jump_first_matching(&mut self, config: &Config) {
+    pub fn jump_first_matching(&mut self, config: &Config, favorites: &Favorites) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
         };
 
+        // Fuzzy matches are ranked, the first of the best matches is selected
         self.items
             .iter()
             .enumerate()
-            .find(|(_, item)| item.matches(config, filter))
+            .filter_map(|(idx, item)| item.filter_score(config, favorites, filter).map(|score| (idx, score)))
+            .max_by_key(|(idx, score)| (*score, std::cmp::Reverse(*idx)))
             .inspect(|(idx, _)| self.state.select(Some(*idx), config.scrolloff));
     }
 }
@@ -440,7 +489,7 @@ mod tests {
     }
 
     mod jump_next_matching {
-        use crate::{config::Config, ui::dirstack::Dir};
+        use crate::{config::Config, shared::favorites::Favorites, ui::dirstack::Dir};
 
         #[test]
         fn jumps_by_half_viewport() {
@@ -454,16 +503,16 @@ mod tests {
 
             val.filter = Some("a".to_string());
 
-            val.jump_next_matching(&Config::default());
+            val.jump_next_matching(&Config::default(), &Favorites::default());
             assert_eq!(val.state.get_selected(), Some(1));
 
-            val.jump_next_matching(&Config::default());
+            val.jump_next_matching(&Config::default(), &Favorites::default());
             assert_eq!(val.state.get_selected(), Some(3));
         }
     }
 
     mod jump_previous_matching {
-        use crate::{config::Config, ui::dirstack::Dir};
+        use crate::{config::Config, shared::favorites::Favorites, ui::dirstack::Dir};
 
         #[test]
         fn jumps_by_half_viewport() {
@@ -480,16 +529,67 @@ mod tests {
 
             val.filter = Some("a".to_string());
 
-            val.jump_previous_matching(&Config::default());
+            val.jump_previous_matching(&Config::default(), &Favorites::default());
             assert_eq!(val.state.get_selected(), Some(3));
 
-            val.jump_previous_matching(&Config::default());
+            val.jump_previous_matching(&Config::default(), &Favorites::default());
             assert_eq!(val.state.get_selected(), Some(1));
         }
     }
 
+    mod toggle_mark_all {
+        use std::collections::BTreeSet;
+
+        use crate::{config::Config, shared::favorites::Favorites, ui::dirstack::Dir};
+
+        fn create_subject() -> Dir<String> {
+            let mut res: Dir<String> = Dir {
+                items: vec!["aa", "ab", "c", "ad", "padding"]
+                    .into_iter()
+                    .map(ToOwned::to_owned)
+                    .collect(),
+                ..Default::default()
+            };
+            res.state.set_content_len(Some(res.items.len()));
+            res
+        }
+
+        #[test]
+        fn marks_only_filtered_items() {
+            let mut val = create_subject();
+            val.set_filter(Some("d".to_string()), &Config::default(), &Favorites::default());
+
+            val.toggle_mark_all(&Config::default(), &Favorites::default());
+
+            assert_eq!(val.marked(), &BTreeSet::from([3, 4]));
+        }
+
+        #[test]
+        fn marks_all_items_without_filter() {
+            let mut val = create_subject();
+            val.state.mark(2);
+
+            val.toggle_mark_all(&Config::default(), &Favorites::default());
+
+            assert_eq!(val.marked(), &BTreeSet::from([0, 1, 2, 3, 4]));
+        }
+
+        #[test]
+        fn unmarks_filtered_items_when_all_are_marked() {
+            let mut val = create_subject();
+            val.state.mark(2);
+            val.state.mark(3);
+            val.state.mark(4);
+            val.set_filter(Some("d".to_string()), &Config::default(), &Favorites::default());
+
+            val.toggle_mark_all(&Config::default(), &Favorites::default());
+
+            assert_eq!(val.marked(), &BTreeSet::from([2]));
+        }
+    }
+
     mod matched_item_count {
-        use crate::{config::Config, ui::dirstack::Dir};
+        use crate::{config::Config, shared::favorites::Favorites, ui::dirstack::Dir};
 
         #[test]
         fn filter_changes_recounts_matched_items() {
@@ -501,20 +601,81 @@ mod tests {
                 filter: None,
                 ..Default::default()
             };
-            val.set_filter(Some("a".to_string()), &Config::default());
+            val.set_filter(Some("a".to_string()), &Config::default(), &Favorites::default());
             assert_eq!(val.matched_item_count, 4);
 
-            val.push_filter('d', &Config::default());
+            val.push_filter('d', &Config::default(), &Favorites::default());
             assert_eq!(val.matched_item_count, 2);
 
-            val.pop_filter(&Config::default());
+            val.pop_filter(&Config::default(), &Favorites::default());
             assert_eq!(val.matched_item_count, 4);
 
-            val.pop_filter(&Config::default());
+            val.pop_filter(&Config::default(), &Favorites::default());
             assert_eq!(val.matched_item_count, 5);
 
-            val.set_filter(None, &Config::default());
+            val.set_filter(None, &Config::default(), &Favorites::default());
             assert_eq!(val.matched_item_count, 0);
         }
     }
+    mod fuzzy_filter {
+        use crate::{
+            config::{Config, Leak},
+            mpd::mpd_client::FilterKind,
+            shared::favorites::Favorites,
+            ui::dirstack::Dir,
+        };
+
+        fn subject() -> Dir<String> {
+            let mut val: Dir<String> = Dir {
+                items: vec!["whatever all", "padding", "the wall", "other"]
+                    .into_iter()
+                    .map(ToOwned::to_owned)
+                    .collect(),
+                ..Default::default()
+            };
+            val.state.set_content_len(Some(val.items.len()));
+            val.state.set_viewport_len(Some(val.items.len()));
+            val
+        }
+
+        #[test]
+        fn matches_characters_in_order() {
+            let config = Config {
+                browser_filter_mode: FilterKind::Fuzzy,
+                ..Default::default()
+            }
+            .leak();
+            let mut val = subject();
+
+            val.set_filter(Some("wll".to_string()), config, &Favorites::default());
+
+            assert_eq!(val.matched_item_count, 2);
+        }
+
+        #[test]
+        fn jumps_to_best_match() {
+            let config = Config {
+                browser_filter_mode: FilterKind::Fuzzy,
+                ..Default::default()
+            }
+            .leak();
+            let mut val = subject();
+            val.set_filter(Some("wall".to_string()), config, &Favorites::default());
+
+            val.jump_first_matching(config, &Favorites::default());
+
+            assert_eq!(val.selected().map(String::as_str), Some("the wall"));
+        }
+
+        #[test]
+        fn jumps_to_first_match_without_fuzzy() {
+            let config = Config::default();
+            let mut val = subject();
+            val.set_filter(Some("all".to_string()), &config, &Favorites::default());
+
+            val.jump_first_matching(&config, &Favorites::default());
+
+            assert_eq!(val.selected().map(String::as_str), Some("whatever all"));
+        }
+    }
 }
@@ -11,21 +11,34 @@ pub use dir::Dir;
 pub use stack::DirStack;
 pub use state::DirState;
 
-use crate::{config::Config, mpd::commands::Song, ui::panes::browser::DirOrSong};
+use crate::{
+    config::{theme::SymbolsConfig, Config},
+    mpd::{commands::Song, mpd_client::Filter},
+    shared::favorites::{self, Favorites},
+    ui::panes::browser::DirOrSong,
+};
 
 pub trait DirStackItem {
     type Item;
     fn as_path(&self) -> &str;
-    fn matches(&self, config: &Config, filter: &str) -> bool;
+    /// How well the item matches the filter, `None` when it does not match at all. Higher is better.
+    fn filter_score(&self, config: &Config, favorites: &Favorites, filter: &str) -> Option<u32>;
+    fn matches(&self, config: &Config, favorites: &Favorites, filter: &str) -> bool {
+        self.filter_score(config, favorites, filter).is_some()
+    }
     fn to_list_item(
         &self,
         config: &Config,
+        favorites: &Favorites,
         is_marked: bool,
         matches_filter: bool,
         additional_content: Option<String>,
     ) -> Self::Item;
-    fn to_list_item_simple(&self, config: &Config) -> Self::Item {
-        self.to_list_item(config, false, false, None)
+    fn to_list_item_simple(&self, config: &Config, favorites: &Favorites) -> Self::Item {
+        self.to_list_item(config, favorites, false, false, None)
+    }
+    fn as_song(&self) -> Option<&Song> {
+        None
     }
 }
 
@@ -39,18 +52,37 @@ impl DirStackItem for DirOrSong {
         }
     }
 
-    fn matches(&self, config: &Config, filter: &str) -> bool {
+    fn as_song(&self) -> Option<&Song> {
+        match self {
+            DirOrSong::Dir { .. } => None,
+            DirOrSong::Song(s) => Some(s),
+        }
+    }
+
+    fn filter_score(&self, config: &Config, favorites: &Favorites, filter: &str) -> Option<u32> {
         match self {
-            DirOrSong::Dir { name, .. } => if name.is_empty() { "Untitled" } else { name.as_str() }
-                .to_lowercase()
-                .contains(&filter.to_lowercase()),
-            DirOrSong::Song(s) => s.matches(config.theme.browser_song_format.0, filter),
+            DirOrSong::Dir { .. } if favorites::split_filter(filter).0 => None,
+            DirOrSong::Dir { name, .. } => {
+                // Directories have no tags so every part of the query is matched against the name
+                let name = if name.is_empty() { "Untitled" } else { name.as_str() };
+                Filter::parse_query(filter, config.browser_filter_mode)
+                    .iter()
+                    .map(|filter| filter.kind.score(name, filter.value))
+                    .sum()
+            }
+            DirOrSong::Song(s) => s.query_score(
+                config.theme.browser_song_format.0,
+                favorites,
+                filter,
+                config.browser_filter_mode,
+            ),
         }
     }
 
     fn to_list_item(
         &self,
         config: &Config,
+        favorites: &Favorites,
         is_marked: bool,
         matches_filter: bool,
         additional_content: Option<String>,
@@ -74,6 +106,7 @@ impl DirStackItem for DirOrSong {
             DirOrSong::Song(s) => {
                 let spans = [marker_span, Span::from(symbols.song), Span::from(" ")]
                     .into_iter()
+                    .chain(favorite_span(s, favorites, symbols))
                     .chain(
                         config
                             .theme
@@ -90,6 +123,8 @@ impl DirStackItem for DirOrSong {
         }
         if matches_filter {
             ListItem::from(value).style(config.theme.highlighted_item_style)
+        } else if let Some(style) = self.as_song().and_then(|song| config.theme.tag_style(song)) {
+            ListItem::from(value).style(style)
         } else {
             ListItem::from(value)
         }
@@ -103,13 +138,23 @@ impl DirStackItem for Song {
         &self.file
     }
 
-    fn matches(&self, config: &Config, filter: &str) -> bool {
-        self.matches(config.theme.browser_song_format.0, filter)
+    fn as_song(&self) -> Option<&Song> {
+        Some(self)
+    }
+
+    fn filter_score(&self, config: &Config, favorites: &Favorites, filter: &str) -> Option<u32> {
+        self.query_score(
+            config.theme.browser_song_format.0,
+            favorites,
+            filter,
+            config.browser_filter_mode,
+        )
     }
 
     fn to_list_item(
         &self,
         config: &Config,
+        favorites: &Favorites,
         is_marked: bool,
         matches_filter: bool,
         additional_content: Option<String>,
@@ -125,25 +170,29 @@ impl DirStackItem for Song {
         let artist = self.artist_str().to_owned();
         let separator_span = Span::from(" - ");
         let icon_span = Span::from(format!("{} ", symbols.song));
-        let mut result = vec![
-            marker_span,
-            icon_span,
-            Span::from(artist),
-            separator_span,
-            Span::from(title),
-        ];
+        let mut result = vec![marker_span, icon_span];
+        result.extend(favorite_span(self, favorites, symbols));
+        result.extend([Span::from(artist), separator_span, Span::from(title)]);
         if let Some(content) = additional_content {
             result.push(Span::raw(content));
         }
         let mut result = ListItem::new(Line::from(result));
         if matches_filter {
             result = result.style(config.theme.highlighted_item_style);
+        } else if let Some(style) = config.theme.tag_style(self) {
+            result = result.style(style);
         }
 
         result
     }
 }
 
+fn favorite_span(song: &Song, favorites: &Favorites, symbols: &SymbolsConfig) -> Option<Span<'static>> {
+    favorites
+        .contains(&song.file)
+        .then(|| Span::from(format!("{} ", symbols.favorite)))
+}
+
 pub trait ScrollingState {
     fn select_scrolling(&mut self, idx: Option<usize>);
     fn get_selected_scrolling(&self) -> Option<usize>;
@@ -194,13 +243,14 @@ impl DirStackItem for String {
         self
     }
 
-    fn matches(&self, _config: &Config, filter: &str) -> bool {
-        self.to_lowercase().contains(&filter.to_lowercase())
+    fn filter_score(&self, config: &Config, _favorites: &Favorites, filter: &str) -> Option<u32> {
+        config.browser_filter_mode.score(self, filter)
     }
 
     fn to_list_item(
         &self,
         config: &Config,
+        _favorites: &Favorites,
         is_marked: bool,
         matches_filter: bool,
         _additional_content: Option<String>,
@@ -7,6 +7,7 @@ pub struct DirStack<T: std::fmt::Debug + DirStackItem + Clone + Send> {
     current: Dir<T>,
     others: Vec<Dir<T>>,
     preview: Option<Vec<ListItem<'static>>>,
+    preview_offset: usize,
     path: Vec<String>,
 }
 
@@ -24,6 +25,7 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> DirStack<T> {
             path: Vec::new(),
             current: Dir::default(),
             preview: None,
+            preview_offset: 0,
         };
         result.push(Vec::new());
         result.current = Dir::new(root);
@@ -78,13 +80,29 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> DirStack<T> {
         if let Some(ref mut p) = self.preview {
             p.clear();
         }
+        self.preview_offset = 0;
     }
 
     /// Returns the element at the second element from the top of the stack
     pub fn set_preview(&mut self, preview: Option<Vec<ListItem<'static>>>) -> &Self {
         self.preview = preview;
+        self.preview_offset = 0;
         self
     }
+
+    /// Number of preview items scrolled out of view at the top
+    pub fn preview_offset(&self) -> usize {
+        self.preview_offset
+    }
+
+    pub fn scroll_preview_down(&mut self, amount: usize) {
+        let len = self.preview.as_ref().map_or(0, Vec::len);
+        self.preview_offset = self.preview_offset.saturating_add(amount).min(len.saturating_sub(1));
+    }
+
+    pub fn scroll_preview_up(&mut self, amount: usize) {
+        self.preview_offset = self.preview_offset.saturating_sub(amount);
+    }
     pub fn replace(&mut self, head: Vec<T>) {
         if self.pop().is_some() {
             let len = head.len();
@@ -117,6 +135,15 @@ impl<T: std::fmt::Debug + DirStackItem + Clone + Send> DirStack<T> {
             None
         }
     }
+
+    /// Pops all levels above the root, returns whether anything was popped
+    pub fn pop_to_root(&mut self) -> bool {
+        let mut popped = false;
+        while self.pop().is_some() {
+            popped = true;
+        }
+        popped
+    }
 }
 
 #[cfg(test)]
@@ -238,4 +265,32 @@ mod tests {
             val.previous();
         }
     }
+
+    mod pop_to_root {
+        use crate::ui::dirstack::DirStack;
+
+        #[test]
+        fn empties_path_and_restores_root_selection() {
+            let root: Vec<String> = vec!["a", "b", "c"].into_iter().map(ToOwned::to_owned).collect();
+            let mut subject: DirStack<String> = DirStack::new(root.clone());
+            subject.current_mut().select_idx(1, 0);
+            subject.push(vec!["d".to_owned(), "e".to_owned()]);
+            subject.push(vec!["f".to_owned()]);
+            assert_eq!(subject.path(), ["b", "d"]);
+
+            assert!(subject.pop_to_root());
+
+            assert!(subject.path().is_empty());
+            assert_eq!(subject.current().items, root);
+            assert_eq!(subject.current().selected(), Some(&root[1]));
+        }
+
+        #[test]
+        fn does_nothing_at_root() {
+            let mut subject: DirStack<String> = DirStack::new(vec!["a".to_owned()]);
+
+            assert!(!subject.pop_to_root());
+            assert!(subject.path().is_empty());
+        }
+    }
 }
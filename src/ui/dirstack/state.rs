@@ -1,6 +1,6 @@
 use std::{collections::BTreeSet, ops::SubAssign};
 
-use ratatui::widgets::ScrollbarState;
+use ratatui::{layout::Rect, widgets::ScrollbarState};
 
 use super::ScrollingState;
 
@@ -11,6 +11,7 @@ pub struct DirState<T: ScrollingState> {
     pub marked: BTreeSet<usize>,
     content_len: Option<usize>,
     viewport_len: Option<usize>,
+    hovered: Option<usize>,
 }
 
 #[allow(dead_code)]
@@ -139,6 +140,30 @@ impl<T: ScrollingState> DirState<T> {
         }
     }
 
+    /// Moves the selection down by `amount` items without wrapping around
+    pub fn scroll_down(&mut self, amount: usize, scrolloff: usize) {
+        if let Some(item_count) = self.content_len {
+            let idx = match self.get_selected() {
+                Some(i) => Some(i.saturating_add(amount).min(item_count.saturating_sub(1))),
+                None if item_count > 0 => Some(0),
+                None => None,
+            };
+            self.select(idx, scrolloff);
+        }
+    }
+
+    /// Moves the selection up by `amount` items without wrapping around
+    pub fn scroll_up(&mut self, amount: usize, scrolloff: usize) {
+        if let Some(item_count) = self.content_len {
+            let idx = match self.get_selected() {
+                Some(i) => Some(i.saturating_sub(amount)),
+                None if item_count > 0 => Some(item_count.saturating_sub(1)),
+                None => None,
+            };
+            self.select(idx, scrolloff);
+        }
+    }
+
     pub fn next_half_viewport(&mut self, scrolloff: usize) {
         if let Some(item_count) = self.content_len {
             if let Some(viewport) = self.viewport_len {
@@ -170,6 +195,34 @@ impl<T: ScrollingState> DirState<T> {
         }
     }
 
+    pub fn next_viewport(&mut self, scrolloff: usize) {
+        if let Some(item_count) = self.content_len {
+            if let Some(viewport) = self.viewport_len {
+                self.select(
+                    self.get_selected()
+                        .map(|i| i.saturating_add(viewport).min(item_count.saturating_sub(1))),
+                    scrolloff,
+                );
+            } else {
+                self.select(None, scrolloff);
+            }
+        } else {
+            self.select(None, scrolloff);
+        }
+    }
+
+    pub fn prev_viewport(&mut self, scrolloff: usize) {
+        if self.content_len.is_some() {
+            if let Some(viewport) = self.viewport_len {
+                self.select(self.get_selected().map(|i| i.saturating_sub(viewport)), scrolloff);
+            } else {
+                self.select(None, scrolloff);
+            }
+        } else {
+            self.select(None, scrolloff);
+        }
+    }
+
     pub fn select(&mut self, idx: Option<usize>, scrolloff: usize) {
         let content_len = self.content_len.unwrap_or_default();
         let idx = idx.map(|idx| idx.max(0).min(content_len.saturating_sub(1)));
@@ -178,6 +231,19 @@ impl<T: ScrollingState> DirState<T> {
         self.scrollbar_state = self.scrollbar_state.position(idx.unwrap_or(0));
     }
 
+    /// Selects the item and scrolls so that it is in the middle of the viewport, if possible
+    pub fn select_centered(&mut self, idx: usize) {
+        let content_len = self.content_len.unwrap_or_default();
+        let viewport_len = self.viewport_len.unwrap_or_default();
+        let idx = idx.min(content_len.saturating_sub(1));
+        self.inner.select_scrolling(Some(idx));
+        self.inner.set_offset(
+            idx.saturating_sub(viewport_len / 2)
+                .min(content_len.saturating_sub(viewport_len)),
+        );
+        self.scrollbar_state = self.scrollbar_state.position(idx);
+    }
+
     fn apply_scrolloff(&mut self, scrolloff: usize) {
         if scrolloff == 0 {
             return;
@@ -241,6 +307,18 @@ impl<T: ScrollingState> DirState<T> {
         self.marked.clear();
     }
 
+    /// Marks all of the given items or unmarks them if all of them are already marked
+    pub fn toggle_mark_all(&mut self, items: impl IntoIterator<Item = usize>) {
+        let items = items.into_iter().collect::<Vec<_>>();
+        if items.iter().all(|idx| self.marked.contains(idx)) {
+            for idx in &items {
+                self.marked.remove(idx);
+            }
+        } else {
+            self.marked.extend(items);
+        }
+    }
+
     pub fn mark(&mut self, idx: usize) -> bool {
         self.marked.insert(idx)
     }
@@ -294,6 +372,26 @@ impl<T: ScrollingState> DirState<T> {
         }
     }
 
+    /// Returns index of the item rendered at the terminal row `y` when the list is rendered in `area`
+    pub fn get_at_y(&self, area: Rect, y: u16) -> Option<usize> {
+        if y < area.y || y >= area.bottom() {
+            return None;
+        }
+
+        self.get_at_rendered_row((y - area.y).into())
+    }
+
+    pub fn get_hovered(&self) -> Option<usize> {
+        self.hovered
+    }
+
+    /// Sets the item under the mouse cursor. Returns true if the hovered item changed.
+    pub fn set_hovered(&mut self, idx: Option<usize>) -> bool {
+        let changed = self.hovered != idx;
+        self.hovered = idx;
+        changed
+    }
+
     pub fn offset(&self) -> usize {
         self.inner.offset()
     }
@@ -323,6 +421,58 @@ mod tests {
         assert_eq!(subject.content_len, Some(1337));
     }
 
+    mod get_at_y {
+        use ratatui::{layout::Rect, widgets::ListState};
+
+        use crate::ui::dirstack::DirState;
+
+        #[test]
+        fn maps_row_to_item() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(10));
+            subject.set_viewport_len(Some(5));
+            let area = Rect::new(0, 3, 20, 5);
+
+            assert_eq!(subject.get_at_y(area, 3), Some(0));
+            assert_eq!(subject.get_at_y(area, 5), Some(2));
+            assert_eq!(subject.get_at_y(area, 7), Some(4));
+        }
+
+        #[test]
+        fn accounts_for_offset() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(10));
+            subject.set_viewport_len(Some(5));
+            subject.as_render_state_ref().select(Some(9));
+            *subject.as_render_state_ref().offset_mut() = 5;
+            let area = Rect::new(0, 3, 20, 5);
+
+            assert_eq!(subject.get_at_y(area, 3), Some(5));
+            assert_eq!(subject.get_at_y(area, 7), Some(9));
+        }
+
+        #[test]
+        fn outside_of_area() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(10));
+            subject.set_viewport_len(Some(5));
+            let area = Rect::new(0, 3, 20, 5);
+
+            assert_eq!(subject.get_at_y(area, 2), None);
+            assert_eq!(subject.get_at_y(area, 8), None);
+        }
+
+        #[test]
+        fn below_last_item() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(2));
+            subject.set_viewport_len(Some(5));
+            let area = Rect::new(0, 3, 20, 5);
+
+            assert_eq!(subject.get_at_y(area, 5), None);
+        }
+    }
+
     mod first {
         use ratatui::widgets::ListState;
 
@@ -513,6 +663,48 @@ mod tests {
         }
     }
 
+    mod scroll {
+        use ratatui::widgets::ListState;
+
+        use crate::ui::dirstack::DirState;
+
+        #[test]
+        fn scroll_down_advances_offset_by_step() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(100));
+            subject.set_viewport_len(Some(10));
+            subject.select(Some(8), 1);
+            assert_eq!(subject.offset(), 0);
+
+            subject.scroll_down(3, 1);
+
+            assert_eq!(subject.get_selected(), Some(11));
+            assert_eq!(subject.offset(), 3);
+        }
+
+        #[test]
+        fn scroll_down_stops_at_last_item() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(10));
+            subject.select(Some(8), 0);
+
+            subject.scroll_down(3, 0);
+
+            assert_eq!(subject.get_selected(), Some(9));
+        }
+
+        #[test]
+        fn scroll_up_stops_at_first_item() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(10));
+            subject.select(Some(2), 0);
+
+            subject.scroll_up(3, 0);
+
+            assert_eq!(subject.get_selected(), Some(0));
+        }
+    }
+
     mod next_half_viewport {
         use ratatui::widgets::ListState;
 
@@ -617,6 +809,88 @@ mod tests {
         }
     }
 
+    mod next_viewport {
+        use ratatui::widgets::ListState;
+
+        use crate::ui::dirstack::DirState;
+
+        #[test]
+        fn when_viewport_is_none() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(5));
+            subject.set_viewport_len(None);
+
+            subject.next_viewport(0);
+
+            assert_eq!(subject.get_selected(), None);
+        }
+
+        #[test]
+        fn goes_forward_by_viewport() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(30));
+            subject.set_viewport_len(Some(10));
+            subject.select(Some(8), 0);
+
+            subject.next_viewport(0);
+
+            assert_eq!(subject.get_selected(), Some(18));
+        }
+
+        #[test]
+        fn caps_at_last_item() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(20));
+            subject.set_viewport_len(Some(10));
+            subject.select(Some(14), 0);
+
+            subject.next_viewport(0);
+
+            assert_eq!(subject.get_selected(), Some(19));
+        }
+    }
+
+    mod prev_viewport {
+        use ratatui::widgets::ListState;
+
+        use crate::ui::dirstack::DirState;
+
+        #[test]
+        fn when_viewport_is_none() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(5));
+            subject.set_viewport_len(None);
+
+            subject.prev_viewport(0);
+
+            assert_eq!(subject.get_selected(), None);
+        }
+
+        #[test]
+        fn goes_back_by_viewport() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(30));
+            subject.set_viewport_len(Some(10));
+            subject.select(Some(18), 0);
+
+            subject.prev_viewport(0);
+
+            assert_eq!(subject.get_selected(), Some(8));
+        }
+
+        #[test]
+        fn caps_at_first_item() {
+            let mut subject: DirState<ListState> = DirState::default();
+            subject.set_content_len(Some(20));
+            subject.set_viewport_len(Some(10));
+            subject.select(Some(4), 0);
+
+            subject.prev_viewport(0);
+
+            assert_eq!(subject.get_selected(), Some(0));
+        }
+    }
+
     mod select {
 
         use ratatui::widgets::ListState;
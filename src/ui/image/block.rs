@@ -0,0 +1,246 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Context, Result};
+use crossbeam::channel::{unbounded, Sender};
+use image::{Rgba, RgbaImage};
+use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+use crate::shared::{color_support::ColorSupport, events::AppEvent, ext::mpsc::RecvLast, macros::status_error};
+
+use super::Backend;
+
+const UPPER_HALF_BLOCK: &str = "▀";
+
+/// Image downsampled to the cell grid, two pixel rows per cell row
+type Thumbnail = (RgbaImage, Rect);
+
+/// Paints the album art with colored upper half blocks, every cell shows two pixels stacked on top of each other.
+/// Needs nothing but color support from the terminal so it works where no image protocol is available.
+#[derive(Debug)]
+pub struct Block {
+    sender: Sender<(Arc<Vec<u8>>, Rect)>,
+    bg_color: Option<Color>,
+    color_support: ColorSupport,
+    /// Filled in by the worker thread once the image is decoded and resized
+    image: Arc<Mutex<Option<Thumbnail>>>,
+    showing: bool,
+}
+
+impl Backend for Block {
+    fn hide(&mut self, _size: Rect) -> Result<()> {
+        self.showing = false;
+        Ok(())
+    }
+
+    fn show(&mut self, data: Arc<Vec<u8>>, area: Rect) -> Result<()> {
+        self.showing = true;
+        Ok(self.sender.send((data, area))?)
+    }
+}
+
+impl Block {
+    pub fn new(bg_color: Option<Color>, color_support: ColorSupport, app_event_sender: Sender<AppEvent>) -> Self {
+        let (sender, receiver) = unbounded::<(Arc<Vec<u8>>, Rect)>();
+        let image = Arc::new(Mutex::new(None));
+
+        let thread_image = Arc::clone(&image);
+        std::thread::Builder::new()
+            .name("block".to_string())
+            .spawn(move || {
+                while let Ok((data, area)) = receiver.recv_last() {
+                    let thumbnail = match thumbnail(&data, area) {
+                        Ok(thumbnail) => thumbnail,
+                        Err(err) => {
+                            status_error!(err:?; "Failed to decode album art");
+                            continue;
+                        }
+                    };
+
+                    if let Ok(mut image) = thread_image.lock() {
+                        *image = Some((thumbnail, area));
+                    }
+                    if let Err(err) = app_event_sender.send(AppEvent::RequestRender) {
+                        log::error!(err:?; "Failed to request render after decoding album art");
+                    }
+                }
+            })
+            .expect("Block thread to be spawned");
+
+        Self {
+            sender,
+            bg_color,
+            color_support,
+            image,
+            showing: false,
+        }
+    }
+
+    /// Unlike the other backends the image is part of the frame, it has to be painted on every render
+    pub fn render(&self, buf: &mut Buffer) {
+        if !self.showing {
+            return;
+        }
+        let Ok(image) = self.image.lock() else {
+            return;
+        };
+        let Some((image, area)) = image.as_ref() else {
+            return;
+        };
+
+        let area = area.intersection(buf.area);
+        let background = self.bg_color.unwrap_or(Color::Reset);
+        for row in 0..area.height {
+            let top_y = u32::from(row) * 2;
+            if top_y >= image.height() {
+                break;
+            }
+            for col in 0..area.width {
+                let x = u32::from(col);
+                if x >= image.width() {
+                    break;
+                }
+
+                let top = pixel_color(*image.get_pixel(x, top_y), self.bg_color);
+                let bottom = image
+                    .get_pixel_checked(x, top_y + 1)
+                    .map_or(background, |pixel| pixel_color(*pixel, self.bg_color));
+                if let Some(cell) = buf.cell_mut((area.x + col, area.y + row)) {
+                    cell.set_symbol(UPPER_HALF_BLOCK)
+                        .set_fg(self.color_support.downsample(top))
+                        .set_bg(self.color_support.downsample(bottom));
+                }
+            }
+        }
+    }
+}
+
+fn thumbnail(data: &[u8], area: Rect) -> Result<RgbaImage> {
+    let image = image::load_from_memory(data).context("Unable to decode image")?;
+    Ok(image
+        .thumbnail(u32::from(area.width), u32::from(area.height) * 2)
+        .into_rgba8())
+}
+
+/// Transparent pixels are blended with the background color, or show the terminal background if it is not an rgb
+/// color
+fn pixel_color(pixel: Rgba<u8>, bg_color: Option<Color>) -> Color {
+    let [r, g, b, alpha] = pixel.0;
+    match bg_color {
+        _ if alpha == u8::MAX => Color::Rgb(r, g, b),
+        Some(Color::Rgb(bg_r, bg_g, bg_b)) => {
+            Color::Rgb(blend(r, bg_r, alpha), blend(g, bg_g, alpha), blend(b, bg_b, alpha))
+        }
+        bg_color if alpha < u8::MAX / 2 => bg_color.unwrap_or(Color::Reset),
+        _ => Color::Rgb(r, g, b),
+    }
+}
+
+fn blend(fg: u8, bg: u8, alpha: u8) -> u8 {
+    let alpha = u16::from(alpha);
+    let value = (u16::from(fg) * alpha + u16::from(bg) * (u16::from(u8::MAX) - alpha)) / u16::from(u8::MAX);
+    u8::try_from(value).unwrap_or(u8::MAX)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{io::Cursor, sync::Arc, time::Duration};
+
+    use crossbeam::channel::{unbounded, Receiver};
+    use image::{ImageFormat, Rgba, RgbaImage};
+    use ratatui::{buffer::Buffer, layout::Rect, style::Color};
+
+    use super::{Block, UPPER_HALF_BLOCK};
+    use crate::{
+        shared::{color_support::ColorSupport, events::AppEvent},
+        ui::image::Backend,
+    };
+
+    fn png(pixels: &[[u8; 4]], width: u32) -> Arc<Vec<u8>> {
+        let height = u32::try_from(pixels.len()).unwrap() / width;
+        let image = RgbaImage::from_fn(width, height, |x, y| Rgba(pixels[(y * width + x) as usize]));
+        let mut data = Vec::new();
+        image.write_to(&mut Cursor::new(&mut data), ImageFormat::Png).unwrap();
+        Arc::new(data)
+    }
+
+    fn new_block(bg_color: Option<Color>, color_support: ColorSupport) -> (Block, Receiver<AppEvent>) {
+        let (tx, rx) = unbounded();
+        (Block::new(bg_color, color_support, tx), rx)
+    }
+
+    /// Shows the image and waits until the worker thread has decoded it
+    fn show(block: &mut Block, rx: &Receiver<AppEvent>, data: Arc<Vec<u8>>, area: Rect) {
+        block.show(data, area).unwrap();
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_secs(5)),
+            Ok(AppEvent::RequestRender)
+        ));
+    }
+
+    #[test]
+    fn paints_two_pixels_per_cell() {
+        let (mut block, rx) = new_block(None, ColorSupport::TrueColor);
+        let area = Rect::new(0, 0, 2, 1);
+        let mut buf = Buffer::empty(area);
+        let data = png(
+            &[[255, 0, 0, 255], [0, 255, 0, 255], [0, 0, 255, 255], [9, 9, 9, 255]],
+            2,
+        );
+
+        show(&mut block, &rx, data, area);
+        block.render(&mut buf);
+
+        assert_eq!(buf[(0, 0)].symbol(), UPPER_HALF_BLOCK);
+        assert_eq!(buf[(0, 0)].fg, Color::Rgb(255, 0, 0));
+        assert_eq!(buf[(0, 0)].bg, Color::Rgb(0, 0, 255));
+        assert_eq!(buf[(1, 0)].fg, Color::Rgb(0, 255, 0));
+        assert_eq!(buf[(1, 0)].bg, Color::Rgb(9, 9, 9));
+    }
+
+    #[test]
+    fn colors_are_downsampled_to_terminal_support() {
+        let (mut block, rx) = new_block(None, ColorSupport::Ansi256);
+        let area = Rect::new(0, 0, 1, 1);
+        let mut buf = Buffer::empty(area);
+
+        show(&mut block, &rx, png(&[[255, 0, 0, 255], [0, 0, 0, 255]], 1), area);
+        block.render(&mut buf);
+
+        assert_eq!(buf[(0, 0)].fg, Color::Indexed(196));
+        assert_eq!(buf[(0, 0)].bg, Color::Indexed(16));
+    }
+
+    #[test]
+    fn transparent_pixels_use_background_color() {
+        let area = Rect::new(0, 0, 1, 1);
+        let data = png(&[[255, 0, 0, 0], [200, 100, 0, 0]], 1);
+
+        let (mut block, rx) = new_block(Some(Color::Rgb(10, 20, 30)), ColorSupport::TrueColor);
+        let mut buf = Buffer::empty(area);
+        show(&mut block, &rx, Arc::clone(&data), area);
+        block.render(&mut buf);
+        assert_eq!(buf[(0, 0)].fg, Color::Rgb(10, 20, 30));
+        assert_eq!(buf[(0, 0)].bg, Color::Rgb(10, 20, 30));
+
+        let (mut block, rx) = new_block(Some(Color::Black), ColorSupport::TrueColor);
+        let mut buf = Buffer::empty(area);
+        show(&mut block, &rx, data, area);
+        block.render(&mut buf);
+        assert_eq!(buf[(0, 0)].fg, Color::Black);
+        assert_eq!(buf[(0, 0)].bg, Color::Black);
+    }
+
+    #[test]
+    fn nothing_is_painted_when_hidden() {
+        let (mut block, rx) = new_block(None, ColorSupport::TrueColor);
+        let area = Rect::new(0, 0, 1, 1);
+        let mut buf = Buffer::empty(area);
+
+        show(&mut block, &rx, png(&[[255, 0, 0, 255]], 1), area);
+        block.hide(area).unwrap();
+        block.render(&mut buf);
+
+        assert_eq!(buf, Buffer::empty(area));
+    }
+}
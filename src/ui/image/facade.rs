@@ -2,12 +2,15 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use anyhow::Result;
-use ratatui::layout::Rect;
+use crossbeam::channel::Sender;
+use ratatui::{buffer::Buffer, layout::Rect};
 
 use crate::config::{Config, ImageMethod};
+use crate::shared::color_support::COLOR_SUPPORT;
+use crate::shared::events::AppEvent;
 use crate::shared::image::ImageProtocol;
 
-use super::{iterm2::Iterm2, kitty::Kitty, Backend};
+use super::{block::Block, iterm2::Iterm2, kitty::Kitty, Backend};
 use super::{
     sixel::Sixel,
     ueberzug::{Layer, Ueberzug},
@@ -17,10 +20,12 @@ pub static IS_SHOWING: AtomicBool = AtomicBool::new(false);
 
 #[derive(Debug)]
 pub struct AlbumArtFacade {
+    method: ImageMethod,
     image_state: ImageState,
     current_album_art: Option<Arc<Vec<u8>>>,
     default_album_art: Arc<Vec<u8>>,
     last_size: Rect,
+    app_event_sender: Sender<AppEvent>,
 }
 
 #[derive(Debug, Default)]
@@ -29,27 +34,47 @@ enum ImageState {
     Ueberzug(Ueberzug),
     Iterm2(Iterm2),
     Sixel(Sixel),
+    Block(Block),
     #[default]
     None,
 }
 
 impl AlbumArtFacade {
-    pub fn new(config: &Config) -> Self {
-        let max_size = config.album_art.max_size_px;
-        let bg_color = config.theme.background_color;
-        let proto = match config.album_art.method.into() {
-            ImageProtocol::Kitty => ImageState::Kitty(Kitty::new(max_size, bg_color)),
-            ImageProtocol::UeberzugWayland => ImageState::Ueberzug(Ueberzug::new(Layer::Wayland, max_size)),
-            ImageProtocol::UeberzugX11 => ImageState::Ueberzug(Ueberzug::new(Layer::X11, max_size)),
-            ImageProtocol::Iterm2 => ImageState::Iterm2(Iterm2::new(max_size, bg_color)),
-            ImageProtocol::Sixel => ImageState::Sixel(Sixel::new(max_size, bg_color)),
-            ImageProtocol::None => ImageState::None,
-        };
+    pub fn new(config: &Config, app_event_sender: Sender<AppEvent>) -> Self {
         Self {
-            image_state: proto,
+            method: config.album_art.method,
+            image_state: ImageState::new(config.album_art.method, config, &app_event_sender),
             current_album_art: None,
             last_size: Rect::default(),
             default_album_art: Arc::new(config.theme.default_album_art.to_vec()),
+            app_event_sender,
+        }
+    }
+
+    #[cfg(test)]
+    pub fn current_album_art(&self) -> Option<&[u8]> {
+        self.current_album_art.as_deref().map(Vec::as_slice)
+    }
+
+    pub fn method(&self) -> ImageMethod {
+        self.method
+    }
+
+    /// Tears down the current image backend and replaces it with a freshly initialized one for the given
+    /// method. The image that was displayed before is shown again using the new method.
+    pub fn set_method(&mut self, method: ImageMethod, config: &Config) -> Result<()> {
+        let was_showing = IS_SHOWING.load(Ordering::Relaxed);
+        self.cleanup()?;
+        self.method = method;
+        self.image_state = ImageState::new(method, config, &self.app_event_sender);
+
+        if !was_showing {
+            return Ok(());
+        }
+        if self.current_album_art.is_some() {
+            self.show_current()
+        } else {
+            self.show_default()
         }
     }
 
@@ -65,6 +90,7 @@ impl AlbumArtFacade {
             ImageState::Ueberzug(ueberzug) => ueberzug.show(data, self.last_size),
             ImageState::Iterm2(iterm2) => iterm2.show(data, self.last_size),
             ImageState::Sixel(s) => s.show(data, self.last_size),
+            ImageState::Block(block) => block.show(data, self.last_size),
             ImageState::None => Ok(()),
         }
     }
@@ -85,15 +111,16 @@ impl AlbumArtFacade {
             ImageState::Ueberzug(ueberzug) => ueberzug.show(data, self.last_size),
             ImageState::Iterm2(iterm2) => iterm2.show(data, self.last_size),
             ImageState::Sixel(s) => s.show(data, self.last_size),
+            ImageState::Block(block) => block.show(data, self.last_size),
             ImageState::None => Ok(()),
         }
     }
 
-    pub fn show(&mut self, data: Vec<u8>) -> Result<()> {
+    pub fn show(&mut self, data: impl Into<Arc<Vec<u8>>>) -> Result<()> {
         IS_SHOWING.store(true, Ordering::Relaxed);
 
+        let data = data.into();
         log::debug!(bytes = data.len(), area:? = self.last_size; "New image received",);
-        let data = Arc::new(data);
         self.current_album_art = Some(Arc::clone(&data));
 
         match &mut self.image_state {
@@ -101,6 +128,7 @@ impl AlbumArtFacade {
             ImageState::Ueberzug(ueberzug) => ueberzug.show(data, self.last_size),
             ImageState::Iterm2(iterm2) => iterm2.show(data, self.last_size),
             ImageState::Sixel(s) => s.show(data, self.last_size),
+            ImageState::Block(block) => block.show(data, self.last_size),
             ImageState::None => Ok(()),
         }
     }
@@ -112,6 +140,7 @@ impl AlbumArtFacade {
             ImageState::Ueberzug(ueberzug) => ueberzug.hide(self.last_size)?,
             ImageState::Iterm2(iterm2) => iterm2.hide(self.last_size)?,
             ImageState::Sixel(s) => s.hide(self.last_size)?,
+            ImageState::Block(block) => block.hide(self.last_size)?,
             ImageState::None => {}
         }
         Ok(())
@@ -125,6 +154,7 @@ impl AlbumArtFacade {
             ImageState::Ueberzug(ueberzug) => Box::new(ueberzug).cleanup(self.last_size),
             ImageState::Iterm2(iterm2) => Box::new(iterm2).cleanup(self.last_size),
             ImageState::Sixel(s) => Box::new(s).cleanup(self.last_size),
+            ImageState::Block(block) => Box::new(block).cleanup(self.last_size),
             ImageState::None => Ok(()),
         }
     }
@@ -132,6 +162,29 @@ impl AlbumArtFacade {
     pub fn set_size(&mut self, area: Rect) {
         self.last_size = area;
     }
+
+    /// Paints the album art into the frame for methods which draw through it rather than the terminal directly
+    pub fn render(&self, buf: &mut Buffer) {
+        if let ImageState::Block(block) = &self.image_state {
+            block.render(buf);
+        }
+    }
+}
+
+impl ImageState {
+    fn new(method: ImageMethod, config: &Config, app_event_sender: &Sender<AppEvent>) -> Self {
+        let max_size = config.album_art.max_size_px;
+        let bg_color = config.theme.background_color;
+        match method.into() {
+            ImageProtocol::Kitty => ImageState::Kitty(Kitty::new(max_size, bg_color)),
+            ImageProtocol::UeberzugWayland => ImageState::Ueberzug(Ueberzug::new(Layer::Wayland, max_size)),
+            ImageProtocol::UeberzugX11 => ImageState::Ueberzug(Ueberzug::new(Layer::X11, max_size)),
+            ImageProtocol::Iterm2 => ImageState::Iterm2(Iterm2::new(max_size, bg_color)),
+            ImageProtocol::Sixel => ImageState::Sixel(Sixel::new(max_size, bg_color)),
+            ImageProtocol::Block => ImageState::Block(Block::new(bg_color, *COLOR_SUPPORT, app_event_sender.clone())),
+            ImageProtocol::None => ImageState::None,
+        }
+    }
 }
 
 impl From<ImageMethod> for ImageProtocol {
@@ -142,7 +195,33 @@ impl From<ImageMethod> for ImageProtocol {
             ImageMethod::UeberzugX11 => ImageProtocol::UeberzugX11,
             ImageMethod::Iterm2 => ImageProtocol::Iterm2,
             ImageMethod::Sixel => ImageProtocol::Sixel,
+            ImageMethod::Block => ImageProtocol::Block,
             ImageMethod::None | ImageMethod::Unsupported => ImageProtocol::None,
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::{AlbumArtFacade, ImageState};
+    use crate::config::{Config, ImageMethod};
+
+    #[test]
+    fn set_method_reinitializes_backend() {
+        let mut config = Config::default();
+        config.album_art.method = ImageMethod::None;
+        let mut facade = AlbumArtFacade::new(&config, crossbeam::channel::unbounded().0);
+        assert!(matches!(facade.image_state, ImageState::None));
+
+        facade.set_method(facade.method().next(), &config).unwrap();
+
+        assert_eq!(facade.method(), ImageMethod::Kitty);
+        assert!(matches!(facade.image_state, ImageState::Kitty(_)));
+
+        facade.set_method(facade.method().next(), &config).unwrap();
+
+        assert_eq!(facade.method(), ImageMethod::Sixel);
+        assert!(matches!(facade.image_state, ImageState::Sixel(_)));
+    }
+}
@@ -17,11 +17,11 @@ use crate::{
     config::Size,
     shared::{
         ext::mpsc::RecvLast,
-        image::{get_gif_frames, get_image_area_size_px, jpg_encode, resize_image},
+        image::{get_gif_frames, get_image_dimensions, jpg_encode, resize_image},
         macros::try_cont,
         tmux::tmux_write,
     },
-    ui::image::{clear_area, facade::IS_SHOWING},
+    ui::image::{cell_size_px, clear_area, facade::IS_SHOWING, fit_image},
 };
 
 use super::Backend;
@@ -30,8 +30,8 @@ use super::Backend;
 struct EncodedData {
     content: String,
     size: usize,
-    width: u32,
-    height: u32,
+    width_cells: u16,
+    height_cells: u16,
 }
 
 #[derive(Debug)]
@@ -75,10 +75,7 @@ impl Iterm2 {
                         continue;
                     };
 
-                    let encoded = try_cont!(
-                        encode(area.width, area.height, &data, max_size),
-                        "Failed to encode data"
-                    );
+                    let encoded = try_cont!(encode(area, &data, max_size), "Failed to encode data");
 
                     let mut w = std::io::stdout().lock();
                     if !IS_SHOWING.load(Ordering::Relaxed) {
@@ -106,42 +103,38 @@ fn display(w: &mut impl Write, data: EncodedData, area: Rect) -> Result<()> {
     let EncodedData {
         content,
         size,
-        width,
-        height,
+        width_cells,
+        height_cells,
     } = data;
 
     queue!(w, SavePosition)?;
     queue!(w, MoveTo(area.x, area.y))?;
 
-    tmux_write!(w, "\x1b]1337;File=inline=1;size={size};width={width}px;height={height}px;preserveAspectRatio=1;doNotMoveCursor=1:{content}\x07")?;
+    tmux_write!(w, "\x1b]1337;File=inline=1;size={size};width={width_cells};height={height_cells};preserveAspectRatio=1;doNotMoveCursor=1:{content}\x07")?;
     queue!(w, RestorePosition)?;
 
     Ok(())
 }
 
-fn encode(width: u16, height: u16, data: &[u8], max_size_px: Size) -> Result<EncodedData> {
+fn encode(area: Rect, data: &[u8], max_size_px: Size) -> Result<EncodedData> {
     let start = std::time::Instant::now();
-    let (area_width, area_height) = match get_image_area_size_px(width, height, max_size_px) {
-        Ok(v) => v,
-        Err(err) => {
-            bail!("Failed to get image size, err: {}", err);
-        }
-    };
+    let cell_px = cell_size_px(area, max_size_px);
 
-    let (len, width_px, height_px, data) = if get_gif_frames(data)?.is_some() {
+    let (len, fit, data) = if let Some(gif) = get_gif_frames(data)? {
         log::debug!("encoding animated gif");
 
-        // Take smaller of the two dimensions to make the gif stretch over available area and not overflow
-        let size = area_width.min(area_height).into();
-
-        (
-            data.len(),
-            size,
-            size,
-            base64::engine::general_purpose::STANDARD.encode(data),
-        )
+        // Gifs are sent as is, the terminal scales them into the cells
+        let fit = fit_image(gif.dimensions, area, cell_px, max_size_px);
+        (data.len(), fit, base64::engine::general_purpose::STANDARD.encode(data))
     } else {
-        let image = match resize_image(data, area_width, area_height) {
+        let image_px = match get_image_dimensions(data) {
+            Ok(v) => v,
+            Err(err) => {
+                bail!("Failed to get image size, err: {}", err);
+            }
+        };
+        let fit = fit_image(image_px, area, cell_px, max_size_px);
+        let image = match resize_image(data, fit.width_px, fit.height_px) {
             Ok(v) => v,
             Err(err) => {
                 bail!("Failed to resize image, err: {}", err);
@@ -150,19 +143,14 @@ fn encode(width: u16, height: u16, data: &[u8], max_size_px: Size) -> Result<Enc
         let Ok(jpg) = jpg_encode(&image) else {
             bail!("Failed to encode image as jpg")
         };
-        (
-            jpg.len(),
-            image.width(),
-            image.height(),
-            base64::engine::general_purpose::STANDARD.encode(&jpg),
-        )
+        (jpg.len(), fit, base64::engine::general_purpose::STANDARD.encode(&jpg))
     };
 
-    log::debug!(compressed_bytes = data.len(), image_bytes = len, elapsed:? = start.elapsed(), area_width, area_height ; "encoded data");
+    log::debug!(compressed_bytes = data.len(), image_bytes = len, elapsed:? = start.elapsed(), fit:?; "encoded data");
     Ok(EncodedData {
         content: data,
         size: len,
-        width: width_px,
-        height: height_px,
+        width_cells: fit.width_cells,
+        height_cells: fit.height_cells,
     })
 }
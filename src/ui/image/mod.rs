@@ -8,8 +8,12 @@ use crossterm::{
 };
 use ratatui::layout::Rect;
 
-use crate::shared::macros::csi_move;
+use crate::{
+    config::Size,
+    shared::{image::get_cell_size_px, macros::csi_move},
+};
 
+pub mod block;
 pub mod facade;
 pub mod iterm2;
 pub mod kitty;
@@ -43,3 +47,87 @@ pub fn clear_area(w: &mut impl Write, colors: Colors, area: Rect) -> Result<()>
 
     Ok(())
 }
+
+/// Size of an image scaled to fit into an area of terminal cells without distorting it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageFit {
+    pub width_px: u16,
+    pub height_px: u16,
+    pub width_cells: u16,
+    pub height_cells: u16,
+}
+
+/// Cell size reported by the terminal. Falls back to one which makes `area` exactly `max_size_px` large.
+pub fn cell_size_px(area: Rect, max_size_px: Size) -> (u16, u16) {
+    get_cell_size_px().unwrap_or_else(|| {
+        (
+            (max_size_px.width / area.width.max(1)).max(1),
+            (max_size_px.height / area.height.max(1)).max(1),
+        )
+    })
+}
+
+/// Scales an image of `image_px` to the largest size which fits both into `area` and `max_size_px` while keeping its
+/// aspect ratio. The cells cover the whole image, whatever is left of the area stays empty.
+pub fn fit_image(image_px: (u32, u32), area: Rect, cell_px: (u16, u16), max_size_px: Size) -> ImageFit {
+    let cell_width = u64::from(cell_px.0.max(1));
+    let cell_height = u64::from(cell_px.1.max(1));
+    let area_width = (u64::from(area.width) * cell_width).min(u64::from(max_size_px.width));
+    let area_height = (u64::from(area.height) * cell_height).min(u64::from(max_size_px.height));
+    let image_width = u64::from(image_px.0.max(1));
+    let image_height = u64::from(image_px.1.max(1));
+
+    let (width, height) = if area_width * image_height <= area_height * image_width {
+        (area_width, image_height * area_width / image_width)
+    } else {
+        (image_width * area_height / image_height, area_height)
+    };
+    let (width, height) = (width.max(1), height.max(1));
+
+    ImageFit {
+        width_px: u16::try_from(width).unwrap_or(u16::MAX),
+        height_px: u16::try_from(height).unwrap_or(u16::MAX),
+        width_cells: u16::try_from(width.div_ceil(cell_width)).map_or(area.width, |w| w.min(area.width)),
+        height_cells: u16::try_from(height.div_ceil(cell_height)).map_or(area.height, |h| h.min(area.height)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+    use test_case::test_case;
+
+    use super::{fit_image, ImageFit};
+    use crate::config::Size;
+
+    const MAX_SIZE: Size = Size {
+        width: 1200,
+        height: 1200,
+    };
+
+    #[test_case((500, 500), Rect::new(0, 0, 20, 10), (10, 20), MAX_SIZE, (200, 200, 20, 10); "square image in square area")]
+    #[test_case((500, 500), Rect::new(0, 0, 40, 10), (10, 20), MAX_SIZE, (200, 200, 20, 10); "square image in wide area")]
+    #[test_case((500, 500), Rect::new(0, 0, 20, 30), (10, 20), MAX_SIZE, (200, 200, 20, 10); "square image in tall area")]
+    #[test_case((1000, 500), Rect::new(0, 0, 20, 10), (10, 20), MAX_SIZE, (200, 100, 20, 5); "wide image")]
+    #[test_case((300, 1000), Rect::new(0, 0, 20, 10), (10, 20), MAX_SIZE, (60, 200, 6, 10); "tall image covers partial cells")]
+    #[test_case((500, 500), Rect::new(0, 0, 40, 20), (10, 20), Size { width: 100, height: 1200 }, (100, 100, 10, 5); "capped by max size")]
+    fn fits_image_into_area(
+        image_px: (u32, u32),
+        area: Rect,
+        cell_px: (u16, u16),
+        max_size_px: Size,
+        expected: (u16, u16, u16, u16),
+    ) {
+        let (width_px, height_px, width_cells, height_cells) = expected;
+
+        assert_eq!(
+            fit_image(image_px, area, cell_px, max_size_px),
+            ImageFit {
+                width_px,
+                height_px,
+                width_cells,
+                height_cells,
+            }
+        );
+    }
+}
@@ -20,14 +20,14 @@ use crate::{
     config::Size,
     shared::{
         ext::mpsc::RecvLast,
-        image::{get_image_area_size_px, resize_image},
+        image::{get_image_dimensions, resize_image},
         macros::{status_error, try_cont},
     },
     tmux,
     ui::image::facade::IS_SHOWING,
 };
 
-use super::{clear_area, Backend};
+use super::{cell_size_px, clear_area, fit_image, Backend};
 
 #[derive(Debug)]
 pub struct Sixel {
@@ -70,7 +70,7 @@ impl Sixel {
                         continue;
                     };
 
-                    let buf = try_cont!(encode(area.width, area.height, &data, max_size), "Failed to encode");
+                    let buf = try_cont!(encode(area, &data, max_size), "Failed to encode");
 
                     let mut w = std::io::stdout().lock();
                     if !IS_SHOWING.load(Ordering::Relaxed) {
@@ -105,17 +105,18 @@ fn display(w: &mut impl Write, data: &[u8], area: Rect) -> Result<()> {
     Ok(())
 }
 
-fn encode(width: u16, height: u16, data: &[u8], max_size: Size) -> Result<Vec<u8>> {
+fn encode(area: Rect, data: &[u8], max_size: Size) -> Result<Vec<u8>> {
     let start = Instant::now();
 
-    let (iwidth, iheight) = match get_image_area_size_px(width, height, max_size) {
+    let image_px = match get_image_dimensions(data) {
         Ok(v) => v,
         Err(err) => {
             bail!("Failed to get image size, err: {}", err);
         }
     };
+    let fit = fit_image(image_px, area, cell_size_px(area, max_size), max_size);
 
-    let image = match resize_image(data, iwidth, iheight) {
+    let image = match resize_image(data, fit.width_px, fit.height_px) {
         Ok(v) => v,
         Err(err) => {
             bail!("Failed to resize image, err: {}", err);
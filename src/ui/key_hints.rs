@@ -0,0 +1,76 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+use ratatui::{
+    style::Style,
+    text::{Line, Span},
+};
+
+use crate::{
+    config::keys::{CommonAction, Key},
+    context::AppContext,
+};
+
+/// Key and short description of an action which is available in the current mode, shown in the bottom bar
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyHint {
+    pub keys: String,
+    pub description: &'static str,
+}
+
+impl KeyHint {
+    /// Creates a hint with all keys bound to the action. Returns `None` if the action is not bound to any key.
+    pub fn for_action<A: PartialEq>(keymap: &HashMap<Key, A>, action: &A, description: &'static str) -> Option<Self> {
+        let keys = keymap
+            .iter()
+            .filter(|(_, a)| *a == action)
+            .map(|(key, _)| key.to_string())
+            .sorted()
+            .join("/");
+        if keys.is_empty() {
+            return None;
+        }
+
+        Some(Self { keys, description })
+    }
+}
+
+pub fn filter_mode(context: &AppContext) -> Vec<KeyHint> {
+    let keymap = &context.config.keybinds.navigation;
+    [
+        KeyHint::for_action(keymap, &CommonAction::Confirm, "apply filter"),
+        KeyHint::for_action(keymap, &CommonAction::Close, "cancel filter"),
+        Some(KeyHint {
+            keys: "<BS>".to_owned(),
+            description: "delete character",
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+pub fn selection_mode(context: &AppContext) -> Vec<KeyHint> {
+    let keymap = &context.config.keybinds.navigation;
+    [
+        KeyHint::for_action(keymap, &CommonAction::Select, "toggle"),
+        KeyHint::for_action(keymap, &CommonAction::InvertSelection, "invert"),
+        KeyHint::for_action(keymap, &CommonAction::Add, "add selected"),
+        KeyHint::for_action(keymap, &CommonAction::Delete, "delete selected"),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+pub fn to_line(hints: &[KeyHint], key_style: Style, text_style: Style) -> Line<'static> {
+    let mut spans = Vec::with_capacity(hints.len() * 3);
+    for (idx, hint) in hints.iter().enumerate() {
+        if idx > 0 {
+            spans.push(Span::styled("  ", text_style));
+        }
+        spans.push(Span::styled(hint.keys.clone(), key_style));
+        spans.push(Span::styled(format!(" {}", hint.description), text_style));
+    }
+    Line::from(spans)
+}
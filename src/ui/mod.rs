@@ -1,8 +1,13 @@
-use std::{collections::HashMap, io::Stdout, ops::AddAssign, time::Duration};
+use std::{
+    collections::HashMap,
+    io::Stdout,
+    ops::AddAssign,
+    time::{Duration, Instant},
+};
 
 use crate::{
     config::tabs::PaneType,
-    core::command::{create_env, run_external},
+    core::command::{create_env, run_external, toggle_favorite},
     shared::events::WorkRequest,
 };
 use anyhow::{anyhow, Context, Result};
@@ -13,7 +18,11 @@ use crossterm::{
 };
 use enum_map::{enum_map, Enum, EnumMap};
 use itertools::Itertools;
-use modals::{decoders::DecodersModal, keybinds::KeybindsModal, outputs::OutputsModal, song_info::SongInfoModal};
+use modals::{
+    confirm_modal::ConfirmModal, decoders::DecodersModal, input_modal::InputModal, keybinds::KeybindsModal,
+    outputs::OutputsModal, partitions::PartitionsModal, playback_settings::PlaybackSettingsModal,
+    select_modal::SelectModal, song_info::SongInfoModal,
+};
 use panes::{PaneContainer, Panes};
 #[cfg(debug_assertions)]
 use ratatui::style::Stylize;
@@ -24,7 +33,7 @@ use ratatui::{
     style::{Color, Style},
     symbols::border,
     text::Text,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame, Terminal,
 };
 use tab_screen::TabScreen;
@@ -35,29 +44,46 @@ use crate::{
         cli::Args,
         keys::{CommonAction, GlobalAction},
         tabs::TabName,
-        Config,
+        Config, DoubleEscapeAction,
     },
     mpd::{
-        commands::{idle::IdleEvent, State},
+        commands::{idle::IdleEvent, status::OnOffOneshot, volume::Bound, State},
         mpd_client::{FilterKind, MpdClient, ValueChange},
     },
     shared::{
+        add_feedback::report_added,
+        double_press::DoublePress,
+        ext::{mpd_client::MpdClientExt, rect::ShrinkExt},
+        fuzzy,
         key_event::KeyEvent,
         macros::{modal, status_error, status_info, status_warn},
         mouse_event::{MouseEvent, MouseEventKind},
+        mute::MuteState,
+        play_history::{HistoryEntry, PlayHistory},
+        preview_play::PreviewPlay,
+        queue_repeat::QueueRepeat,
+        random::Rng,
+        seek_acceleration::{SeekAccelerator, SeekDirection},
     },
     MpdQueryResult,
 };
 use crate::{context::AppContext, mpd::version::Version};
 
-use self::{modals::Modal, panes::Pane, widgets::header::Header};
+use self::{
+    dirstack::{DirState, ScrollingState},
+    modals::Modal,
+    panes::Pane,
+    widgets::{header::Header, screensaver::Screensaver},
+};
 
 pub mod browser;
 pub mod dirstack;
 pub mod image;
+pub mod key_hints;
 pub mod modals;
 pub mod panes;
 pub mod tab_screen;
+pub mod theme_preview;
 pub mod widgets;
 
 #[derive(Debug)]
@@ -83,15 +109,31 @@ pub struct Ui<'ui> {
     modals: Vec<Box<dyn Modal>>,
     status_message: Option<StatusMessage>,
     rendered_frames_count: u32,
+    marquee_frame: usize,
+    screensaver_active: bool,
+    /// No input was received for the screensaver timeout, the screensaver is shown once playback is not active
+    user_idle: bool,
     command: Option<String>,
     active_tab: TabName,
     tabs: HashMap<TabName, TabScreen>,
     areas: EnumMap<Areas, Rect>,
     tab_bar: AppTabs<'ui>,
+    seek_accelerator: SeekAccelerator,
+    mute: MuteState,
+    preview_play: PreviewPlay,
+    history: PlayHistory,
+    queue_repeat: QueueRepeat,
+    double_escape: DoublePress,
 }
 
 const OPEN_DECODERS_MODAL: &str = "open_decoders_modal";
+const DB_UPDATE_SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 const OPEN_OUTPUTS_MODAL: &str = "open_outputs_modal";
+const OPEN_PARTITIONS_MODAL: &str = "open_partitions_modal";
+pub(in crate::ui) const ADD_TO_PLAYLIST: &str = "add_to_playlist";
+pub(in crate::ui) const CONFIRM_QUEUE_SIZE: &str = "confirm_queue_size";
+const SHOW_FAVORITES: &str = "show_favorites";
+const REPEAT_QUEUE: &str = "repeat_queue";
 
 macro_rules! screen_call {
     ($self:ident, $fn:ident($($param:expr),+)) => {
@@ -105,6 +147,7 @@ macro_rules! screen_call {
 #[derive(Debug, Enum)]
 enum Areas {
     Header,
+    HeaderBar,
     Tabs,
     Content,
     Bar,
@@ -112,14 +155,26 @@ enum Areas {
 
 impl<'ui> Ui<'ui> {
     pub fn new(context: &AppContext) -> Result<Ui<'ui>> {
-        let active_tab = *context.config.tabs.names.first().context("Expected at least one tab")?;
+        let active_tab = match context.config.default_tab {
+            Some(tab) => tab,
+            None => *context.config.tabs.names.first().context("Expected at least one tab")?,
+        };
         Ok(Self {
             panes: PaneContainer::new(context),
-            tab_bar: AppTabs::new(active_tab, context.config),
+            tab_bar: AppTabs::new(active_tab, context.config, context.draw_borders),
             status_message: None,
             rendered_frames_count: 0,
+            marquee_frame: 0,
+            screensaver_active: false,
+            user_idle: false,
             modals: Vec::default(),
             command: None,
+            seek_accelerator: SeekAccelerator::default(),
+            mute: MuteState::default(),
+            preview_play: PreviewPlay::default(),
+            queue_repeat: QueueRepeat::default(),
+            double_escape: DoublePress::default(),
+            history: PlayHistory::new(context.config.history_size),
             active_tab,
             tabs: context
                 .config
@@ -135,21 +190,31 @@ impl<'ui> Ui<'ui> {
     }
 
     fn calc_areas(&mut self, area: Rect, context: &AppContext) -> Result<()> {
-        let tab_area_height = match (context.config.theme.tab_bar.enabled, context.config.theme.draw_borders) {
+        let tab_area_height = match (context.config.theme.tab_bar.enabled, context.draw_borders) {
             (true, true) => 3,
             (true, false) => 1,
             (false, _) => 0,
         };
 
+        let header_rows = u16::try_from(context.config.theme.header.rows.len())?;
+        let header_bar_height = u16::from(context.config.theme.progress_bar.position.in_header());
+
         let [header_area, tabs_area, content_area, bar_area] = Layout::vertical([
-            Constraint::Length(u16::try_from(context.config.theme.header.rows.len())?),
+            Constraint::Length(header_rows + header_bar_height),
             Constraint::Length(tab_area_height), // Tab bar
             Constraint::Percentage(100),
             Constraint::Min(1),
         ])
+        .spacing(context.config.theme.section_gap)
         .areas(area);
+        let padding = context.config.theme.content_padding;
+        let content_area = content_area.pad(padding.horizontal, padding.vertical);
+        let [header_area, header_bar_area] =
+            Layout::vertical([Constraint::Length(header_rows), Constraint::Length(header_bar_height)])
+                .areas(header_area);
 
         self.areas[Areas::Header] = header_area;
+        self.areas[Areas::HeaderBar] = header_bar_area;
         self.areas[Areas::Tabs] = tabs_area;
         self.areas[Areas::Content] = content_area;
         self.areas[Areas::Bar] = bar_area;
@@ -157,6 +222,47 @@ impl<'ui> Ui<'ui> {
         Ok(())
     }
 
+    /// The layout depends on the borders so it is recalculated right away
+    fn toggle_borders(&mut self, context: &mut AppContext) -> Result<()> {
+        context.draw_borders = !context.draw_borders;
+
+        self.tab_bar = AppTabs::new(self.active_tab, context.config, context.draw_borders);
+        let area = self.areas[Areas::Header].union(self.areas[Areas::Bar]);
+        self.resize(area, context)?;
+        context.render()?;
+
+        Ok(())
+    }
+
+    /// Seeks to `percent` of the current song. Does nothing while the duration is unknown, for example for streams.
+    fn seek_to_percent(&self, percent: f32, context: &AppContext) {
+        let Some(position) = position_at_percent(context.status.duration, percent) else {
+            return;
+        };
+        context.command(move |client| {
+            client.seek_current(ValueChange::Set(position))?;
+            Ok(())
+        });
+    }
+
+    /// The bottom bar only shows the progress bar when it is positioned there and no status line is configured
+    fn is_on_progress_bar(&self, event: MouseEvent, context: &AppContext) -> bool {
+        self.areas[Areas::HeaderBar].contains(event.into())
+            || (context.config.theme.progress_bar.position.in_bottom()
+                && context.config.theme.status_line.is_none()
+                && self.areas[Areas::Bar].contains(event.into()))
+    }
+
+    fn render_progress_bar(frame: &mut Frame, area: Rect, context: &AppContext) {
+        let elapsed_bar = context.config.as_styled_progress_bar();
+        let elapsed_bar = if context.status.duration == Duration::ZERO {
+            elapsed_bar.value(0.0)
+        } else {
+            elapsed_bar.value(context.status.elapsed.as_secs_f32() / context.status.duration.as_secs_f32())
+        };
+        frame.render_widget(elapsed_bar, area);
+    }
+
     fn change_tab(&mut self, new_tab: TabName, context: &AppContext) -> Result<()> {
         screen_call!(self, on_hide(&context))?;
         self.active_tab = new_tab;
@@ -171,6 +277,11 @@ impl<'ui> Ui<'ui> {
             frame.render_widget(Block::default().style(Style::default().bg(bg_color)), frame.area());
         }
         self.rendered_frames_count.add_assign(1);
+
+        if self.screensaver_active {
+            frame.render_widget(Screensaver::new(context), frame.area());
+            return Ok(());
+        }
         if self
             .status_message
             .as_ref()
@@ -179,14 +290,21 @@ impl<'ui> Ui<'ui> {
             self.status_message = None;
         }
 
-        let header = Header::new(context);
+        self.marquee_frame = self.marquee_frame.wrapping_add(1);
+        let header = Header::new(context).marquee_frame(self.marquee_frame);
         frame.render_widget(header, self.areas[Areas::Header]);
 
+        // Stays visible even when the bottom bar is taken by a status message
+        if self.areas[Areas::HeaderBar].height > 0 && context.config.status_update_interval_ms.is_some() {
+            Self::render_progress_bar(frame, self.areas[Areas::HeaderBar], context);
+        }
+
         if self.areas[Areas::Tabs].height > 0 {
             self.tab_bar.set_selected(self.active_tab);
             self.tab_bar.render(self.areas[Areas::Tabs], frame.buffer_mut());
         }
 
+        let key_hints = screen_call!(self, key_hints(context));
         if let Some(command) = &self.command {
             let [leader_area, command_area] =
                 *Layout::horizontal([Constraint::Length(1), Constraint::Percentage(100)]).split(self.areas[Areas::Bar])
@@ -205,14 +323,26 @@ impl<'ui> Ui<'ui> {
                 .alignment(ratatui::prelude::Alignment::Center)
                 .style(Style::default().fg(level.into()).bg(Color::Black));
             frame.render_widget(status_bar, self.areas[Areas::Bar]);
-        } else if context.config.status_update_interval_ms.is_some() {
-            let elapsed_bar = context.config.as_styled_progress_bar();
-            let elapsed_bar = if context.status.duration == Duration::ZERO {
-                elapsed_bar.value(0.0)
-            } else {
-                elapsed_bar.value(context.status.elapsed.as_secs_f32() / context.status.duration.as_secs_f32())
-            };
-            frame.render_widget(elapsed_bar, self.areas[Areas::Bar]);
+        } else if let Some(hints) = key_hints.filter(|hints| !hints.is_empty()) {
+            let line = key_hints::to_line(
+                &hints,
+                context.config.as_focused_border_style(),
+                context.config.as_text_style(),
+            );
+            frame.render_widget(
+                Paragraph::new(line).alignment(ratatui::prelude::Alignment::Center),
+                self.areas[Areas::Bar],
+            );
+        } else if let Some(status_line) = &context.config.theme.status_line {
+            Header::new(context).marquee_frame(self.marquee_frame).render_row(
+                status_line,
+                self.areas[Areas::Bar],
+                frame.buffer_mut(),
+            );
+        } else if context.config.status_update_interval_ms.is_some()
+            && context.config.theme.progress_bar.position.in_bottom()
+        {
+            Self::render_progress_bar(frame, self.areas[Areas::Bar], context);
         }
 
         #[cfg(debug_assertions)]
@@ -231,6 +361,19 @@ impl<'ui> Ui<'ui> {
 
         screen_call!(self, render(frame, self.areas[Areas::Content], context))?;
 
+        if context.status.updating_db.is_some() {
+            self.render_db_update_indicator(frame, context);
+        }
+        if let Some(remaining) = self.queue_repeat.remaining() {
+            let times = if remaining == 1 { "time" } else { "times" };
+            self.render_corner_label(
+                frame,
+                context,
+                &format!(" Queue repeats {remaining} more {times} "),
+                false,
+            );
+        }
+
         for modal in &mut self.modals {
             modal.render(frame, context)?;
         }
@@ -238,7 +381,90 @@ impl<'ui> Ui<'ui> {
         Ok(())
     }
 
-    pub fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+    /// Small spinner in the bottom right corner of the content area while MPD updates its database. The rest of the
+    /// UI stays usable.
+    fn render_db_update_indicator(&self, frame: &mut Frame, context: &AppContext) {
+        let spinner_idx = usize::try_from(self.rendered_frames_count).unwrap_or_default();
+        let spinner = DB_UPDATE_SPINNER[spinner_idx % DB_UPDATE_SPINNER.len()];
+        self.render_corner_label(frame, context, &format!(" {spinner} Updating database "), true);
+    }
+
+    /// Draws a short label over the bottom left or bottom right corner of the content area
+    fn render_corner_label(&self, frame: &mut Frame, context: &AppContext, text: &str, right: bool) {
+        let content_area = self.areas[Areas::Content];
+        let width = u16::try_from(text.chars().count())
+            .unwrap_or(u16::MAX)
+            .min(content_area.width);
+        let x = if right {
+            content_area.right().saturating_sub(width)
+        } else {
+            content_area.x
+        };
+        let area = Rect::new(
+            x,
+            content_area.bottom().saturating_sub(1),
+            width,
+            1.min(content_area.height),
+        );
+
+        frame.render_widget(Clear, area);
+        frame.render_widget(
+            Paragraph::new(text.to_owned())
+                .style(context.config.as_text_style().patch(context.config.theme.borders_style)),
+            area,
+        );
+    }
+
+    /// Shows the screensaver unless something is playing, in which case it is shown once the playback stops or
+    /// pauses. Panes are hidden so that images do not stay on the screen.
+    pub fn show_screensaver(&mut self, context: &mut AppContext) -> Result<()> {
+        self.user_idle = true;
+        if self.screensaver_active || context.status.state == State::Play {
+            return Ok(());
+        }
+
+        self.screensaver_active = true;
+        screen_call!(self, on_hide(context))?;
+        context.render()?;
+
+        Ok(())
+    }
+
+    /// Shows the screensaver when the user went idle while something was playing
+    pub fn on_player_state_changed(&mut self, context: &mut AppContext) -> Result<()> {
+        if self.user_idle {
+            self.show_screensaver(context)?;
+        }
+        Ok(())
+    }
+
+    /// Hides the screensaver if it is active. Returns true if the screensaver was active.
+    fn hide_screensaver(&mut self, context: &mut AppContext) -> Result<bool> {
+        self.user_idle = false;
+        if !self.screensaver_active {
+            return Ok(false);
+        }
+
+        self.screensaver_active = false;
+        screen_call!(self, before_show(self.areas[Areas::Content], context))?;
+        context.render()?;
+
+        Ok(true)
+    }
+
+    pub fn handle_mouse_event(&mut self, mut event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        if self.hide_screensaver(context)? {
+            return Ok(());
+        }
+
+        if context.config.reverse_scroll {
+            event.kind = match event.kind {
+                MouseEventKind::ScrollUp => MouseEventKind::ScrollDown,
+                MouseEventKind::ScrollDown => MouseEventKind::ScrollUp,
+                kind => kind,
+            };
+        }
+
         if let Some(ref mut modal) = self.modals.last_mut() {
             modal.handle_mouse_event(event, context)?;
             return Ok(());
@@ -263,7 +489,7 @@ impl<'ui> Ui<'ui> {
                     Ok(())
                 });
             }
-            MouseEventKind::LeftClick if self.areas[Areas::Bar].contains(event.into()) => {
+            MouseEventKind::LeftClick if self.is_on_progress_bar(event, context) => {
                 if !matches!(context.status.state, State::Play | State::Pause) {
                     return Ok(());
                 }
@@ -289,18 +515,17 @@ impl<'ui> Ui<'ui> {
                 context.render()?;
             }
             MouseEventKind::LeftClick if self.areas[Areas::Tabs].contains(event.into()) => {
-                if let Some(tab_name) = self
-                    .tab_bar
-                    .get_tab_idx_at(event.into())
-                    .and_then(|idx| context.config.tabs.names.get(idx))
-                {
-                    if &self.active_tab != tab_name {
-                        self.change_tab(*tab_name, context)?;
+                if let Some(tab_name) = self.tab_bar.get_tab_at(event.into()) {
+                    if self.active_tab != tab_name {
+                        self.change_tab(tab_name, context)?;
                         context.render()?;
                         return Ok(());
                     }
                 }
             }
+            MouseEventKind::Move => {
+                screen_call!(self, handle_mouse_event(event, context))?;
+            }
             _ if self.areas[Areas::Content].contains(event.into()) => {
                 screen_call!(self, handle_mouse_event(event, context))?;
             }
@@ -310,6 +535,10 @@ impl<'ui> Ui<'ui> {
     }
 
     pub fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<KeyHandleResult> {
+        if self.hide_screensaver(context)? {
+            return Ok(KeyHandleResult::None);
+        }
+
         if let Some(ref mut command) = self.command {
             let action = key.as_common_action(context);
             if let Some(CommonAction::Close) = action {
@@ -357,7 +586,36 @@ impl<'ui> Ui<'ui> {
             return Ok(KeyHandleResult::None);
         }
 
+        let is_close = key.is_common_action(CommonAction::Close, context);
         screen_call!(self, handle_action(key, context))?;
+        if self.preview_play.is_active() {
+            if let Some(song) = screen_call!(self, selected_song(context)) {
+                self.preview_play.on_selected(&song);
+            }
+        }
+
+        if is_close && self.double_escape.press(Instant::now()) {
+            match context.config.double_escape_action {
+                DoubleEscapeAction::None => {}
+                DoubleEscapeAction::GoBack => {
+                    screen_call!(self, go_back(context))?;
+                }
+                DoubleEscapeAction::GoToQueue => {
+                    let Some(tab_name) = tab_with_pane(context, PaneType::Queue) else {
+                        status_error!("No tab with the queue pane is configured");
+                        return Ok(KeyHandleResult::None);
+                    };
+                    if self.active_tab != tab_name {
+                        self.change_tab(tab_name, context)?;
+                    }
+                    if let Some(tab) = self.tabs.get_mut(&tab_name) {
+                        tab.focus_pane(PaneType::Queue);
+                    }
+                    context.render()?;
+                }
+            }
+            return Ok(KeyHandleResult::None);
+        }
 
         if let Some(action) = key.as_global_action(context) {
             match action {
@@ -377,8 +635,9 @@ impl<'ui> Ui<'ui> {
                     context.render()?;
                 }
                 GlobalAction::NextTrack if context.status.state == State::Play => {
+                    let wrap = context.config.wrap_next;
                     context.command(move |client| {
-                        client.next()?;
+                        client.next_or_wrap(wrap)?;
                         Ok(())
                     });
                 }
@@ -394,6 +653,22 @@ impl<'ui> Ui<'ui> {
                         Ok(())
                     });
                 }
+                GlobalAction::StopAndClear if !context.config.confirm_destructive_actions => {
+                    context.command(|client| Ok(client.stop_and_clear()?));
+                }
+                GlobalAction::StopAndClear => {
+                    modal!(
+                        context,
+                        ConfirmModal::new(context)
+                            .message("Are you sure you want to stop playback and clear the queue? This action cannot be undone.")
+                            .on_confirm(|context| {
+                                context.command(|client| Ok(client.stop_and_clear()?));
+                                Ok(())
+                            })
+                            .confirm_label("Clear")
+                            .size(45, 6)
+                    );
+                }
                 GlobalAction::ToggleRepeat => {
                     let repeat = !context.status.repeat;
                     context.command(move |client| {
@@ -401,6 +676,14 @@ impl<'ui> Ui<'ui> {
                         Ok(())
                     });
                 }
+                GlobalAction::ToggleRepeatOne => {
+                    let enable = !context.status.is_repeat_one();
+                    context.command(move |client| {
+                        client.repeat(enable)?;
+                        client.single(if enable { OnOffOneshot::On } else { OnOffOneshot::Off })?;
+                        Ok(())
+                    });
+                }
                 GlobalAction::ToggleRandom => {
                     let random = !context.status.random;
                     context.command(move |client| {
@@ -430,6 +713,14 @@ impl<'ui> Ui<'ui> {
                         Ok(())
                     });
                 }
+                GlobalAction::CycleReplayGainMode => {
+                    let mode = context.status.replay_gain_mode.unwrap_or_default().cycle();
+                    context.command(move |client| {
+                        client.replay_gain_mode(mode)?;
+                        status_info!("Replay gain mode set to {mode}");
+                        Ok(())
+                    });
+                }
                 GlobalAction::TogglePause if matches!(context.status.state, State::Play | State::Pause) => context
                     .command(move |client| {
                         client.pause_toggle()?;
@@ -450,16 +741,102 @@ impl<'ui> Ui<'ui> {
                         Ok(())
                     });
                 }
-                GlobalAction::SeekForward if matches!(context.status.state, State::Play | State::Pause) => context
-                    .command(move |client| {
-                        client.seek_current(ValueChange::Increase(5))?;
+                GlobalAction::ToggleMute => {
+                    let volume = self.mute.toggle(*context.status.volume.value());
+                    if self.mute.is_muted() {
+                        status_info!("Muted");
+                    } else {
+                        status_info!("Unmuted");
+                    }
+                    context.command(move |client| {
+                        client.volume(ValueChange::Set(volume.into()))?;
                         Ok(())
-                    }),
-                GlobalAction::SeekBack if matches!(context.status.state, State::Play | State::Pause) => context
-                    .command(move |client| {
-                        client.seek_current(ValueChange::Decrease(5))?;
+                    });
+                }
+                GlobalAction::TogglePreviewPlay => self.preview_play.toggle(context),
+                GlobalAction::PlayRandomSong => {
+                    let replace = context.config.random_song_replaces_queue;
+                    context.command(move |client| {
+                        if let Some(file) = client.play_random_song(&mut Rng::from_entropy(), replace)? {
+                            status_info!("Playing random song '{file}'");
+                        } else {
+                            status_warn!("No songs found in the music database");
+                        }
                         Ok(())
-                    }),
+                    });
+                }
+                GlobalAction::SetVolume => {
+                    modal!(
+                        context,
+                        InputModal::new(context)
+                            .title("Set volume")
+                            .confirm_label("Set")
+                            .input_label("Volume (0-100):")
+                            .initial_value(context.status.volume.value().to_string())
+                            .on_confirm(|context, value| {
+                                match value.trim().parse::<u8>() {
+                                    Ok(volume) if volume <= 100 => {
+                                        context.command(move |client| {
+                                            client.volume(ValueChange::Set(volume.into()))?;
+                                            Ok(())
+                                        });
+                                    }
+                                    _ => status_error!("Invalid volume '{}', expected a number from 0 to 100", value),
+                                }
+                                Ok(())
+                            })
+                    );
+                }
+                GlobalAction::RepeatQueue => {
+                    modal!(
+                        context,
+                        InputModal::new(context)
+                            .title("Repeat queue")
+                            .confirm_label("Repeat")
+                            .input_label("Number of times to play the queue:")
+                            .on_confirm(|context, value| {
+                                match value.trim().parse::<u32>() {
+                                    Ok(times) if times > 0 => {
+                                        context.query().id(REPEAT_QUEUE).replace_id(REPEAT_QUEUE).query(
+                                            move |client| {
+                                                client.repeat(times > 1)?;
+                                                Ok(MpdQueryResult::RepeatQueue { times })
+                                            },
+                                        );
+                                    }
+                                    _ => status_error!("Invalid number '{}', expected a positive number", value),
+                                }
+                                Ok(())
+                            })
+                    );
+                }
+                GlobalAction::SeekForward if matches!(context.status.state, State::Play | State::Pause) => {
+                    let step = self.seek_accelerator.next_step(
+                        SeekDirection::Forward,
+                        Instant::now(),
+                        context.config.seek_step_secs,
+                        &context.config.seek_acceleration,
+                    );
+                    context.command(move |client| {
+                        client.seek_current(ValueChange::Increase(step))?;
+                        Ok(())
+                    });
+                }
+                GlobalAction::SeekBack if matches!(context.status.state, State::Play | State::Pause) => {
+                    let step = self.seek_accelerator.next_step(
+                        SeekDirection::Back,
+                        Instant::now(),
+                        context.config.seek_step_secs,
+                        &context.config.seek_acceleration,
+                    );
+                    context.command(move |client| {
+                        client.seek_current(ValueChange::Decrease(step))?;
+                        Ok(())
+                    });
+                }
+                GlobalAction::SeekToPercent(percent) if matches!(context.status.state, State::Play | State::Pause) => {
+                    self.seek_to_percent(f32::from(percent), context);
+                }
                 GlobalAction::NextTab => {
                     self.change_tab(context.config.next_screen(self.active_tab), context)?;
                     context.render()?;
@@ -481,6 +858,7 @@ impl<'ui> Ui<'ui> {
                 GlobalAction::Stop => {}
                 GlobalAction::SeekBack => {}
                 GlobalAction::SeekForward => {}
+                GlobalAction::SeekToPercent(_) => {}
                 GlobalAction::ExternalCommand { command, .. } => {
                     run_external(command, create_env(context, std::iter::empty::<&str>()));
                 }
@@ -496,6 +874,13 @@ impl<'ui> Ui<'ui> {
                         .replace_id(OPEN_OUTPUTS_MODAL)
                         .query(|client| Ok(MpdQueryResult::Outputs(client.outputs()?.0)));
                 }
+                GlobalAction::ShowPartitions => {
+                    context
+                        .query()
+                        .id(OPEN_PARTITIONS_MODAL)
+                        .replace_id(OPEN_PARTITIONS_MODAL)
+                        .query(|client| Ok(MpdQueryResult::Partitions(client.list_partitions()?.0)));
+                }
                 GlobalAction::ShowDecoders => {
                     context
                         .query()
@@ -503,9 +888,78 @@ impl<'ui> Ui<'ui> {
                         .replace_id(OPEN_DECODERS_MODAL)
                         .query(|client| Ok(MpdQueryResult::Decoders(client.decoders()?.0)));
                 }
+                GlobalAction::ShowPlaybackSettings => {
+                    modal!(context, PlaybackSettingsModal::new());
+                }
+                GlobalAction::ShowHistory => {
+                    let entries = self.history.recent();
+                    if entries.is_empty() {
+                        status_info!("No songs have been played yet");
+                        return Ok(KeyHandleResult::None);
+                    }
+                    let feedback = context.config.add_feedback;
+                    let autoplay = context.config.autoplay_on_add;
+                    modal!(
+                        context,
+                        SelectModal::new(context)
+                            .options(entries)
+                            .confirm_label("Add")
+                            .title("Recently played")
+                            .on_confirm(move |context, entry: &HistoryEntry, _idx| {
+                                let entry = entry.clone();
+                                context.command(move |client| {
+                                    client.add_and_autoplay(autoplay, |client| entry.add_to_queue(client))?;
+                                    report_added(feedback, &format!("'{entry}'"), 1);
+                                    Ok(())
+                                });
+                                Ok(())
+                            })
+                    );
+                }
+                GlobalAction::ShowFavorites => {
+                    if !context.supported_commands.contains("sticker") {
+                        status_warn!("Favorites require the sticker database to be enabled in MPD");
+                        return Ok(KeyHandleResult::None);
+                    }
+                    context
+                        .query()
+                        .id(SHOW_FAVORITES)
+                        .replace_id(SHOW_FAVORITES)
+                        .query(|client| {
+                            Ok(MpdQueryResult::SongsList {
+                                data: client.favorite_songs()?,
+                                origin_path: None,
+                            })
+                        });
+                }
+                GlobalAction::ToggleFavorite => {
+                    let song = self
+                        .tabs
+                        .get(&self.active_tab)
+                        .and_then(|tab| tab.selected_song(&mut self.panes, context))
+                        .or_else(|| context.find_current_song_in_queue().map(|(_, song)| song.clone()));
+                    if let Some(song) = song {
+                        toggle_favorite(&song, context);
+                    } else {
+                        status_info!("No song is selected or playing");
+                    }
+                }
+                GlobalAction::ToggleBorders => {
+                    self.toggle_borders(context)?;
+                }
+                GlobalAction::CycleImageMethod => {
+                    self.panes.album_art.cycle_image_method(context)?;
+                }
+                GlobalAction::ReloadAlbumArt => {
+                    let is_visible = self
+                        .tabs
+                        .get(&self.active_tab)
+                        .is_some_and(|tab| tab.panes.panes_iter().any(|pane| pane.pane == PaneType::AlbumArt));
+                    self.panes.album_art.reload_album_art(is_visible, context)?;
+                }
                 GlobalAction::ShowCurrentSongInfo => {
                     if let Some((_, current_song)) = context.find_current_song_in_queue() {
-                        modal!(context, SongInfoModal::new(current_song.clone()));
+                        modal!(context, SongInfoModal::new(current_song.clone(), context));
                     } else {
                         status_info!("No song is currently playing");
                     }
@@ -541,6 +995,21 @@ impl<'ui> Ui<'ui> {
                 self.on_event(UiEvent::ModalClosed, context)?;
                 context.render()?;
             }
+            UiAppEvent::GoToAlbum(album) => {
+                let Some(tab_name) = tab_with_pane(context, PaneType::Albums) else {
+                    status_error!("No tab with the albums pane is configured");
+                    return Ok(());
+                };
+
+                if self.active_tab != tab_name {
+                    self.change_tab(tab_name, context)?;
+                }
+                if let Some(tab) = self.tabs.get_mut(&tab_name) {
+                    tab.focus_pane(PaneType::Albums);
+                }
+                self.panes.albums.go_to_album(album, context)?;
+                context.render()?;
+            }
         }
         Ok(())
     }
@@ -551,6 +1020,11 @@ impl<'ui> Ui<'ui> {
         screen_call!(self, resize(self.areas[Areas::Content], context))
     }
 
+    /// Must be called whenever a new volume was received from MPD
+    pub fn on_volume_changed(&mut self, context: &AppContext) {
+        self.mute.on_volume_changed(*context.status.volume.value());
+    }
+
     pub fn on_event(&mut self, mut event: UiEvent, context: &mut AppContext) -> Result<()> {
         let contains_pane = |p| {
             self.tabs
@@ -564,6 +1038,7 @@ impl<'ui> Ui<'ui> {
                 status_warn!("The music database has been updated. Some parts of the UI may have been reinitialized to prevent inconsistent behaviours.");
             }
             UiEvent::StoredPlaylist => {}
+            UiEvent::Sticker => {}
             UiEvent::LogAdded(_) =>
             {
                 #[cfg(debug_assertions)]
@@ -575,7 +1050,19 @@ impl<'ui> Ui<'ui> {
             UiEvent::ModalClosed => {}
             UiEvent::Exit => {}
             UiEvent::LyricsIndexed => {}
-            UiEvent::SongChanged => {}
+            UiEvent::SongChanged => {
+                self.marquee_frame = 0;
+                self.history.on_song_change(context);
+                if self.queue_repeat.on_song_change(
+                    context.status.song,
+                    context.status.playlistlength,
+                    context.status.repeat,
+                    context.status.random,
+                ) {
+                    context.command(|client| Ok(client.repeat(false)?));
+                    status_info!("Playing the queue for the last time, repeat turned off");
+                }
+            }
             UiEvent::Reconnected => {}
         }
 
@@ -588,6 +1075,7 @@ impl<'ui> Ui<'ui> {
                 Panes::Albums(p) => p.on_event(&mut event, contains_pane(PaneType::Albums), context),
                 Panes::Artists(p) => p.on_event(&mut event, contains_pane(PaneType::Artists), context),
                 Panes::Playlists(p) => p.on_event(&mut event, contains_pane(PaneType::Playlists), context),
+                Panes::RecentlyAdded(p) => p.on_event(&mut event, contains_pane(PaneType::RecentlyAdded), context),
                 Panes::Search(p) => p.on_event(&mut event, contains_pane(PaneType::Search), context),
                 Panes::AlbumArtists(p) => p.on_event(&mut event, contains_pane(PaneType::AlbumArtists), context),
                 Panes::AlbumArt(p) => p.on_event(&mut event, contains_pane(PaneType::AlbumArt), context),
@@ -614,6 +1102,7 @@ impl<'ui> Ui<'ui> {
                 Panes::Albums(p) => p.on_query_finished(id, data, context),
                 Panes::Artists(p) => p.on_query_finished(id, data, context),
                 Panes::Playlists(p) => p.on_query_finished(id, data, context),
+                Panes::RecentlyAdded(p) => p.on_query_finished(id, data, context),
                 Panes::Search(p) => p.on_query_finished(id, data, context),
                 Panes::AlbumArtists(p) => p.on_query_finished(id, data, context),
                 Panes::AlbumArt(p) => p.on_query_finished(id, data, context),
@@ -623,9 +1112,79 @@ impl<'ui> Ui<'ui> {
                 (OPEN_OUTPUTS_MODAL, MpdQueryResult::Outputs(outputs)) => {
                     modal!(context, OutputsModal::new(outputs));
                 }
+                (OPEN_PARTITIONS_MODAL, MpdQueryResult::Partitions(partitions)) => {
+                    modal!(context, PartitionsModal::new(partitions));
+                }
                 (OPEN_DECODERS_MODAL, MpdQueryResult::Decoders(decoders)) => {
                     modal!(context, DecodersModal::new(decoders));
                 }
+                (ADD_TO_PLAYLIST, MpdQueryResult::AddToPlaylist { playlists, song_file }) => {
+                    modal!(
+                        context,
+                        SelectModal::new(context)
+                            .options(playlists)
+                            .confirm_label("Add")
+                            .title("Select a playlist")
+                            .on_confirm(move |context, selected: &String, _idx| {
+                                let selected = selected.to_owned();
+                                let song_file = song_file.clone();
+                                context.command(move |client| {
+                                    client.add_to_playlist(&selected, &song_file, None)?;
+                                    status_info!("Song added to playlist {}", selected);
+                                    Ok(())
+                                });
+                                Ok(())
+                            })
+                    );
+                }
+                (SHOW_FAVORITES, MpdQueryResult::SongsList { data, .. }) => {
+                    if data.is_empty() {
+                        status_info!("No songs have been marked as favorite yet");
+                        return Ok(());
+                    }
+                    let feedback = context.config.add_feedback;
+                    let autoplay = context.config.autoplay_on_add;
+                    modal!(
+                        context,
+                        SelectModal::new(context)
+                            .options(data.iter().map(HistoryEntry::from).collect_vec())
+                            .confirm_label("Add")
+                            .title("Favorites")
+                            .on_confirm(move |context, entry: &HistoryEntry, _idx| {
+                                let entry = entry.clone();
+                                context.command(move |client| {
+                                    client.add_and_autoplay(autoplay, |client| entry.add_to_queue(client))?;
+                                    report_added(feedback, &format!("'{entry}'"), 1);
+                                    Ok(())
+                                });
+                                Ok(())
+                            })
+                    );
+                }
+                (CONFIRM_QUEUE_SIZE, MpdQueryResult::ConfirmQueueSize(add)) => {
+                    modal!(
+                        context,
+                        ConfirmModal::new(context)
+                            .message("Adding these songs will grow the queue past the configured size. Do you want to add them anyway?")
+                            .on_confirm(move |context| {
+                                if let Some(add) = add.lock().ok().and_then(|mut add| add.take()) {
+                                    context.command(add.callback);
+                                }
+                                Ok(())
+                            })
+                            .confirm_label("Add")
+                            .size(45, 7)
+                    );
+                }
+                (REPEAT_QUEUE, MpdQueryResult::RepeatQueue { times }) => {
+                    self.queue_repeat.start(times, context.status.song);
+                    if times > 1 {
+                        status_info!("Playing the queue {times} times");
+                    } else {
+                        status_info!("Playing the queue once");
+                    }
+                    context.render()?;
+                }
                 (id, mut data) => {
                     // TODO a proper modal target
                     for modal in &mut self.modals {
@@ -639,6 +1198,27 @@ impl<'ui> Ui<'ui> {
     }
 }
 
+/// Position in whole seconds at `percent` of the `duration`, `None` if the duration is zero
+fn position_at_percent(duration: Duration, percent: f32) -> Option<u32> {
+    if duration.is_zero() {
+        return None;
+    }
+    let position = duration.mul_f32(percent.clamp(0.0, 100.0) / 100.0);
+    Some(u32::try_from(position.as_secs()).unwrap_or(u32::MAX))
+}
+
+/// First configured tab which contains the given pane
+fn tab_with_pane(context: &AppContext, pane_type: PaneType) -> Option<TabName> {
+    context.config.tabs.names.iter().copied().find(|name| {
+        context
+            .config
+            .tabs
+            .tabs
+            .get(name)
+            .is_some_and(|tab| tab.panes.panes_iter().any(|pane| pane.pane == pane_type))
+    })
+}
+
 #[derive(Debug)]
 pub struct ModalWrapper(Box<dyn Modal + Send + Sync>);
 
@@ -646,6 +1226,7 @@ pub struct ModalWrapper(Box<dyn Modal + Send + Sync>);
 pub enum UiAppEvent {
     Modal(ModalWrapper),
     PopModal,
+    GoToAlbum(String),
 }
 
 #[derive(Debug, Hash, Eq, PartialEq)]
@@ -654,6 +1235,7 @@ pub enum UiEvent {
     Player,
     Database,
     StoredPlaylist,
+    Sticker,
     LogAdded(Vec<u8>),
     ModalOpened,
     ModalClosed,
@@ -671,6 +1253,7 @@ impl TryFrom<IdleEvent> for UiEvent {
             IdleEvent::Player => UiEvent::Player,
             IdleEvent::Database => UiEvent::Database,
             IdleEvent::StoredPlaylist => UiEvent::StoredPlaylist,
+            IdleEvent::Sticker => UiEvent::Sticker,
             _ => return Err(()),
         })
     }
@@ -685,6 +1268,41 @@ pub fn restore_terminal<B: Backend + std::io::Write>(terminal: &mut Terminal<B>,
     Ok(terminal.show_cursor()?)
 }
 
+/// Puts the terminal back into its original state without needing access to the [`Terminal`] instance
+pub fn reset_terminal(enable_mouse: bool) -> Result<()> {
+    if enable_mouse {
+        execute!(std::io::stdout(), DisableMouseCapture)?;
+    }
+    disable_raw_mode()?;
+    execute!(std::io::stdout(), LeaveAlternateScreen, crossterm::cursor::Show)?;
+    Ok(())
+}
+
+/// Runs the restore callback when dropped unless it was disarmed first. Ensures that the terminal does not stay
+/// in raw mode and alternate screen when rmpc exits early because of an error.
+pub struct TerminalGuard<F: FnOnce()> {
+    restore: Option<F>,
+}
+
+impl<F: FnOnce()> TerminalGuard<F> {
+    pub fn new(restore: F) -> Self {
+        Self { restore: Some(restore) }
+    }
+
+    /// The terminal is going to be restored by other means, the guard will do nothing on drop
+    pub fn disarm(mut self) {
+        self.restore = None;
+    }
+}
+
+impl<F: FnOnce()> Drop for TerminalGuard<F> {
+    fn drop(&mut self) {
+        if let Some(restore) = self.restore.take() {
+            restore();
+        }
+    }
+}
+
 pub fn setup_terminal(enable_mouse: bool) -> Result<Terminal<CrosstermBackend<Stdout>>> {
     let mut stdout = std::io::stdout();
     enable_raw_mode()?;
@@ -721,6 +1339,7 @@ impl From<&FilterKind> for &'static str {
             FilterKind::Contains => "Contains value",
             FilterKind::StartsWith => "Starts with value",
             FilterKind::Regex => "Regex",
+            FilterKind::Fuzzy => "Fuzzy",
         }
     }
 }
@@ -732,6 +1351,7 @@ impl std::fmt::Display for FilterKind {
             FilterKind::Contains => write!(f, "Contains value"),
             FilterKind::StartsWith => write!(f, "Starts with value"),
             FilterKind::Regex => write!(f, "Regex"),
+            FilterKind::Fuzzy => write!(f, "Fuzzy"),
         }
     }
 }
@@ -742,10 +1362,24 @@ impl FilterKind {
             FilterKind::Exact => FilterKind::Contains,
             FilterKind::Contains => FilterKind::StartsWith,
             FilterKind::StartsWith => FilterKind::Regex,
-            FilterKind::Regex => FilterKind::Exact,
+            FilterKind::Regex => FilterKind::Fuzzy,
+            FilterKind::Fuzzy => FilterKind::Exact,
         };
         self
     }
+
+    /// Client side counterpart of the MPD filter used by the in-pane filters, always case insensitive. Returns how
+    /// well the value matches, only fuzzy matches differ in score. Regex is not supported and matches like
+    /// [`FilterKind::Contains`].
+    pub fn score(self, value: &str, filter: &str) -> Option<u32> {
+        let matches = match self {
+            FilterKind::Fuzzy => return fuzzy::score(value, filter),
+            FilterKind::Exact => value.to_lowercase() == filter.to_lowercase(),
+            FilterKind::StartsWith => value.to_lowercase().starts_with(&filter.to_lowercase()),
+            FilterKind::Contains | FilterKind::Regex => value.to_lowercase().contains(&filter.to_lowercase()),
+        };
+        matches.then_some(0)
+    }
 }
 
 impl Config {
@@ -769,15 +1403,15 @@ impl Config {
             .unwrap_or(&current_screen)
     }
 
-    fn as_header_table_block(&self) -> ratatui::widgets::Block {
-        if !self.theme.draw_borders {
+    fn as_header_table_block(&self, draw_borders: bool) -> ratatui::widgets::Block {
+        if !draw_borders {
             return ratatui::widgets::Block::default();
         }
         Block::default().border_style(self.as_border_style())
     }
 
-    fn as_tabs_block(&self) -> ratatui::widgets::Block {
-        if !self.theme.draw_borders {
+    fn as_tabs_block(&self, draw_borders: bool) -> ratatui::widgets::Block {
+        if !draw_borders {
             return ratatui::widgets::Block::default()/* .padding(Padding::new(0, 0, 1, 1)) */;
         }
 
@@ -813,12 +1447,22 @@ impl Config {
             .track_char(self.theme.progress_bar.symbols[2])
     }
 
-    fn as_styled_scrollbar(&self) -> ratatui::widgets::Scrollbar {
+    /// Returns `None` when the scrollbar should not be drawn for the given state
+    fn as_styled_scrollbar<T: ScrollingState>(&self, state: &DirState<T>) -> Option<ratatui::widgets::Scrollbar<'_>> {
+        if !self
+            .theme
+            .scrollbar
+            .visibility
+            .is_visible(state.content_len(), state.viewport_len())
+        {
+            return None;
+        }
+
         let symbols = self.theme.scrollbar.symbols;
         let track = if symbols[0].is_empty() { None } else { Some(symbols[0]) };
         let begin = if symbols[2].is_empty() { None } else { Some(symbols[2]) };
         let end = if symbols[3].is_empty() { None } else { Some(symbols[3]) };
-        ratatui::widgets::Scrollbar::default()
+        let scrollbar = ratatui::widgets::Scrollbar::default()
             .orientation(ratatui::widgets::ScrollbarOrientation::VerticalRight)
             .track_symbol(track)
             .thumb_symbol(self.theme.scrollbar.symbols[1])
@@ -827,6 +1471,244 @@ impl Config {
             .track_style(self.theme.scrollbar.track_style)
             .begin_style(self.theme.scrollbar.ends_style)
             .end_style(self.theme.scrollbar.ends_style)
-            .thumb_style(self.theme.scrollbar.thumb_style)
+            .thumb_style(self.theme.scrollbar.thumb_style);
+        Some(scrollbar)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use rstest::rstest;
+
+    use crossbeam::channel::{Receiver, Sender};
+    use ratatui::layout::Rect;
+
+    use crossterm::event::{KeyCode, KeyEvent as CKeyEvent, KeyModifiers};
+
+    use std::time::Duration;
+
+    use super::{position_at_percent, Areas, TerminalGuard, Ui};
+    use crate::{
+        config::{
+            tabs::TabName,
+            theme::{progress_bar::ProgressBarPosition, HeaderConfigRow},
+            DoubleEscapeAction, Leak,
+        },
+        context::AppContext,
+        mpd::commands::{State, Volume},
+        shared::{
+            events::{ClientRequest, WorkRequest},
+            key_event::KeyEvent,
+            mouse_event::{MouseEvent, MouseEventKind},
+        },
+        tests::fixtures::{app_context, client_request_channel, work_request_channel},
+    };
+
+    #[rstest]
+    fn starts_on_first_tab_by_default(app_context: AppContext) {
+        let ui = Ui::new(&app_context).expect("ui to be created");
+
+        assert_eq!(ui.active_tab, app_context.config.tabs.names[0]);
+    }
+
+    #[rstest]
+    fn starts_on_configured_default_tab(mut app_context: AppContext) {
+        let mut config = app_context.config.clone();
+        config.default_tab = Some(TabName::from("Albums"));
+        app_context.config = config.leak();
+
+        let ui = Ui::new(&app_context).expect("ui to be created");
+
+        assert_eq!(ui.active_tab, TabName::from("Albums"));
+    }
+
+    #[rstest]
+    fn toggling_borders_recomputes_layout(mut app_context: AppContext) {
+        let mut ui = Ui::new(&app_context).expect("ui to be created");
+        let area = Rect::new(0, 0, 100, 100);
+        ui.calc_areas(area, &app_context).expect("areas to be calculated");
+        assert_eq!(ui.areas[Areas::Tabs].height, 3);
+
+        let config = app_context.config;
+
+        ui.toggle_borders(&mut app_context).expect("borders to be toggled");
+
+        assert!(!app_context.draw_borders);
+        assert!(std::ptr::eq(config, app_context.config), "config is left untouched");
+        assert_eq!(ui.areas[Areas::Tabs].height, 1);
+        assert_eq!(ui.areas[Areas::Content].y, ui.areas[Areas::Tabs].bottom());
+        assert_eq!(
+            ui.areas[Areas::Header].union(ui.areas[Areas::Bar]),
+            area,
+            "layout still spans the whole terminal"
+        );
+    }
+
+    #[rstest]
+    fn header_progress_bar_is_below_header(mut app_context: AppContext) {
+        let mut config = app_context.config.clone();
+        config.theme.progress_bar.position = ProgressBarPosition::Header;
+        app_context.config = config.leak();
+        let mut ui = Ui::new(&app_context).expect("ui to be created");
+        let area = Rect::new(0, 0, 100, 100);
+
+        ui.calc_areas(area, &app_context).expect("areas to be calculated");
+
+        let header_rows = u16::try_from(app_context.config.theme.header.rows.len()).expect("header to be small");
+        assert_eq!(ui.areas[Areas::Header].height, header_rows);
+        assert_eq!(ui.areas[Areas::HeaderBar].height, 1);
+        assert_eq!(ui.areas[Areas::HeaderBar].y, ui.areas[Areas::Header].bottom());
+        assert!(ui.areas[Areas::Tabs].y >= ui.areas[Areas::HeaderBar].bottom());
+    }
+
+    #[rstest]
+    fn header_progress_bar_is_omitted_by_default(app_context: AppContext) {
+        let mut ui = Ui::new(&app_context).expect("ui to be created");
+
+        ui.calc_areas(Rect::new(0, 0, 100, 100), &app_context)
+            .expect("areas to be calculated");
+
+        assert_eq!(ui.areas[Areas::HeaderBar].height, 0);
+    }
+
+    #[rstest]
+    fn double_escape_switches_to_queue(mut app_context: AppContext) {
+        let mut config = app_context.config.clone();
+        config.double_escape_action = DoubleEscapeAction::GoToQueue;
+        config.default_tab = Some(TabName::from("Albums"));
+        app_context.config = config.leak();
+        let mut ui = Ui::new(&app_context).expect("ui to be created");
+        let escape = || -> KeyEvent { CKeyEvent::new(KeyCode::Esc, KeyModifiers::NONE).into() };
+
+        ui.handle_key(&mut escape(), &mut app_context)
+            .expect("key to be handled");
+        assert_eq!(ui.active_tab, TabName::from("Albums"));
+
+        ui.handle_key(&mut escape(), &mut app_context)
+            .expect("key to be handled");
+        assert_eq!(ui.active_tab, TabName::from("Queue"));
+    }
+
+    #[rstest]
+    fn screensaver_waits_for_playback_to_stop_and_hides_on_input(mut app_context: AppContext) {
+        let mut ui = Ui::new(&app_context).expect("ui to be created");
+        let key = || -> KeyEvent { CKeyEvent::new(KeyCode::Char('x'), KeyModifiers::NONE).into() };
+        app_context.status.state = State::Play;
+
+        ui.show_screensaver(&mut app_context)
+            .expect("screensaver to be handled");
+        assert!(!ui.screensaver_active);
+
+        app_context.status.state = State::Pause;
+        ui.on_player_state_changed(&mut app_context)
+            .expect("state change to be handled");
+        assert!(ui.screensaver_active);
+
+        ui.handle_key(&mut key(), &mut app_context).expect("key to be handled");
+        assert!(!ui.screensaver_active);
+
+        // Input re-arms the idle timeout, a later state change alone does not show the screensaver
+        app_context.status.state = State::Stop;
+        ui.on_player_state_changed(&mut app_context)
+            .expect("state change to be handled");
+        assert!(!ui.screensaver_active);
+    }
+
+    #[rstest]
+    fn mixer_update_after_mute_keeps_saved_volume(mut app_context: AppContext) {
+        let mut ui = Ui::new(&app_context).expect("ui to be created");
+        let mute = || -> KeyEvent { CKeyEvent::new(KeyCode::Char('m'), KeyModifiers::NONE).into() };
+        app_context.status.volume = Volume::new(65);
+
+        ui.handle_key(&mut mute(), &mut app_context).expect("key to be handled");
+        assert!(ui.mute.is_muted());
+
+        // MPD reports the muted volume back through the mixer idle event
+        app_context.status.volume = Volume::new(0);
+        ui.on_volume_changed(&app_context);
+        assert!(ui.mute.is_muted());
+
+        ui.handle_key(&mut mute(), &mut app_context).expect("key to be handled");
+        assert!(!ui.mute.is_muted());
+    }
+
+    #[rstest]
+    fn bottom_bar_click_seeks_only_when_it_shows_the_progress_bar(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let rx = client_request_channel.1.clone();
+        let mut app_context = app_context(work_request_channel, client_request_channel);
+        app_context.status.state = State::Play;
+        app_context.status.duration = Duration::from_secs(200);
+        let mut ui = Ui::new(&app_context).expect("ui to be created");
+        ui.calc_areas(Rect::new(0, 0, 100, 100), &app_context)
+            .expect("areas to be calculated");
+        let bar = ui.areas[Areas::Bar];
+        let click = MouseEvent {
+            x: bar.x + 10,
+            y: bar.y,
+            kind: MouseEventKind::LeftClick,
+        };
+
+        ui.handle_mouse_event(click, &mut app_context)
+            .expect("click to be handled");
+        assert!(matches!(rx.try_recv(), Ok(ClientRequest::Command(_))));
+
+        let mut config = app_context.config.clone();
+        config.theme.progress_bar.position = ProgressBarPosition::Header;
+        app_context.config = config.leak();
+        ui.handle_mouse_event(click, &mut app_context)
+            .expect("click to be handled");
+        assert!(rx.try_recv().is_err());
+
+        let mut config = app_context.config.clone();
+        config.theme.progress_bar.position = ProgressBarPosition::Bottom;
+        config.theme.status_line = Some(HeaderConfigRow {
+            left: &[],
+            center: &[],
+            right: &[],
+        });
+        app_context.config = config.leak();
+        ui.handle_mouse_event(click, &mut app_context)
+            .expect("click to be handled");
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn position_at_percent_of_duration() {
+        let duration = Duration::from_secs(200);
+
+        assert_eq!(position_at_percent(duration, 0.0), Some(0));
+        assert_eq!(position_at_percent(duration, 50.0), Some(100));
+        assert_eq!(position_at_percent(duration, 90.0), Some(180));
+        assert_eq!(position_at_percent(duration, 150.0), Some(200));
+    }
+
+    #[test]
+    fn position_at_percent_without_duration() {
+        assert_eq!(position_at_percent(Duration::ZERO, 50.0), None);
+    }
+
+    #[test]
+    fn terminal_guard_restores_on_drop() {
+        let restored = Cell::new(0);
+
+        {
+            let _guard = TerminalGuard::new(|| restored.set(restored.get() + 1));
+        }
+
+        assert_eq!(restored.get(), 1);
+    }
+
+    #[test]
+    fn disarmed_terminal_guard_does_nothing() {
+        let restored = Cell::new(0);
+
+        TerminalGuard::new(|| restored.set(restored.get() + 1)).disarm();
+
+        assert_eq!(restored.get(), 0);
     }
 }
@@ -193,6 +193,7 @@ impl<Callback: FnMut(&AppContext) -> Result<()>> Modal for ConfirmModal<'_, Call
             }
             MouseEventKind::MiddleClick => {}
             MouseEventKind::RightClick => {}
+            MouseEventKind::Move => {}
             MouseEventKind::ScrollUp => {
                 if self.button_group.get_button_idx_at(event.into()).is_some() {
                     self.button_group_state.prev();
@@ -0,0 +1,228 @@
+use anyhow::Result;
+use ratatui::{
+    layout::{Position, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Clear, List, ListState},
+    Frame,
+};
+
+use crate::{
+    config::keys::CommonAction,
+    context::AppContext,
+    shared::{
+        key_event::KeyEvent,
+        macros::pop_modal,
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::dirstack::DirState,
+};
+
+use super::Modal;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContextMenuEntry {
+    Add,
+    AddNext,
+    ReplaceAndPlay,
+    AddToPlaylist,
+    GoToAlbum,
+}
+
+impl ContextMenuEntry {
+    pub fn label(self) -> &'static str {
+        match self {
+            ContextMenuEntry::Add => "Add to queue",
+            ContextMenuEntry::AddNext => "Add next",
+            ContextMenuEntry::ReplaceAndPlay => "Replace queue and play",
+            ContextMenuEntry::AddToPlaylist => "Add to playlist",
+            ContextMenuEntry::GoToAlbum => "Go to album",
+        }
+    }
+}
+
+/// Small popup menu rendered next to the position where the user right-clicked
+pub struct ContextMenuModal<Callback: FnMut(&AppContext, ContextMenuEntry) -> Result<()>> {
+    anchor: Position,
+    entries: Vec<ContextMenuEntry>,
+    scrolling_state: DirState<ListState>,
+    area: Rect,
+    callback: Option<Callback>,
+}
+
+impl<Callback: FnMut(&AppContext, ContextMenuEntry) -> Result<()>> std::fmt::Debug for ContextMenuModal<Callback> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "ContextMenuModal(anchor = {:?}, entries = {:?}, area = {:?})",
+            self.anchor, self.entries, self.area
+        )
+    }
+}
+
+impl<Callback: FnMut(&AppContext, ContextMenuEntry) -> Result<()>> ContextMenuModal<Callback> {
+    pub fn new(anchor: Position) -> Self {
+        let mut scrolling_state = DirState::default();
+        scrolling_state.select(Some(0), 0);
+
+        Self {
+            anchor,
+            entries: Vec::new(),
+            scrolling_state,
+            area: Rect::default(),
+            callback: None,
+        }
+    }
+
+    pub fn entries(mut self, entries: Vec<ContextMenuEntry>) -> Self {
+        self.entries = entries;
+        self
+    }
+
+    pub fn on_confirm(mut self, callback: Callback) -> Self {
+        self.callback = Some(callback);
+        self
+    }
+
+    fn confirm(&mut self, context: &AppContext) -> Result<()> {
+        let entry = self
+            .scrolling_state
+            .get_selected()
+            .and_then(|idx| self.entries.get(idx).copied());
+        pop_modal!(context);
+        if let (Some(entry), Some(callback)) = (entry, self.callback.as_mut()) {
+            (callback)(context, entry)?;
+        }
+        Ok(())
+    }
+}
+
+/// Computes the area of the menu so that its top left corner is at the anchor while keeping the whole
+/// menu inside of the screen
+fn menu_area(anchor: Position, screen: Rect, entries: &[ContextMenuEntry]) -> Rect {
+    let content_width = entries.iter().map(|e| e.label().len()).max().unwrap_or_default();
+    let width = u16::try_from(content_width + 4).unwrap_or(u16::MAX).min(screen.width);
+    let height = u16::try_from(entries.len() + 2).unwrap_or(u16::MAX).min(screen.height);
+
+    let x = anchor.x.min(screen.right().saturating_sub(width)).max(screen.x);
+    let y = anchor.y.min(screen.bottom().saturating_sub(height)).max(screen.y);
+
+    Rect::new(x, y, width, height)
+}
+
+impl<Callback: FnMut(&AppContext, ContextMenuEntry) -> Result<()>> Modal for ContextMenuModal<Callback> {
+    fn render(&mut self, frame: &mut Frame, app: &mut AppContext) -> Result<()> {
+        self.area = menu_area(self.anchor, frame.area(), &self.entries);
+        frame.render_widget(Clear, self.area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), self.area);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style());
+        let list_area = block.inner(self.area);
+
+        self.scrolling_state.set_content_len(Some(self.entries.len()));
+        self.scrolling_state.set_viewport_len(Some(list_area.height.into()));
+
+        let list = List::new(self.entries.iter().map(|e| format!(" {}", e.label())))
+            .style(app.config.as_text_style())
+            .highlight_style(app.config.theme.current_item_style);
+
+        frame.render_widget(block, self.area);
+        frame.render_stateful_widget(list, list_area, self.scrolling_state.as_render_state_ref());
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = key.as_common_action(context) {
+            match action {
+                CommonAction::Down => {
+                    self.scrolling_state.next(context.config.scrolloff, true);
+                    context.render()?;
+                }
+                CommonAction::Up => {
+                    self.scrolling_state.prev(context.config.scrolloff, true);
+                    context.render()?;
+                }
+                CommonAction::Confirm => {
+                    self.confirm(context)?;
+                }
+                CommonAction::Close => {
+                    pop_modal!(context);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        let inner = self.area.inner(ratatui::layout::Margin::new(1, 1));
+        let hovered = self
+            .scrolling_state
+            .get_at_y(inner, event.y)
+            .filter(|_| inner.contains(event.into()));
+
+        match event.kind {
+            MouseEventKind::LeftClick | MouseEventKind::DoubleClick => {
+                if let Some(idx) = hovered {
+                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                    self.confirm(context)?;
+                } else if !self.area.contains(event.into()) {
+                    pop_modal!(context);
+                }
+            }
+            MouseEventKind::RightClick | MouseEventKind::MiddleClick => {
+                if !self.area.contains(event.into()) {
+                    pop_modal!(context);
+                }
+            }
+            MouseEventKind::Move => {
+                if let Some(idx) = hovered {
+                    if self.scrolling_state.get_selected() != Some(idx) {
+                        self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                        context.render()?;
+                    }
+                }
+            }
+            MouseEventKind::ScrollDown => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::{Position, Rect};
+
+    use super::{menu_area, ContextMenuEntry};
+
+    const ENTRIES: [ContextMenuEntry; 2] = [ContextMenuEntry::Add, ContextMenuEntry::AddNext];
+
+    #[test]
+    fn menu_is_anchored_at_cursor() {
+        let result = menu_area(Position::new(10, 5), Rect::new(0, 0, 100, 50), &ENTRIES);
+
+        assert_eq!(result, Rect::new(10, 5, 16, 4));
+    }
+
+    #[test]
+    fn menu_is_moved_to_fit_on_screen() {
+        let result = menu_area(Position::new(95, 49), Rect::new(0, 0, 100, 50), &ENTRIES);
+
+        assert_eq!(result, Rect::new(84, 46, 16, 4));
+    }
+}
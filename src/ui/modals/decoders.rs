@@ -164,14 +164,16 @@ impl Modal for DecodersModal {
         frame.render_widget(block, popup_area);
         frame.render_widget(header_table, header_area);
         frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
-        frame.render_stateful_widget(
-            app.config.as_styled_scrollbar(),
-            popup_area.inner(Margin {
-                horizontal: 0,
-                vertical: 1,
-            }),
-            self.scrolling_state.as_scrollbar_state_ref(),
-        );
+        if let Some(scrollbar) = app.config.as_styled_scrollbar(&self.scrolling_state) {
+            frame.render_stateful_widget(
+                scrollbar,
+                popup_area.inner(Margin {
+                    horizontal: 0,
+                    vertical: 1,
+                }),
+                self.scrolling_state.as_scrollbar_state_ref(),
+            );
+        }
 
         return Ok(());
     }
@@ -189,6 +191,16 @@ impl Modal for DecodersModal {
 
                     context.render()?;
                 }
+                CommonAction::PageDown => {
+                    self.scrolling_state.next_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::PageUp => {
+                    self.scrolling_state.prev_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
                 CommonAction::Up => {
                     self.scrolling_state
                         .prev(context.config.scrolloff, context.config.wrap_navigation);
@@ -233,6 +245,7 @@ impl Modal for DecodersModal {
             MouseEventKind::DoubleClick => {}
             MouseEventKind::MiddleClick => {}
             MouseEventKind::RightClick => {}
+            MouseEventKind::Move => {}
             MouseEventKind::ScrollDown if self.table_area.contains(event.into()) => {
                 self.scrolling_state.next(context.config.scrolloff, false);
                 context.render()?;
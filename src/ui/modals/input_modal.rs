@@ -247,6 +247,7 @@ impl<'a, C: FnMut(&AppContext, &str) -> Result<()> + 'a> Modal for InputModal<'a
             }
             MouseEventKind::MiddleClick => {}
             MouseEventKind::RightClick => {}
+            MouseEventKind::Move => {}
             MouseEventKind::ScrollUp => {
                 if self.button_group.get_button_idx_at(event.into()).is_some() {
                     self.input_focused = false;
@@ -11,10 +11,13 @@ use crate::{
 };
 
 pub mod confirm_modal;
+pub mod context_menu;
 pub mod decoders;
 pub mod input_modal;
 pub mod keybinds;
 pub mod outputs;
+pub mod partitions;
+pub mod playback_settings;
 pub mod select_modal;
 pub mod song_info;
 
@@ -12,51 +12,110 @@ use crate::{
     mpd::{commands::Output, mpd_client::MpdClient},
     shared::{
         key_event::KeyEvent,
-        macros::pop_modal,
+        macros::{modal, pop_modal, status_info},
         mouse_event::{MouseEvent, MouseEventKind},
     },
     ui::dirstack::DirState,
     MpdQueryResult,
 };
 
-use super::{Modal, RectExt};
+use super::{input_modal::InputModal, Modal, RectExt};
 
 #[derive(Debug)]
 pub struct OutputsModal {
     scrolling_state: DirState<TableState>,
     outputs_table_area: Rect,
     outputs: Vec<Output>,
+    /// Every output followed by its attributes, one per table row
+    entries: Vec<Entry>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Entry {
+    Output {
+        id: u32,
+    },
+    Attribute {
+        output_id: u32,
+        name: String,
+        value: String,
+    },
 }
 
 impl OutputsModal {
     pub fn new(outputs: Vec<Output>) -> Self {
         let mut result = Self {
+            entries: entries(&outputs),
             outputs,
             scrolling_state: DirState::default(),
             outputs_table_area: Rect::default(),
         };
-        result.scrolling_state.set_content_len(Some(result.outputs.len()));
+        result.scrolling_state.set_content_len(Some(result.entries.len()));
         result.scrolling_state.first();
 
         result
     }
 
-    pub fn toggle_selected_output(&mut self, context: &AppContext) {
-        let Some(idx) = self.scrolling_state.get_selected() else {
-            return;
-        };
-        let Some(output) = self.outputs.get(idx) else {
-            return;
+    /// Toggles the selected output or edits the selected attribute
+    pub fn confirm_selected(&mut self, context: &AppContext) -> Result<()> {
+        let Some(entry) = self
+            .scrolling_state
+            .get_selected()
+            .and_then(|idx| self.entries.get(idx))
+        else {
+            return Ok(());
         };
 
-        let id = output.id;
-        context.query().id("refresh_outputs").query(move |client| {
-            client.toggle_output(id)?;
-            Ok(MpdQueryResult::Outputs(client.outputs()?.0))
-        });
+        match entry {
+            Entry::Output { id } => {
+                let id = *id;
+                context.query().id("refresh_outputs").query(move |client| {
+                    client.toggle_output(id)?;
+                    Ok(MpdQueryResult::Outputs(client.outputs()?.0))
+                });
+            }
+            Entry::Attribute { output_id, name, value } => {
+                let output_id = *output_id;
+                let name = name.clone();
+                modal!(
+                    context,
+                    InputModal::new(context)
+                        .title("Set output attribute")
+                        .confirm_label("Set")
+                        .input_label("Value:")
+                        .initial_value(value.clone())
+                        .on_confirm(move |context, value| {
+                            let name = name.clone();
+                            let value = value.to_owned();
+                            context.query().id("refresh_outputs").query(move |client| {
+                                client.set_output_attribute(output_id, &name, &value)?;
+                                status_info!("Output attribute '{name}' set to '{value}'");
+                                Ok(MpdQueryResult::Outputs(client.outputs()?.0))
+                            });
+                            Ok(())
+                        })
+                );
+            }
+        }
+        Ok(())
     }
 }
 
+fn entries(outputs: &[Output]) -> Vec<Entry> {
+    outputs
+        .iter()
+        .flat_map(|output| {
+            std::iter::once(Entry::Output { id: output.id }).chain(output.attributes.iter().map(|(name, value)| {
+                Entry::Attribute {
+                    output_id: output.id,
+                    name: name.clone(),
+                    value: value.clone(),
+                }
+            }))
+        })
+        .collect()
+}
+
 impl Modal for OutputsModal {
     fn render(&mut self, frame: &mut ratatui::Frame, app: &mut AppContext) -> anyhow::Result<()> {
         let popup_area = frame.area().centered_exact(60, 10);
@@ -77,12 +136,22 @@ impl Modal for OutputsModal {
             vertical: 1,
         });
 
-        let rows = self.outputs.iter().map(|output| {
-            Row::new([
-                Cell::from(output.id.to_string()),
-                Cell::from(output.name.clone()),
-                Cell::from(if output.enabled { "yes" } else { "no" }),
-            ])
+        let rows = self.entries.iter().map(|entry| match entry {
+            Entry::Output { id } => {
+                let output = self.outputs.iter().find(|output| output.id == *id);
+                Row::new([
+                    Cell::from(id.to_string()),
+                    Cell::from(output.map(|output| output.name.clone()).unwrap_or_default()),
+                    Cell::from(if output.is_some_and(|output| output.enabled) {
+                        "yes"
+                    } else {
+                        "no"
+                    }),
+                ])
+            }
+            Entry::Attribute { name, value, .. } => {
+                Row::new([Cell::from(""), Cell::from(format!("  {name}={value}")), Cell::from("")])
+            }
         });
 
         self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
@@ -108,14 +177,16 @@ impl Modal for OutputsModal {
 
         frame.render_widget(block, popup_area);
         frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
-        frame.render_stateful_widget(
-            app.config.as_styled_scrollbar(),
-            popup_area.inner(Margin {
-                horizontal: 0,
-                vertical: 1,
-            }),
-            self.scrolling_state.as_scrollbar_state_ref(),
-        );
+        if let Some(scrollbar) = app.config.as_styled_scrollbar(&self.scrolling_state) {
+            frame.render_stateful_widget(
+                scrollbar,
+                popup_area.inner(Margin {
+                    horizontal: 0,
+                    vertical: 1,
+                }),
+                self.scrolling_state.as_scrollbar_state_ref(),
+            );
+        }
 
         Ok(())
     }
@@ -124,10 +195,12 @@ impl Modal for OutputsModal {
         match (id, data) {
             ("refresh_outputs", MpdQueryResult::Outputs(outputs)) => {
                 self.outputs = std::mem::take(outputs);
+                self.entries = entries(&self.outputs);
+                self.scrolling_state.set_content_len(Some(self.entries.len()));
                 context.render()?;
             }
             _ => {}
-        };
+        }
         Ok(())
     }
 
@@ -144,6 +217,16 @@ impl Modal for OutputsModal {
 
                     context.render()?;
                 }
+                CommonAction::PageDown => {
+                    self.scrolling_state.next_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::PageUp => {
+                    self.scrolling_state.prev_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
                 CommonAction::Up => {
                     self.scrolling_state
                         .prev(context.config.scrolloff, context.config.wrap_navigation);
@@ -167,7 +250,7 @@ impl Modal for OutputsModal {
                     context.render()?;
                 }
                 CommonAction::Confirm => {
-                    self.toggle_selected_output(context);
+                    self.confirm_selected(context)?;
                 }
                 CommonAction::Close => {
                     pop_modal!(context);
@@ -189,11 +272,12 @@ impl Modal for OutputsModal {
                 }
             }
             MouseEventKind::DoubleClick if self.outputs_table_area.contains(event.into()) => {
-                self.toggle_selected_output(context);
+                self.confirm_selected(context)?;
                 context.render()?;
             }
             MouseEventKind::MiddleClick => {}
             MouseEventKind::RightClick => {}
+            MouseEventKind::Move => {}
             MouseEventKind::ScrollDown if self.outputs_table_area.contains(event.into()) => {
                 self.scrolling_state.next(context.config.scrolloff, false);
                 context.render()?;
@@ -211,3 +295,37 @@ impl Modal for OutputsModal {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{entries, Entry};
+    use crate::mpd::commands::Output;
+
+    #[test]
+    fn attributes_follow_their_output() {
+        let outputs = [
+            Output {
+                id: 0,
+                attributes: [("dop".to_owned(), "0".to_owned())].into(),
+                ..Default::default()
+            },
+            Output {
+                id: 1,
+                ..Default::default()
+            },
+        ];
+
+        assert_eq!(
+            entries(&outputs),
+            [
+                Entry::Output { id: 0 },
+                Entry::Attribute {
+                    output_id: 0,
+                    name: "dop".to_owned(),
+                    value: "0".to_owned()
+                },
+                Entry::Output { id: 1 },
+            ]
+        );
+    }
+}
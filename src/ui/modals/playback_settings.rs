@@ -0,0 +1,282 @@
+use anyhow::Result;
+use ratatui::{
+    layout::{Constraint, Margin, Rect},
+    style::Style,
+    symbols::border,
+    widgets::{Block, Borders, Cell, Clear, Row, Table, TableState},
+};
+
+use crate::{
+    config::keys::CommonAction,
+    context::AppContext,
+    mpd::{commands::Status, mpd_client::MpdClient},
+    shared::{
+        key_event::KeyEvent,
+        macros::{modal, pop_modal, status_error},
+        mouse_event::{MouseEvent, MouseEventKind},
+    },
+    ui::dirstack::DirState,
+};
+
+use super::{input_modal::InputModal, Modal, RectExt};
+
+/// MPD options which control how songs transition into each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Setting {
+    Crossfade,
+    MixrampDb,
+    MixrampDelay,
+}
+
+const SETTINGS: [Setting; 3] = [Setting::Crossfade, Setting::MixrampDb, Setting::MixrampDelay];
+
+impl Setting {
+    fn label(self) -> &'static str {
+        match self {
+            Setting::Crossfade => "Crossfade",
+            Setting::MixrampDb => "MixRamp threshold",
+            Setting::MixrampDelay => "MixRamp delay",
+        }
+    }
+
+    fn input_label(self) -> &'static str {
+        match self {
+            Setting::Crossfade => "Crossfade in seconds:",
+            Setting::MixrampDb => "MixRamp threshold in dB:",
+            Setting::MixrampDelay => "MixRamp delay in seconds, empty to disable:",
+        }
+    }
+
+    /// Current value formatted for the input, empty when the option is not set
+    fn value(self, status: &Status) -> String {
+        match self {
+            Setting::Crossfade => status.xfade.unwrap_or_default().to_string(),
+            Setting::MixrampDb => status.mixrampdb.map(|db| db.to_string()).unwrap_or_default(),
+            Setting::MixrampDelay => status.mixrampdelay.map(|delay| delay.to_string()).unwrap_or_default(),
+        }
+    }
+
+    fn display_value(self, status: &Status) -> String {
+        match self {
+            Setting::Crossfade => format!("{}s", status.xfade.unwrap_or_default()),
+            Setting::MixrampDb => status.mixrampdb.map_or_else(|| "-".to_owned(), |db| format!("{db} dB")),
+            Setting::MixrampDelay => status
+                .mixrampdelay
+                .map_or_else(|| "disabled".to_owned(), |delay| format!("{delay}s")),
+        }
+    }
+
+    fn apply(self, value: &str, context: &AppContext) -> Result<()> {
+        let value = value.trim();
+        match self {
+            Setting::Crossfade => {
+                let seconds: u32 = value.parse()?;
+                context.command(move |client| Ok(client.crossfade(seconds)?));
+            }
+            Setting::MixrampDb => {
+                let db: f32 = value.parse()?;
+                context.command(move |client| Ok(client.mixramp_db(db)?));
+            }
+            Setting::MixrampDelay => {
+                let seconds: Option<f32> = if value.is_empty() { None } else { Some(value.parse()?) };
+                context.command(move |client| Ok(client.mixramp_delay(seconds)?));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Shows the crossfade and mixramp settings of MPD and allows changing them. The values are always taken from the
+/// latest status so changes made by other clients show up as well.
+#[derive(Debug)]
+pub struct PlaybackSettingsModal {
+    scrolling_state: DirState<TableState>,
+    table_area: Rect,
+}
+
+impl PlaybackSettingsModal {
+    pub fn new() -> Self {
+        let mut result = Self {
+            scrolling_state: DirState::default(),
+            table_area: Rect::default(),
+        };
+        result.scrolling_state.set_content_len(Some(SETTINGS.len()));
+        result.scrolling_state.first();
+
+        result
+    }
+
+    fn edit_selected(&self, context: &AppContext) -> Result<()> {
+        let Some(setting) = self
+            .scrolling_state
+            .get_selected()
+            .and_then(|idx| SETTINGS.get(idx))
+            .copied()
+        else {
+            return Ok(());
+        };
+
+        modal!(
+            context,
+            InputModal::new(context)
+                .title(setting.label())
+                .confirm_label("Set")
+                .input_label(setting.input_label())
+                .initial_value(setting.value(&context.status))
+                .on_confirm(move |context, value| {
+                    if let Err(err) = setting.apply(value, context) {
+                        status_error!(err:?; "Invalid value '{}' for {}", value, setting.label());
+                    }
+                    Ok(())
+                })
+        );
+        Ok(())
+    }
+}
+
+impl Modal for PlaybackSettingsModal {
+    fn render(&mut self, frame: &mut ratatui::Frame, app: &mut AppContext) -> anyhow::Result<()> {
+        let popup_area = frame.area().centered_exact(50, 6);
+        frame.render_widget(Clear, popup_area);
+        if let Some(bg_color) = app.config.theme.modal_background_color {
+            frame.render_widget(Block::default().style(Style::default().bg(bg_color)), popup_area);
+        }
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(border::ROUNDED)
+            .border_style(app.config.as_border_style())
+            .title_alignment(ratatui::prelude::Alignment::Center)
+            .title("Playback settings");
+
+        let rows = SETTINGS.iter().map(|setting| {
+            Row::new([
+                Cell::from(setting.label()),
+                Cell::from(setting.display_value(&app.status)),
+            ])
+        });
+
+        let table_area = popup_area.inner(Margin {
+            horizontal: 2,
+            vertical: 1,
+        });
+        self.scrolling_state.set_viewport_len(Some(table_area.height.into()));
+        self.table_area = table_area;
+
+        let table = Table::new(rows, [Constraint::Percentage(60), Constraint::Percentage(40)])
+            .column_spacing(0)
+            .style(app.config.as_text_style())
+            .row_highlight_style(app.config.theme.current_item_style);
+
+        frame.render_widget(block, popup_area);
+        frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
+
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        if let Some(action) = key.as_common_action(context) {
+            match action {
+                CommonAction::Up => {
+                    self.scrolling_state
+                        .prev(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Down => {
+                    self.scrolling_state
+                        .next(context.config.scrolloff, context.config.wrap_navigation);
+
+                    context.render()?;
+                }
+                CommonAction::Bottom => {
+                    self.scrolling_state.last();
+
+                    context.render()?;
+                }
+                CommonAction::Top => {
+                    self.scrolling_state.first();
+
+                    context.render()?;
+                }
+                CommonAction::Confirm => {
+                    self.edit_selected(context)?;
+                }
+                CommonAction::Close => {
+                    pop_modal!(context);
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &mut AppContext) -> Result<()> {
+        match event.kind {
+            MouseEventKind::LeftClick if self.table_area.contains(event.into()) => {
+                let y: usize = event.y.saturating_sub(self.table_area.y).into();
+                if let Some(idx) = self.scrolling_state.get_at_rendered_row(y) {
+                    self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                    context.render()?;
+                }
+            }
+            MouseEventKind::DoubleClick if self.table_area.contains(event.into()) => {
+                self.edit_selected(context)?;
+            }
+            MouseEventKind::ScrollDown if self.table_area.contains(event.into()) => {
+                self.scrolling_state.next(context.config.scrolloff, false);
+                context.render()?;
+            }
+            MouseEventKind::ScrollUp if self.table_area.contains(event.into()) => {
+                self.scrolling_state.prev(context.config.scrolloff, false);
+                context.render()?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use test_case::test_case;
+
+    use super::Setting;
+    use crate::{
+        mpd::commands::Status,
+        shared::events::ClientRequest,
+        tests::fixtures::{app_context, client_request_channel, work_request_channel},
+    };
+
+    #[test]
+    fn values_are_taken_from_status() {
+        let status = Status {
+            xfade: Some(5),
+            mixrampdb: Some(-17.5),
+            mixrampdelay: None,
+            ..Default::default()
+        };
+
+        assert_eq!(Setting::Crossfade.value(&status), "5");
+        assert_eq!(Setting::MixrampDb.value(&status), "-17.5");
+        assert_eq!(Setting::MixrampDelay.value(&status), "");
+        assert_eq!(Setting::MixrampDelay.display_value(&status), "disabled");
+    }
+
+    #[test_case(Setting::Crossfade, "3", true)]
+    #[test_case(Setting::Crossfade, "-1", false)]
+    #[test_case(Setting::MixrampDb, "-17", true)]
+    #[test_case(Setting::MixrampDb, "loud", false)]
+    #[test_case(Setting::MixrampDelay, "", true)]
+    #[test_case(Setting::MixrampDelay, "2.5", true)]
+    fn only_valid_values_are_sent(setting: Setting, value: &str, valid: bool) {
+        let (work_request_channel, client_request_channel) = (work_request_channel(), client_request_channel());
+        let rx = client_request_channel.1.clone();
+        let context = app_context(work_request_channel, client_request_channel);
+
+        assert_eq!(setting.apply(value, &context).is_ok(), valid);
+        assert_eq!(matches!(rx.try_recv(), Ok(ClientRequest::Command(_))), valid);
+    }
+}
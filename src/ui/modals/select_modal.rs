@@ -163,11 +163,9 @@ impl<V: Display, Callback: FnMut(&AppContext, &V, usize) -> Result<()>> Modal fo
         self.options_area = list_area;
 
         frame.render_stateful_widget(playlists, list_area, self.scrolling_state.as_render_state_ref());
-        frame.render_stateful_widget(
-            app.config.as_styled_scrollbar(),
-            scrollbar_area,
-            self.scrolling_state.as_scrollbar_state_ref(),
-        );
+        if let Some(scrollbar) = app.config.as_styled_scrollbar(&self.scrolling_state) {
+            frame.render_stateful_widget(scrollbar, scrollbar_area, self.scrolling_state.as_scrollbar_state_ref());
+        }
         frame.render_stateful_widget(&mut self.button_group, buttons_area, &mut self.button_group_state);
         Ok(())
     }
@@ -294,6 +292,7 @@ impl<V: Display, Callback: FnMut(&AppContext, &V, usize) -> Result<()>> Modal fo
             }
             MouseEventKind::MiddleClick => {}
             MouseEventKind::RightClick => {}
+            MouseEventKind::Move => {}
             MouseEventKind::ScrollUp if self.button_group.get_button_idx_at(event.into()).is_some() => {
                 self.focused = FocusedComponent::Buttons;
                 self.button_group_state.prev();
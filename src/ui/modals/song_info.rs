@@ -2,11 +2,15 @@ use crate::{
     config::keys::CommonAction,
     context::AppContext,
     mpd::commands::Song,
+    mpd::mpd_client::MpdClient,
     shared::{
+        ext::duration::humanize_since,
         key_event::KeyEvent,
         macros::pop_modal,
         mouse_event::{MouseEvent, MouseEventKind},
+        play_history::LAST_PLAYED_STICKER,
     },
+    MpdQueryResult,
 };
 use anyhow::Result;
 use itertools::Itertools;
@@ -28,15 +32,34 @@ pub struct SongInfoModal {
     scrolling_state: DirState<TableState>,
     table_area: Rect,
     song: Song,
+    /// Value of the song's last played sticker, fetched after the modal is opened
+    last_played: Option<String>,
 }
 
+const LAST_PLAYED: &str = "song_info_last_played";
+
 impl SongInfoModal {
-    pub fn new(song: Song) -> Self {
+    pub fn new(song: Song, context: &AppContext) -> Self {
+        if !song.is_stream() && context.supported_commands.contains("sticker") {
+            let file = song.file.clone();
+            context
+                .query()
+                .id(LAST_PLAYED)
+                .replace_id(LAST_PLAYED)
+                .query(move |client| {
+                    Ok(MpdQueryResult::LastPlayed {
+                        timestamp: client.sticker_get(&file, LAST_PLAYED_STICKER)?,
+                        file,
+                    })
+                });
+        }
+
         let mut scrolling_state = DirState::default();
         scrolling_state.select(Some(0), 0);
         Self {
             scrolling_state,
             song,
+            last_played: None,
             table_area: Rect::default(),
         }
     }
@@ -92,7 +115,7 @@ impl Modal for SongInfoModal {
         .areas(table_area);
         value_area.width = value_area.width.saturating_sub(1); // account for the column spacing
 
-        let Self { song, .. } = self;
+        let Self { song, last_played, .. } = self;
         let mut rows = Vec::new();
 
         rows.extend(SongInfoModal::row("File", tag_area.width, &song.file, value_area.width));
@@ -127,6 +150,26 @@ impl Modal for SongInfoModal {
                 value_area.width,
             ));
         }
+        let added = song
+            .metadata
+            .get("last-modified")
+            .and_then(|v| humanize_since(v, chrono::Utc::now()))
+            .unwrap_or_default();
+        if !added.is_empty() {
+            rows.extend(SongInfoModal::row("Added", tag_area.width, &added, value_area.width));
+        }
+        let last_played = last_played
+            .as_deref()
+            .and_then(|v| humanize_since(v, chrono::Utc::now()))
+            .unwrap_or_default();
+        if !last_played.is_empty() {
+            rows.extend(SongInfoModal::row(
+                "Last played",
+                tag_area.width,
+                &last_played,
+                value_area.width,
+            ));
+        }
 
         rows.extend(
             song.metadata
@@ -167,18 +210,31 @@ impl Modal for SongInfoModal {
         frame.render_widget(block, popup_area);
         frame.render_widget(header_table, header_area);
         frame.render_stateful_widget(table, table_area, self.scrolling_state.as_render_state_ref());
-        frame.render_stateful_widget(
-            app.config.as_styled_scrollbar(),
-            popup_area.inner(Margin {
-                horizontal: 0,
-                vertical: 1,
-            }),
-            self.scrolling_state.as_scrollbar_state_ref(),
-        );
+        if let Some(scrollbar) = app.config.as_styled_scrollbar(&self.scrolling_state) {
+            frame.render_stateful_widget(
+                scrollbar,
+                popup_area.inner(Margin {
+                    horizontal: 0,
+                    vertical: 1,
+                }),
+                self.scrolling_state.as_scrollbar_state_ref(),
+            );
+        }
 
         return Ok(());
     }
 
+    fn on_query_finished(&mut self, id: &'static str, data: &mut MpdQueryResult, context: &AppContext) -> Result<()> {
+        match (id, data) {
+            (LAST_PLAYED, MpdQueryResult::LastPlayed { file, timestamp }) if *file == self.song.file => {
+                self.last_played = timestamp.take();
+                context.render()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     fn handle_key(&mut self, key: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         if let Some(action) = key.as_common_action(context) {
             match action {
@@ -192,6 +248,16 @@ impl Modal for SongInfoModal {
 
                     context.render()?;
                 }
+                CommonAction::PageDown => {
+                    self.scrolling_state.next_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::PageUp => {
+                    self.scrolling_state.prev_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
                 CommonAction::Up => {
                     self.scrolling_state
                         .prev(context.config.scrolloff, context.config.wrap_navigation);
@@ -239,6 +305,7 @@ impl Modal for SongInfoModal {
             }
             MouseEventKind::MiddleClick => {}
             MouseEventKind::RightClick => {}
+            MouseEventKind::Move => {}
             MouseEventKind::ScrollDown => {
                 self.scrolling_state.next(context.config.scrolloff, false);
                 context.render()?;
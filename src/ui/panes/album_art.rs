@@ -2,10 +2,18 @@ use crate::{
     config::tabs::PaneType,
     context::AppContext,
     mpd::mpd_client::MpdClient,
-    shared::{image::ImageProtocol, key_event::KeyEvent},
-    ui::{image::facade::AlbumArtFacade, UiEvent},
+    shared::{image::ImageProtocol, key_event::KeyEvent, macros::status_info},
+    ui::{
+        image::facade::{AlbumArtFacade, IS_SHOWING},
+        UiEvent,
+    },
     MpdQueryResult,
 };
+use std::{
+    collections::VecDeque,
+    sync::{atomic::Ordering, Arc},
+};
+
 use anyhow::Result;
 use ratatui::{layout::Rect, Frame};
 
@@ -14,54 +22,175 @@ use super::Pane;
 #[derive(Debug)]
 pub struct AlbumArtPane {
     album_art: AlbumArtFacade,
+    cache: AlbumArtCache,
 }
 
 const ALBUM_ART: &str = "album_art";
+/// Total size in bytes of the album art kept around
+const CACHE_CAPACITY: usize = 32 * 1024 * 1024;
+
+/// Album art of recently played directories, most recently used last. Songs of an album usually share their
+/// directory and album art so returning to an album does not download the art again.
+#[derive(Debug, Default)]
+struct AlbumArtCache {
+    entries: VecDeque<(String, Arc<Vec<u8>>)>,
+    size: usize,
+}
+
+impl AlbumArtCache {
+    fn get(&mut self, dir: &str) -> Option<Arc<Vec<u8>>> {
+        let idx = self.entries.iter().position(|(key, _)| key == dir)?;
+        let entry = self.entries.remove(idx)?;
+        let data = Arc::clone(&entry.1);
+        self.entries.push_back(entry);
+        Some(data)
+    }
+
+    /// Art larger than the whole cache is not kept at all
+    fn insert(&mut self, dir: String, data: Arc<Vec<u8>>) {
+        self.remove(&dir);
+        if data.len() > CACHE_CAPACITY {
+            return;
+        }
+        while self.size + data.len() > CACHE_CAPACITY {
+            let Some((_, evicted)) = self.entries.pop_front() else {
+                break;
+            };
+            self.size -= evicted.len();
+        }
+        self.size += data.len();
+        self.entries.push_back((dir, data));
+    }
+
+    fn remove(&mut self, dir: &str) {
+        if let Some(idx) = self.entries.iter().position(|(key, _)| key == dir) {
+            if let Some((_, removed)) = self.entries.remove(idx) {
+                self.size -= removed.len();
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.size = 0;
+    }
+}
+
+/// Directory of the song its art is cached under. Songs in the root of the library do not share a directory with
+/// an album and are cached by their own uri instead.
+fn cache_key(song_uri: &str) -> &str {
+    song_uri.rsplit_once('/').map_or(song_uri, |(dir, _)| dir)
+}
 
 impl AlbumArtPane {
     pub fn new(context: &AppContext) -> Self {
         Self {
-            album_art: AlbumArtFacade::new(context.config),
+            album_art: AlbumArtFacade::new(context.config, context.app_event_sender.clone()),
+            cache: AlbumArtCache::default(),
+        }
+    }
+
+    /// Switches to the next album art method and shows the current album art again with it
+    pub fn cycle_image_method(&mut self, context: &AppContext) -> Result<()> {
+        let previous = self.album_art.method();
+        let method = previous.next();
+        self.album_art.set_method(method, context.config)?;
+        status_info!("Album art method switched to {method}");
+
+        // Album art is not fetched at all while no method is active
+        if matches!(previous.into(), ImageProtocol::None)
+            && IS_SHOWING.load(Ordering::Relaxed)
+            && self.fetch_album_art(context, true, false)?.is_none()
+        {
+            self.album_art.show_default()?;
+        }
+        context.render()?;
+        Ok(())
+    }
+
+    /// Forgets the cached art of the current song's directory and downloads it again, for covers changed outside
+    /// of rmpc. The art is only fetched right away if the pane is visible.
+    pub fn reload_album_art(&mut self, is_visible: bool, context: &AppContext) -> Result<()> {
+        let Some((_, current_song)) = context.find_current_song_in_queue() else {
+            status_info!("No song is currently playing");
+            return Ok(());
+        };
+        self.cache.remove(cache_key(&current_song.file));
+
+        if !is_visible {
+            return Ok(());
         }
+        status_info!("Reloading album art");
+        if self.fetch_album_art(context, false, false)?.is_none() {
+            self.album_art.show_default()?;
+        }
+        Ok(())
     }
 
-    /// returns none if album art is supposed to be hidden
-    fn fetch_album_art(context: &AppContext) -> Option<()> {
-        if matches!(context.config.album_art.method.into(), ImageProtocol::None) {
-            return None;
+    /// returns none if album art is supposed to be hidden. Art cached for the directory of the song is shown right
+    /// away unless `use_cache` is false. With `only_same_song` the art is not downloaded when MPD already plays
+    /// another song by then, it is fetched on the following `SongChanged` instead.
+    fn fetch_album_art(&mut self, context: &AppContext, use_cache: bool, only_same_song: bool) -> Result<Option<()>> {
+        if matches!(self.album_art.method().into(), ImageProtocol::None) {
+            return Ok(None);
         };
 
-        let (_, current_song) = context.find_current_song_in_queue()?;
+        let Some((_, current_song)) = context.find_current_song_in_queue() else {
+            return Ok(None);
+        };
 
         let disabled_protos = &context.config.album_art.disabled_protocols;
         let song_uri = current_song.file.as_str();
         if disabled_protos.iter().any(|proto| song_uri.starts_with(proto)) {
             log::debug!(uri = song_uri; "Not downloading album art because the protocol is disabled");
-            return None;
+            return Ok(None);
+        }
+
+        if use_cache && !current_song.is_stream() {
+            if let Some(data) = self.cache.get(cache_key(song_uri)) {
+                log::debug!(uri = song_uri; "Using cached album art");
+                self.album_art.show(data)?;
+                return Ok(Some(()));
+            }
         }
 
         let song_uri = song_uri.to_owned();
+        let song_id = current_song.id;
+        let max_size = context.config.album_art.max_file_size_bytes;
         context
             .query()
             .id(ALBUM_ART)
             .replace_id(ALBUM_ART)
             .target(PaneType::AlbumArt)
-            .query(move |client| {
-                let start = std::time::Instant::now();
-                log::debug!(file = song_uri.as_str(); "Searching for album art");
-                let result = client.find_album_art(&song_uri)?;
-                log::debug!(elapsed:? = start.elapsed(), size = result.as_ref().map(|v|v.len()); "Found album art");
+            .query(move |client| search_album_art(client, song_uri, song_id, only_same_song, max_size));
 
-                Ok(MpdQueryResult::AlbumArt(result))
-            });
+        Ok(Some(()))
+    }
+}
 
-        Some(())
+fn search_album_art(
+    client: &mut impl MpdClient,
+    song_uri: String,
+    song_id: u32,
+    only_same_song: bool,
+    max_size: usize,
+) -> Result<MpdQueryResult> {
+    if only_same_song && client.get_status()?.songid != Some(song_id) {
+        log::debug!(file = song_uri.as_str(); "Not searching for album art because the song changed");
+        return Ok(MpdQueryResult::Any(Box::new(())));
     }
+    let start = std::time::Instant::now();
+    log::debug!(file = song_uri.as_str(); "Searching for album art");
+    let data = client.find_album_art(&song_uri, max_size)?;
+    log::debug!(elapsed:? = start.elapsed(), size = data.as_ref().map(|v|v.len()); "Found album art");
+
+    Ok(MpdQueryResult::AlbumArt { song_uri, data })
 }
 
 impl Pane for AlbumArtPane {
-    fn render(&mut self, _frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
         self.album_art.set_size(area);
+        self.album_art.render(frame.buffer_mut());
         Ok(())
     }
 
@@ -83,18 +212,37 @@ impl Pane for AlbumArtPane {
     }
 
     fn before_show(&mut self, context: &AppContext) -> Result<()> {
-        if AlbumArtPane::fetch_album_art(context).is_none() {
+        if self.fetch_album_art(context, true, false)?.is_none() {
             self.album_art.show_default()?;
         }
         Ok(())
     }
 
-    fn on_query_finished(&mut self, id: &'static str, data: MpdQueryResult, _context: &AppContext) -> Result<()> {
+    fn on_query_finished(&mut self, id: &'static str, data: MpdQueryResult, context: &AppContext) -> Result<()> {
         match (id, data) {
-            (ALBUM_ART, MpdQueryResult::AlbumArt(Some(data))) => {
+            // Queued requests are replaced on song change but one might have already been in flight, its result
+            // would flash the art of a song which is not playing anymore
+            (ALBUM_ART, MpdQueryResult::AlbumArt { song_uri, .. })
+                if !context
+                    .find_current_song_in_queue()
+                    .is_some_and(|(_, song)| song.file == song_uri) =>
+            {
+                log::debug!(uri = song_uri.as_str(); "Dropping album art of a song which is not current anymore");
+            }
+            (
+                ALBUM_ART,
+                MpdQueryResult::AlbumArt {
+                    song_uri,
+                    data: Some(data),
+                },
+            ) => {
+                let data = Arc::new(data);
+                if !song_uri.contains("://") {
+                    self.cache.insert(cache_key(&song_uri).to_owned(), Arc::clone(&data));
+                }
                 self.album_art.show(data)?;
             }
-            (ALBUM_ART, MpdQueryResult::AlbumArt(None)) => {
+            (ALBUM_ART, MpdQueryResult::AlbumArt { data: None, .. }) => {
                 self.album_art.show_default()?;
             }
             _ => {}
@@ -103,9 +251,23 @@ impl Pane for AlbumArtPane {
     }
 
     fn on_event(&mut self, event: &mut UiEvent, is_visible: bool, context: &AppContext) -> Result<()> {
+        if matches!(event, UiEvent::Reconnected) {
+            self.cache.clear();
+        }
+
         match event {
-            UiEvent::SongChanged | UiEvent::Reconnected if is_visible => {
-                if AlbumArtPane::fetch_album_art(context).is_none() {
+            // Files with an image per chapter and streams can change their art without changing the song id
+            UiEvent::SongChanged | UiEvent::Reconnected | UiEvent::Player
+                if is_visible
+                    && (context.config.album_art.refresh_on_player_event || !matches!(event, UiEvent::Player)) =>
+            {
+                // Art of files with an image per chapter changes within the same directory. The status is not
+                // updated yet on player events so the song might have changed, that is handled by `SongChanged`.
+                let is_player_event = matches!(event, UiEvent::Player);
+                if self
+                    .fetch_album_art(context, !is_player_event, is_player_event)?
+                    .is_none()
+                {
                     self.album_art.show_default()?;
                 }
             }
@@ -133,21 +295,25 @@ mod tests {
     use crossbeam::channel::RecvTimeoutError;
     use crossbeam::channel::{Receiver, Sender};
     use rstest::rstest;
+    use std::sync::Arc;
     use std::time::Duration;
 
-    use super::AlbumArtPane;
+    use super::{cache_key, search_album_art, AlbumArtCache, AlbumArtPane, CACHE_CAPACITY};
 
     use crate::config::Config;
     use crate::config::ImageMethod;
     use crate::config::Leak;
+    use crate::context::AppContext;
     use crate::mpd::commands::Song;
     use crate::mpd::commands::State;
     use crate::shared::events::{ClientRequest, WorkRequest};
     use crate::shared::mpd_query::MpdQuery;
+    use crate::tests::fixtures::mpd_client::{client, TestMpdClient};
     use crate::tests::fixtures::work_request_channel;
     use crate::tests::fixtures::{app_context, client_request_channel};
     use crate::ui::panes::Pane;
     use crate::ui::UiEvent;
+    use crate::MpdQueryResult;
     use crate::{config::tabs::PaneType, ui::panes::album_art::ALBUM_ART};
 
     #[rstest]
@@ -156,6 +322,7 @@ mod tests {
     #[case(ImageMethod::UeberzugX11, true)]
     #[case(ImageMethod::Iterm2, true)]
     #[case(ImageMethod::Sixel, true)]
+    #[case(ImageMethod::Block, true)]
     #[case(ImageMethod::Unsupported, false)]
     #[case(ImageMethod::None, false)]
     fn searches_for_album_art_before_show(
@@ -203,6 +370,7 @@ mod tests {
     #[case(ImageMethod::UeberzugX11, true)]
     #[case(ImageMethod::Iterm2, true)]
     #[case(ImageMethod::Sixel, true)]
+    #[case(ImageMethod::Block, true)]
     #[case(ImageMethod::Unsupported, false)]
     #[case(ImageMethod::None, false)]
     fn searches_for_album_art_on_event(
@@ -242,4 +410,242 @@ mod tests {
             assert!(result.is_err_and(|err| RecvTimeoutError::Timeout == err));
         }
     }
+
+    #[rstest]
+    #[case(true, true)]
+    #[case(false, false)]
+    fn searches_for_album_art_on_player_event_when_enabled(
+        #[case] refresh_on_player_event: bool,
+        #[case] should_search: bool,
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let rx = client_request_channel.1.clone();
+        let mut app_context = app_context(work_request_channel, client_request_channel);
+        let selected_song_id = 333;
+        let mut config = Config::default();
+        config.album_art.method = ImageMethod::Kitty;
+        config.album_art.refresh_on_player_event = refresh_on_player_event;
+        app_context.config = config.leak();
+        app_context.queue.push(Song {
+            id: selected_song_id,
+            ..Default::default()
+        });
+        app_context.status.songid = Some(selected_song_id);
+        app_context.status.state = State::Play;
+        let mut screen = AlbumArtPane::new(&app_context);
+
+        screen.on_event(&mut UiEvent::Player, true, &app_context).unwrap();
+
+        if should_search {
+            assert!(matches!(
+                rx.recv_timeout(Duration::from_millis(100)).unwrap(),
+                ClientRequest::Query(MpdQuery { id: ALBUM_ART, .. })
+            ));
+        } else {
+            let result = rx.recv_timeout(Duration::from_millis(100));
+            assert!(result.is_err_and(|err| RecvTimeoutError::Timeout == err));
+        }
+    }
+
+    #[rstest]
+    fn only_art_of_the_latest_song_is_applied_on_rapid_song_changes(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let rx = client_request_channel.1.clone();
+        let mut app_context = app_context(work_request_channel, client_request_channel);
+        let mut config = Config::default();
+        config.album_art.method = ImageMethod::Kitty;
+        app_context.config = config.leak();
+        for (id, file) in [(1, "a.flac"), (2, "b.flac"), (3, "c.flac")] {
+            app_context.queue.push(Song {
+                id,
+                file: file.to_owned(),
+                ..Default::default()
+            });
+        }
+        app_context.status.state = State::Play;
+        let mut screen = AlbumArtPane::new(&app_context);
+        let mut requested = Vec::new();
+        for id in [1, 2, 3] {
+            app_context.status.songid = Some(id);
+            screen.on_event(&mut UiEvent::SongChanged, true, &app_context).unwrap();
+            requested.push(rx.recv_timeout(Duration::from_millis(100)).unwrap());
+        }
+        // every request replaces the previous one so only the last one is fetched unless one was already in flight
+        assert!(requested.iter().all(|request| matches!(
+            request,
+            ClientRequest::Query(MpdQuery {
+                replace_id: Some(ALBUM_ART),
+                ..
+            })
+        )));
+
+        // results of the superseded songs arrive late, after the one of the current song
+        for (song_uri, data) in [("c.flac", 3), ("a.flac", 1), ("b.flac", 2)] {
+            let result = MpdQueryResult::AlbumArt {
+                song_uri: song_uri.to_owned(),
+                data: Some(vec![data]),
+            };
+            screen.on_query_finished(ALBUM_ART, result, &app_context).unwrap();
+        }
+
+        assert_eq!(screen.album_art.current_album_art(), Some([3].as_slice()));
+    }
+
+    fn context_with_songs(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) -> AppContext {
+        let mut app_context = app_context(work_request_channel, client_request_channel);
+        let mut config = Config::default();
+        config.album_art.method = ImageMethod::Kitty;
+        app_context.config = config.leak();
+        for (id, file) in [(1, "album/a.flac"), (2, "album/b.flac"), (3, "other/c.flac")] {
+            app_context.queue.push(Song {
+                id,
+                file: file.to_owned(),
+                ..Default::default()
+            });
+        }
+        app_context.status.songid = Some(1);
+        app_context.status.state = State::Play;
+        app_context
+    }
+
+    #[rstest]
+    fn reload_drops_cached_art_and_fetches_it_again(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let rx = client_request_channel.1.clone();
+        let app_context = context_with_songs(work_request_channel, client_request_channel);
+        let mut screen = AlbumArtPane::new(&app_context);
+        screen.cache.insert("album".to_owned(), Arc::new(vec![1]));
+        screen.cache.insert("other".to_owned(), Arc::new(vec![2]));
+
+        screen.reload_album_art(true, &app_context).unwrap();
+
+        assert!(screen.cache.get("album").is_none());
+        assert!(screen.cache.get("other").is_some());
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_millis(100)).unwrap(),
+            ClientRequest::Query(MpdQuery {
+                id: ALBUM_ART,
+                replace_id: Some(ALBUM_ART),
+                ..
+            })
+        ));
+    }
+
+    #[rstest]
+    fn second_song_of_the_same_directory_uses_cached_art(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let rx = client_request_channel.1.clone();
+        let mut app_context = context_with_songs(work_request_channel, client_request_channel);
+        let mut screen = AlbumArtPane::new(&app_context);
+        screen.before_show(&app_context).unwrap();
+        rx.recv_timeout(Duration::from_millis(100)).unwrap();
+        let result = MpdQueryResult::AlbumArt {
+            song_uri: "album/a.flac".to_owned(),
+            data: Some(vec![1]),
+        };
+        screen.on_query_finished(ALBUM_ART, result, &app_context).unwrap();
+
+        app_context.status.songid = Some(2);
+        screen.before_show(&app_context).unwrap();
+
+        assert!(rx
+            .recv_timeout(Duration::from_millis(100))
+            .is_err_and(|err| RecvTimeoutError::Timeout == err));
+        assert_eq!(screen.album_art.current_album_art(), Some([1].as_slice()));
+
+        app_context.status.songid = Some(3);
+        screen.before_show(&app_context).unwrap();
+
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_millis(100)).unwrap(),
+            ClientRequest::Query(MpdQuery { id: ALBUM_ART, .. })
+        ));
+    }
+
+    #[rstest]
+    fn cached_art_is_dropped_on_reconnect(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let rx = client_request_channel.1.clone();
+        let app_context = context_with_songs(work_request_channel, client_request_channel);
+        let mut screen = AlbumArtPane::new(&app_context);
+        let result = MpdQueryResult::AlbumArt {
+            song_uri: "album/a.flac".to_owned(),
+            data: Some(vec![1]),
+        };
+        screen.on_query_finished(ALBUM_ART, result, &app_context).unwrap();
+
+        screen.on_event(&mut UiEvent::Reconnected, true, &app_context).unwrap();
+
+        assert!(matches!(
+            rx.recv_timeout(Duration::from_millis(100)).unwrap(),
+            ClientRequest::Query(MpdQuery { id: ALBUM_ART, .. })
+        ));
+    }
+
+    #[rstest]
+    #[case(false, Some(1), true)]
+    #[case(true, Some(1), true)]
+    #[case(true, Some(2), false)]
+    fn player_event_search_is_skipped_after_song_change(
+        mut client: TestMpdClient,
+        #[case] only_same_song: bool,
+        #[case] current_song_id: Option<u32>,
+        #[case] should_search: bool,
+    ) {
+        client.status.songid = current_song_id;
+
+        let result = search_album_art(&mut client, "song.flac".to_owned(), 1, only_same_song, 1024).unwrap();
+
+        assert_eq!(matches!(result, MpdQueryResult::AlbumArt { .. }), should_search);
+        assert_eq!(client.calls.contains_key("find_album_art"), should_search);
+    }
+
+    #[test]
+    fn cache_evicts_least_recently_used_directory() {
+        let art_size = CACHE_CAPACITY / 4;
+        let mut cache = AlbumArtCache::default();
+        for i in 0..4 {
+            cache.insert(format!("dir_{i}"), Arc::new(vec![0; art_size]));
+        }
+        cache.get("dir_0").unwrap();
+
+        cache.insert("new".to_owned(), Arc::new(vec![0; art_size]));
+
+        assert!(cache.get("dir_0").is_some());
+        assert!(cache.get("dir_1").is_none());
+        assert!(cache.get("new").is_some());
+        assert_eq!(cache.entries.len(), 4);
+        assert_eq!(cache.size, CACHE_CAPACITY);
+    }
+
+    #[test]
+    fn cache_skips_art_larger_than_capacity() {
+        let mut cache = AlbumArtCache::default();
+        cache.insert("dir".to_owned(), Arc::new(vec![0; 10]));
+
+        cache.insert("huge".to_owned(), Arc::new(vec![0; CACHE_CAPACITY + 1]));
+
+        assert!(cache.get("huge").is_none());
+        assert!(cache.get("dir").is_some());
+        assert_eq!(cache.size, 10);
+    }
+
+    #[test]
+    fn songs_in_library_root_do_not_share_cache_key() {
+        assert_eq!(cache_key("artist/album/song.flac"), "artist/album");
+        assert_eq!(cache_key("song.flac"), "song.flac");
+        assert_ne!(cache_key("song.flac"), cache_key("other.flac"));
+    }
 }
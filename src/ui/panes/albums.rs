@@ -7,10 +7,14 @@ use crate::{
         errors::MpdError,
         mpd_client::{Filter, MpdClient, Tag},
     },
-    shared::{ext::mpd_client::MpdClientExt, key_event::KeyEvent, macros::status_info, mouse_event::MouseEvent},
+    shared::{
+        add_feedback::report_added, ext::mpd_client::MpdClientExt, key_event::KeyEvent, macros::status_warn,
+        mouse_event::MouseEvent,
+    },
     ui::{
-        browser::BrowserPane,
+        browser::{add_within_limit, BrowserPane},
         dirstack::{DirStack, DirStackItem},
+        key_hints::KeyHint,
         widgets::browser::Browser,
         UiEvent,
     },
@@ -28,9 +32,13 @@ pub struct AlbumsPane {
     filter_input_mode: bool,
     browser: Browser<DirOrSong>,
     initialized: bool,
+    pending_album: Option<String>,
 }
 
 const INIT: &str = "init";
+const EMPTY_MESSAGE: &str =
+    "No albums found. If this is a fresh MPD setup, run `:update` to scan your music directory.";
+const MISSING_TAG_MESSAGE: &str = "Albums are not available because MPD does not provide the Album tag.";
 const OPEN_OR_PLAY: &str = "open_or_play";
 const PREVIEW: &str = "preview";
 
@@ -39,11 +47,41 @@ impl AlbumsPane {
         Self {
             stack: DirStack::default(),
             filter_input_mode: false,
-            browser: Browser::new(context.config),
+            browser: Browser::new(context.config, "Albums"),
             initialized: false,
+            pending_album: None,
         }
     }
 
+    /// Opens the given album. If the list of albums is not loaded yet, the album is opened once it is.
+    pub fn go_to_album(&mut self, album: String, context: &AppContext) -> Result<()> {
+        self.pending_album = Some(album);
+        if !self.stack.current().items.is_empty() || !self.stack.path().is_empty() {
+            self.open_pending_album(context)?;
+        }
+        Ok(())
+    }
+
+    fn open_pending_album(&mut self, context: &AppContext) -> Result<()> {
+        let Some(album) = self.pending_album.take() else {
+            return Ok(());
+        };
+        while self.stack.pop().is_some() {}
+
+        let Some(idx) = self
+            .stack
+            .current()
+            .items
+            .iter()
+            .position(|item| item.as_path() == album)
+        else {
+            status_warn!("Album '{album}' was not found");
+            return Ok(());
+        };
+        self.stack.current_mut().select_idx(idx, context.config.scrolloff);
+        self.open_or_play(false, context)
+    }
+
     fn open_or_play(&mut self, autoplay: bool, context: &AppContext) -> Result<()> {
         let Some(current) = self.stack.current().selected() else {
             log::error!("Failed to move deeper inside dir. Current value is None");
@@ -91,16 +129,21 @@ impl AlbumsPane {
 }
 
 impl Pane for AlbumsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
         self.browser
             .set_filter_input_active(self.filter_input_mode)
+            .set_draw_borders(context.draw_borders)
+            .set_favorites(&context.favorites)
             .render(area, frame.buffer_mut(), &mut self.stack);
 
         Ok(())
     }
 
     fn before_show(&mut self, context: &AppContext) -> Result<()> {
-        if !self.initialized {
+        if !self.initialized && !context.supports_tag(Tag::Album) {
+            self.browser.set_empty_message(Some(MISSING_TAG_MESSAGE));
+            self.initialized = true;
+        } else if !self.initialized {
             context
                 .query()
                 .id(INIT)
@@ -119,8 +162,9 @@ impl Pane for AlbumsPane {
         Ok(())
     }
 
-    fn on_event(&mut self, event: &mut UiEvent, _is_visible: bool, context: &AppContext) -> Result<()> {
+    fn on_event(&mut self, event: &mut UiEvent, is_visible: bool, context: &AppContext) -> Result<()> {
         match event {
+            UiEvent::Database if !context.supports_tag(Tag::Album) => {}
             UiEvent::Database => {
                 context
                     .query()
@@ -139,6 +183,10 @@ impl Pane for AlbumsPane {
                 self.initialized = false;
                 self.before_show(context)?;
             }
+            // The preview shows the rating of the selected song
+            UiEvent::Sticker if is_visible => {
+                self.prepare_preview(context)?;
+            }
             _ => {}
         }
         Ok(())
@@ -148,6 +196,18 @@ impl Pane for AlbumsPane {
         self.handle_mouse_action(event, context)
     }
 
+    fn go_back(&mut self, context: &AppContext) -> Result<()> {
+        self.go_to_parent(context)
+    }
+
+    fn key_hints(&self, context: &AppContext) -> Option<Vec<KeyHint>> {
+        self.browser_key_hints(context)
+    }
+
+    fn selected_song(&self, _context: &AppContext) -> Option<&Song> {
+        self.browser_selected_song()
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         self.handle_filter_input(event, context)?;
         self.handle_common_action(event, context)?;
@@ -168,6 +228,7 @@ impl Pane for AlbumsPane {
                 context.render()?;
             }
             (INIT, MpdQueryResult::LsInfo { data, origin_path: _ }) => {
+                self.browser.set_empty_message(data.is_empty().then_some(EMPTY_MESSAGE));
                 self.stack = DirStack::new(
                     data.into_iter()
                         .map(|v| DirOrSong::Dir {
@@ -176,7 +237,11 @@ impl Pane for AlbumsPane {
                         })
                         .collect::<Vec<_>>(),
                 );
-                self.prepare_preview(context)?;
+                if self.pending_album.is_some() {
+                    self.open_pending_album(context)?;
+                } else {
+                    self.prepare_preview(context)?;
+                }
             }
             (OPEN_OR_PLAY, MpdQueryResult::DirOrSong { data, origin_path }) => {
                 if let Some(origin_path) = origin_path {
@@ -244,26 +309,53 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
         self.open_or_play(false, context)
     }
 
+    fn filter_for_item(&self, item: &DirOrSong) -> Option<Vec<(Tag, String)>> {
+        let name = item.dir_name_or_file_name().into_owned();
+        match self.stack.path() {
+            [album] => Some(vec![(Tag::File, name), (Tag::Album, album.clone())]),
+            [] => Some(vec![(Tag::Album, name)]),
+            _ => None,
+        }
+    }
+
     fn add(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
         match self.stack.path() {
             [album] => {
                 let album = album.clone();
                 let name = item.dir_name_or_file_name().into_owned();
-                context.command(move |client| {
-                    client.find_add(&[Filter::new(Tag::File, &name), Filter::new(Tag::Album, album.as_str())])?;
-
-                    status_info!("'{name}' added to queue");
-                    Ok(())
-                });
+                add_within_limit(
+                    context,
+                    |_| Ok(1),
+                    move |client| {
+                        client.add_and_autoplay(autoplay, |client| {
+                            client.find_add(
+                                &[Filter::new(Tag::File, &name), Filter::new(Tag::Album, album.as_str())],
+                                None,
+                            )
+                        })?;
+
+                        report_added(feedback, &format!("'{name}'"), 1);
+                        Ok(())
+                    },
+                );
             }
             [] => {
                 let name = item.dir_name_or_file_name().into_owned();
-                context.command(move |client| {
-                    client.find_add(&[Filter::new(Tag::Album, &name)])?;
-
-                    status_info!("Album '{name}' added to queue");
-                    Ok(())
-                });
+                let count_name = name.clone();
+                add_within_limit(
+                    context,
+                    move |client| Ok(client.count(&[Filter::new(Tag::Album, &count_name)])?.songs),
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| {
+                            client.find_add(&[Filter::new(Tag::Album, &name)], None)
+                        })?;
+
+                        report_added(feedback, &format!("Album '{name}'"), count);
+                        Ok(())
+                    },
+                );
             }
             _ => {}
         };
@@ -272,21 +364,34 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
     }
 
     fn add_all(&self, context: &AppContext) -> Result<()> {
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
         match self.stack.path() {
             [album] => {
                 let album = album.clone();
-                context.command(move |client| {
-                    client.find_add(&[Filter::new(Tag::Album, album.as_str())])?;
-                    status_info!("Album '{}' added to queue", album);
-                    Ok(())
-                });
+                let count_album = album.clone();
+                add_within_limit(
+                    context,
+                    move |client| Ok(client.count(&[Filter::new(Tag::Album, &count_album)])?.songs),
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| {
+                            client.find_add(&[Filter::new(Tag::Album, album.as_str())], None)
+                        })?;
+                        report_added(feedback, &format!("Album '{album}'"), count);
+                        Ok(())
+                    },
+                );
             }
             [] => {
-                context.command(move |client| {
-                    client.add("/")?; // add the whole library
-                    status_info!("All albums added to queue");
-                    Ok(())
-                });
+                add_within_limit(
+                    context,
+                    |client| Ok(client.count_in_dir("")?.songs),
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| client.add("/"))?; // add the whole library
+                        report_added(feedback, "All albums", count);
+                        Ok(())
+                    },
+                );
             }
             _ => {}
         };
@@ -305,6 +410,7 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
         match self.stack.path() {
             [album] => {
                 let album = album.clone();
+                let with_rating = context.supported_commands.contains("sticker");
                 context
                     .query()
                     .id(PREVIEW)
@@ -319,13 +425,13 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
                                     album,
                                     current
                                 ))?
-                                .to_preview(&config.theme.symbols)
-                                .collect_vec(),
+                                .to_rated_preview(client, &config.theme.symbols, with_rating),
                         );
                         Ok(MpdQueryResult::Preview { data, origin_path })
                     });
             }
             [] => {
+                let favorites = context.favorites.clone();
                 context
                     .query()
                     .id(PREVIEW)
@@ -334,7 +440,7 @@ impl BrowserPane<DirOrSong> for AlbumsPane {
                     .query(move |client| {
                         let data = Some(
                             list_titles(client, &current)?
-                                .map(|v| v.to_list_item_simple(config))
+                                .map(|v| v.to_list_item_simple(config, &favorites))
                                 .collect_vec(),
                         );
                         Ok(MpdQueryResult::Preview { data, origin_path })
@@ -3,6 +3,7 @@ use std::{cmp::Ordering, collections::HashMap};
 use crate::{
     config::{
         artists::{AlbumDisplayMode, AlbumSortMode},
+        keys::ArtistsActions,
         tabs::PaneType,
     },
     context::AppContext,
@@ -11,10 +12,14 @@ use crate::{
         commands::Song,
         mpd_client::{Filter, MpdClient, Tag},
     },
-    shared::{ext::mpd_client::MpdClientExt, key_event::KeyEvent, macros::status_info, mouse_event::MouseEvent},
+    shared::{
+        add_feedback::report_added, ext::mpd_client::MpdClientExt, key_event::KeyEvent, macros::status_info,
+        mouse_event::MouseEvent,
+    },
     ui::{
-        browser::BrowserPane,
+        browser::{add_within_limit, BrowserPane},
         dirstack::{DirStack, DirStackItem},
+        key_hints::KeyHint,
         widgets::browser::Browser,
         UiEvent,
     },
@@ -42,6 +47,13 @@ pub struct ArtistsPane {
 }
 
 const INIT: &str = "init";
+const ARTISTS_EMPTY_MESSAGE: &str =
+    "No artists found. If this is a fresh MPD setup, run `:update` to scan your music directory.";
+const ALBUM_ARTISTS_EMPTY_MESSAGE: &str =
+    "No album artists found. If this is a fresh MPD setup, run `:update` to scan your music directory.";
+const ARTISTS_MISSING_TAG_MESSAGE: &str = "Artists are not available because MPD does not provide the Artist tag.";
+const ALBUM_ARTISTS_MISSING_TAG_MESSAGE: &str =
+    "Album artists are not available because MPD does not provide the AlbumArtist tag.";
 const OPEN_OR_PLAY: &str = "open_or_play";
 const PREVIEW: &str = "preview";
 
@@ -60,16 +72,44 @@ struct CachedAlbum {
 
 impl ArtistsPane {
     pub fn new(mode: ArtistsPaneMode, context: &AppContext) -> Self {
+        let root_name = match mode {
+            ArtistsPaneMode::AlbumArtist => "Album Artists",
+            ArtistsPaneMode::Artist => "Artists",
+        };
         Self {
             mode,
             stack: DirStack::default(),
             filter_input_mode: false,
-            browser: Browser::new(context.config),
+            browser: Browser::new(context.config, root_name),
             initialized: false,
             cache: ArtistsCache::default(),
         }
     }
 
+    /// Adds every song of the artist under cursor. Only works at the top level where the artists are listed.
+    fn add_discography(&self, context: &AppContext) {
+        if !self.stack.path().is_empty() {
+            return;
+        }
+        let Some(artist) = self
+            .stack
+            .current()
+            .selected()
+            .map(|item| item.dir_name_or_file_name().into_owned())
+        else {
+            return;
+        };
+
+        let autoplay = context.config.autoplay_on_add;
+        context.command(move |client| {
+            match client.add_discography(&artist, autoplay)? {
+                0 => status_info!("No songs found for '{artist}'"),
+                count => status_info!("Added {count} songs by '{artist}' to queue"),
+            }
+            Ok(())
+        });
+    }
+
     fn artist_tag(&self) -> Tag {
         match self.mode {
             ArtistsPaneMode::AlbumArtist => Tag::AlbumArtist,
@@ -210,16 +250,24 @@ impl ArtistsPane {
 }
 
 impl Pane for ArtistsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
         self.browser
             .set_filter_input_active(self.filter_input_mode)
+            .set_draw_borders(context.draw_borders)
+            .set_favorites(&context.favorites)
             .render(area, frame.buffer_mut(), &mut self.stack);
 
         Ok(())
     }
 
     fn before_show(&mut self, context: &AppContext) -> Result<()> {
-        if !self.initialized {
+        if !self.initialized && !context.supports_tag(self.artist_tag()) {
+            self.browser.set_empty_message(Some(match self.mode {
+                ArtistsPaneMode::AlbumArtist => ALBUM_ARTISTS_MISSING_TAG_MESSAGE,
+                ArtistsPaneMode::Artist => ARTISTS_MISSING_TAG_MESSAGE,
+            }));
+            self.initialized = true;
+        } else if !self.initialized {
             let target = self.target_pane();
             let artist_tag = self.artist_tag();
             context
@@ -243,6 +291,7 @@ impl Pane for ArtistsPane {
 
     fn on_event(&mut self, event: &mut UiEvent, _is_visible: bool, context: &AppContext) -> Result<()> {
         match event {
+            UiEvent::Database if !context.supports_tag(self.artist_tag()) => {}
             UiEvent::Database => {
                 let target = self.target_pane();
                 let artist_tag = self.artist_tag();
@@ -273,8 +322,26 @@ impl Pane for ArtistsPane {
         self.handle_mouse_action(event, context)
     }
 
+    fn go_back(&mut self, context: &AppContext) -> Result<()> {
+        self.go_to_parent(context)
+    }
+
+    fn key_hints(&self, context: &AppContext) -> Option<Vec<KeyHint>> {
+        self.browser_key_hints(context)
+    }
+
+    fn selected_song(&self, _context: &AppContext) -> Option<&Song> {
+        self.browser_selected_song()
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         self.handle_filter_input(event, context)?;
+        if let Some(action) = event.as_artists_action(context) {
+            match action {
+                ArtistsActions::AddDiscography => self.add_discography(context),
+            }
+            return Ok(());
+        }
         self.handle_common_action(event, context)?;
         self.handle_global_action(event, context)?;
         Ok(())
@@ -306,7 +373,9 @@ impl Pane for ArtistsPane {
                 let preview = cached_artist
                     .0
                     .iter()
-                    .map(|album| DirOrSong::name_only(album.name.clone()).to_list_item_simple(context.config))
+                    .map(|album| {
+                        DirOrSong::name_only(album.name.clone()).to_list_item_simple(context.config, &context.favorites)
+                    })
                     .collect();
                 self.stack.set_preview(Some(preview));
                 context.render()?;
@@ -341,6 +410,11 @@ impl Pane for ArtistsPane {
                 context.render()?;
             }
             (INIT, MpdQueryResult::LsInfo { data, origin_path: _ }) => {
+                let empty_message = match self.mode {
+                    ArtistsPaneMode::AlbumArtist => ALBUM_ARTISTS_EMPTY_MESSAGE,
+                    ArtistsPaneMode::Artist => ARTISTS_EMPTY_MESSAGE,
+                };
+                self.browser.set_empty_message(data.is_empty().then_some(empty_message));
                 self.stack = DirStack::new(data.into_iter().map(DirOrSong::name_only).collect_vec());
                 self.prepare_preview(context)?;
                 context.render()?;
@@ -400,6 +474,8 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
     }
 
     fn add(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
         match self.stack.path() {
             [artist, album] => {
                 let artist_tag = self.artist_tag();
@@ -419,16 +495,25 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
                     return Ok(());
                 };
 
-                context.command(move |client| {
-                    client.find_add(&[
-                        Filter::new(artist_tag, artist.as_str()),
-                        Filter::new(Tag::Album, original_name.as_str()),
-                        Filter::new(Tag::File, &name),
-                    ])?;
-
-                    status_info!("'{name}' added to queue");
-                    Ok(())
-                });
+                add_within_limit(
+                    context,
+                    |_| Ok(1),
+                    move |client| {
+                        client.add_and_autoplay(autoplay, |client| {
+                            client.find_add(
+                                &[
+                                    Filter::new(artist_tag, artist.as_str()),
+                                    Filter::new(Tag::Album, original_name.as_str()),
+                                    Filter::new(Tag::File, &name),
+                                ],
+                                None,
+                            )
+                        })?;
+
+                        report_added(feedback, &format!("'{name}'"), 1);
+                        Ok(())
+                    },
+                );
             }
             [artist] => {
                 let artist = artist.clone();
@@ -448,25 +533,49 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
                     return Ok(());
                 };
 
-                context.command(move |client| {
-                    client.find_add(&[
-                        Filter::new(artist_tag, artist.as_str()),
-                        Filter::new(Tag::Album, &original_name),
-                    ])?;
-
-                    status_info!("Album '{name}' by '{artist}' added to queue");
-                    Ok(())
-                });
+                let (count_artist, count_album) = (artist.clone(), original_name.clone());
+                add_within_limit(
+                    context,
+                    move |client| {
+                        Ok(client
+                            .count(&[
+                                Filter::new(artist_tag, &count_artist),
+                                Filter::new(Tag::Album, &count_album),
+                            ])?
+                            .songs)
+                    },
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| {
+                            client.find_add(
+                                &[
+                                    Filter::new(artist_tag, artist.as_str()),
+                                    Filter::new(Tag::Album, &original_name),
+                                ],
+                                None,
+                            )
+                        })?;
+
+                        report_added(feedback, &format!("Album '{name}' by '{artist}'"), count);
+                        Ok(())
+                    },
+                );
             }
             [] => {
                 let name = item.dir_name_or_file_name().into_owned();
                 let artist_tag = self.artist_tag();
-                context.command(move |client| {
-                    client.find_add(&[Filter::new(artist_tag, &name)])?;
-
-                    status_info!("All songs by '{name}' added to queue");
-                    Ok(())
-                });
+                let count_name = name.clone();
+                add_within_limit(
+                    context,
+                    move |client| Ok(client.count(&[Filter::new(artist_tag, &count_name)])?.songs),
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| {
+                            client.find_add(&[Filter::new(artist_tag, &name)], None)
+                        })?;
+
+                        report_added(feedback, &format!("All songs by '{name}'"), count);
+                        Ok(())
+                    },
+                );
             }
             _ => {}
         };
@@ -475,6 +584,8 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
     }
 
     fn add_all(&self, context: &AppContext) -> Result<()> {
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
         let artist_tag = self.artist_tag();
         match self.stack.path() {
             [artist, album] => {
@@ -492,29 +603,57 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
                     return Ok(());
                 };
 
-                context.command(move |client| {
-                    client.find_add(&[
-                        Filter::new(artist_tag, artist.as_str()),
-                        Filter::new(Tag::Album, original_name.as_str()),
-                    ])?;
-                    status_info!("Album '{original_name}' by '{artist}' added to queue");
-                    Ok(())
-                });
+                let (count_artist, count_album) = (artist.clone(), original_name.clone());
+                add_within_limit(
+                    context,
+                    move |client| {
+                        Ok(client
+                            .count(&[
+                                Filter::new(artist_tag, &count_artist),
+                                Filter::new(Tag::Album, &count_album),
+                            ])?
+                            .songs)
+                    },
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| {
+                            client.find_add(
+                                &[
+                                    Filter::new(artist_tag, artist.as_str()),
+                                    Filter::new(Tag::Album, original_name.as_str()),
+                                ],
+                                None,
+                            )
+                        })?;
+                        report_added(feedback, &format!("Album '{original_name}' by '{artist}'"), count);
+                        Ok(())
+                    },
+                );
             }
             [artist] => {
                 let artist = artist.clone();
-                context.command(move |client| {
-                    client.find_add(&[Filter::new(artist_tag, artist.as_str())])?;
-                    status_info!("All albums by '{artist}' added to queue");
-                    Ok(())
-                });
+                let count_artist = artist.clone();
+                add_within_limit(
+                    context,
+                    move |client| Ok(client.count(&[Filter::new(artist_tag, &count_artist)])?.songs),
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| {
+                            client.find_add(&[Filter::new(artist_tag, artist.as_str())], None)
+                        })?;
+                        report_added(feedback, &format!("All albums by '{artist}'"), count);
+                        Ok(())
+                    },
+                );
             }
             [] => {
-                context.command(move |client| {
-                    client.add("/")?; // add the whole library
-                    status_info!("All songs added to queue");
-                    Ok(())
-                });
+                add_within_limit(
+                    context,
+                    |client| Ok(client.count_in_dir("")?.songs),
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| client.add("/"))?; // add the whole library
+                        report_added(feedback, "All songs", count);
+                        Ok(())
+                    },
+                );
             }
             _ => {}
         };
@@ -560,7 +699,7 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
                 };
                 let songs = songs
                     .iter()
-                    .map(|song| song.to_list_item_simple(context.config))
+                    .map(|song| song.to_list_item_simple(context.config, &context.favorites))
                     .collect();
                 self.stack_mut().set_preview(Some(songs));
                 context.render()?;
@@ -572,7 +711,8 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
                             .0
                             .iter()
                             .map(|CachedAlbum { name, .. }| {
-                                DirOrSong::name_only(name.to_owned()).to_list_item_simple(context.config)
+                                DirOrSong::name_only(name.to_owned())
+                                    .to_list_item_simple(context.config, &context.favorites)
                             })
                             .collect(),
                     ));
@@ -605,9 +745,11 @@ impl BrowserPane<DirOrSong> for ArtistsPane {
 
 #[cfg(test)]
 mod tests {
+    use ratatui::{backend::TestBackend, Terminal};
+
     use crate::{
         config::{Config, Leak},
-        tests::fixtures::{app_context, config},
+        tests::fixtures::{app_context, config, terminal},
     };
 
     use super::*;
@@ -708,4 +850,38 @@ mod tests {
         assert_eq!(result[0].name, "album_b");
         assert_eq!(result[1].name, "album_a");
     }
+
+    #[rstest]
+    #[case(vec![], true)]
+    #[case(vec!["artist".to_owned()], false)]
+    fn empty_database_shows_hint(
+        app_context: AppContext,
+        mut terminal: Terminal<TestBackend>,
+        #[case] artists: Vec<String>,
+        #[case] expected_hint: bool,
+    ) {
+        let mut pane = ArtistsPane::new(ArtistsPaneMode::Artist, &app_context);
+
+        pane.on_query_finished(
+            INIT,
+            MpdQueryResult::LsInfo {
+                data: artists,
+                origin_path: None,
+            },
+            &app_context,
+        )
+        .unwrap();
+        terminal
+            .draw(|frame| pane.render(frame, frame.area(), &app_context).unwrap())
+            .unwrap();
+
+        let content = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect::<String>();
+        assert_eq!(content.contains("No artists found."), expected_hint);
+    }
 }
@@ -3,17 +3,31 @@ use itertools::Itertools;
 use ratatui::{prelude::Rect, widgets::StatefulWidget, Frame};
 
 use crate::{
-    config::tabs::PaneType,
+    config::{
+        directories::{DirConfirmAction, SongConfirmAction},
+        keys::DirectoriesActions,
+        tabs::PaneType,
+    },
     context::AppContext,
     mpd::{
         client::Client,
         commands::{lsinfo::LsInfoEntry, Song},
+        errors::MpdError,
         mpd_client::{Filter, FilterKind, MpdClient, Tag},
     },
-    shared::{ext::mpd_client::MpdClientExt, key_event::KeyEvent, macros::status_info, mouse_event::MouseEvent},
+    shared::{
+        add_feedback::report_added,
+        events::WorkRequest,
+        ext::mpd_client::MpdClientExt,
+        key_event::KeyEvent,
+        library_tree::{LibraryTree, Preload, PreloadOutcome},
+        macros::status_warn,
+        mouse_event::MouseEvent,
+    },
     ui::{
-        browser::BrowserPane,
+        browser::{add_files, add_within_limit, BrowserPane},
         dirstack::{DirStack, DirStackItem},
+        key_hints::KeyHint,
         widgets::browser::Browser,
         UiEvent,
     },
@@ -28,23 +42,51 @@ pub struct DirectoriesPane {
     filter_input_mode: bool,
     browser: Browser<DirOrSong>,
     initialized: bool,
+    /// Preloaded database the pane is browsed from, when `preload_library` is enabled. `None` until it is loaded or
+    /// after the database changed, the pane falls back to querying MPD for each directory in the meantime.
+    library: Option<LibraryTree>,
+    preload: Preload,
 }
 
 const INIT: &str = "init";
+const EMPTY_MESSAGE: &str =
+    "The music database is empty. If this is a fresh MPD setup, run `:update` to scan your music directory.";
 const OPEN_OR_PLAY: &str = "open_or_play";
 const PREVIEW: &str = "preview";
+pub const PRELOAD_LIBRARY: &str = "preload_library";
 
 impl DirectoriesPane {
     pub fn new(context: &AppContext) -> Self {
-        Self {
+        let mut result = Self {
             stack: DirStack::default(),
             filter_input_mode: false,
-            browser: Browser::new(context.config),
+            browser: Browser::new(context.config, "Directories"),
             initialized: false,
+            library: None,
+            preload: Preload::default(),
+        };
+        result.preload_library(context);
+
+        result
+    }
+
+    fn preload_library(&mut self, context: &AppContext) {
+        if !context.config.preload_library || !context.config.tabs.active_panes.contains(&PaneType::Directories) {
+            return;
+        }
+
+        if self.preload.request() {
+            Self::request_preload(context);
+        }
+    }
+
+    fn request_preload(context: &AppContext) {
+        if let Err(err) = context.work_sender.send(WorkRequest::PreloadLibrary) {
+            log::error!(error:? = err; "Failed to request library preload");
         }
     }
 
-    fn open_or_play(&mut self, autoplay: bool, context: &AppContext) -> Result<()> {
+    fn open_or_add(&mut self, context: &AppContext) -> Result<()> {
         let Some(selected) = self.stack.current().selected() else {
             log::error!("Failed to move deeper inside dir. Current value is None");
             return Ok(());
@@ -56,6 +98,14 @@ impl DirectoriesPane {
 
         match selected {
             DirOrSong::Dir { .. } => {
+                if let Some(items) = self.library.as_ref().and_then(|library| library.dir(&next_path)) {
+                    let items = items.to_vec();
+                    self.stack_mut().push(items);
+                    self.prepare_preview(context)?;
+                    context.render()?;
+                    return Ok(());
+                }
+
                 context
                     .query()
                     .id(OPEN_OR_PLAY)
@@ -87,21 +137,70 @@ impl DirectoriesPane {
             }
             t @ DirOrSong::Song(_) => {
                 self.add(t, context)?;
-                let queue_len = context.queue.len();
-                if autoplay {
-                    context.command(move |client| Ok(client.play_last(queue_len)?));
-                }
             }
         };
 
         Ok(())
     }
+
+    fn add_from_cursor(&self, context: &AppContext) {
+        let dir = self.stack.current();
+        let Some((idx, _)) = dir.selected_with_idx() else {
+            return;
+        };
+
+        let files = songs_from(&dir.items, idx)
+            .into_iter()
+            .filter_map(|item| match item {
+                DirOrSong::Song(song) => Some(song.file),
+                DirOrSong::Dir { .. } => None,
+            })
+            .collect_vec();
+        if !files.is_empty() {
+            add_files(files, "Songs from cursor", context);
+        }
+    }
+}
+
+/// Songs at and after `idx` in the directory listing, in listing order. Subdirectories are skipped.
+fn songs_from(items: &[DirOrSong], idx: usize) -> Vec<DirOrSong> {
+    items
+        .iter()
+        .skip(idx)
+        .filter(|item| matches!(item, DirOrSong::Song(_)))
+        .cloned()
+        .collect()
+}
+
+/// Changes the queue for the `Confirm` action on a song. Songs added and played start at `queue_len`, the length of
+/// the queue before adding.
+fn confirm_song(
+    client: &mut impl MpdClient,
+    action: SongConfirmAction,
+    file: &str,
+    queue_len: usize,
+    autoplay: bool,
+) -> Result<(), MpdError> {
+    match action {
+        SongConfirmAction::Add => client.add_and_autoplay(autoplay, |client| client.add(file)),
+        SongConfirmAction::AddAndPlay => {
+            client.add(file)?;
+            client.play_last(queue_len)
+        }
+        SongConfirmAction::ReplaceAndPlay => {
+            client.clear()?;
+            client.add(file)?;
+            client.play_pos(0)
+        }
+    }
 }
 
 impl Pane for DirectoriesPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> anyhow::Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> anyhow::Result<()> {
         self.browser
             .set_filter_input_active(self.filter_input_mode)
+            .set_draw_borders(context.draw_borders)
+            .set_favorites(&context.favorites)
             .render(area, frame.buffer_mut(), &mut self.stack);
 
         Ok(())
@@ -109,6 +208,14 @@ impl Pane for DirectoriesPane {
 
     fn before_show(&mut self, context: &AppContext) -> Result<()> {
         if !self.initialized {
+            if let Some(root) = self.library.as_ref().and_then(|library| library.dir(&[])) {
+                self.browser.set_empty_message(root.is_empty().then_some(EMPTY_MESSAGE));
+                self.stack = DirStack::new(root.to_vec());
+                self.prepare_preview(context)?;
+                self.initialized = true;
+                return Ok(());
+            }
+
             context
                 .query()
                 .id(INIT)
@@ -132,9 +239,12 @@ impl Pane for DirectoriesPane {
         Ok(())
     }
 
-    fn on_event(&mut self, event: &mut UiEvent, _is_visible: bool, context: &AppContext) -> Result<()> {
+    fn on_event(&mut self, event: &mut UiEvent, is_visible: bool, context: &AppContext) -> Result<()> {
         match event {
             UiEvent::Database => {
+                // The preloaded library is stale now, browse live until it is loaded again
+                self.library = None;
+                self.preload_library(context);
                 context
                     .query()
                     .id(INIT)
@@ -154,9 +264,15 @@ impl Pane for DirectoriesPane {
                     });
             }
             UiEvent::Reconnected => {
+                self.library = None;
+                self.preload_library(context);
                 self.initialized = false;
                 self.before_show(context)?;
             }
+            // The preview shows the rating of the selected song
+            UiEvent::Sticker if is_visible => {
+                self.prepare_preview(context)?;
+            }
             _ => {}
         }
         Ok(())
@@ -166,8 +282,26 @@ impl Pane for DirectoriesPane {
         self.handle_mouse_action(event, context)
     }
 
+    fn go_back(&mut self, context: &AppContext) -> Result<()> {
+        self.go_to_parent(context)
+    }
+
+    fn key_hints(&self, context: &AppContext) -> Option<Vec<KeyHint>> {
+        self.browser_key_hints(context)
+    }
+
+    fn selected_song(&self, _context: &AppContext) -> Option<&Song> {
+        self.browser_selected_song()
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         self.handle_filter_input(event, context)?;
+        if let Some(action) = event.as_directories_action(context) {
+            match action {
+                DirectoriesActions::AddFromCursor => self.add_from_cursor(context),
+            }
+            return Ok(());
+        }
         self.handle_common_action(event, context)?;
         self.handle_global_action(event, context)?;
         Ok(())
@@ -186,6 +320,7 @@ impl Pane for DirectoriesPane {
                 context.render()?;
             }
             (INIT, MpdQueryResult::DirOrSong { data, origin_path: _ }) => {
+                self.browser.set_empty_message(data.is_empty().then_some(EMPTY_MESSAGE));
                 self.stack = DirStack::new(data);
                 self.prepare_preview(context)?;
                 context.render()?;
@@ -201,6 +336,19 @@ impl Pane for DirectoriesPane {
                 self.prepare_preview(context)?;
                 context.render()?;
             }
+            (PRELOAD_LIBRARY, MpdQueryResult::Library(library)) => match self.preload.on_finished(library.is_some()) {
+                PreloadOutcome::Loaded => {
+                    log::debug!("Library preloaded for the directories pane");
+                    self.library = library;
+                }
+                PreloadOutcome::Stale => {
+                    log::debug!("Database changed while preloading the library, loading it again");
+                    Self::request_preload(context);
+                }
+                PreloadOutcome::Failed => {
+                    status_warn!("Failed to preload the library, directories are browsed live for this session");
+                }
+            },
             _ => {}
         };
         Ok(())
@@ -236,6 +384,8 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
     }
 
     fn add(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
         match item {
             DirOrSong::Dir {
                 name: dirname,
@@ -244,22 +394,35 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
                 let mut next_path = self.stack.path().to_vec();
                 next_path.push(dirname.clone());
                 let next_path = next_path.join(std::path::MAIN_SEPARATOR_STR).to_string();
-
-                context.command(move |client| {
-                    client.add(&next_path)?;
-                    status_info!("Directory '{next_path}' added to queue");
-                    Ok(())
-                });
+                let dir = next_path.clone();
+
+                add_within_limit(
+                    context,
+                    move |client| Ok(client.count_in_dir(&dir)?.songs),
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| client.add(&next_path))?;
+                        report_added(feedback, &format!("Directory '{next_path}'"), count);
+                        Ok(())
+                    },
+                );
             }
             DirOrSong::Song(song) => {
                 let file = song.file.clone();
-                context.command(move |client| {
-                    client.add(&file)?;
-                    if let Ok(Some(song)) = client.find_one(&[Filter::new(Tag::File, &file)]) {
-                        status_info!("'{}' by '{}' added to queue", song.title_str(), song.artist_str());
-                    }
-                    Ok(())
-                });
+                add_within_limit(
+                    context,
+                    |_| Ok(1),
+                    move |client| {
+                        client.add_and_autoplay(autoplay, |client| client.add(&file))?;
+                        if let Ok(Some(song)) = client.find_one(&[Filter::new(Tag::File, &file)]) {
+                            report_added(
+                                feedback,
+                                &format!("'{}' by '{}'", song.title_str(), song.artist_str()),
+                                1,
+                            );
+                        }
+                        Ok(())
+                    },
+                );
             }
         };
 
@@ -270,21 +433,55 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
 
     fn add_all(&self, context: &AppContext) -> Result<()> {
         let path = self.stack().path().join(std::path::MAIN_SEPARATOR_STR);
-        context.command(move |client| {
-            client.add(&path)?;
-            status_info!("Directory '{path}' added to queue");
-            Ok(())
-        });
+        let dir = path.clone();
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
+        add_within_limit(
+            context,
+            move |client| Ok(client.count_in_dir(&dir)?.songs),
+            move |client| {
+                let count = client.count_added(autoplay, |client| client.add(&path))?;
+                report_added(feedback, &format!("Directory '{path}'"), count);
+                Ok(())
+            },
+        );
 
         Ok(())
     }
 
     fn open(&mut self, context: &AppContext) -> Result<()> {
-        self.open_or_play(true, context)
+        let Some(selected) = self.stack.current().selected().cloned() else {
+            return Ok(());
+        };
+
+        match selected {
+            DirOrSong::Dir { .. } => {
+                if context.config.directories.confirm_on_dir == DirConfirmAction::AddAndDescend {
+                    self.add(&selected, context)?;
+                }
+                self.open_or_add(context)
+            }
+            DirOrSong::Song(song) => {
+                let action = context.config.directories.confirm_on_song;
+                let feedback = context.config.add_feedback;
+                let autoplay = context.config.autoplay_on_add;
+                let queue_len = context.queue.len();
+                let what = format!("'{}' by '{}'", song.title_str(), song.artist_str());
+                context.command(move |client| {
+                    confirm_song(client, action, &song.file, queue_len, autoplay)?;
+                    if action != SongConfirmAction::ReplaceAndPlay {
+                        report_added(feedback, &what, 1);
+                    }
+                    Ok(())
+                });
+                context.render()?;
+                Ok(())
+            }
+        }
     }
 
     fn next(&mut self, context: &AppContext) -> Result<()> {
-        self.open_or_play(false, context)
+        self.open_or_add(context)
     }
 
     fn prepare_preview(&mut self, context: &AppContext) -> Result<()> {
@@ -295,8 +492,17 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
                     log::error!("Failed to move deeper inside dir. Next path is None");
                     return Ok(());
                 };
-                let next_path = next_path.join("/").to_string();
                 let config = context.config;
+                if let Some(items) = self.library.as_ref().and_then(|library| library.dir(&next_path)) {
+                    let preview = items
+                        .iter()
+                        .map(|item| item.to_list_item_simple(config, &context.favorites))
+                        .collect();
+                    self.stack_mut().set_preview(Some(preview));
+                    return Ok(());
+                }
+                let next_path = next_path.join("/").to_string();
+                let favorites = context.favorites.clone();
 
                 self.stack_mut().clear_preview();
                 context
@@ -326,7 +532,7 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
                             LsInfoEntry::Playlist(_) => None,
                         })
                         .sorted()
-                        .map(|v| v.to_list_item_simple(config))
+                        .map(|v| v.to_list_item_simple(config, &favorites))
                         .collect();
 
                         Ok(MpdQueryResult::Preview {
@@ -338,6 +544,7 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
             Some(DirOrSong::Song(song)) => {
                 let file = song.file.clone();
                 let config = context.config;
+                let with_rating = context.supported_commands.contains("sticker");
                 context
                     .query()
                     .id(PREVIEW)
@@ -347,7 +554,7 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
                         Ok(MpdQueryResult::Preview {
                             data: client
                                 .find_one(&[Filter::new(Tag::File, &file)])?
-                                .map(|v| v.to_preview(&config.theme.symbols).collect()),
+                                .map(|v| v.to_rated_preview(client, &config.theme.symbols, with_rating)),
                             origin_path,
                         })
                     });
@@ -361,3 +568,126 @@ impl BrowserPane<DirOrSong> for DirectoriesPane {
         self.browser.areas
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crossbeam::channel::{Receiver, Sender};
+    use rstest::rstest;
+
+    use super::{confirm_song, songs_from, DirectoriesPane, INIT};
+    use crate::{
+        config::{directories::SongConfirmAction, tabs::PaneType},
+        mpd::{commands::State, mpd_client::MpdClient},
+        shared::{
+            events::{ClientRequest, WorkRequest},
+            mpd_query::MpdQuery,
+        },
+        tests::fixtures::{
+            app_context, client_request_channel,
+            mpd_client::{client, TestMpdClient},
+            work_request_channel,
+        },
+        ui::{
+            browser::BrowserPane,
+            dirstack::DirStack,
+            panes::{browser::DirOrSong, Pane},
+            UiEvent,
+        },
+        MpdQueryResult,
+    };
+
+    fn dir(name: &str) -> DirOrSong {
+        DirOrSong::Dir {
+            name: name.to_owned(),
+            full_path: name.to_owned(),
+        }
+    }
+
+    #[rstest]
+    fn database_change_lists_root_again_after_going_to_root(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+    ) {
+        let rx = client_request_channel.1.clone();
+        let app_context = app_context(work_request_channel, client_request_channel);
+        let mut pane = DirectoriesPane::new(&app_context);
+        pane.stack = DirStack::new(vec![dir("a"), dir("b")]);
+        pane.stack.push(vec![dir("a/c")]);
+        assert_eq!(pane.stack.path(), ["a"]);
+
+        pane.go_to_root(&app_context).unwrap();
+        assert!(pane.stack.path().is_empty());
+
+        while rx.try_recv().is_ok() {}
+        pane.on_event(&mut UiEvent::Database, true, &app_context).unwrap();
+        let requested_root = std::iter::from_fn(|| rx.recv_timeout(Duration::from_millis(100)).ok()).any(|request| {
+            matches!(
+                request,
+                ClientRequest::Query(MpdQuery {
+                    id: INIT,
+                    target: Some(PaneType::Directories),
+                    ..
+                })
+            )
+        });
+        assert!(requested_root);
+
+        pane.on_query_finished(
+            INIT,
+            MpdQueryResult::DirOrSong {
+                data: vec![dir("b"), dir("d")],
+                origin_path: None,
+            },
+            &app_context,
+        )
+        .unwrap();
+        assert!(pane.stack.path().is_empty());
+        assert_eq!(pane.stack.current().items, vec![dir("b"), dir("d")]);
+    }
+
+    #[rstest]
+    #[case(SongConfirmAction::Add, vec![0, 1, 2, 3], None, false)]
+    #[case(SongConfirmAction::AddAndPlay, vec![0, 1, 2, 3], Some(3), false)]
+    #[case(SongConfirmAction::ReplaceAndPlay, vec![3], Some(0), true)]
+    fn confirm_on_song_changes_queue_as_configured(
+        mut client: TestMpdClient,
+        #[case] action: SongConfirmAction,
+        #[case] expected_queue: Vec<usize>,
+        #[case] expected_current: Option<usize>,
+        #[case] expected_clear: bool,
+    ) {
+        client.queue = vec![0, 1, 2];
+        client.status.state = State::Stop;
+
+        confirm_song(&mut client, action, "artist_1_album_1_file_3", 3, false).expect("confirm to succeed");
+
+        assert_eq!(client.queue, expected_queue);
+        assert_eq!(client.current_song_idx, expected_current);
+        assert_eq!(client.call_order.contains(&"clear"), expected_clear);
+    }
+
+    #[rstest]
+    fn add_from_cursor_enqueues_selected_song_and_the_rest(mut client: TestMpdClient) {
+        let mut items = vec![DirOrSong::Dir {
+            name: "subdir".to_owned(),
+            full_path: "artist_1/subdir".to_owned(),
+        }];
+        items.extend(client.songs[0..5].iter().cloned().map(DirOrSong::Song));
+
+        // Second of the five songs, the directory is listed first
+        let songs = songs_from(&items, 2);
+        let files = songs
+            .iter()
+            .filter_map(|item| match item {
+                DirOrSong::Song(song) => Some(song.file.as_str()),
+                DirOrSong::Dir { .. } => None,
+            })
+            .collect::<Vec<_>>();
+        client.add_multiple(&files).expect("add to succeed");
+
+        assert_eq!(client.queue, vec![1, 2, 3, 4]);
+        assert_eq!(client.calls.get("add_multiple"), Some(&1));
+    }
+}
@@ -71,11 +71,9 @@ impl Pane for LogsPane {
         let logs_wg = List::new(lines)
             .style(config.as_text_style())
             .highlight_style(config.theme.current_item_style);
-        frame.render_stateful_widget(
-            config.as_styled_scrollbar(),
-            area,
-            self.scrolling_state.as_scrollbar_state_ref(),
-        );
+        if let Some(scrollbar) = config.as_styled_scrollbar(&self.scrolling_state) {
+            frame.render_stateful_widget(scrollbar, area, self.scrolling_state.as_scrollbar_state_ref());
+        }
         let mut area = area;
         area.width = area.width.saturating_sub(1);
         frame.render_stateful_widget(logs_wg, area, self.scrolling_state.as_render_state_ref());
@@ -109,12 +107,14 @@ impl Pane for LogsPane {
 
         match event.kind {
             MouseEventKind::ScrollUp => {
-                self.scrolling_state.prev(context.config.scrolloff, false);
+                self.scrolling_state
+                    .scroll_up(context.config.scroll_amount, context.config.scrolloff);
 
                 context.render()?;
             }
             MouseEventKind::ScrollDown => {
-                self.scrolling_state.next(context.config.scrolloff, false);
+                self.scrolling_state
+                    .scroll_down(context.config.scroll_amount, context.config.scrolloff);
 
                 context.render()?;
             }
@@ -149,6 +149,16 @@ impl Pane for LogsPane {
 
                     context.render()?;
                 }
+                CommonAction::PageDown => {
+                    self.scrolling_state.next_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
+                CommonAction::PageUp => {
+                    self.scrolling_state.prev_viewport(context.config.scrolloff);
+
+                    context.render()?;
+                }
                 CommonAction::Up => {
                     self.scrolling_state
                         .prev(context.config.scrolloff, config.wrap_navigation);
@@ -179,6 +189,7 @@ impl Pane for LogsPane {
                 CommonAction::Add => {}
                 CommonAction::Select => {}
                 CommonAction::InvertSelection => {}
+                CommonAction::SelectAll => {}
                 CommonAction::Delete => {}
                 CommonAction::Rename => {}
                 CommonAction::MoveUp => {}
@@ -187,10 +198,16 @@ impl Pane for LogsPane {
                 CommonAction::Confirm => {}
                 CommonAction::FocusInput => {}
                 CommonAction::AddAll => {}
+                CommonAction::CopyFilePath => {}
+                CommonAction::OpenInTagEditor => {}
                 CommonAction::PaneDown => {}
                 CommonAction::PaneUp => {}
                 CommonAction::PaneRight => {}
                 CommonAction::PaneLeft => {}
+                CommonAction::GoToParent => {}
+                CommonAction::GoToRoot => {}
+                CommonAction::IncreaseRating => {}
+                CommonAction::DecreaseRating => {}
             }
         }
 
@@ -16,6 +16,7 @@ use ratatui::{
     text::{Line, Span},
     Frame,
 };
+use recently_added::RecentlyAddedPane;
 use search::SearchPane;
 use strum::Display;
 
@@ -29,12 +30,24 @@ use crate::{
         },
     },
     context::AppContext,
-    mpd::commands::{status::OnOffOneshot, volume::Bound, Song, Status},
-    shared::{ext::duration::DurationExt, key_event::KeyEvent, mouse_event::MouseEvent},
+    mpd::{
+        commands::{
+            status::{AudioQuality, OnOffOneshot},
+            volume::Bound,
+            ReplayGainMode, Song, Status,
+        },
+        mpd_client::{Filter, FilterKind, Tag},
+    },
+    shared::{
+        ext::duration::{humanize_since, DurationExt},
+        favorites::{self, Favorites},
+        key_event::KeyEvent,
+        mouse_event::MouseEvent,
+    },
     MpdQueryResult,
 };
 
-use super::{widgets::volume::Volume, UiEvent};
+use super::{key_hints::KeyHint, widgets::volume::Volume, UiEvent};
 
 pub mod album_art;
 pub mod albums;
@@ -45,6 +58,7 @@ pub mod logs;
 pub mod lyrics;
 pub mod playlists;
 pub mod queue;
+pub mod recently_added;
 pub mod search;
 
 #[derive(Debug, Display, strum::EnumDiscriminants)]
@@ -57,6 +71,7 @@ pub enum Panes<'a> {
     AlbumArtists(&'a mut ArtistsPane),
     Albums(&'a mut AlbumsPane),
     Playlists(&'a mut PlaylistsPane),
+    RecentlyAdded(&'a mut RecentlyAddedPane),
     Search(&'a mut SearchPane),
     AlbumArt(&'a mut AlbumArtPane),
     Lyrics(&'a mut LyricsPane),
@@ -72,6 +87,7 @@ pub struct PaneContainer {
     pub artists: ArtistsPane,
     pub album_artists: ArtistsPane,
     pub playlists: PlaylistsPane,
+    pub recently_added: RecentlyAddedPane,
     pub search: SearchPane,
     pub album_art: AlbumArtPane,
     pub lyrics: LyricsPane,
@@ -88,6 +104,7 @@ impl PaneContainer {
             artists: ArtistsPane::new(ArtistsPaneMode::Artist, context),
             album_artists: ArtistsPane::new(ArtistsPaneMode::AlbumArtist, context),
             playlists: PlaylistsPane::new(context),
+            recently_added: RecentlyAddedPane::new(context),
             search: SearchPane::new(context),
             album_art: AlbumArtPane::new(context),
             lyrics: LyricsPane::new(context),
@@ -104,6 +121,7 @@ impl PaneContainer {
             PaneType::AlbumArtists => Panes::AlbumArtists(&mut self.album_artists),
             PaneType::Albums => Panes::Albums(&mut self.albums),
             PaneType::Playlists => Panes::Playlists(&mut self.playlists),
+            PaneType::RecentlyAdded => Panes::RecentlyAdded(&mut self.recently_added),
             PaneType::Search => Panes::Search(&mut self.search),
             PaneType::AlbumArt => Panes::AlbumArt(&mut self.album_art),
             PaneType::Lyrics => Panes::Lyrics(&mut self.lyrics),
@@ -140,8 +158,23 @@ pub(super) trait Pane {
         Ok(())
     }
 
+    /// Moves one level up, like leaving a directory in the browsers. Panes without levels do nothing.
+    fn go_back(&mut self, context: &AppContext) -> Result<()> {
+        Ok(())
+    }
+
     fn calculate_areas(&mut self, area: Rect, context: &AppContext) {}
 
+    /// Keys available in the current mode of the pane, like filter input. Shown in the bottom bar while active.
+    fn key_hints(&self, context: &AppContext) -> Option<Vec<KeyHint>> {
+        None
+    }
+
+    /// Song under the cursor, used to play a snippet of it in preview play mode
+    fn selected_song(&self, context: &AppContext) -> Option<&Song> {
+        None
+    }
+
     fn resize(&mut self, area: Rect, context: &AppContext) -> Result<()> {
         Ok(())
     }
@@ -160,10 +193,44 @@ pub(crate) mod browser {
 
     use crate::{
         config::theme::SymbolsConfig,
-        mpd::commands::{lsinfo::LsInfoEntry, Song},
+        core::command::RATING_STICKER,
+        mpd::{
+            commands::{lsinfo::LsInfoEntry, Song},
+            mpd_client::MpdClient,
+        },
     };
 
     impl Song {
+        /// Preview of the song followed by its rating when `with_rating` is set. The rating is left out when it
+        /// cannot be read so that the rest of the preview is still shown.
+        pub(crate) fn to_rated_preview(
+            &self,
+            client: &mut impl MpdClient,
+            symbols: &SymbolsConfig,
+            with_rating: bool,
+        ) -> Vec<ListItem<'static>> {
+            let rating = if with_rating {
+                client
+                    .sticker_get(&self.file, RATING_STICKER)
+                    .inspect_err(|err| log::warn!(error:? = err, file = self.file.as_str(); "Failed to read rating"))
+                    .ok()
+                    .flatten()
+            } else {
+                None
+            };
+
+            self.to_preview(symbols)
+                .chain(rating.map(|rating| {
+                    ListItem::new(Line::from(vec![
+                        Span::from(" "),
+                        Span::styled("Rating", Style::default().fg(Color::Yellow)),
+                        Span::from(": "),
+                        Span::from(rating),
+                    ]))
+                }))
+                .collect()
+        }
+
         pub(crate) fn to_preview(&self, _symbols: &SymbolsConfig) -> impl Iterator<Item = ListItem<'static>> {
             let key_style = Style::default().fg(Color::Yellow);
             let separator = Span::from(": ");
@@ -503,27 +570,73 @@ impl Song {
                 .get("track")
                 .map(|v| Cow::Owned(v.parse::<u32>().map_or_else(|_| v.clone(), |v| format!("{v:0>2}")))),
             SongProperty::Duration => self.duration.map(|d| Cow::Owned(d.to_string())),
+            SongProperty::Added => self
+                .metadata
+                .get("last-modified")
+                .and_then(|v| humanize_since(v, chrono::Utc::now()))
+                .map(Cow::Owned),
             SongProperty::Other(name) => self.metadata.get(*name).map(|v| Cow::Borrowed(v.as_str())),
         }
     }
 
-    pub fn matches(&self, formats: &[&Property<'static, SongProperty>], filter: &str) -> bool {
+    pub fn matches(&self, formats: &[&Property<'static, SongProperty>], favorites: &Favorites, filter: &str) -> bool {
+        self.query_score(formats, favorites, filter, FilterKind::Contains)
+            .is_some()
+    }
+
+    /// Best score of the formatted properties for the filter, see [`FilterKind::score`]
+    pub fn filter_score(
+        &self,
+        formats: &[&Property<'static, SongProperty>],
+        filter: &str,
+        kind: FilterKind,
+    ) -> Option<u32> {
+        let mut best = None;
         for format in formats {
-            let match_found = match &format.kind {
-                PropertyKindOrText::Text(value) => Some(value.to_lowercase().contains(&filter.to_lowercase())),
+            let score = match &format.kind {
+                PropertyKindOrText::Text(value) => kind.score(value, filter),
                 PropertyKindOrText::Property(property) => self.format(property).map_or_else(
-                    || format.default.map(|f| self.matches(&[f], filter)),
-                    |p| Some(p.to_lowercase().contains(filter)),
+                    || format.default.and_then(|f| self.filter_score(&[f], filter, kind)),
+                    |p| kind.score(&p, filter),
                 ),
-                PropertyKindOrText::Group(_) => format
-                    .as_string(Some(self))
-                    .map(|v| v.to_lowercase().contains(&filter.to_lowercase())),
+                PropertyKindOrText::Group(_) => format.as_string(Some(self)).and_then(|v| kind.score(&v, filter)),
             };
-            if match_found.is_some_and(|v| v) {
-                return true;
+            best = best.max(score);
+        }
+        best
+    }
+
+    /// Score for a filter typed in the browsers, see [`Filter::parse_query`]. Every filter has to match. [`Tag::Any`]
+    /// filters are matched against the formatted properties, the rest against the tag of the song. The
+    /// [`favorites::FAVORITES_FILTER`] word only keeps favorite songs.
+    pub fn query_score(
+        &self,
+        formats: &[&Property<'static, SongProperty>],
+        favorites: &Favorites,
+        query: &str,
+        kind: FilterKind,
+    ) -> Option<u32> {
+        let (favorites_only, query) = favorites::split_filter(query);
+        if favorites_only {
+            if !favorites.contains(&self.file) {
+                return None;
+            }
+            if query.is_empty() {
+                return Some(0);
             }
         }
-        return false;
+
+        Filter::parse_query(&query, kind)
+            .iter()
+            .map(|filter| match filter.tag {
+                Tag::Any => self.filter_score(formats, filter.value, kind),
+                Tag::File => kind.score(&self.file, filter.value),
+                tag => self
+                    .metadata
+                    .get(&tag.as_str().to_lowercase())
+                    .and_then(|value| kind.score(value, filter.value)),
+            })
+            .sum()
     }
 
     fn default_as_line_ellipsized<'song>(
@@ -652,34 +765,67 @@ impl Property<'static, PropertyKind> {
                     || self.default_as_span(song, status),
                     |v| Some(Either::Left(Span::styled(v.to_string(), style))),
                 ),
+                StatusProperty::Output => status.output.as_deref().filter(|v| !v.is_empty()).map_or_else(
+                    || self.default_as_span(song, status),
+                    |v| Some(Either::Left(Span::styled(v, style))),
+                ),
             },
             PropertyKindOrText::Property(PropertyKind::Widget(w)) => match w {
                 WidgetProperty::Volume => Some(Either::Left(Span::styled(
                     Volume::get_str(*status.volume.value()),
                     style,
                 ))),
+                WidgetProperty::Quality {
+                    high_style,
+                    medium_style,
+                    low_style,
+                    high_bitrate,
+                    low_bitrate,
+                } => status
+                    .audio_quality(song.map(|song| song.file.as_str()), *high_bitrate, *low_bitrate)
+                    .map_or_else(
+                        || self.default_as_span(song, status),
+                        |quality| {
+                            let quality_style = match quality {
+                                AudioQuality::High => high_style,
+                                AudioQuality::Medium => medium_style,
+                                AudioQuality::Low => low_style,
+                            };
+                            Some(Either::Left(Span::styled("●", style.patch(*quality_style))))
+                        },
+                    ),
                 WidgetProperty::States {
                     active_style,
                     separator_style,
+                    oneshot_style,
                 } => {
                     let separator = Span::styled(" / ", *separator_style);
-                    Some(Either::Right(vec![
-                        Span::styled("Repeat", if status.repeat { *active_style } else { style }),
+                    let mut spans = vec![
+                        if status.is_repeat_one() {
+                            Span::styled("Repeat one", *active_style)
+                        } else {
+                            Span::styled("Repeat", if status.repeat { *active_style } else { style })
+                        },
                         separator.clone(),
                         Span::styled("Random", if status.random { *active_style } else { style }),
                         separator.clone(),
                         match status.consume {
                             OnOffOneshot::On => Span::styled("Consume", *active_style),
                             OnOffOneshot::Off => Span::styled("Consume", style),
-                            OnOffOneshot::Oneshot => Span::styled("Oneshot(C)", *active_style),
+                            OnOffOneshot::Oneshot => Span::styled(" Consume once ", *oneshot_style),
                         },
-                        separator,
+                        separator.clone(),
                         match status.single {
                             OnOffOneshot::On => Span::styled("Single", *active_style),
                             OnOffOneshot::Off => Span::styled("Single", style),
-                            OnOffOneshot::Oneshot => Span::styled("Oneshot(S)", *active_style),
+                            OnOffOneshot::Oneshot => Span::styled(" Single once ", *oneshot_style),
                         },
-                    ]))
+                    ];
+                    if let Some(mode) = status.replay_gain_mode.filter(|mode| *mode != ReplayGainMode::Off) {
+                        spans.push(separator);
+                        spans.push(Span::styled(format!("ReplayGain {mode}"), *active_style));
+                    }
+                    Some(Either::Right(spans))
                 }
             },
             PropertyKindOrText::Group(group) => {
@@ -765,9 +911,11 @@ mod format_tests {
         use ratatui::text::Span;
         use test_case::test_case;
 
+        use ratatui::style::{Color, Style};
+
         use crate::{
-            config::theme::properties::{PropertyKind, StatusProperty},
-            mpd::commands::{status::OnOffOneshot, State, Status, Volume},
+            config::theme::properties::{PropertyKind, StatusProperty, WidgetProperty},
+            mpd::commands::{status::OnOffOneshot, ReplayGainMode, State, Status, Volume},
         };
 
         use super::*;
@@ -802,6 +950,25 @@ mod format_tests {
             assert_eq!(result, Some(expected.to_string()));
         }
 
+        #[test]
+        fn added_property_resolves_to_relative_time() {
+            let format = Property::<'static, SongProperty> {
+                kind: PropertyKindOrText::Property(SongProperty::Added),
+                style: None,
+                default: None,
+            };
+            let last_modified = (chrono::Utc::now() - chrono::Duration::days(2)).to_rfc3339();
+
+            let song = Song {
+                metadata: HashMap::from([("last-modified".to_string(), last_modified)]),
+                ..Default::default()
+            };
+
+            let result = format.as_string(Some(&song));
+
+            assert_eq!(result, Some("2d ago".to_string()));
+        }
+
         #[test_case(StatusProperty::Volume, "100")]
         #[test_case(StatusProperty::Repeat, "On")]
         #[test_case(StatusProperty::Random, "On")]
@@ -811,6 +978,7 @@ mod format_tests {
         #[test_case(StatusProperty::Duration, "2:03")]
         #[test_case(StatusProperty::Crossfade, "3")]
         #[test_case(StatusProperty::Bitrate, "123")]
+        #[test_case(StatusProperty::Output, "Speakers")]
         fn status_property_resolves_correctly(prop: StatusProperty, expected: &str) {
             let format = Property::<'static, PropertyKind> {
                 kind: PropertyKindOrText::Property(PropertyKind::Status(prop)),
@@ -840,6 +1008,7 @@ mod format_tests {
                 duration: Duration::from_secs(123),
                 xfade: Some(3),
                 state: State::Play,
+                output: Some("Speakers".to_owned()),
                 ..Default::default()
             };
 
@@ -850,6 +1019,110 @@ mod format_tests {
                 Some(either::Either::<Span<'_>, Vec<Span<'_>>>::Left(Span::raw(expected)))
             );
         }
+
+        #[test]
+        fn states_widget_shows_oneshot_badge_until_song_ends() {
+            let active_style = Style::default().fg(Color::White);
+            let oneshot_style = Style::default().fg(Color::Red);
+            let format = Property::<'static, PropertyKind> {
+                kind: PropertyKindOrText::Property(PropertyKind::Widget(WidgetProperty::States {
+                    active_style,
+                    separator_style: Style::default(),
+                    oneshot_style,
+                })),
+                style: None,
+                default: None,
+            };
+            let mut status = Status {
+                songid: Some(1),
+                single: OnOffOneshot::Oneshot,
+                consume: OnOffOneshot::On,
+                ..Default::default()
+            };
+            let states = |status: &Status| match format.as_span(None, status) {
+                Some(either::Either::Right(spans)) => spans
+                    .into_iter()
+                    .filter(|span| span.content != " / ")
+                    .map(|span| (span.content.into_owned(), span.style))
+                    .collect::<Vec<_>>(),
+                _ => Vec::new(),
+            };
+
+            assert_eq!(
+                states(&status)[2..],
+                [
+                    ("Consume".to_owned(), active_style),
+                    (" Single once ".to_owned(), oneshot_style),
+                ]
+            );
+
+            // MPD turns the oneshot mode off once the current song ends
+            status.songid = Some(2);
+            status.single = OnOffOneshot::Off;
+
+            assert_eq!(
+                states(&status)[2..],
+                [
+                    ("Consume".to_owned(), active_style),
+                    ("Single".to_owned(), Style::default()),
+                ]
+            );
+        }
+
+        #[test]
+        fn states_widget_distinguishes_repeat_one_from_repeat() {
+            let active_style = Style::default().fg(Color::White);
+            let format = Property::<'static, PropertyKind> {
+                kind: PropertyKindOrText::Property(PropertyKind::Widget(WidgetProperty::States {
+                    active_style,
+                    separator_style: Style::default(),
+                    oneshot_style: Style::default(),
+                })),
+                style: None,
+                default: None,
+            };
+            let mut status = Status {
+                repeat: true,
+                ..Default::default()
+            };
+            let repeat = |status: &Status| match format.as_span(None, status) {
+                Some(either::Either::Right(spans)) => spans.into_iter().next().map(|span| span.content.into_owned()),
+                _ => None,
+            };
+
+            assert_eq!(repeat(&status).as_deref(), Some("Repeat"));
+
+            status.single = OnOffOneshot::On;
+
+            assert_eq!(repeat(&status).as_deref(), Some("Repeat one"));
+        }
+
+        #[test]
+        fn states_widget_shows_replay_gain_mode_unless_off() {
+            let format = Property::<'static, PropertyKind> {
+                kind: PropertyKindOrText::Property(PropertyKind::Widget(WidgetProperty::States {
+                    active_style: Style::default(),
+                    separator_style: Style::default(),
+                    oneshot_style: Style::default(),
+                })),
+                style: None,
+                default: None,
+            };
+            let mut status = Status {
+                replay_gain_mode: Some(ReplayGainMode::Off),
+                ..Default::default()
+            };
+            let last = |status: &Status| match format.as_span(None, status) {
+                Some(either::Either::Right(spans)) => spans.last().map(|span| span.content.clone().into_owned()),
+                _ => None,
+            };
+
+            assert_eq!(last(&status).as_deref(), Some("Single"));
+
+            status.replay_gain_mode = Some(ReplayGainMode::Album);
+
+            assert_eq!(last(&status).as_deref(), Some("ReplayGain Album"));
+        }
     }
 
     mod property {
@@ -1178,4 +1451,74 @@ mod format_tests {
             assert_eq!(result, Some("innerfallbackouter".to_owned()));
         }
     }
+
+    mod query {
+        use std::collections::{HashMap, HashSet};
+
+        use test_case::test_case;
+
+        use super::*;
+        use crate::{mpd::mpd_client::FilterKind, shared::favorites::Favorites};
+
+        #[test_case("time", true; "plain filter matches formatted title")]
+        #[test_case("artist:pink", true; "tag filter")]
+        #[test_case("artist:pink title:time", true; "multiple tags")]
+        #[test_case("artist:pink title:money", false; "one tag does not match")]
+        #[test_case("artist:time", false; "title does not match artist tag")]
+        #[test_case("album:dark time", true; "tag and bare word")]
+        #[test_case("file:music/time", true; "file path")]
+        fn matches_all_parts_of_query(query: &str, expected: bool) {
+            let format = Property::<'static, SongProperty> {
+                kind: PropertyKindOrText::Property(SongProperty::Title),
+                style: None,
+                default: None,
+            };
+            let song = Song {
+                file: "music/time.flac".to_owned(),
+                metadata: HashMap::from([
+                    ("title".to_string(), "Time".to_owned()),
+                    ("artist".to_string(), "Pink Floyd".to_owned()),
+                    ("album".to_string(), "The Dark Side of the Moon".to_owned()),
+                ]),
+                ..Default::default()
+            };
+
+            let result = song.query_score(&[&format], &Favorites::default(), query, FilterKind::Contains);
+
+            assert_eq!(result.is_some(), expected);
+        }
+
+        #[test]
+        fn favorites_filter_only_matches_favorites() {
+            let format = Property::<'static, SongProperty> {
+                kind: PropertyKindOrText::Property(SongProperty::Title),
+                style: None,
+                default: None,
+            };
+            let song = |file: &str| Song {
+                file: file.to_owned(),
+                metadata: HashMap::from([("title".to_string(), "Money".to_owned())]),
+                ..Default::default()
+            };
+            let favorite = song("query_tests/favorite.flac");
+            let other = song("query_tests/other.flac");
+            let favorites = Favorites::new(HashSet::from([favorite.file.clone()]));
+
+            assert!(favorite
+                .query_score(&[&format], &favorites, "is:favorite", FilterKind::Exact)
+                .is_some());
+            assert!(favorite
+                .query_score(&[&format], &favorites, "money is:favorite", FilterKind::Contains)
+                .is_some());
+            assert!(favorite
+                .query_score(&[&format], &favorites, "time is:favorite", FilterKind::Contains)
+                .is_none());
+            assert!(other
+                .query_score(&[&format], &favorites, "is:favorite", FilterKind::Contains)
+                .is_none());
+            assert!(other
+                .query_score(&[&format], &favorites, "money", FilterKind::Contains)
+                .is_some());
+        }
+    }
 }
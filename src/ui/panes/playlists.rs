@@ -11,14 +11,16 @@ use crate::{
         mpd_client::{Filter, MpdClient, SingleOrRange, Tag},
     },
     shared::{
+        add_feedback::report_added,
         ext::mpd_client::MpdClientExt,
         key_event::KeyEvent,
         macros::{modal, status_error, status_info},
         mouse_event::MouseEvent,
     },
     ui::{
-        browser::{BrowserPane, MoveDirection},
+        browser::{add_within_limit, BrowserPane, MoveDirection},
         dirstack::{DirStack, DirStackItem},
+        key_hints::KeyHint,
         modals::{confirm_modal::ConfirmModal, input_modal::InputModal},
         widgets::browser::Browser,
         UiEvent,
@@ -41,6 +43,7 @@ pub struct PlaylistsPane {
 }
 
 const INIT: &str = "init";
+const EMPTY_MESSAGE: &str = "No playlists saved yet. Save the queue or search results as a playlist to see it here.";
 const REINIT: &str = "reinit";
 const OPEN_OR_PLAY: &str = "open_or_play";
 const PREVIEW: &str = "preview";
@@ -50,7 +53,7 @@ impl PlaylistsPane {
         Self {
             stack: DirStack::default(),
             filter_input_mode: false,
-            browser: Browser::new(context.config),
+            browser: Browser::new(context.config, "Playlists"),
             initialized: false,
             selected_song: None,
         }
@@ -99,9 +102,11 @@ impl PlaylistsPane {
 }
 
 impl Pane for PlaylistsPane {
-    fn render(&mut self, frame: &mut Frame, area: Rect, _context: &AppContext) -> Result<()> {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
         self.browser
             .set_filter_input_active(self.filter_input_mode)
+            .set_draw_borders(context.draw_borders)
+            .set_favorites(&context.favorites)
             .render(area, frame.buffer_mut(), &mut self.stack);
 
         Ok(())
@@ -136,7 +141,7 @@ impl Pane for PlaylistsPane {
         Ok(())
     }
 
-    fn on_event(&mut self, event: &mut UiEvent, _is_visible: bool, context: &AppContext) -> Result<()> {
+    fn on_event(&mut self, event: &mut UiEvent, is_visible: bool, context: &AppContext) -> Result<()> {
         let id = match event {
             UiEvent::Database => Some(INIT),
             UiEvent::StoredPlaylist => Some(REINIT),
@@ -172,6 +177,10 @@ impl Pane for PlaylistsPane {
                 self.initialized = false;
                 self.before_show(context)?;
             }
+            // The preview shows the rating of the selected song
+            UiEvent::Sticker if is_visible => {
+                self.prepare_preview(context)?;
+            }
             _ => {}
         }
 
@@ -182,6 +191,18 @@ impl Pane for PlaylistsPane {
         self.handle_mouse_action(event, context)
     }
 
+    fn go_back(&mut self, context: &AppContext) -> Result<()> {
+        self.go_to_parent(context)
+    }
+
+    fn key_hints(&self, context: &AppContext) -> Option<Vec<KeyHint>> {
+        self.browser_key_hints(context)
+    }
+
+    fn selected_song(&self, _context: &AppContext) -> Option<&Song> {
+        self.browser_selected_song()
+    }
+
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
         self.handle_filter_input(event, context)?;
         self.handle_common_action(event, context)?;
@@ -214,10 +235,12 @@ impl Pane for PlaylistsPane {
                 context.render()?;
             }
             (INIT, MpdQueryResult::DirOrSong { data, origin_path: _ }) => {
+                self.browser.set_empty_message(data.is_empty().then_some(EMPTY_MESSAGE));
                 self.stack = DirStack::new(data);
                 self.prepare_preview(context)?;
             }
             (REINIT, MpdQueryResult::DirOrSong { data, .. }) => {
+                self.browser.set_empty_message(data.is_empty().then_some(EMPTY_MESSAGE));
                 let mut new_stack = DirStack::new(data);
                 let old_viewport_len = self.stack.current().state.viewport_len();
                 let old_content_len = self.stack.current().state.content_len();
@@ -373,20 +396,26 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
     }
 
     fn add_all(&self, context: &AppContext) -> Result<()> {
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
         match self.stack().path() {
             [playlist] => {
                 let playlist = playlist.clone();
-                context.command(move |client| {
-                    client.load_playlist(&playlist)?;
-                    status_info!("Playlist '{playlist}' added to queue");
-                    Ok(())
-                });
+                let count_playlist = playlist.clone();
+                add_within_limit(
+                    context,
+                    move |client| Ok(client.list_playlist(&count_playlist)?.0.len()),
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| client.load_playlist(&playlist))?;
+                        report_added(feedback, &format!("Playlist '{playlist}'"), count);
+                        Ok(())
+                    },
+                );
             }
             [] => {
                 for playlist in &self.stack().current().items {
                     self.add(playlist, context)?;
                 }
-                status_info!("All playlists added to queue");
             }
             _ => {}
         };
@@ -395,24 +424,39 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
     }
 
     fn add(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
         match item {
             DirOrSong::Dir { name: d, .. } => {
                 let d = d.clone();
-                context.command(move |client| {
-                    client.load_playlist(&d)?;
-                    status_info!("Playlist '{d}' added to queue");
-                    Ok(())
-                });
+                let count_playlist = d.clone();
+                add_within_limit(
+                    context,
+                    move |client| Ok(client.list_playlist(&count_playlist)?.0.len()),
+                    move |client| {
+                        let count = client.count_added(autoplay, |client| client.load_playlist(&d))?;
+                        report_added(feedback, &format!("Playlist '{d}'"), count);
+                        Ok(())
+                    },
+                );
             }
             DirOrSong::Song(s) => {
                 let file = s.file.clone();
-                context.command(move |client| {
-                    client.add(&file)?;
-                    if let Ok(Some(song)) = client.find_one(&[Filter::new(Tag::File, &file)]) {
-                        status_info!("'{}' by '{}' added to queue", song.title_str(), song.artist_str());
-                    }
-                    Ok(())
-                });
+                add_within_limit(
+                    context,
+                    |_| Ok(1),
+                    move |client| {
+                        client.add_and_autoplay(autoplay, |client| client.add(&file))?;
+                        if let Ok(Some(song)) = client.find_one(&[Filter::new(Tag::File, &file)]) {
+                            report_added(
+                                feedback,
+                                &format!("'{}' by '{}'", song.title_str(), song.artist_str()),
+                                1,
+                            );
+                        }
+                        Ok(())
+                    },
+                );
             }
         };
 
@@ -492,6 +536,8 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
 
     fn prepare_preview(&mut self, context: &AppContext) -> Result<()> {
         let config = context.config;
+        let favorites = context.favorites.clone();
+        let with_rating = context.supported_commands.contains("sticker");
         let s = self.stack().current().selected().cloned();
         self.stack_mut().clear_preview();
         let origin_path = Some(self.stack().path().to_vec());
@@ -507,13 +553,12 @@ impl BrowserPane<DirOrSong> for PlaylistsPane {
                             .list_playlist_info(d, None)?
                             .into_iter()
                             .map(DirOrSong::Song)
-                            .map(|s| s.to_list_item_simple(config))
+                            .map(|s| s.to_list_item_simple(config, &favorites))
                             .collect_vec(),
                         DirOrSong::Song(song) => c
                             .find_one(&[Filter::new(Tag::File, &song.file)])?
                             .context(anyhow!("File '{}' was listed but not found", song.file))?
-                            .to_preview(&config.theme.symbols)
-                            .collect_vec(),
+                            .to_rated_preview(c, &config.theme.symbols, with_rating),
                     }))
                 })?;
 
@@ -1,35 +1,44 @@
+use std::{
+    collections::BTreeSet,
+    ops::RangeInclusive,
+    time::{Duration, Instant},
+};
+
 use anyhow::Result;
 use crossterm::event::KeyCode;
 use enum_map::{enum_map, Enum, EnumMap};
 use itertools::Itertools;
+use strum::Display;
 
 use crate::{
     config::{
         keys::{GlobalAction, QueueActions},
-        tabs::PaneType,
         theme::{
             properties::{Property, SongProperty},
             PercentOrLength,
         },
     },
     context::AppContext,
-    core::command::{create_env, run_external},
+    core::command::{change_rating, create_env, run_external, run_tag_editor},
     mpd::{
         commands::Song,
-        mpd_client::{MpdClient, QueueMoveTarget},
+        mpd_client::{MpdClient, QueueMoveTarget, Tag},
     },
     shared::{
+        clipboard,
         ext::{btreeset_ranges::BTreeSetRanges, rect::ShrinkExt},
+        favorites::Favorites,
         key_event::KeyEvent,
         macros::{modal, status_error, status_info, status_warn},
         mouse_event::{MouseEvent, MouseEventKind},
     },
     ui::{
         dirstack::DirState,
+        key_hints::{self, KeyHint},
         modals::{
             confirm_modal::ConfirmModal, input_modal::InputModal, select_modal::SelectModal, song_info::SongInfoModal,
         },
-        UiEvent,
+        UiEvent, ADD_TO_PLAYLIST,
     },
     MpdQueryResult,
 };
@@ -37,7 +46,7 @@ use log::error;
 use ratatui::{
     layout::Flex,
     prelude::{Constraint, Layout, Rect},
-    style::Stylize,
+    style::{Styled, Stylize},
     text::{Line, Span},
     widgets::{Block, Borders, Row, Table, TableState},
     Frame,
@@ -54,6 +63,65 @@ pub struct QueuePane {
     column_widths: Vec<Constraint>,
     column_formats: Vec<&'static Property<'static, SongProperty>>,
     areas: EnumMap<Areas, Rect>,
+    follow_playback: bool,
+    last_manual_scroll: Option<Instant>,
+    /// Id of the song the view was last centered on while following the playback
+    followed_song_id: Option<u32>,
+    sort: Option<QueueSort>,
+    /// Queue positions of the displayed rows while the queue is sorted, empty otherwise
+    display_order: Vec<usize>,
+}
+
+/// How long following the playback is paused after the user moves around the queue
+const FOLLOW_PAUSE_AFTER_SCROLL: Duration = Duration::from_secs(5);
+
+/// Column the queue is sorted by. Sorting only changes the order the songs are displayed in, the actual order
+/// of the queue in MPD is left untouched.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display)]
+enum SortColumn {
+    Artist,
+    Album,
+    Title,
+    Duration,
+}
+
+impl SortColumn {
+    fn next(self) -> Option<Self> {
+        match self {
+            SortColumn::Artist => Some(SortColumn::Album),
+            SortColumn::Album => Some(SortColumn::Title),
+            SortColumn::Title => Some(SortColumn::Duration),
+            SortColumn::Duration => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct QueueSort {
+    column: SortColumn,
+    descending: bool,
+}
+
+impl QueueSort {
+    /// Queue positions in the order they should be displayed in. Songs which compare equal keep their order.
+    fn order(self, queue: &[Song]) -> Vec<usize> {
+        let mut order = (0..queue.len()).collect_vec();
+        order.sort_by(|a, b| {
+            let (a, b) = (&queue[*a], &queue[*b]);
+            let ordering = match self.column {
+                SortColumn::Artist => a.artist().cmp(&b.artist()),
+                SortColumn::Album => a.album().cmp(&b.album()),
+                SortColumn::Title => a.title().cmp(&b.title()),
+                SortColumn::Duration => a.duration.cmp(&b.duration),
+            };
+            if self.descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        order
+    }
 }
 
 #[derive(Debug, Enum)]
@@ -64,8 +132,6 @@ enum Areas {
     TableBlock,
 }
 
-const ADD_TO_PLAYLIST: &str = "add_to_playlist";
-
 impl QueuePane {
     pub fn new(context: &AppContext) -> Self {
         let config = context.config;
@@ -87,20 +153,82 @@ impl QueuePane {
             areas: enum_map! {
                 _ => Rect::default(),
             },
+            follow_playback: config.follow_playback,
+            last_manual_scroll: None,
+            followed_song_id: None,
+            sort: None,
+            display_order: Vec::new(),
         }
     }
+
+    fn update_display_order(&mut self, queue: &[Song]) {
+        self.display_order = self.sort.map(|sort| sort.order(queue)).unwrap_or_default();
+    }
+
+    /// Position in the queue of the song displayed at `row`
+    fn queue_idx(&self, row: usize) -> usize {
+        self.display_order.get(row).copied().unwrap_or(row)
+    }
+
+    /// Row at which the song at `queue_idx` is displayed
+    fn row_of(&self, queue_idx: usize) -> usize {
+        self.display_order
+            .iter()
+            .position(|idx| *idx == queue_idx)
+            .unwrap_or(queue_idx)
+    }
+
+    fn selected_song<'a>(&self, queue: &'a [Song]) -> Option<&'a Song> {
+        self.scrolling_state
+            .get_selected()
+            .and_then(|row| queue.get(self.queue_idx(row)))
+    }
+
+    /// Centers the view on the currently playing song when it changes unless the user moved around the queue
+    /// recently
+    fn follow_current_song(&mut self, context: &AppContext) -> Result<()> {
+        if !self.follow_playback
+            || self
+                .last_manual_scroll
+                .is_some_and(|at| at.elapsed() < FOLLOW_PAUSE_AFTER_SCROLL)
+        {
+            return Ok(());
+        }
+
+        if let Some((idx, song)) = context.find_current_song_in_queue() {
+            if self.followed_song_id == Some(song.id) {
+                return Ok(());
+            }
+            self.followed_song_id = Some(song.id);
+            self.scrolling_state.select_centered(self.row_of(idx));
+            context.render()?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Pane for QueuePane {
     fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> anyhow::Result<()> {
-        let AppContext { queue, config, .. } = context;
+        let AppContext {
+            queue,
+            config,
+            favorites,
+            ..
+        } = context;
         let queue_len = queue.len();
         self.calculate_areas(area, context);
+        self.update_display_order(queue);
 
-        let title = self
+        let filter_title = self
             .filter
             .as_ref()
             .map(|v| format!("[FILTER]: {v}{} ", if self.filter_input_mode { "█" } else { "" }));
+        let sort_title = self
+            .sort
+            .map(|sort| format!("[SORT]: {} {} ", sort.column, if sort.descending { "▼" } else { "▲" }));
+        let title = format!("{}{}", sort_title.unwrap_or_default(), filter_title.unwrap_or_default());
+        let title = (!title.is_empty()).then_some(title);
 
         let table_block = {
             let mut b = Block::default().border_style(config.as_border_style().bold());
@@ -122,51 +250,78 @@ impl Pane for QueuePane {
 
         let formats = &config.theme.song_table_format;
 
-        let marker_symbol_len = config.theme.symbols.marker.chars().count();
-        let table_items = queue
-            .iter()
-            .enumerate()
-            .map(|(idx, song)| {
-                let is_current = context
-                    .find_current_song_in_queue()
-                    .map(|(_, song)| song.id)
-                    .is_some_and(|v| v == song.id);
+        let current_song_id = context.find_current_song_in_queue().map(|(_, song)| song.id);
+        let current_song_symbol = config.theme.symbols.current_song;
+        let current_song_symbol_len = current_song_symbol.map_or(0, |symbol| symbol.chars().count());
+        let position_width = if config.theme.show_queue_position {
+            queue_len.to_string().len()
+        } else {
+            0
+        };
+        let table_items = (0..queue_len)
+            .map(|row_idx| {
+                let idx = self.queue_idx(row_idx);
+                let song = &queue[idx];
+                let is_current = current_song_id.is_some_and(|v| v == song.id);
 
-                let is_marked = self.scrolling_state.get_marked().contains(&idx);
+                let is_marked = self.scrolling_state.get_marked().contains(&row_idx);
                 let columns = (0..formats.len()).map(|i| {
                     let mut max_len: usize = widths[i].width.into();
-                    // We have to subtract marker symbol length from max len in order to make space
-                    // for the marker symbol in case we are in the first column of the table and the
-                    // song is marked.
-                    if is_marked && i == 0 {
-                        max_len = max_len.saturating_sub(marker_symbol_len);
+                    let mut prefix = Vec::new();
+                    if i == 0 {
+                        if config.theme.show_queue_position {
+                            prefix.push(Span::from(format!("{:>position_width$} ", idx + 1)));
+                        }
+                        if let Some(symbol) = current_song_symbol {
+                            prefix.push(if is_current {
+                                Span::styled(symbol, config.theme.current_song_style)
+                            } else {
+                                Span::from(" ".repeat(current_song_symbol_len))
+                            });
+                        }
+                        // Make space for the marker symbol in case the song is marked
+                        if is_marked {
+                            prefix.push(Span::styled(
+                                config.theme.symbols.marker,
+                                config.theme.highlighted_item_style,
+                            ));
+                        }
+                        if favorites.contains(&song.file) {
+                            prefix.push(Span::from(format!("{} ", config.theme.symbols.favorite)));
+                        }
+                        max_len = max_len.saturating_sub(prefix.iter().map(Span::width).sum());
                     }
 
                     let mut line = song
                         .as_line_ellipsized(formats[i].prop, max_len, &config.theme.symbols)
                         .unwrap_or_default()
                         .alignment(formats[i].alignment.into());
-
-                    if is_marked && i == 0 {
-                        let marker_span =
-                            Span::styled(config.theme.symbols.marker, config.theme.highlighted_item_style);
-                        line.spans.splice(..0, std::iter::once(marker_span));
-                    };
+                    line.spans.splice(..0, prefix);
 
                     line
                 });
 
-                let is_highlighted = is_current
-                    || self
-                        .filter
-                        .as_ref()
-                        .is_some_and(|filter| song.matches(self.column_formats.as_slice(), filter));
-
-                if is_highlighted {
+                let row = if is_current {
+                    Row::new(columns.map(|column| column.patch_style(config.theme.current_song_style)))
+                        .style(config.theme.current_song_style)
+                } else if self
+                    .filter
+                    .as_ref()
+                    .is_some_and(|filter| song.matches(self.column_formats.as_slice(), favorites, filter))
+                {
                     Row::new(columns.map(|column| column.patch_style(config.theme.highlighted_item_style)))
                         .style(config.theme.highlighted_item_style)
+                } else if let Some(style) = config.theme.tag_style(song) {
+                    Row::new(columns.map(|column| column.patch_style(style))).style(style)
                 } else {
                     Row::new(columns)
+                };
+
+                if self.scrolling_state.get_hovered() == Some(row_idx) {
+                    let style = Styled::style(&row).patch(config.theme.hovered_item_style);
+                    row.set_style(style)
+                } else {
+                    row
                 }
             })
             .collect_vec();
@@ -178,7 +333,7 @@ impl Pane for QueuePane {
                 })))
                 .style(config.as_text_style())
                 .widths(self.column_widths.clone())
-                .block(config.as_header_table_block());
+                .block(config.as_header_table_block(context.draw_borders));
 
             frame.render_widget(header_table, self.areas[Areas::TableHeader]);
         }
@@ -196,11 +351,13 @@ impl Pane for QueuePane {
 
         self.scrolling_state
             .set_viewport_len(Some(self.areas[Areas::Table].height.into()));
-        frame.render_stateful_widget(
-            config.as_styled_scrollbar(),
-            self.areas[Areas::Scrollbar],
-            self.scrolling_state.as_scrollbar_state_ref(),
-        );
+        if let Some(scrollbar) = config.as_styled_scrollbar(&self.scrolling_state) {
+            frame.render_stateful_widget(
+                scrollbar,
+                self.areas[Areas::Scrollbar],
+                self.scrolling_state.as_scrollbar_state_ref(),
+            );
+        }
 
         Ok(())
     }
@@ -241,24 +398,31 @@ impl Pane for QueuePane {
         self.scrolling_state.set_content_len(Some(context.queue.len()));
         self.scrolling_state
             .set_viewport_len(Some(self.areas[Areas::Table].height as usize));
-        let to_select = self
-            .scrolling_state
-            .get_selected()
-            .or(context.find_current_song_in_queue().map(|v| v.0).or(Some(0)));
+        let to_select = self.scrolling_state.get_selected().or(context
+            .find_current_song_in_queue()
+            .map(|v| self.row_of(v.0))
+            .or(Some(0)));
         self.scrolling_state.select(to_select, context.config.scrolloff);
 
         Ok(())
     }
 
-    fn on_event(&mut self, event: &mut UiEvent, _is_visible: bool, context: &AppContext) -> Result<()> {
+    fn on_event(&mut self, event: &mut UiEvent, is_visible: bool, context: &AppContext) -> Result<()> {
         match event {
             UiEvent::SongChanged => {
                 if let Some((idx, _)) = context.find_current_song_in_queue() {
                     if context.config.select_current_song_on_change {
-                        self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                        self.scrolling_state
+                            .select(Some(self.row_of(idx)), context.config.scrolloff);
                         context.render()?;
                     }
                 }
+                if is_visible {
+                    self.follow_current_song(context)?;
+                }
+            }
+            UiEvent::Player if is_visible => {
+                self.follow_current_song(context)?;
             }
             UiEvent::Reconnected => {
                 self.before_show(context)?;
@@ -270,10 +434,29 @@ impl Pane for QueuePane {
     }
 
     fn handle_mouse_event(&mut self, event: MouseEvent, context: &AppContext) -> Result<()> {
+        if matches!(event.kind, MouseEventKind::Move) {
+            let hovered = if self.areas[Areas::Table].contains(event.into()) {
+                self.scrolling_state.get_at_y(self.areas[Areas::Table], event.y)
+            } else {
+                None
+            };
+            if self.scrolling_state.set_hovered(hovered) {
+                context.render()?;
+            }
+            return Ok(());
+        }
+
         if !self.areas[Areas::Table].contains(event.into()) {
             return Ok(());
         }
 
+        if matches!(
+            event.kind,
+            MouseEventKind::LeftClick | MouseEventKind::ScrollDown | MouseEventKind::ScrollUp
+        ) {
+            self.last_manual_scroll = Some(Instant::now());
+        }
+
         match event.kind {
             MouseEventKind::LeftClick => {
                 let clicked_row: usize = event.y.saturating_sub(self.areas[Areas::Table].y).into();
@@ -289,7 +472,7 @@ impl Pane for QueuePane {
                 if let Some(song) = self
                     .scrolling_state
                     .get_at_rendered_row(clicked_row)
-                    .and_then(|idx| context.queue.get(idx))
+                    .and_then(|row| context.queue.get(self.queue_idx(row)))
                 {
                     let id = song.id;
                     context.command(move |client| {
@@ -304,7 +487,7 @@ impl Pane for QueuePane {
                 if let Some(selected_song) = self
                     .scrolling_state
                     .get_at_rendered_row(clicked_row)
-                    .and_then(|idx| context.queue.get(idx))
+                    .and_then(|row| context.queue.get(self.queue_idx(row)))
                 {
                     let id = selected_song.id;
                     context.command(move |client| {
@@ -314,43 +497,24 @@ impl Pane for QueuePane {
                 }
             }
             MouseEventKind::ScrollDown => {
-                self.scrolling_state.next(context.config.scrolloff, false);
+                self.scrolling_state
+                    .scroll_down(context.config.scroll_amount, context.config.scrolloff);
                 context.render()?;
             }
             MouseEventKind::ScrollUp => {
-                self.scrolling_state.prev(context.config.scrolloff, false);
+                self.scrolling_state
+                    .scroll_up(context.config.scroll_amount, context.config.scrolloff);
                 context.render()?;
             }
             MouseEventKind::RightClick => {}
+            MouseEventKind::Move => {}
         }
 
         Ok(())
     }
 
-    fn on_query_finished(&mut self, id: &'static str, data: MpdQueryResult, context: &AppContext) -> Result<()> {
-        match (id, data) {
-            (ADD_TO_PLAYLIST, MpdQueryResult::AddToPlaylist { playlists, song_file }) => {
-                modal!(
-                    context,
-                    SelectModal::new(context)
-                        .options(playlists)
-                        .confirm_label("Add")
-                        .title("Select a playlist")
-                        .on_confirm(move |context, selected: &String, _idx| {
-                            let selected = selected.to_owned();
-                            let song_file = song_file.clone();
-                            context.command(move |client| {
-                                client.add_to_playlist(&selected, &song_file, None)?;
-                                status_info!("Song added to playlist {}", selected);
-                                Ok(())
-                            });
-                            Ok(())
-                        })
-                );
-            }
-            _ => {}
-        };
-        Ok(())
+    fn key_hints(&self, context: &AppContext) -> Option<Vec<KeyHint>> {
+        self.filter_input_mode.then(|| key_hints::filter_mode(context))
     }
 
     fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
@@ -374,7 +538,7 @@ impl Pane for QueuePane {
                             if let Some(ref mut f) = self.filter {
                                 f.push(c);
                             };
-                            self.jump_first(&context.queue, context.config.scrolloff);
+                            self.jump_first(&context.queue, &context.favorites, context.config.scrolloff);
 
                             context.render()?;
                         }
@@ -392,7 +556,13 @@ impl Pane for QueuePane {
         } else if let Some(action) = event.as_queue_action(context) {
             match action {
                 QueueActions::Delete if !self.scrolling_state.marked.is_empty() => {
-                    for range in self.scrolling_state.marked.ranges().rev() {
+                    let marked: BTreeSet<usize> = self
+                        .scrolling_state
+                        .marked
+                        .iter()
+                        .map(|row| self.queue_idx(*row))
+                        .collect();
+                    for range in marked.ranges().rev() {
                         context.command(move |client| {
                             client.delete_from_queue(range.into())?;
                             Ok(())
@@ -403,11 +573,7 @@ impl Pane for QueuePane {
                     context.render()?;
                 }
                 QueueActions::Delete => {
-                    if let Some(selected_song) = self
-                        .scrolling_state
-                        .get_selected()
-                        .and_then(|idx| context.queue.get(idx))
-                    {
+                    if let Some(selected_song) = self.selected_song(&context.queue) {
                         let id = selected_song.id;
                         context.command(move |client| {
                             client.delete_id(id)?;
@@ -417,6 +583,9 @@ impl Pane for QueuePane {
                         status_error!("No song selected");
                     }
                 }
+                QueueActions::DeleteAll if !context.config.confirm_destructive_actions => {
+                    context.command(|client| Ok(client.clear()?));
+                }
                 QueueActions::DeleteAll => {
                     modal!(
                         context,
@@ -431,11 +600,7 @@ impl Pane for QueuePane {
                     );
                 }
                 QueueActions::Play => {
-                    if let Some(selected_song) = self
-                        .scrolling_state
-                        .get_selected()
-                        .and_then(|idx| context.queue.get(idx))
-                    {
+                    if let Some(selected_song) = self.selected_song(&context.queue) {
                         let id = selected_song.id;
                         context.command(move |client| {
                             client.play_id(id)?;
@@ -445,12 +610,145 @@ impl Pane for QueuePane {
                 }
                 QueueActions::JumpToCurrent => {
                     if let Some((idx, _)) = context.find_current_song_in_queue() {
-                        self.scrolling_state.select(Some(idx), context.config.scrolloff);
+                        self.scrolling_state
+                            .select(Some(self.row_of(idx)), context.config.scrolloff);
                         context.render()?;
                     } else {
                         status_info!("No song is currently playing");
                     }
                 }
+                QueueActions::JumpToNextAlbum | QueueActions::JumpToPreviousAlbum => {
+                    let Some(selected) = self.scrolling_state.get_selected() else {
+                        return Ok(());
+                    };
+                    let albums = (0..context.queue.len())
+                        .map(|row| context.queue[self.queue_idx(row)].album().map(String::as_str))
+                        .collect_vec();
+                    let forward = matches!(action, QueueActions::JumpToNextAlbum);
+                    if let Some(row) = album_boundary(&albums, selected, forward, context.config.wrap_navigation) {
+                        self.scrolling_state.select(Some(row), context.config.scrolloff);
+                        context.render()?;
+                    }
+                }
+                QueueActions::MoveToTop
+                | QueueActions::MoveToBottom
+                | QueueActions::MoveUpBy(_)
+                | QueueActions::MoveDownBy(_)
+                | QueueActions::Swap
+                | QueueActions::ShuffleSelected
+                    if self.sort.is_some() =>
+                {
+                    status_warn!("Songs cannot be moved while the queue is sorted");
+                }
+                QueueActions::CycleSort => {
+                    self.sort = match self.sort {
+                        None => Some(SortColumn::Artist),
+                        Some(sort) => sort.column.next(),
+                    }
+                    .map(|column| QueueSort {
+                        column,
+                        descending: self.sort.is_some_and(|sort| sort.descending),
+                    });
+                    self.on_sort_changed(context)?;
+                }
+                QueueActions::ToggleSortDirection => {
+                    if let Some(sort) = &mut self.sort {
+                        sort.descending = !sort.descending;
+                        self.on_sort_changed(context)?;
+                    } else {
+                        status_warn!("The queue is not sorted");
+                    }
+                }
+                QueueActions::MoveToTop => self.move_to_edge(true, context)?,
+                QueueActions::MoveToBottom => self.move_to_edge(false, context)?,
+                QueueActions::MoveUpBy(count) => {
+                    self.move_selected_by(-isize::try_from(count).unwrap_or(isize::MAX), context)?;
+                }
+                QueueActions::MoveDownBy(count) => {
+                    self.move_selected_by(isize::try_from(count).unwrap_or(isize::MAX), context)?;
+                }
+                QueueActions::Swap => {
+                    let Some((first, second)) = self.scrolling_state.get_marked().iter().copied().collect_tuple()
+                    else {
+                        status_warn!("Select exactly two songs to swap them");
+                        return Ok(());
+                    };
+                    let (Some(first_song), Some(second_song)) = (context.queue.get(first), context.queue.get(second))
+                    else {
+                        return Ok(());
+                    };
+
+                    let (first_id, second_id) = (first_song.id, second_song.id);
+                    context.command(move |client| {
+                        client.swap_id(first_id, second_id)?;
+                        Ok(())
+                    });
+                    context.queue.swap(first, second);
+                    context.render()?;
+                }
+                QueueActions::Prioritize => {
+                    let ids = if self.scrolling_state.marked.is_empty() {
+                        self.selected_song(&context.queue)
+                            .map(|song| song.id)
+                            .into_iter()
+                            .collect_vec()
+                    } else {
+                        self.scrolling_state
+                            .marked
+                            .iter()
+                            .filter_map(|row| context.queue.get(self.queue_idx(*row)))
+                            .map(|song| song.id)
+                            .collect_vec()
+                    };
+                    if ids.is_empty() {
+                        status_error!("No song selected");
+                        return Ok(());
+                    }
+
+                    let prio = if context
+                        .queue
+                        .iter()
+                        .filter(|song| ids.contains(&song.id))
+                        .all(|song| song.priority() > 0)
+                    {
+                        0
+                    } else {
+                        u8::MAX
+                    };
+                    context.command(move |client| {
+                        for id in ids {
+                            client.set_priority_id(id, prio)?;
+                        }
+                        Ok(())
+                    });
+                    if prio > 0 {
+                        status_info!("Prioritized in random playback");
+                    } else {
+                        status_info!("Priority reset");
+                    }
+                }
+                QueueActions::ToggleFollowPlayback => {
+                    self.follow_playback = !self.follow_playback;
+                    self.last_manual_scroll = None;
+                    self.followed_song_id = None;
+                    if self.follow_playback {
+                        status_info!("Following playback");
+                        self.follow_current_song(context)?;
+                    } else {
+                        status_info!("Stopped following playback");
+                    }
+                }
+                QueueActions::ShuffleSelected => {
+                    let Some(range) = contiguous_range(self.scrolling_state.get_marked()) else {
+                        status_warn!("Select a contiguous range of songs to shuffle");
+                        return Ok(());
+                    };
+
+                    context.command(move |client| {
+                        client.shuffle(Some(range.into()))?;
+                        Ok(())
+                    });
+                }
                 QueueActions::Save => {
                     modal!(
                         context,
@@ -476,17 +774,12 @@ impl Pane for QueuePane {
                     );
                 }
                 QueueActions::AddToPlaylist => {
-                    if let Some(selected_song) = self
-                        .scrolling_state
-                        .get_selected()
-                        .and_then(|idx| context.queue.get(idx))
-                    {
+                    if let Some(selected_song) = self.selected_song(&context.queue) {
                         let uri = selected_song.file.clone();
                         context
                             .query()
                             .id(ADD_TO_PLAYLIST)
                             .replace_id(ADD_TO_PLAYLIST)
-                            .target(PaneType::Queue)
                             .query(move |client| {
                                 let playlists = client
                                     .list_playlists()?
@@ -502,18 +795,72 @@ impl Pane for QueuePane {
                     }
                 }
                 QueueActions::ShowInfo => {
-                    if let Some(selected_song) = self
-                        .scrolling_state
-                        .get_selected()
-                        .and_then(|idx| context.queue.get(idx))
-                    {
-                        modal!(context, SongInfoModal::new(selected_song.clone()));
+                    if let Some(selected_song) = self.selected_song(&context.queue) {
+                        modal!(context, SongInfoModal::new(selected_song.clone(), context));
+                    } else {
+                        status_error!("No song selected");
+                    }
+                }
+                QueueActions::EditTags => {
+                    if let Some(selected_song) = self.selected_song(&context.queue) {
+                        let song = selected_song.clone();
+                        modal!(
+                            context,
+                            SelectModal::new(context)
+                                .options(vec![Tag::Title, Tag::Artist, Tag::Album, Tag::AlbumArtist, Tag::Genre])
+                                .confirm_label("Edit")
+                                .title("Select a tag to edit")
+                                .on_confirm(move |context, tag: &Tag, _idx| {
+                                    let tag = *tag;
+                                    let id = song.id;
+                                    let current = song
+                                        .metadata
+                                        .get(&tag.to_string().to_lowercase())
+                                        .cloned()
+                                        .unwrap_or_default();
+                                    modal!(
+                                        context,
+                                        InputModal::new(context)
+                                            .title("Edit tag")
+                                            .confirm_label("Save")
+                                            .input_label("New value, leave empty to remove the tag:")
+                                            .initial_value(current)
+                                            .on_confirm(move |context, value| {
+                                                let value = value.trim().to_owned();
+                                                context.command(move |client| {
+                                                    client.clear_tag_id(id, tag)?;
+                                                    if !value.is_empty() {
+                                                        client.add_tag_id(id, tag, &value)?;
+                                                    }
+                                                    status_info!("Tag '{tag}' updated");
+                                                    Ok(())
+                                                });
+                                                Ok(())
+                                            })
+                                    );
+                                    Ok(())
+                                })
+                        );
                     } else {
                         status_error!("No song selected");
                     }
                 }
             }
         } else if let Some(action) = event.as_common_action(context) {
+            if matches!(
+                action,
+                CommonAction::Up
+                    | CommonAction::Down
+                    | CommonAction::UpHalf
+                    | CommonAction::DownHalf
+                    | CommonAction::PageUp
+                    | CommonAction::PageDown
+                    | CommonAction::Top
+                    | CommonAction::Bottom
+            ) {
+                self.last_manual_scroll = Some(Instant::now());
+            }
+
             match action {
                 CommonAction::Up => {
                     if !context.queue.is_empty() {
@@ -531,6 +878,9 @@ impl Pane for QueuePane {
 
                     context.render()?;
                 }
+                CommonAction::MoveUp | CommonAction::MoveDown if self.sort.is_some() => {
+                    status_warn!("Songs cannot be moved while the queue is sorted");
+                }
                 CommonAction::MoveUp if !self.scrolling_state.get_marked().is_empty() => {
                     if context.queue.is_empty() {
                         return Ok(());
@@ -609,69 +959,32 @@ impl Pane for QueuePane {
                     context.render()?;
                     return Ok(());
                 }
-                CommonAction::MoveUp => {
-                    if context.queue.is_empty() {
-                        return Ok(());
+                CommonAction::MoveUp => self.move_selected_by(-1, context)?,
+                CommonAction::MoveDown => self.move_selected_by(1, context)?,
+                CommonAction::DownHalf => {
+                    if !context.queue.is_empty() {
+                        self.scrolling_state.next_half_viewport(context.config.scrolloff);
                     }
 
-                    let Some(idx) = self.scrolling_state.get_selected() else {
-                        return Ok(());
-                    };
-
-                    let Some(selected) = self
-                        .scrolling_state
-                        .get_selected()
-                        .and_then(|idx| context.queue.get(idx))
-                    else {
-                        return Ok(());
-                    };
-
-                    let new_idx = idx.saturating_sub(1);
-                    let id = selected.id;
-                    context.command(move |client| {
-                        client.move_id(id, QueueMoveTarget::Absolute(new_idx))?;
-                        Ok(())
-                    });
-                    self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
-                    context.queue.swap(idx, new_idx);
                     context.render()?;
                 }
-                CommonAction::MoveDown => {
-                    if context.queue.is_empty() {
-                        return Ok(());
+                CommonAction::UpHalf => {
+                    if !context.queue.is_empty() {
+                        self.scrolling_state.prev_half_viewport(context.config.scrolloff);
                     }
 
-                    let Some(idx) = self.scrolling_state.get_selected() else {
-                        return Ok(());
-                    };
-                    let Some(selected) = self
-                        .scrolling_state
-                        .get_selected()
-                        .and_then(|idx| context.queue.get(idx))
-                    else {
-                        return Ok(());
-                    };
-
-                    let new_idx = (idx + 1).min(context.queue.len() - 1);
-                    let id = selected.id;
-                    context.command(move |client| {
-                        client.move_id(id, QueueMoveTarget::Absolute(new_idx))?;
-                        Ok(())
-                    });
-                    self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
-                    context.queue.swap(idx, new_idx);
                     context.render()?;
                 }
-                CommonAction::DownHalf => {
+                CommonAction::PageDown => {
                     if !context.queue.is_empty() {
-                        self.scrolling_state.next_half_viewport(context.config.scrolloff);
+                        self.scrolling_state.next_viewport(context.config.scrolloff);
                     }
 
                     context.render()?;
                 }
-                CommonAction::UpHalf => {
+                CommonAction::PageUp => {
                     if !context.queue.is_empty() {
-                        self.scrolling_state.prev_half_viewport(context.config.scrolloff);
+                        self.scrolling_state.prev_viewport(context.config.scrolloff);
                     }
 
                     context.render()?;
@@ -699,12 +1012,12 @@ impl Pane for QueuePane {
                     context.render()?;
                 }
                 CommonAction::NextResult => {
-                    self.jump_forward(&context.queue, context.config.scrolloff);
+                    self.jump_forward(&context.queue, &context.favorites, context.config.scrolloff);
 
                     context.render()?;
                 }
                 CommonAction::PreviousResult => {
-                    self.jump_back(&context.queue, context.config.scrolloff);
+                    self.jump_back(&context.queue, &context.favorites, context.config.scrolloff);
 
                     context.render()?;
                 }
@@ -722,8 +1035,59 @@ impl Pane for QueuePane {
 
                     context.render()?;
                 }
+                CommonAction::SelectAll => {
+                    let filter = self.filter.as_ref();
+                    let items = (0..context.queue.len())
+                        .filter(|row| {
+                            filter.map_or(true, |filter| {
+                                context.queue[self.queue_idx(*row)].matches(
+                                    self.column_formats.as_slice(),
+                                    &context.favorites,
+                                    filter,
+                                )
+                            })
+                        })
+                        .collect_vec();
+                    self.scrolling_state.toggle_mark_all(items);
+
+                    context.render()?;
+                }
                 CommonAction::Add => {}
                 CommonAction::AddAll => {}
+                CommonAction::CopyFilePath => {
+                    if let Some(selected_song) = self.selected_song(&context.queue) {
+                        let path = selected_song.resolved_path(context.config.music_directory);
+                        clipboard::copy(&path)?;
+                        status_info!("Copied '{path}' to clipboard");
+                    } else {
+                        status_error!("No song selected");
+                    }
+                }
+                CommonAction::OpenInTagEditor => {
+                    let Some(command) = context.config.tag_editor else {
+                        status_warn!("No tag editor configured");
+                        return Ok(());
+                    };
+                    if let Some(selected_song) = self.selected_song(&context.queue) {
+                        run_tag_editor(command, selected_song, context);
+                    } else {
+                        status_error!("No song selected");
+                    }
+                }
+                CommonAction::IncreaseRating => {
+                    if let Some(selected_song) = self.selected_song(&context.queue) {
+                        change_rating(selected_song, 1, context);
+                    } else {
+                        status_error!("No song selected");
+                    }
+                }
+                CommonAction::DecreaseRating => {
+                    if let Some(selected_song) = self.selected_song(&context.queue) {
+                        change_rating(selected_song, -1, context);
+                    } else {
+                        status_error!("No song selected");
+                    }
+                }
                 CommonAction::Delete => {}
                 CommonAction::Rename => {}
                 CommonAction::Close => {}
@@ -733,14 +1097,13 @@ impl Pane for QueuePane {
                 CommonAction::PaneUp => {}
                 CommonAction::PaneRight => {}
                 CommonAction::PaneLeft => {}
+                CommonAction::GoToParent => {}
+                CommonAction::GoToRoot => {}
             }
         } else if let Some(action) = event.as_global_action(context) {
             match action {
                 GlobalAction::ExternalCommand { command, .. } => {
-                    let song = self
-                        .scrolling_state
-                        .get_selected()
-                        .and_then(|idx| context.queue.get(idx).map(|song| song.file.as_str()));
+                    let song = self.selected_song(&context.queue).map(|song| song.file.as_str());
 
                     run_external(command, create_env(context, song));
                 }
@@ -755,7 +1118,7 @@ impl Pane for QueuePane {
 }
 
 impl QueuePane {
-    pub fn jump_forward(&mut self, queue: &[Song], scrolloff: usize) {
+    pub fn jump_forward(&mut self, queue: &[Song], favorites: &Favorites, scrolloff: usize) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
@@ -768,14 +1131,14 @@ impl QueuePane {
         let length = queue.len();
         for i in selected + 1..length + selected {
             let i = i % length;
-            if queue[i].matches(self.column_formats.as_slice(), filter) {
+            if queue[self.queue_idx(i)].matches(self.column_formats.as_slice(), favorites, filter) {
                 self.scrolling_state.select(Some(i), scrolloff);
                 break;
             }
         }
     }
 
-    pub fn jump_back(&mut self, queue: &[Song], scrolloff: usize) {
+    pub fn jump_back(&mut self, queue: &[Song], favorites: &Favorites, scrolloff: usize) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
@@ -788,23 +1151,527 @@ impl QueuePane {
         let length = queue.len();
         for i in (0..length).rev() {
             let i = (i + selected) % length;
-            if queue[i].matches(self.column_formats.as_slice(), filter) {
+            if queue[self.queue_idx(i)].matches(self.column_formats.as_slice(), favorites, filter) {
                 self.scrolling_state.select(Some(i), scrolloff);
                 break;
             }
         }
     }
 
-    pub fn jump_first(&mut self, queue: &[Song], scrolloff: usize) {
+    pub fn jump_first(&mut self, queue: &[Song], favorites: &Favorites, scrolloff: usize) {
         let Some(filter) = self.filter.as_ref() else {
             status_warn!("No filter set");
             return;
         };
 
-        queue
-            .iter()
+        if let Some(row) = (0..queue.len())
+            .find(|row| queue[self.queue_idx(*row)].matches(self.column_formats.as_slice(), favorites, filter))
+        {
+            self.scrolling_state.select(Some(row), scrolloff);
+        }
+    }
+
+    /// Recomputes the displayed order while keeping the song under cursor selected. Marks refer to the displayed
+    /// rows so they are cleared.
+    fn on_sort_changed(&mut self, context: &AppContext) -> Result<()> {
+        let selected = self.scrolling_state.get_selected().map(|row| self.queue_idx(row));
+        self.update_display_order(&context.queue);
+        self.scrolling_state.marked.clear();
+        if let Some(idx) = selected {
+            self.scrolling_state
+                .select(Some(self.row_of(idx)), context.config.scrolloff);
+        }
+
+        if let Some(sort) = self.sort {
+            status_info!(
+                "Queue sorted by {} in {} order",
+                sort.column.to_string().to_lowercase(),
+                if sort.descending { "descending" } else { "ascending" }
+            );
+        } else {
+            status_info!("Queue displayed in playback order");
+        }
+        context.render()?;
+        Ok(())
+    }
+
+    /// Moves the song under cursor by `offset` positions, clamped to the ends of the queue, and keeps it selected so
+    /// that repeated moves keep moving the same song
+    fn move_selected_by(&mut self, offset: isize, context: &mut AppContext) -> Result<()> {
+        let Some(idx) = self.scrolling_state.get_selected() else {
+            return Ok(());
+        };
+        let Some(selected) = context.queue.get(idx) else {
+            return Ok(());
+        };
+
+        let new_idx = idx.saturating_add_signed(offset).min(context.queue.len() - 1);
+        if new_idx == idx {
+            return Ok(());
+        }
+
+        let id = selected.id;
+        context.command(move |client| {
+            client.move_id(id, QueueMoveTarget::Absolute(new_idx))?;
+            Ok(())
+        });
+        let song = context.queue.remove(idx);
+        context.queue.insert(new_idx, song);
+        self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
+        context.render()?;
+
+        Ok(())
+    }
+
+    /// Moves the marked songs, or the song under cursor when nothing is marked, to the top or the bottom of the
+    /// queue. Marked songs keep their relative order.
+    fn move_to_edge(&mut self, to_top: bool, context: &mut AppContext) -> Result<()> {
+        let queue_len = context.queue.len();
+        if self.scrolling_state.get_marked().is_empty() {
+            let Some(idx) = self.scrolling_state.get_selected() else {
+                return Ok(());
+            };
+            let Some(song) = context.queue.get(idx) else {
+                return Ok(());
+            };
+
+            let id = song.id;
+            let new_idx = if to_top { 0 } else { queue_len - 1 };
+            context.command(move |client| {
+                client.move_id(id, QueueMoveTarget::Absolute(new_idx))?;
+                Ok(())
+            });
+            let song = context.queue.remove(idx);
+            context.queue.insert(new_idx, song);
+            self.scrolling_state.select(Some(new_idx), context.config.scrolloff);
+            context.render()?;
+            return Ok(());
+        }
+
+        let moves = moves_to_edge(self.scrolling_state.get_marked(), queue_len, to_top);
+        context.command(move |client| {
+            for (range, target) in moves {
+                client.move_in_queue(range.into(), QueueMoveTarget::Absolute(target))?;
+            }
+            Ok(())
+        });
+
+        let (mut marked_songs, other_songs): (Vec<_>, Vec<_>) = std::mem::take(&mut context.queue)
+            .into_iter()
+            .enumerate()
+            .partition(|(idx, _)| self.scrolling_state.marked.contains(idx));
+        let marked_len = marked_songs.len();
+        context.queue = if to_top {
+            marked_songs.extend(other_songs);
+            marked_songs.into_iter().map(|(_, song)| song).collect()
+        } else {
+            other_songs
+                .into_iter()
+                .chain(marked_songs)
+                .map(|(_, song)| song)
+                .collect()
+        };
+
+        let new_marked = if to_top {
+            0..marked_len
+        } else {
+            queue_len - marked_len..queue_len
+        };
+        self.scrolling_state
+            .select(Some(new_marked.start), context.config.scrolloff);
+        self.scrolling_state.marked = new_marked.collect();
+
+        context.render()?;
+        Ok(())
+    }
+}
+
+/// Returns the range spanned by the indices if there are at least two of them and there are no gaps between them
+fn contiguous_range(indices: &BTreeSet<usize>) -> Option<RangeInclusive<usize>> {
+    if indices.len() < 2 {
+        return None;
+    }
+    indices.ranges().exactly_one().ok()
+}
+
+/// Row of the first song of the next album after `from`, or when going back, of the album at `from` unless `from`
+/// is its first song already, in which case the previous album. Albums are runs of consecutive rows with the same
+/// album. Past the last or the first album the other end is returned when `wrap` is set, otherwise `None`.
+fn album_boundary(albums: &[Option<&str>], from: usize, forward: bool, wrap: bool) -> Option<usize> {
+    let boundaries = (0..albums.len())
+        .filter(|&row| row == 0 || albums[row] != albums[row - 1])
+        .collect_vec();
+    let result = if forward {
+        boundaries.iter().find(|&&row| row > from)
+    } else {
+        boundaries.iter().rev().find(|&&row| row < from)
+    };
+
+    match result {
+        Some(row) => Some(*row),
+        None if !wrap => None,
+        None if forward => boundaries.first().copied(),
+        None => boundaries.last().copied(),
+    }
+}
+
+/// Ranges of queue positions together with the absolute positions they have to be moved to, in order, so that
+/// the songs at `indices` end up at the top or the bottom of the queue. Each move leaves the positions of the
+/// remaining ranges intact which keeps the relative order of the songs.
+fn moves_to_edge(indices: &BTreeSet<usize>, queue_len: usize, to_top: bool) -> Vec<(RangeInclusive<usize>, usize)> {
+    if to_top {
+        let mut target = 0;
+        indices
+            .ranges()
+            .map(|range| {
+                let result = (range.clone(), target);
+                target += range.count();
+                result
+            })
+            .collect()
+    } else {
+        let mut target = queue_len;
+        indices
+            .ranges()
+            .rev()
+            .map(|range| {
+                target -= range.clone().count();
+                (range, target)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use std::{
+        collections::{BTreeSet, HashMap},
+        time::Instant,
+    };
+
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+    use ratatui::{
+        backend::TestBackend,
+        style::{Color, Style},
+        Terminal,
+    };
+    use rstest::rstest;
+
+    use test_case::test_case;
+
+    use super::{album_boundary, contiguous_range, moves_to_edge, QueuePane, QueueSort, SortColumn};
+    use crate::{
+        config::{theme::TagStyle, Leak},
+        context::AppContext,
+        mpd::commands::{Song, State},
+        tests::fixtures::{app_context, terminal},
+        ui::{key_hints::KeyHint, panes::Pane, UiEvent},
+    };
+
+    fn song(id: u32, artist: &str) -> Song {
+        Song {
+            id,
+            file: format!("{artist}.mp3"),
+            duration: None,
+            metadata: HashMap::from([("artist".to_owned(), artist.to_owned())]),
+        }
+    }
+
+    #[rstest]
+    fn current_song_row_has_marker_and_style(mut app_context: AppContext, mut terminal: Terminal<TestBackend>) {
+        let mut config = app_context.config.clone();
+        config.theme.symbols.current_song = Some(">");
+        config.theme.current_song_style = Style::default().fg(Color::Red);
+        config.theme.show_queue_position = true;
+        app_context.config = config.leak();
+        app_context.queue = vec![song(1, "first"), song(2, "second"), song(3, "third")];
+        app_context.status.songid = Some(2);
+        app_context.status.state = State::Play;
+        let mut pane = QueuePane::new(&app_context);
+
+        terminal
+            .draw(|frame| pane.render(frame, frame.area(), &app_context).unwrap())
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let rows = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let current_row = rows.iter().position(|row| row.contains("2 >second")).unwrap();
+        let marker_x = u16::try_from(rows[current_row].find('>').unwrap()).unwrap();
+        let current_row = u16::try_from(current_row).unwrap();
+
+        assert!(rows.iter().any(|row| row.contains("1  first")));
+        assert!(rows.iter().any(|row| row.contains("3  third")));
+        assert_eq!(buffer[(marker_x, current_row)].fg, Color::Red);
+        assert_eq!(buffer[(marker_x + 1, current_row)].fg, Color::Red);
+    }
+
+    #[rstest]
+    fn row_with_configured_genre_has_tag_style(mut app_context: AppContext, mut terminal: Terminal<TestBackend>) {
+        let mut config = app_context.config.clone();
+        config.theme.tag_styles = vec![TagStyle {
+            tag: "genre",
+            value: "jazz",
+            style: Style::default().fg(Color::Yellow),
+        }]
+        .leak();
+        app_context.config = config.leak();
+        let mut jazz = song(2, "second");
+        jazz.metadata.insert("genre".to_owned(), "Jazz".to_owned());
+        app_context.queue = vec![song(1, "first"), jazz];
+        let mut pane = QueuePane::new(&app_context);
+
+        terminal
+            .draw(|frame| pane.render(frame, frame.area(), &app_context).unwrap())
+            .unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let position_of = |text: &str| {
+            (0..buffer.area.height).find_map(|y| {
+                let row = (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>();
+                row.find(text).map(|x| (u16::try_from(x).unwrap(), y))
+            })
+        };
+        let (jazz_x, jazz_y) = position_of("second").unwrap();
+        let (other_x, other_y) = position_of("first").unwrap();
+
+        assert_eq!(buffer[(jazz_x, jazz_y)].fg, Color::Yellow);
+        assert_ne!(buffer[(other_x, other_y)].fg, Color::Yellow);
+    }
+
+    #[rstest]
+    fn entering_filter_mode_shows_key_hints(mut app_context: AppContext) {
+        app_context.queue = vec![song(1, "first"), song(2, "second")];
+        let mut pane = QueuePane::new(&app_context);
+        assert_eq!(pane.key_hints(&app_context), None);
+
+        pane.handle_action(
+            &mut KeyEvent::new(KeyCode::Char('/'), KeyModifiers::NONE).into(),
+            &mut app_context,
+        )
+        .unwrap();
+
+        assert_eq!(
+            pane.key_hints(&app_context),
+            Some(vec![
+                KeyHint {
+                    keys: "<CR>".to_owned(),
+                    description: "apply filter",
+                },
+                KeyHint {
+                    keys: "<C-c>/<Esc>".to_owned(),
+                    description: "cancel filter",
+                },
+                KeyHint {
+                    keys: "<BS>".to_owned(),
+                    description: "delete character",
+                },
+            ])
+        );
+    }
+
+    #[rstest]
+    fn moving_down_selects_moved_song_and_clamps_at_bottom(mut app_context: AppContext) {
+        app_context.queue = vec![song(1, "first"), song(2, "second"), song(3, "third")];
+        let mut pane = QueuePane::new(&app_context);
+        pane.scrolling_state.set_content_len(Some(app_context.queue.len()));
+        pane.scrolling_state.select(Some(0), 0);
+        let ids = |context: &AppContext| context.queue.iter().map(|song| song.id).collect::<Vec<_>>();
+
+        pane.move_selected_by(1, &mut app_context).unwrap();
+
+        assert_eq!(pane.scrolling_state.get_selected(), Some(1));
+        assert_eq!(ids(&app_context), [2, 1, 3]);
+
+        pane.move_selected_by(5, &mut app_context).unwrap();
+
+        assert_eq!(pane.scrolling_state.get_selected(), Some(2));
+        assert_eq!(ids(&app_context), [2, 3, 1]);
+
+        pane.move_selected_by(-5, &mut app_context).unwrap();
+
+        assert_eq!(pane.scrolling_state.get_selected(), Some(0));
+        assert_eq!(ids(&app_context), [1, 2, 3]);
+    }
+
+    #[test]
+    fn move_to_top_keeps_relative_order() {
+        let marked = BTreeSet::from([2, 3, 6, 8]);
+
+        assert_eq!(
+            moves_to_edge(&marked, 10, true),
+            vec![(2..=3, 0), (6..=6, 2), (8..=8, 3)]
+        );
+    }
+
+    #[test]
+    fn move_to_bottom_keeps_relative_order() {
+        let marked = BTreeSet::from([2, 3, 6, 8]);
+
+        assert_eq!(
+            moves_to_edge(&marked, 10, false),
+            vec![(8..=8, 9), (6..=6, 8), (2..=3, 6)]
+        );
+    }
+
+    #[test_case(0, true, false, Some(3); "next album")]
+    #[test_case(4, true, false, Some(5); "next album from the middle of one")]
+    #[test_case(5, true, false, Some(6); "album without a tag counts as an album")]
+    #[test_case(6, true, false, None; "clamped at the last album")]
+    #[test_case(6, true, true, Some(0); "wraps to the first album")]
+    #[test_case(4, false, false, Some(3); "start of the current album")]
+    #[test_case(3, false, false, Some(0); "previous album")]
+    #[test_case(0, false, false, None; "clamped at the first album")]
+    #[test_case(0, false, true, Some(6); "wraps to the last album")]
+    fn album_boundary_over_consecutive_albums(from: usize, forward: bool, wrap: bool, expected: Option<usize>) {
+        let albums = [
+            Some("a"),
+            Some("a"),
+            Some("a"),
+            Some("b"),
+            Some("b"),
+            None,
+            Some("a"),
+            Some("a"),
+        ];
+
+        assert_eq!(album_boundary(&albums, from, forward, wrap), expected);
+    }
+
+    #[test]
+    fn contiguous_selection_is_shuffled_as_range() {
+        assert_eq!(contiguous_range(&BTreeSet::from([4, 5, 6, 7])), Some(4..=7));
+        assert_eq!(contiguous_range(&BTreeSet::from([4, 5, 7])), None);
+        assert_eq!(contiguous_range(&BTreeSet::from([4])), None);
+    }
+
+    #[rstest]
+    #[case(true, None, Some(250), 250 - 49)]
+    #[case(false, None, Some(0), 0)]
+    #[case(true, Some(Instant::now()), Some(0), 0)]
+    fn song_change_centers_view_when_following_playback(
+        mut app_context: AppContext,
+        mut terminal: Terminal<TestBackend>,
+        #[case] follow_playback: bool,
+        #[case] last_manual_scroll: Option<Instant>,
+        #[case] expected_selected: Option<usize>,
+        #[case] expected_offset: usize,
+    ) {
+        let mut config = app_context.config.clone();
+        config.follow_playback = follow_playback;
+        app_context.config = config.leak();
+        app_context.queue = (0..500).map(|id| song(id, &format!("artist_{id}"))).collect();
+        let mut pane = QueuePane::new(&app_context);
+        pane.before_show(&app_context).unwrap();
+        terminal
+            .draw(|frame| pane.render(frame, frame.area(), &app_context).unwrap())
+            .unwrap();
+        pane.last_manual_scroll = last_manual_scroll;
+
+        app_context.status.songid = Some(250);
+        app_context.status.state = State::Play;
+        pane.on_event(&mut UiEvent::SongChanged, true, &app_context).unwrap();
+
+        assert_eq!(pane.scrolling_state.get_selected(), expected_selected);
+        assert_eq!(pane.scrolling_state.offset(), expected_offset);
+    }
+
+    #[rstest]
+    fn player_event_does_not_move_cursor_without_song_change(
+        mut app_context: AppContext,
+        mut terminal: Terminal<TestBackend>,
+    ) {
+        let mut config = app_context.config.clone();
+        config.follow_playback = true;
+        app_context.config = config.leak();
+        app_context.queue = (0..500).map(|id| song(id, &format!("artist_{id}"))).collect();
+        let mut pane = QueuePane::new(&app_context);
+        pane.before_show(&app_context).unwrap();
+        terminal
+            .draw(|frame| pane.render(frame, frame.area(), &app_context).unwrap())
+            .unwrap();
+        app_context.status.songid = Some(250);
+        app_context.status.state = State::Play;
+        pane.on_event(&mut UiEvent::SongChanged, true, &app_context).unwrap();
+        pane.scrolling_state.select(Some(240), 0);
+
+        app_context.status.state = State::Pause;
+        pane.on_event(&mut UiEvent::Player, true, &app_context).unwrap();
+        assert_eq!(pane.scrolling_state.get_selected(), Some(240));
+
+        app_context.status.songid = Some(251);
+        app_context.status.state = State::Play;
+        pane.on_event(&mut UiEvent::Player, true, &app_context).unwrap();
+        assert_eq!(pane.scrolling_state.get_selected(), Some(251));
+    }
+
+    #[rstest]
+    #[case(SortColumn::Artist, false, vec![1, 3, 0, 2])]
+    #[case(SortColumn::Artist, true, vec![2, 0, 1, 3])]
+    #[case(SortColumn::Duration, false, vec![3, 2, 1, 0])]
+    fn sort_orders_queue_positions(#[case] column: SortColumn, #[case] descending: bool, #[case] expected: Vec<usize>) {
+        let queue = ["beta", "alpha", "gamma", "alpha"]
+            .into_iter()
             .enumerate()
-            .find(|(_, item)| item.matches(self.column_formats.as_slice(), filter))
-            .inspect(|(idx, _)| self.scrolling_state.select(Some(*idx), scrolloff));
+            .map(|(idx, artist)| Song {
+                duration: Some(std::time::Duration::from_secs(100 - idx as u64)),
+                ..song(u32::try_from(idx).unwrap(), artist)
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(QueueSort { column, descending }.order(&queue), expected);
+    }
+
+    #[rstest]
+    fn sorted_rows_map_to_queue_positions(mut app_context: AppContext, mut terminal: Terminal<TestBackend>) {
+        let mut config = app_context.config.clone();
+        config.theme.show_queue_position = true;
+        app_context.config = config.leak();
+        app_context.queue = vec![song(10, "charlie"), song(11, "alpha"), song(12, "bravo")];
+        let mut pane = QueuePane::new(&app_context);
+        pane.before_show(&app_context).unwrap();
+
+        pane.handle_action(
+            &mut KeyEvent::new(KeyCode::Char('o'), KeyModifiers::NONE).into(),
+            &mut app_context,
+        )
+        .unwrap();
+        terminal
+            .draw(|frame| pane.render(frame, frame.area(), &app_context).unwrap())
+            .unwrap();
+
+        // The cursor stays on the same song, now displayed last
+        assert_eq!(pane.scrolling_state.get_selected(), Some(2));
+        assert_eq!(pane.selected_song(&app_context.queue).map(|song| song.id), Some(10));
+        pane.scrolling_state.select(Some(0), 0);
+        assert_eq!(pane.selected_song(&app_context.queue).map(|song| song.id), Some(11));
+        assert_eq!(pane.row_of(0), 2);
+
+        // Displayed rows show the real queue position
+        let buffer = terminal.backend().buffer();
+        let rows = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>();
+        let alpha_row = rows.iter().position(|row| row.contains("2 alpha")).unwrap();
+        let charlie_row = rows.iter().position(|row| row.contains("1 charlie")).unwrap();
+        assert!(alpha_row < charlie_row);
+
+        // The actual queue is left untouched
+        assert_eq!(
+            app_context.queue.iter().map(|song| song.id).collect::<Vec<_>>(),
+            vec![10, 11, 12]
+        );
     }
 }
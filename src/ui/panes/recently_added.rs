@@ -0,0 +1,312 @@
+use anyhow::Result;
+use chrono::{SecondsFormat, TimeDelta, Utc};
+use itertools::Itertools;
+use ratatui::{prelude::Rect, widgets::StatefulWidget, Frame};
+
+use crate::{
+    config::tabs::PaneType,
+    context::AppContext,
+    mpd::{
+        client::Client,
+        commands::Song,
+        mpd_client::{Filter, MpdClient, Tag},
+    },
+    shared::{add_feedback::report_added, ext::mpd_client::MpdClientExt, key_event::KeyEvent, mouse_event::MouseEvent},
+    ui::{
+        browser::{add_within_limit, BrowserPane},
+        dirstack::{DirStack, DirStackItem},
+        key_hints::KeyHint,
+        widgets::browser::Browser,
+        UiEvent,
+    },
+    MpdQueryResult,
+};
+
+use super::{browser::DirOrSong, Pane};
+
+/// Songs added to the database in the last `recently_added_days` days, newest first
+#[derive(Debug)]
+pub struct RecentlyAddedPane {
+    stack: DirStack<DirOrSong>,
+    filter_input_mode: bool,
+    browser: Browser<DirOrSong>,
+    initialized: bool,
+}
+
+const INIT: &str = "init";
+const EMPTY_MESSAGE: &str = "No songs were added to the music database recently.";
+const PREVIEW: &str = "preview";
+
+impl RecentlyAddedPane {
+    pub fn new(context: &AppContext) -> Self {
+        Self {
+            stack: DirStack::default(),
+            filter_input_mode: false,
+            browser: Browser::new(context.config, "Recently added"),
+            initialized: false,
+        }
+    }
+
+    fn load(context: &AppContext) {
+        let days = context.config.recently_added_days;
+        context
+            .query()
+            .id(INIT)
+            .replace_id(INIT)
+            .target(PaneType::RecentlyAdded)
+            .query(move |client| {
+                let since = (Utc::now() - TimeDelta::days(days.into())).to_rfc3339_opts(SecondsFormat::Secs, true);
+                Ok(MpdQueryResult::SongsList {
+                    data: newest_first(client.modified_since(&since)?),
+                    origin_path: None,
+                })
+            });
+    }
+}
+
+/// MPD reports the modification time in UTC ISO 8601 so the timestamps can be compared as strings
+fn newest_first(mut songs: Vec<Song>) -> Vec<Song> {
+    songs.sort_by(|a, b| b.metadata.get("last-modified").cmp(&a.metadata.get("last-modified")));
+    songs
+}
+
+impl Pane for RecentlyAddedPane {
+    fn render(&mut self, frame: &mut Frame, area: Rect, context: &AppContext) -> Result<()> {
+        self.browser
+            .set_filter_input_active(self.filter_input_mode)
+            .set_draw_borders(context.draw_borders)
+            .set_favorites(&context.favorites)
+            .render(area, frame.buffer_mut(), &mut self.stack);
+
+        Ok(())
+    }
+
+    fn before_show(&mut self, context: &AppContext) -> Result<()> {
+        if !self.initialized {
+            Self::load(context);
+            self.initialized = true;
+        }
+
+        Ok(())
+    }
+
+    fn on_event(&mut self, event: &mut UiEvent, is_visible: bool, context: &AppContext) -> Result<()> {
+        match event {
+            UiEvent::Database => {
+                Self::load(context);
+            }
+            UiEvent::Reconnected => {
+                self.initialized = false;
+                self.before_show(context)?;
+            }
+            // The preview shows the rating of the selected song
+            UiEvent::Sticker if is_visible => {
+                self.prepare_preview(context)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn handle_mouse_event(&mut self, event: MouseEvent, context: &AppContext) -> Result<()> {
+        self.handle_mouse_action(event, context)
+    }
+
+    fn go_back(&mut self, context: &AppContext) -> Result<()> {
+        self.go_to_parent(context)
+    }
+
+    fn key_hints(&self, context: &AppContext) -> Option<Vec<KeyHint>> {
+        self.browser_key_hints(context)
+    }
+
+    fn selected_song(&self, _context: &AppContext) -> Option<&Song> {
+        self.browser_selected_song()
+    }
+
+    fn handle_action(&mut self, event: &mut KeyEvent, context: &mut AppContext) -> Result<()> {
+        self.handle_filter_input(event, context)?;
+        self.handle_common_action(event, context)?;
+        self.handle_global_action(event, context)?;
+        Ok(())
+    }
+
+    fn on_query_finished(&mut self, id: &'static str, data: MpdQueryResult, context: &AppContext) -> Result<()> {
+        match (id, data) {
+            (PREVIEW, MpdQueryResult::Preview { data, origin_path }) => {
+                if let Some(origin_path) = origin_path {
+                    if origin_path != self.stack().path() {
+                        log::trace!(origin_path:?, current_path:? = self.stack().path(); "Dropping preview because it does not belong to this path");
+                        return Ok(());
+                    }
+                }
+                self.stack_mut().set_preview(data);
+                context.render()?;
+            }
+            (INIT, MpdQueryResult::SongsList { data, origin_path: _ }) => {
+                self.browser.set_empty_message(data.is_empty().then_some(EMPTY_MESSAGE));
+                self.stack = DirStack::new(data.into_iter().map(DirOrSong::Song).collect());
+                self.prepare_preview(context)?;
+                context.render()?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+impl BrowserPane<DirOrSong> for RecentlyAddedPane {
+    fn stack(&self) -> &DirStack<DirOrSong> {
+        &self.stack
+    }
+
+    fn stack_mut(&mut self) -> &mut DirStack<DirOrSong> {
+        &mut self.stack
+    }
+
+    fn set_filter_input_mode_active(&mut self, active: bool) {
+        self.filter_input_mode = active;
+    }
+
+    fn is_filter_input_mode_active(&self) -> bool {
+        self.filter_input_mode
+    }
+
+    fn list_songs_in_item(&self, item: DirOrSong) -> impl FnOnce(&mut Client<'_>) -> Result<Vec<Song>> + 'static {
+        move |_client| {
+            Ok(match item {
+                DirOrSong::Dir { .. } => Vec::new(),
+                DirOrSong::Song(song) => vec![song],
+            })
+        }
+    }
+
+    fn open(&mut self, context: &AppContext) -> Result<()> {
+        let Some(item) = self.stack.current().selected() else {
+            return Ok(());
+        };
+        self.add(item, context)?;
+        let queue_len = context.queue.len();
+        context.command(move |client| Ok(client.play_last(queue_len)?));
+
+        Ok(())
+    }
+
+    fn next(&mut self, context: &AppContext) -> Result<()> {
+        let Some(item) = self.stack.current().selected() else {
+            return Ok(());
+        };
+        self.add(item, context)
+    }
+
+    fn add(&self, item: &DirOrSong, context: &AppContext) -> Result<()> {
+        let DirOrSong::Song(song) = item else {
+            return Ok(());
+        };
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
+        let file = song.file.clone();
+        let what = format!("'{}' by '{}'", song.title_str(), song.artist_str());
+        add_within_limit(
+            context,
+            |_| Ok(1),
+            move |client| {
+                client.add_and_autoplay(autoplay, |client| client.add(&file))?;
+                report_added(feedback, &what, 1);
+                Ok(())
+            },
+        );
+
+        Ok(())
+    }
+
+    fn add_all(&self, context: &AppContext) -> Result<()> {
+        let feedback = context.config.add_feedback;
+        let autoplay = context.config.autoplay_on_add;
+        let files = self
+            .stack
+            .current()
+            .items
+            .iter()
+            .filter_map(|item| match item {
+                DirOrSong::Song(song) => Some(song.file.clone()),
+                DirOrSong::Dir { .. } => None,
+            })
+            .collect_vec();
+        let count = files.len();
+        add_within_limit(
+            context,
+            move |_| Ok(count),
+            move |client| {
+                let count = client.count_added(autoplay, |client| {
+                    client.add_multiple(&files.iter().map(String::as_str).collect_vec())
+                })?;
+                report_added(feedback, "Recently added songs", count);
+                Ok(())
+            },
+        );
+
+        Ok(())
+    }
+
+    fn prepare_preview(&mut self, context: &AppContext) -> Result<()> {
+        let Some(current) = self.stack().current().selected().map(DirStackItem::as_path) else {
+            return Ok(());
+        };
+        let file = current.to_owned();
+        let config = context.config;
+        let origin_path = Some(self.stack().path().to_vec());
+        let with_rating = context.supported_commands.contains("sticker");
+
+        self.stack_mut().clear_preview();
+        context
+            .query()
+            .id(PREVIEW)
+            .replace_id("recently_added_preview")
+            .target(PaneType::RecentlyAdded)
+            .query(move |client| {
+                Ok(MpdQueryResult::Preview {
+                    data: client
+                        .find_one(&[Filter::new(Tag::File, &file)])?
+                        .map(|v| v.to_rated_preview(client, &config.theme.symbols, with_rating)),
+                    origin_path,
+                })
+            });
+
+        Ok(())
+    }
+
+    fn browser_areas(&self) -> [Rect; 3] {
+        self.browser.areas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::newest_first;
+    use crate::mpd::commands::Song;
+
+    fn song(file: &str, last_modified: &str) -> Song {
+        Song {
+            file: file.to_owned(),
+            metadata: HashMap::from([("last-modified".to_owned(), last_modified.to_owned())]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn newest_songs_come_first() {
+        let songs = newest_first(vec![
+            song("old", "2024-01-02T10:00:00Z"),
+            song("newest", "2024-03-01T08:00:00Z"),
+            song("new", "2024-02-15T23:59:59Z"),
+        ]);
+
+        assert_eq!(
+            songs.iter().map(|song| song.file.as_str()).collect::<Vec<_>>(),
+            ["newest", "new", "old"]
+        );
+    }
+}
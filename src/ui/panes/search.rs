@@ -1,4 +1,5 @@
 use std::rc::Rc;
+use std::time::Duration;
 
 use anyhow::Context;
 use anyhow::Result;
@@ -14,25 +15,37 @@ use ratatui::{
 };
 
 use crate::config::keys::GlobalAction;
+use crate::config::keys::SearchActions;
 use crate::config::tabs::PaneType;
 use crate::config::Config;
 use crate::config::Search;
 use crate::context::AppContext;
 use crate::core::command::create_env;
 use crate::core::command::run_external;
+use crate::core::command::{change_rating, run_tag_editor};
+use crate::mpd::commands::lsinfo::LsInfoEntry;
 use crate::mpd::commands::Song;
+use crate::shared::clipboard;
+use crate::shared::ext::duration::DurationExt;
 use crate::shared::ext::mpd_client::MpdClientExt;
+use crate::shared::favorites::Favorites;
 use crate::shared::key_event::KeyEvent;
+use crate::shared::macros::modal;
+use crate::shared::macros::status_error;
 use crate::shared::macros::status_info;
 use crate::shared::macros::status_warn;
 use crate::shared::mouse_event::MouseEvent;
 use crate::shared::mouse_event::MouseEventKind;
+use crate::shared::time_budget;
+use crate::ui::browser::{add_files, add_within_limit};
 use crate::ui::dirstack::Dir;
 use crate::ui::dirstack::DirStackItem;
+use crate::ui::modals::confirm_modal::ConfirmModal;
+use crate::ui::modals::input_modal::InputModal;
 use crate::ui::UiEvent;
 use crate::MpdQueryResult;
 use crate::{
-    mpd::mpd_client::{Filter, FilterKind, MpdClient, Tag},
+    mpd::mpd_client::{filter_command, Filter, FilterKind, MpdClient, Tag},
     ui::widgets::{button::Button, input::Input},
 };
 
@@ -44,12 +57,15 @@ pub struct SearchPane {
     phase: Phase,
     preview: Option<Vec<ListItem<'static>>>,
     songs_dir: Dir<Song>,
+    /// Full result set of the last search while the displayed results are refined by a client side filter
+    unrefined_songs: Option<Vec<Song>>,
     input_areas: Rc<[Rect]>,
     column_areas: [Rect; 3],
 }
 
 const PREVIEW: &str = "preview";
 const SEARCH: &str = "search";
+const SAVE_AS_PLAYLIST: &str = "save_as_playlist";
 
 impl SearchPane {
     pub fn new(context: &AppContext) -> Self {
@@ -58,6 +74,7 @@ impl SearchPane {
             preview: None,
             phase: Phase::Search,
             songs_dir: Dir::default(),
+            unrefined_songs: None,
             inputs: InputGroups::new(
                 &config.search,
                 [
@@ -85,28 +102,32 @@ impl SearchPane {
     }
 
     fn add_current(&mut self, autoplay: bool, context: &AppContext) -> Result<()> {
+        let autoplay_on_add = context.config.autoplay_on_add;
         if !self.songs_dir.marked().is_empty() {
-            for idx in self.songs_dir.marked() {
-                let item = self.songs_dir.items[*idx].file.clone();
-                context.command(move |client| {
-                    client.add(&item)?;
-                    Ok(())
-                });
-            }
-            status_info!("Added {} songs to queue", self.songs_dir.marked().len());
+            let files = self
+                .songs_dir
+                .marked()
+                .iter()
+                .map(|idx| self.songs_dir.items[*idx].file.clone())
+                .collect_vec();
+            add_files(files, "Marked songs", context);
 
             context.render()?;
         } else if let Some(item) = self.songs_dir.selected() {
             let item = item.file.clone();
-            context.command(move |client| {
-                client.add(&item)?;
-                status_info!("Added '{item}' to queue");
-                Ok(())
-            });
             let queue_len = context.queue.len();
-            if autoplay {
-                context.command(move |client| Ok(client.play_last(queue_len)?));
-            }
+            add_within_limit(
+                context,
+                |_| Ok(1),
+                move |client| {
+                    client.add_and_autoplay(autoplay_on_add, |client| client.add(&item))?;
+                    status_info!("Added '{item}' to queue");
+                    if autoplay {
+                        client.play_last(queue_len)?;
+                    }
+                    Ok(())
+                },
+            );
 
             context.render()?;
         }
@@ -119,6 +140,7 @@ impl SearchPane {
         frame: &mut ratatui::prelude::Frame<'_>,
         area: ratatui::prelude::Rect,
         config: &Config,
+        favorites: &Favorites,
     ) {
         let title = self.songs_dir.filter().as_ref().map(|v| {
             format!(
@@ -138,7 +160,8 @@ impl SearchPane {
             }
             b.padding(Padding::new(0, 2, 0, 0))
         };
-        let current = List::new(self.songs_dir.to_list_items(config)).highlight_style(config.theme.current_item_style);
+        let current =
+            List::new(self.songs_dir.to_list_items(config, favorites)).highlight_style(config.theme.current_item_style);
         let directory = &mut self.songs_dir;
 
         directory.state.set_content_len(Some(directory.items.len()));
@@ -157,11 +180,9 @@ impl SearchPane {
         self.column_areas[1] = inner_block;
         frame.render_widget(block, area);
         frame.render_stateful_widget(current, inner_block, directory.state.as_render_state_ref());
-        frame.render_stateful_widget(
-            config.as_styled_scrollbar(),
-            area,
-            directory.state.as_scrollbar_state_ref(),
-        );
+        if let Some(scrollbar) = config.as_styled_scrollbar(&directory.state) {
+            frame.render_stateful_widget(scrollbar, area, directory.state.as_scrollbar_state_ref());
+        }
     }
 
     fn prepare_preview(&mut self, context: &AppContext) {
@@ -171,7 +192,7 @@ impl SearchPane {
         match &self.phase {
             Phase::SearchTextboxInput => {}
             Phase::Search => {
-                let data = Some(self.songs_dir.to_list_items(context.config));
+                let data = Some(self.songs_dir.to_list_items(context.config, &context.favorites));
                 context
                     .query()
                     .id(PREVIEW)
@@ -190,6 +211,7 @@ impl SearchPane {
                 };
                 let config = context.config;
                 let file = current.file.clone();
+                let with_rating = context.supported_commands.contains("sticker");
 
                 context
                     .query()
@@ -202,8 +224,7 @@ impl SearchPane {
                                 .find(&[Filter::new(Tag::File, &file)])?
                                 .first()
                                 .context("Expected to find exactly one song")?
-                                .to_preview(&config.theme.symbols)
-                                .collect_vec(),
+                                .to_rated_preview(client, &config.theme.symbols, with_rating),
                         );
                         Ok(MpdQueryResult::Preview {
                             data,
@@ -347,54 +368,161 @@ impl SearchPane {
             })
     }
 
-    fn search_add(&mut self, context: &AppContext) {
+    /// Returns the filter built from the non-empty textboxes and whether the search should be case sensitive
+    fn active_filter(&self) -> (Vec<(&'static str, String, FilterKind)>, bool) {
         let (filter_kind, case_sensitive) = self.filter_type();
-        let filter = self.inputs.textbox_inputs.iter().filter_map(|input| match &input {
-            Textbox { value, filter_key, .. } if !value.is_empty() => {
-                Some((filter_key as &'static str, value.to_owned(), filter_kind))
-            }
-            _ => None,
-        });
+        let filter = self
+            .inputs
+            .textbox_inputs
+            .iter()
+            .filter_map(|input| match &input {
+                Textbox { value, filter_key, .. } if !value.is_empty() => {
+                    Some((*filter_key, value.to_owned(), filter_kind))
+                }
+                _ => None,
+            })
+            .collect_vec();
 
-        let filter = filter.collect_vec();
+        (filter, case_sensitive)
+    }
 
+    /// Copies the raw MPD command of the current search to the clipboard, useful for debugging and scripting
+    fn copy_mpd_command(&self) -> Result<()> {
+        let (filter, case_sensitive) = self.active_filter();
         if filter.is_empty() {
-            return;
+            status_warn!("Nothing to search for");
+            return Ok(());
+        }
+
+        let command = search_command(&filter, case_sensitive);
+        clipboard::copy(&command)?;
+        status_info!("Copied '{command}' to clipboard");
+        Ok(())
+    }
+
+    /// Replaces the queue with all songs matching the current search, asks for confirmation first if configured
+    fn replace_queue(&mut self, context: &AppContext) -> Result<()> {
+        let (filter, case_sensitive) = self.active_filter();
+        if filter.is_empty() {
+            status_warn!("Nothing to search for");
+            return Ok(());
         }
 
-        if case_sensitive {
+        let replace = move |context: &AppContext| {
+            let filter = filter.clone();
             context.command(move |client| {
-                client.find_add(
+                replace_queue_with_search(
+                    client,
                     &filter
                         .iter()
                         .map(|(key, value, kind)| Filter::new(*key, value).with_type(*kind))
                         .collect_vec(),
+                    case_sensitive,
                 )?;
+                status_info!("Queue replaced with search results");
                 Ok(())
             });
+            Ok(())
+        };
+
+        if context.config.confirm_destructive_actions {
+            modal!(
+                context,
+                ConfirmModal::new(context)
+                    .message("Are you sure you want to replace the queue with the search results? This action cannot be undone.")
+                    .on_confirm(replace)
+                    .confirm_label("Replace")
+                    .size(45, 7)
+            );
         } else {
-            context.command(move |client| {
-                client.search_add(
-                    &filter
-                        .iter()
-                        .map(|(key, value, kind)| Filter::new(*key, value).with_type(*kind))
-                        .collect_vec(),
-                )?;
-                Ok(())
-            });
+            replace(context)?;
         }
+
+        Ok(())
     }
 
-    fn search(&mut self, context: &AppContext) {
-        let (filter_kind, case_sensitive) = self.filter_type();
-        let filter = self.inputs.textbox_inputs.iter().filter_map(|input| match &input {
-            Textbox { value, filter_key, .. } if !value.is_empty() => {
-                Some((filter_key as &'static str, value.to_owned(), filter_kind))
-            }
-            _ => None,
-        });
+    /// Asks for a time budget and adds a random selection of songs matching the current search which fits into it.
+    /// Picks from the whole library when the search is empty.
+    fn fill_time_budget(&self, context: &AppContext) -> Result<()> {
+        let (filter, case_sensitive) = self.active_filter();
+
+        modal!(
+            context,
+            InputModal::new(context)
+                .title("Fill queue by time budget")
+                .confirm_label("Fill")
+                .input_label("Minutes:")
+                .on_confirm(move |context, value| {
+                    let Ok(minutes) = value.trim().parse::<u64>() else {
+                        status_error!("Invalid time budget '{}', expected number of minutes", value);
+                        return Ok(());
+                    };
+                    let budget = Duration::from_secs(minutes.saturating_mul(60));
+                    let filter = filter.clone();
+                    context.command(move |client| {
+                        let filter = filter
+                            .iter()
+                            .map(|(key, value, kind)| Filter::new(*key, value).with_type(*kind))
+                            .collect_vec();
+                        let (songs, total) = fill_queue_by_time_budget(client, &filter, case_sensitive, budget)?;
+                        status_info!(
+                            "Added {} songs with total duration {} out of {}",
+                            songs.len(),
+                            total.to_string(),
+                            budget.to_string()
+                        );
+                        Ok(())
+                    });
+                    Ok(())
+                })
+        );
+
+        Ok(())
+    }
 
-        let filter = filter.collect_vec();
+    fn search_add(&mut self, context: &AppContext) {
+        let (filter, case_sensitive) = self.active_filter();
+
+        if filter.is_empty() {
+            return;
+        }
+
+        let autoplay = context.config.autoplay_on_add;
+        let count_filter = filter.clone();
+        add_within_limit(
+            context,
+            move |client| {
+                let filter = count_filter
+                    .iter()
+                    .map(|(key, value, kind)| Filter::new(*key, value).with_type(*kind))
+                    .collect_vec();
+                let songs = if case_sensitive {
+                    client.find(&filter)?
+                } else {
+                    client.search(&filter)?
+                };
+                Ok(songs.len())
+            },
+            move |client| {
+                let filter = filter
+                    .iter()
+                    .map(|(key, value, kind)| Filter::new(*key, value).with_type(*kind))
+                    .collect_vec();
+                client.add_and_autoplay(autoplay, |client| {
+                    if case_sensitive {
+                        client.find_add(&filter, None)
+                    } else {
+                        client.search_add(&filter, None)
+                    }
+                })?;
+                status_info!("All found songs added to queue");
+                Ok(())
+            },
+        );
+    }
+
+    fn search(&mut self, context: &AppContext) {
+        let (filter, case_sensitive) = self.active_filter();
 
         if filter.is_empty() {
             let _ = std::mem::take(&mut self.songs_dir);
@@ -440,6 +568,84 @@ impl SearchPane {
         }
     }
 
+    /// Asks for a playlist name and stores the currently displayed results under it
+    fn save_as_playlist(&self, context: &AppContext) -> Result<()> {
+        let files = self.songs_dir.items.iter().map(|song| song.file.clone()).collect_vec();
+        if files.is_empty() {
+            status_warn!("No search results to save");
+            return Ok(());
+        }
+
+        modal!(
+            context,
+            InputModal::new(context)
+                .title("Save search results as playlist")
+                .confirm_label("Save")
+                .input_label("Playlist name:")
+                .on_confirm(move |context, name| {
+                    let name = name.to_owned();
+                    let files = files.clone();
+                    context
+                        .query()
+                        .id(SAVE_AS_PLAYLIST)
+                        .replace_id(SAVE_AS_PLAYLIST)
+                        .target(PaneType::Search)
+                        .query(move |client| {
+                            let exists = client.list_playlists()?.iter().any(|p| p.name == name);
+                            Ok(MpdQueryResult::SaveAsPlaylist { name, files, exists })
+                        });
+                    Ok(())
+                })
+        );
+
+        Ok(())
+    }
+
+    /// Narrows the current results to the songs matching the current filter. The full result set is kept so the
+    /// refinement can be cleared later. Refining repeatedly narrows the results further.
+    fn refine_results(&mut self, context: &AppContext) {
+        let Some(filter) = self.songs_dir.filter().filter(|f| !f.is_empty()).map(str::to_owned) else {
+            return;
+        };
+        let refined = self
+            .songs_dir
+            .items
+            .iter()
+            .filter(|song| DirStackItem::matches(*song, context.config, &context.favorites, &filter))
+            .cloned()
+            .collect_vec();
+        self.unrefined_songs
+            .get_or_insert_with(|| std::mem::take(&mut self.songs_dir.items));
+        self.songs_dir = Dir::new(refined);
+    }
+
+    /// Restores the full result set of the last search, returns false if the results were not refined
+    fn clear_refinement(&mut self) -> bool {
+        let Some(songs) = self.unrefined_songs.take() else {
+            return false;
+        };
+        self.songs_dir = Dir::new(songs);
+        true
+    }
+
+    /// Focuses the configured default tag and sets the filter kind to the configured search mode
+    fn seed_defaults(&mut self, search_config: &Search) {
+        for val in &mut self.inputs.filter_inputs {
+            if let FilterInputVariant::SelectFilterKind { ref mut value } = val.variant {
+                *value = search_config.mode;
+            }
+        }
+        match search_config.default_tag {
+            Some(tag) => {
+                if !self.inputs.focus_textbox(tag) {
+                    log::warn!(tag; "Configured default search tag is not in the list of searchable tags");
+                    self.inputs.first();
+                }
+            }
+            None => self.inputs.first(),
+        }
+    }
+
     fn activate_input(&mut self, context: &AppContext) {
         match self.inputs.focused_mut() {
             FocusedInputGroup::Textboxes(_) => self.phase = Phase::SearchTextboxInput,
@@ -447,6 +653,7 @@ impl SearchPane {
                 // Reset is the only button in this group at the moment
                 self.reset(&context.config.search);
                 self.songs_dir = Dir::default();
+                self.unrefined_songs = None;
                 self.prepare_preview(context);
             }
             FocusedInputGroup::Filters(FilterInput {
@@ -498,7 +705,7 @@ impl Pane for SearchPane {
         &mut self,
         frame: &mut ratatui::prelude::Frame,
         area: ratatui::prelude::Rect,
-        AppContext { config, .. }: &AppContext,
+        AppContext { config, favorites, .. }: &AppContext,
     ) -> anyhow::Result<()> {
         let widths = &config.theme.column_widths;
         let [previous_area, current_area_init, preview_area] = *Layout::horizontal([
@@ -553,7 +760,7 @@ impl Pane for SearchPane {
                 }
             }
             Phase::BrowseResults { filter_input_on: _ } => {
-                self.render_song_column(frame, current_area, config);
+                self.render_song_column(frame, current_area, config, favorites);
                 self.render_input_column(frame, previous_area, config);
                 if let Some(preview) = &self.preview {
                     let preview = List::new(preview.clone()).highlight_style(config.theme.current_item_style);
@@ -568,10 +775,27 @@ impl Pane for SearchPane {
         Ok(())
     }
 
-    fn on_event(&mut self, event: &mut UiEvent, _is_visible: bool, context: &AppContext) -> Result<()> {
+    fn selected_song(&self, _context: &AppContext) -> Option<&Song> {
+        match self.phase {
+            Phase::BrowseResults { .. } => self.songs_dir.selected(),
+            Phase::Search | Phase::SearchTextboxInput => None,
+        }
+    }
+
+    fn before_show(&mut self, context: &AppContext) -> Result<()> {
+        // Only seed the defaults when there is no search in progress so switching tabs keeps the current query
+        if matches!(self.phase, Phase::Search) && self.inputs.textbox_inputs.iter().all(|i| i.value.is_empty()) {
+            self.seed_defaults(&context.config.search);
+        }
+
+        Ok(())
+    }
+
+    fn on_event(&mut self, event: &mut UiEvent, is_visible: bool, context: &AppContext) -> Result<()> {
         match event {
             UiEvent::Database => {
                 self.songs_dir = Dir::default();
+                self.unrefined_songs = None;
                 self.prepare_preview(context);
                 self.phase = Phase::Search;
 
@@ -581,6 +805,11 @@ impl Pane for SearchPane {
                 self.phase = Phase::Search;
                 self.preview = None;
                 self.songs_dir = Dir::default();
+                self.unrefined_songs = None;
+            }
+            // The preview shows the rating of the selected song
+            UiEvent::Sticker if is_visible => {
+                self.prepare_preview(context);
             }
             _ => {}
         }
@@ -603,9 +832,40 @@ impl Pane for SearchPane {
                 self.preview = data;
                 context.render()?;
             }
+            (
+                SAVE_AS_PLAYLIST,
+                MpdQueryResult::SaveAsPlaylist {
+                    name,
+                    files,
+                    exists: true,
+                },
+            ) => {
+                modal!(
+                    context,
+                    ConfirmModal::new(context)
+                        .message("A playlist with this name already exists. Do you want to overwrite it?")
+                        .on_confirm(move |context| {
+                            save_as_playlist(context, name.clone(), files.clone(), true);
+                            Ok(())
+                        })
+                        .confirm_label("Overwrite")
+                        .size(45, 6)
+                );
+            }
+            (
+                SAVE_AS_PLAYLIST,
+                MpdQueryResult::SaveAsPlaylist {
+                    name,
+                    files,
+                    exists: false,
+                },
+            ) => {
+                save_as_playlist(context, name, files, false);
+            }
             (SEARCH, MpdQueryResult::SongsList { data, origin_path: _ }) => {
                 self.songs_dir = Dir::new(data);
-                self.preview = Some(self.songs_dir.to_list_items(context.config));
+                self.unrefined_songs = None;
+                self.preview = Some(self.songs_dir.to_list_items(context.config, &context.favorites));
                 context.render()?;
             }
             _ => {}
@@ -696,7 +956,8 @@ impl Pane for SearchPane {
                     context.render()?;
                 }
                 Phase::BrowseResults { .. } => {
-                    self.songs_dir.next(context.config.scrolloff, false);
+                    self.songs_dir
+                        .scroll_down(context.config.scroll_amount, context.config.scrolloff);
                     self.prepare_preview(context);
 
                     context.render()?;
@@ -714,7 +975,8 @@ impl Pane for SearchPane {
                     context.render()?;
                 }
                 Phase::BrowseResults { .. } => {
-                    self.songs_dir.prev(context.config.scrolloff, false);
+                    self.songs_dir
+                        .scroll_up(context.config.scroll_amount, context.config.scrolloff);
                     self.prepare_preview(context);
 
                     context.render()?;
@@ -766,7 +1028,15 @@ impl Pane for SearchPane {
                 }
             },
             Phase::Search => {
-                if let Some(action) = event.as_global_action(context) {
+                if let Some(action) = event.as_search_action(context) {
+                    match action {
+                        SearchActions::SaveAsPlaylist => self.save_as_playlist(context)?,
+                        SearchActions::ReplaceQueue => self.replace_queue(context)?,
+                        SearchActions::FillTimeBudget => self.fill_time_budget(context)?,
+                        SearchActions::RefineResults => {}
+                        SearchActions::CopyMpdCommand => self.copy_mpd_command()?,
+                    }
+                } else if let Some(action) = event.as_global_action(context) {
                     if let GlobalAction::ExternalCommand { command, .. } = action {
                         let songs = self.songs_dir.items.iter().map(|song| song.file.as_str());
                         run_external(command, create_env(context, songs));
@@ -775,6 +1045,8 @@ impl Pane for SearchPane {
                     }
                 } else if let Some(action) = event.as_common_action(context) {
                     match action {
+                        CommonAction::CopyFilePath => {}
+                        CommonAction::OpenInTagEditor => {}
                         CommonAction::Down => {
                             if config.wrap_navigation {
                                 self.inputs.next();
@@ -797,6 +1069,8 @@ impl Pane for SearchPane {
                         CommonAction::MoveUp => {}
                         CommonAction::DownHalf => {}
                         CommonAction::UpHalf => {}
+                        CommonAction::PageDown => {}
+                        CommonAction::PageUp => {}
                         CommonAction::Right if !self.songs_dir.items.is_empty() => {
                             self.phase = Phase::BrowseResults { filter_input_on: false };
                             self.preview = None;
@@ -821,6 +1095,7 @@ impl Pane for SearchPane {
                         CommonAction::PreviousResult => {}
                         CommonAction::Select => {}
                         CommonAction::InvertSelection => {}
+                        CommonAction::SelectAll => {}
                         CommonAction::Rename => {}
                         CommonAction::Close => {}
                         CommonAction::Confirm => {
@@ -837,8 +1112,6 @@ impl Pane for SearchPane {
                         CommonAction::AddAll => {
                             self.search_add(context);
 
-                            status_info!("All found songs added to queue");
-
                             context.render()?;
                         }
                         CommonAction::FocusInput => {}
@@ -856,6 +1129,10 @@ impl Pane for SearchPane {
                         CommonAction::PaneUp => {}
                         CommonAction::PaneRight => {}
                         CommonAction::PaneLeft => {}
+                        CommonAction::GoToParent => {}
+                        CommonAction::GoToRoot => {}
+                        CommonAction::IncreaseRating => {}
+                        CommonAction::DecreaseRating => {}
                     }
                 }
             }
@@ -864,7 +1141,7 @@ impl Pane for SearchPane {
             } => match event.as_common_action(context) {
                 Some(CommonAction::Close) => {
                     *filter_input_on = false;
-                    self.songs_dir.set_filter(None, config);
+                    self.songs_dir.set_filter(None, config, &context.favorites);
                     self.prepare_preview(context);
 
                     context.render()?;
@@ -878,14 +1155,14 @@ impl Pane for SearchPane {
                     event.stop_propagation();
                     match event.code() {
                         KeyCode::Char(c) => {
-                            self.songs_dir.push_filter(c, config);
-                            self.songs_dir.jump_first_matching(config);
+                            self.songs_dir.push_filter(c, config, &context.favorites);
+                            self.songs_dir.jump_first_matching(config, &context.favorites);
                             self.prepare_preview(context);
 
                             context.render()?;
                         }
                         KeyCode::Backspace => {
-                            self.songs_dir.pop_filter(config);
+                            self.songs_dir.pop_filter(config, &context.favorites);
 
                             context.render()?;
                         }
@@ -896,7 +1173,20 @@ impl Pane for SearchPane {
             Phase::BrowseResults {
                 filter_input_on: filter_input_modce @ false,
             } => {
-                if let Some(action) = event.as_global_action(context) {
+                if let Some(action) = event.as_search_action(context) {
+                    match action {
+                        SearchActions::SaveAsPlaylist => self.save_as_playlist(context)?,
+                        SearchActions::ReplaceQueue => self.replace_queue(context)?,
+                        SearchActions::FillTimeBudget => self.fill_time_budget(context)?,
+                        SearchActions::RefineResults => {
+                            self.refine_results(context);
+                            self.prepare_preview(context);
+
+                            context.render()?;
+                        }
+                        SearchActions::CopyMpdCommand => self.copy_mpd_command()?,
+                    }
+                } else if let Some(action) = event.as_global_action(context) {
                     match action {
                         GlobalAction::ExternalCommand { command, .. } if !self.songs_dir.marked().is_empty() => {
                             let songs = self.songs_dir.marked_items().map(|song| song.file.as_str());
@@ -912,6 +1202,32 @@ impl Pane for SearchPane {
                     }
                 } else if let Some(action) = event.as_common_action(context) {
                     match action {
+                        CommonAction::CopyFilePath => {
+                            if let Some(song) = self.songs_dir.selected() {
+                                let path = song.resolved_path(context.config.music_directory);
+                                clipboard::copy(&path)?;
+                                status_info!("Copied '{path}' to clipboard");
+                            }
+                        }
+                        CommonAction::OpenInTagEditor => {
+                            let Some(command) = context.config.tag_editor else {
+                                status_warn!("No tag editor configured");
+                                return Ok(());
+                            };
+                            if let Some(song) = self.songs_dir.selected() {
+                                run_tag_editor(command, song, context);
+                            }
+                        }
+                        CommonAction::IncreaseRating => {
+                            if let Some(song) = self.songs_dir.selected() {
+                                change_rating(song, 1, context);
+                            }
+                        }
+                        CommonAction::DecreaseRating => {
+                            if let Some(song) = self.songs_dir.selected() {
+                                change_rating(song, -1, context);
+                            }
+                        }
                         CommonAction::Down => {
                             self.songs_dir
                                 .next(context.config.scrolloff, context.config.wrap_navigation);
@@ -940,8 +1256,20 @@ impl Pane for SearchPane {
 
                             context.render()?;
                         }
+                        CommonAction::PageDown => {
+                            self.songs_dir.next_viewport(context.config.scrolloff);
+                            self.prepare_preview(context);
+
+                            context.render()?;
+                        }
+                        CommonAction::PageUp => {
+                            self.songs_dir.prev_viewport(context.config.scrolloff);
+                            self.prepare_preview(context);
+
+                            context.render()?;
+                        }
                         CommonAction::Right => self.add_current(false, context)?,
-                        CommonAction::Left => {
+                        CommonAction::Left | CommonAction::GoToParent | CommonAction::GoToRoot => {
                             self.phase = Phase::Search;
                             self.prepare_preview(context);
 
@@ -960,19 +1288,20 @@ impl Pane for SearchPane {
                             context.render()?;
                         }
                         CommonAction::EnterSearch => {
-                            self.songs_dir.set_filter(Some(String::new()), config);
+                            self.songs_dir
+                                .set_filter(Some(String::new()), config, &context.favorites);
                             *filter_input_modce = true;
 
                             context.render()?;
                         }
                         CommonAction::NextResult => {
-                            self.songs_dir.jump_next_matching(config);
+                            self.songs_dir.jump_next_matching(config, &context.favorites);
                             self.prepare_preview(context);
 
                             context.render()?;
                         }
                         CommonAction::PreviousResult => {
-                            self.songs_dir.jump_previous_matching(config);
+                            self.songs_dir.jump_previous_matching(config, &context.favorites);
                             self.prepare_preview(context);
 
                             context.render()?;
@@ -989,8 +1318,19 @@ impl Pane for SearchPane {
 
                             context.render()?;
                         }
+                        CommonAction::SelectAll => {
+                            self.songs_dir.toggle_mark_all(config, &context.favorites);
+
+                            context.render()?;
+                        }
                         CommonAction::Rename => {}
-                        CommonAction::Close => {}
+                        CommonAction::Close => {
+                            if self.clear_refinement() {
+                                self.prepare_preview(context);
+
+                                context.render()?;
+                            }
+                        }
                         CommonAction::Confirm => {
                             self.add_current(true, context)?;
 
@@ -998,9 +1338,14 @@ impl Pane for SearchPane {
                         }
                         CommonAction::FocusInput => {}
                         CommonAction::Add => self.add_current(false, context)?,
+                        CommonAction::AddAll if self.unrefined_songs.is_some() => {
+                            let files = self.songs_dir.items.iter().map(|song| song.file.clone()).collect_vec();
+                            add_files(files, "All refined songs", context);
+
+                            context.render()?;
+                        }
                         CommonAction::AddAll => {
                             self.search_add(context);
-                            status_info!("All found songs added to queue");
 
                             context.render()?;
                         }
@@ -1017,6 +1362,59 @@ impl Pane for SearchPane {
     }
 }
 
+fn replace_queue_with_search(client: &mut impl MpdClient, filter: &[Filter<'_>], case_sensitive: bool) -> Result<()> {
+    client.clear()?;
+    if case_sensitive {
+        client.find_add(filter, None)?;
+    } else {
+        client.search_add(filter, None)?;
+    }
+    Ok(())
+}
+
+/// Adds a random selection of songs matching the filter, or of the whole library when the filter is empty, which
+/// fits into the budget. Returns the added songs and their total duration.
+fn fill_queue_by_time_budget(
+    client: &mut impl MpdClient,
+    filter: &[Filter<'_>],
+    case_sensitive: bool,
+    budget: Duration,
+) -> Result<(Vec<Song>, Duration)> {
+    let mut songs = if filter.is_empty() {
+        client
+            .list_all_info(None)?
+            .0
+            .into_iter()
+            .filter_map(|entry| match entry {
+                LsInfoEntry::File(song) => Some(song),
+                LsInfoEntry::Dir(_) | LsInfoEntry::Playlist(_) => None,
+            })
+            .collect_vec()
+    } else if case_sensitive {
+        client.find(filter)?
+    } else {
+        client.search(filter)?
+    };
+    time_budget::shuffle(&mut songs);
+    let (songs, total) = time_budget::fill(songs, budget);
+    client.add_multiple(&songs.iter().map(|song| song.file.as_str()).collect_vec())?;
+    Ok((songs, total))
+}
+
+fn save_as_playlist(context: &AppContext, name: String, files: Vec<String>, overwrite: bool) {
+    context.command(move |client| {
+        match client.save_as_playlist(&name, &files.iter().map(String::as_str).collect_vec(), overwrite) {
+            Ok(()) => {
+                status_info!("Saved {} songs to playlist '{}'", files.len(), name);
+            }
+            Err(err) => {
+                status_error!(err:?; "Failed to save playlist '{}'", name);
+            }
+        }
+        Ok(())
+    });
+}
+
 enum FocusedInputGroup<T, F, B> {
     Textboxes(T),
     Filters(F),
@@ -1060,6 +1458,17 @@ impl<const N2: usize, const N3: usize> InputGroups<N2, N3> {
         self.focused_idx = FocusedInput::Textboxes(0);
     }
 
+    /// Focuses the textbox for the given tag, returns false if there is no such textbox
+    pub fn focus_textbox(&mut self, filter_key: &str) -> bool {
+        match self.textbox_inputs.iter().position(|i| i.filter_key == filter_key) {
+            Some(idx) => {
+                self.focused_idx = FocusedInput::Textboxes(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn last(&mut self) {
         self.focused_idx = FocusedInput::Buttons(self.button_inputs.len() - 1);
     }
@@ -1169,6 +1578,15 @@ impl<const N2: usize, const N3: usize> InputGroups<N2, N3> {
     }
 }
 
+/// Protocol command sent to MPD for the search
+fn search_command(filter: &[(&'static str, String, FilterKind)], case_sensitive: bool) -> String {
+    let filter = filter
+        .iter()
+        .map(|(key, value, kind)| Filter::new(*key, value).with_type(*kind))
+        .collect_vec();
+    filter_command(if case_sensitive { "find" } else { "search" }, &filter)
+}
+
 #[derive(Debug)]
 enum Phase {
     SearchTextboxInput,
@@ -1205,3 +1623,208 @@ struct ButtonInput {
 enum ButtonInputVariant {
     Reset,
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crossbeam::channel::{Receiver, Sender};
+    use crossterm::event::KeyModifiers;
+    use rstest::rstest;
+
+    use super::*;
+    use crate::{
+        config::{search::SearchableTag, Leak},
+        mpd::mpd_client::FilterKind,
+        shared::{
+            events::{ClientRequest, WorkRequest},
+            mpd_query::MpdQuery,
+        },
+        tests::fixtures::{
+            app_context, client_request_channel, config,
+            mpd_client::{client, TestMpdClient},
+            work_request_channel,
+        },
+        ui::CONFIRM_QUEUE_SIZE,
+    };
+
+    fn song(title: &str) -> Song {
+        Song {
+            file: format!("{title}.mp3"),
+            metadata: HashMap::from([("title".to_owned(), title.to_owned())]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn search_command_is_the_raw_mpd_query() {
+        let filter = [
+            ("artist", "mrs singer".to_owned(), FilterKind::Contains),
+            ("album", "the greatest".to_owned(), FilterKind::Exact),
+        ];
+
+        assert_eq!(
+            search_command(&filter, false),
+            "search \"((artist =~ '.*mrs singer.*') AND (album == 'the greatest'))\""
+        );
+        assert!(search_command(&filter, true).starts_with("find \"("));
+    }
+
+    #[rstest]
+    fn refining_narrows_and_clearing_restores(app_context: AppContext) {
+        let config = app_context.config;
+        let mut pane = SearchPane::new(&app_context);
+        pane.songs_dir = Dir::new(vec![song("foo bar"), song("foo baz"), song("qux")]);
+
+        pane.songs_dir
+            .set_filter(Some("foo".to_owned()), config, &app_context.favorites);
+        pane.refine_results(&app_context);
+        let refined = pane.songs_dir.items.iter().map(|s| s.file.as_str()).collect_vec();
+        assert_eq!(refined, ["foo bar.mp3", "foo baz.mp3"]);
+
+        pane.songs_dir
+            .set_filter(Some("baz".to_owned()), config, &app_context.favorites);
+        pane.refine_results(&app_context);
+        let refined = pane.songs_dir.items.iter().map(|s| s.file.as_str()).collect_vec();
+        assert_eq!(refined, ["foo baz.mp3"]);
+
+        assert!(pane.clear_refinement());
+        let restored = pane.songs_dir.items.iter().map(|s| s.file.as_str()).collect_vec();
+        assert_eq!(restored, ["foo bar.mp3", "foo baz.mp3", "qux.mp3"]);
+        assert!(!pane.clear_refinement());
+    }
+
+    #[rstest]
+    fn confirming_filter_keeps_results_until_refined(mut app_context: AppContext) {
+        let key = |code, modifiers| -> KeyEvent { crossterm::event::KeyEvent::new(code, modifiers).into() };
+        let mut pane = SearchPane::new(&app_context);
+        pane.songs_dir = Dir::new(vec![song("foo bar"), song("qux")]);
+        pane.songs_dir
+            .set_filter(Some("foo".to_owned()), app_context.config, &app_context.favorites);
+        pane.phase = Phase::BrowseResults { filter_input_on: true };
+
+        pane.handle_action(&mut key(KeyCode::Enter, KeyModifiers::NONE), &mut app_context)
+            .expect("key to be handled");
+        assert_eq!(pane.songs_dir.items.len(), 2);
+
+        pane.handle_action(&mut key(KeyCode::Char('F'), KeyModifiers::SHIFT), &mut app_context)
+            .expect("key to be handled");
+        let refined = pane.songs_dir.items.iter().map(|s| s.file.as_str()).collect_vec();
+        assert_eq!(refined, ["foo bar.mp3"]);
+    }
+
+    #[rstest]
+    #[case(false, "search_add")]
+    #[case(true, "find_add")]
+    fn replace_queue_clears_then_adds_query(
+        mut client: TestMpdClient,
+        #[case] case_sensitive: bool,
+        #[case] expected_add: &str,
+    ) {
+        client.queue = vec![0, 1, 2];
+
+        replace_queue_with_search(&mut client, &[Filter::new(Tag::Artist, "artist_2")], case_sensitive)
+            .expect("replace to succeed");
+
+        assert_eq!(client.calls.get("clear"), Some(&1));
+        assert_eq!(client.calls.get(expected_add), Some(&1));
+        assert_eq!(client.queue, (30..40).collect_vec());
+    }
+
+    #[rstest]
+    fn fill_time_budget_picks_only_matching_songs(mut client: TestMpdClient) {
+        let budget = Duration::from_secs(30);
+
+        let (songs, total) =
+            fill_queue_by_time_budget(&mut client, &[Filter::new(Tag::Artist, "artist_2")], false, budget).unwrap();
+
+        assert!(total <= budget);
+        assert!(songs
+            .iter()
+            .all(|song| song.artist().is_some_and(|artist| artist == "artist_2")));
+        assert_eq!(client.queue.len(), songs.len());
+    }
+
+    #[rstest]
+    fn fill_time_budget_picks_from_whole_library_without_filter(mut client: TestMpdClient) {
+        let budget = client.songs.iter().filter_map(|song| song.duration).sum::<Duration>();
+
+        let (songs, total) = fill_queue_by_time_budget(&mut client, &[], false, budget).unwrap();
+
+        assert_eq!(total, budget);
+        assert_eq!(
+            songs.len(),
+            client
+                .songs
+                .iter()
+                .filter(|song| song.duration.is_some_and(|d| !d.is_zero()))
+                .count()
+        );
+        assert_eq!(client.queue.len(), songs.len());
+    }
+
+    #[rstest]
+    #[case(Some(10), true)]
+    #[case(None, false)]
+    fn add_all_found_songs_checks_queue_size(
+        work_request_channel: (Sender<WorkRequest>, Receiver<WorkRequest>),
+        client_request_channel: (Sender<ClientRequest>, Receiver<ClientRequest>),
+        mut config: Config,
+        #[case] queue_size_warning: Option<usize>,
+        #[case] checks_queue_size: bool,
+    ) {
+        let rx = client_request_channel.1.clone();
+        let mut app_context = app_context(work_request_channel, client_request_channel);
+        config.search.tags = &[SearchableTag {
+            label: "Artist",
+            value: "artist",
+        }];
+        config.queue_size_warning = queue_size_warning;
+        app_context.config = config.leak();
+        let mut pane = SearchPane::new(&app_context);
+        pane.inputs.textbox_inputs[0].value = "artist".to_owned();
+
+        pane.search_add(&app_context);
+
+        let request = rx.recv_timeout(Duration::from_millis(100)).unwrap();
+        assert_eq!(
+            matches!(
+                request,
+                ClientRequest::Query(MpdQuery {
+                    id: CONFIRM_QUEUE_SIZE,
+                    ..
+                })
+            ),
+            checks_queue_size
+        );
+    }
+
+    #[rstest]
+    fn before_show_seeds_configured_tag_and_kind(mut app_context: AppContext, mut config: Config) {
+        config.search.tags = &[
+            SearchableTag {
+                label: "Artist",
+                value: "artist",
+            },
+            SearchableTag {
+                label: "Title",
+                value: "title",
+            },
+        ];
+        config.search.default_tag = Some("title");
+        config.search.mode = FilterKind::Exact;
+        app_context.config = config.leak();
+        let mut pane = SearchPane::new(&app_context);
+
+        pane.before_show(&app_context).expect("before_show to succeed");
+
+        let FocusedInputGroup::Textboxes(textbox) = pane.inputs.focused() else {
+            panic!("Expected a textbox to be focused");
+        };
+        assert_eq!(textbox.filter_key, "title");
+        assert!(pane.inputs.filter_inputs.iter().any(|input| input.variant
+            == FilterInputVariant::SelectFilterKind {
+                value: FilterKind::Exact
+            }));
+    }
+}
@@ -10,9 +10,10 @@ use ratatui::{
 use crate::{
     config::{
         keys::CommonAction,
-        tabs::{Pane, PaneOrSplitWithPosition, SubPaneWithPosition},
+        tabs::{Pane, PaneOrSplitWithPosition, PaneType, SubPaneWithPosition},
     },
     context::AppContext,
+    mpd::commands::Song,
     shared::{
         geometry::Point,
         id::Id,
@@ -21,7 +22,7 @@ use crate::{
     },
 };
 
-use super::{Pane as _, PaneContainer, Panes};
+use super::{key_hints::KeyHint, Pane as _, PaneContainer, Panes};
 
 #[derive(Debug)]
 pub struct TabScreen {
@@ -60,6 +61,7 @@ macro_rules! screen_call {
             Panes::AlbumArtists(s) => s.$fn($($param),+),
             Panes::Albums(s) => s.$fn($($param),+),
             Panes::Playlists(s) => s.$fn($($param),+),
+            Panes::RecentlyAdded(s) => s.$fn($($param),+),
             Panes::Search(s) => s.$fn($($param),+),
             Panes::AlbumArt(s) => s.$fn($($param),+),
             Panes::Lyrics(s) => s.$fn($($param),+),
@@ -234,12 +236,58 @@ impl TabScreen {
         Ok(())
     }
 
+    pub(in crate::ui) fn go_back(&mut self, panes: &mut PaneContainer, context: &AppContext) -> Result<()> {
+        let Some(focused) = self.focused else {
+            return Ok(());
+        };
+
+        let pane = panes.get_mut(focused.pane);
+        screen_call!(pane, go_back(context))
+    }
+
+    /// Focuses the first focusable pane of the given type. Returns false if the tab does not contain it.
+    pub(in crate::ui) fn focus_pane(&mut self, pane_type: PaneType) -> bool {
+        let Some(pane) = self
+            .panes
+            .panes_iter()
+            .find(|pane| pane.pane == pane_type && pane.focusable)
+        else {
+            return false;
+        };
+        self.focused = Some(pane);
+        true
+    }
+
     pub(in crate::ui) fn handle_mouse_event(
         &mut self,
         panes: &mut PaneContainer,
         event: MouseEvent,
         context: &AppContext,
     ) -> Result<()> {
+        // Hover has to be updated in every pane, not only in the focused one, so that the highlight is removed
+        // when the cursor leaves a pane
+        if matches!(event.kind, MouseEventKind::Move) {
+            for pane in self.panes.panes_iter() {
+                let pane = panes.get_mut(pane.pane);
+                screen_call!(pane, handle_mouse_event(event, context))?;
+            }
+            return Ok(());
+        }
+
+        // Scrolling goes to the pane under the cursor regardless of which pane is focused
+        if matches!(event.kind, MouseEventKind::ScrollUp | MouseEventKind::ScrollDown) {
+            let Some(pane) = self
+                .pane_areas
+                .iter()
+                .find(|(_, area)| area.contains(event.into()))
+                .and_then(|(pane_id, _)| self.panes.panes_iter().find(|pane| &pane.id == pane_id))
+            else {
+                return Ok(());
+            };
+            let pane = panes.get_mut(pane.pane);
+            return screen_call!(pane, handle_mouse_event(event, context));
+        }
+
         if matches!(event.kind, MouseEventKind::LeftClick) {
             let Some(pane) = self
                 .pane_areas
@@ -265,6 +313,18 @@ impl TabScreen {
         screen_call!(pane, handle_mouse_event(event, context))
     }
 
+    pub fn key_hints(&self, panes: &mut PaneContainer, context: &AppContext) -> Option<Vec<KeyHint>> {
+        let focused = self.focused?;
+        let pane = panes.get_mut(focused.pane);
+        screen_call!(pane, key_hints(context))
+    }
+
+    pub fn selected_song(&self, panes: &mut PaneContainer, context: &AppContext) -> Option<Song> {
+        let focused = self.focused?;
+        let pane = panes.get_mut(focused.pane);
+        screen_call!(pane, selected_song(context)).cloned()
+    }
+
     pub fn on_hide(&mut self, panes: &mut PaneContainer, context: &AppContext) -> Result<()> {
         for pane in self.panes.panes_iter() {
             let screen = panes.get_mut(pane.pane);
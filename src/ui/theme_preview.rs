@@ -0,0 +1,229 @@
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+    io::Write,
+    path::Path,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use crossbeam::channel::unbounded;
+use crossterm::{
+    queue,
+    style::{Attribute, Colors, Print, ResetColor, SetAttribute, SetColors},
+};
+use ratatui::{
+    backend::{Backend, TestBackend},
+    buffer::Buffer,
+    layout::{Constraint, Layout},
+    style::{Modifier, Style},
+    text::Span,
+    widgets::Block,
+    Frame, Terminal,
+};
+
+use super::{
+    modals::{confirm_modal::ConfirmModal, Modal},
+    panes::{queue::QueuePane, Pane},
+    widgets::header::Header,
+};
+use crate::{
+    config::{theme::UiConfigFile, Config, Leak},
+    context::AppContext,
+    mpd::commands::{volume::Volume, Song, State, Status},
+    shared::{favorites::Favorites, lrc::LrcIndex},
+};
+
+const PREVIEW_WIDTH: u16 = 100;
+const PREVIEW_HEIGHT: u16 = 24;
+const MODIFIER_ATTRIBUTES: [(Modifier, Attribute); 6] = [
+    (Modifier::BOLD, Attribute::Bold),
+    (Modifier::DIM, Attribute::Dim),
+    (Modifier::ITALIC, Attribute::Italic),
+    (Modifier::UNDERLINED, Attribute::Underlined),
+    (Modifier::REVERSED, Attribute::Reverse),
+    (Modifier::CROSSED_OUT, Attribute::CrossedOut),
+];
+
+/// Renders a single frame with the theme at `path` to the terminal and exits. Nothing is read from MPD, the frame
+/// is filled with made up songs instead.
+#[allow(dead_code)]
+pub fn preview_file(path: &Path) -> Result<()> {
+    let file =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read theme file '{}'", path.display()))?;
+    let config = config_from_str(&file).with_context(|| format!("Invalid theme file '{}'", path.display()))?;
+    let mut context = preview_context(config.leak());
+
+    let width = crossterm::terminal::size().map_or(PREVIEW_WIDTH, |(cols, _)| cols);
+    let mut terminal = Terminal::new(TestBackend::new(width, PREVIEW_HEIGHT))?;
+    draw(&mut terminal, &mut context)?;
+
+    let mut stdout = std::io::stdout().lock();
+    write_buffer(terminal.backend().buffer(), &mut stdout)?;
+    stdout.flush()?;
+
+    Ok(())
+}
+
+/// Writes the buffer line by line with ANSI styling. Unlike drawing through a regular terminal backend this keeps
+/// the preview in the scrollback and works when the output is piped.
+fn write_buffer(buffer: &Buffer, writer: &mut impl Write) -> Result<()> {
+    for y in 0..buffer.area.height {
+        let mut skip = 0;
+        for x in 0..buffer.area.width {
+            let cell = &buffer[(x, y)];
+            if skip > 0 {
+                skip -= 1;
+                continue;
+            }
+            skip = Span::raw(cell.symbol()).width().saturating_sub(1);
+
+            queue!(
+                writer,
+                SetAttribute(Attribute::Reset),
+                SetColors(Colors::new(cell.fg.into(), cell.bg.into()))
+            )?;
+            for (modifier, attribute) in MODIFIER_ATTRIBUTES {
+                if cell.modifier.contains(modifier) {
+                    queue!(writer, SetAttribute(attribute))?;
+                }
+            }
+            queue!(writer, Print(cell.symbol()))?;
+        }
+        queue!(writer, SetAttribute(Attribute::Reset), ResetColor, Print("\n"))?;
+    }
+
+    Ok(())
+}
+
+fn config_from_str(theme: &str) -> Result<Config> {
+    let theme: UiConfigFile = ron::de::from_str(theme)?;
+    Ok(Config {
+        theme: theme.try_into()?,
+        ..Config::default()
+    })
+}
+
+fn draw<B: Backend>(terminal: &mut Terminal<B>, context: &mut AppContext) -> Result<()> {
+    let mut result = Ok(());
+    terminal.draw(|frame| result = render(frame, context))?;
+    result
+}
+
+/// Renders the header, queue, progress bar or status line and a confirm modal on top, roughly what the user sees while using rmpc
+fn render(frame: &mut Frame, context: &mut AppContext) -> Result<()> {
+    let config = context.config;
+    if let Some(bg_color) = config.theme.background_color {
+        frame.render_widget(Block::default().style(Style::default().bg(bg_color)), frame.area());
+    }
+
+    let [header_area, content_area, bar_area] = Layout::vertical([
+        Constraint::Length(u16::try_from(config.theme.header.rows.len())?),
+        Constraint::Percentage(100),
+        Constraint::Min(1),
+    ])
+    .spacing(config.theme.section_gap)
+    .areas(frame.area());
+
+    frame.render_widget(Header::new(context), header_area);
+
+    let mut queue = QueuePane::new(context);
+    queue.before_show(context)?;
+    queue.render(frame, content_area, context)?;
+
+    if let Some(status_line) = &config.theme.status_line {
+        Header::new(context).render_row(status_line, bar_area, frame.buffer_mut());
+    } else {
+        let progress = context.status.elapsed.as_secs_f32() / context.status.duration.as_secs_f32();
+        frame.render_widget(config.as_styled_progress_bar().value(progress), bar_area);
+    }
+
+    ConfirmModal::new(context)
+        .message("This is how modals look with this theme.")
+        .on_confirm(|_| Ok(()))
+        .render(frame, context)?;
+
+    Ok(())
+}
+
+fn preview_context(config: &'static Config) -> AppContext {
+    let queue: Vec<Song> = [
+        ("Aurora Lane", "Harbor Lights", "Night Ferry", 241),
+        ("Aurora Lane", "Harbor Lights", "Salt and Static", 215),
+        ("Aurora Lane", "Harbor Lights", "Low Tide", 198),
+        ("The Quiet Hours", "Paper Moons", "Lanterns", 305),
+        ("The Quiet Hours", "Paper Moons", "Northbound", 187),
+    ]
+    .into_iter()
+    .zip(1..)
+    .map(|((artist, album, title, duration), id)| Song {
+        id,
+        file: format!("{artist}/{album}/{title}.flac"),
+        duration: Some(Duration::from_secs(duration)),
+        metadata: HashMap::from([
+            ("artist".to_owned(), artist.to_owned()),
+            ("album".to_owned(), album.to_owned()),
+            ("title".to_owned(), title.to_owned()),
+        ]),
+    })
+    .collect();
+    let status = Status {
+        volume: Volume::new(70),
+        repeat: true,
+        state: State::Play,
+        playlistlength: u32::try_from(queue.len()).unwrap_or_default(),
+        song: Some(1),
+        songid: Some(2),
+        elapsed: Duration::from_secs(83),
+        duration: Duration::from_secs(215),
+        bitrate: Some(1411),
+        output: Some("Speakers".to_owned()),
+        ..Default::default()
+    };
+
+    AppContext {
+        config,
+        draw_borders: config.theme.draw_borders,
+        status,
+        queue,
+        supported_commands: HashSet::new(),
+        supported_tags: HashSet::new(),
+        app_event_sender: unbounded().0,
+        work_sender: unbounded().0,
+        client_request_sender: unbounded().0,
+        needs_render: Cell::new(false),
+        lrc_index: LrcIndex::default(),
+        favorites: Favorites::default(),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use ratatui::{backend::TestBackend, Terminal};
+
+    use super::{config_from_str, draw, preview_context};
+    use crate::config::Leak;
+
+    #[test]
+    fn example_theme_preview_renders() {
+        let config = config_from_str(include_str!(
+            "../../docs/src/content/docs/next/assets/example_theme.ron"
+        ))
+        .unwrap();
+        let mut context = preview_context(config.leak());
+        let mut terminal = Terminal::new(TestBackend::new(100, 24)).unwrap();
+
+        draw(&mut terminal, &mut context).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        let content = buffer
+            .content()
+            .iter()
+            .map(ratatui::buffer::Cell::symbol)
+            .collect::<String>();
+        assert!(content.contains("Salt and Static"));
+        assert!(content.contains("Northbound"));
+        assert!(content.contains("This is how modals look"));
+    }
+}
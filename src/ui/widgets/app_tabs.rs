@@ -45,10 +45,18 @@ impl AppTabs<'_> {
             .find(|(_, area)| area.contains(position))
             .map(|v| v.0)
     }
+
+    /// Returns the tab whose label was rendered at the given position. Tabs which did not fit into the tab bar
+    /// have an empty area and are never returned.
+    pub fn get_tab_at(&self, position: Position) -> Option<TabName> {
+        self.get_tab_idx_at(position)
+            .and_then(|idx| self.config.tabs.names.get(idx))
+            .copied()
+    }
 }
 
 impl<'a> AppTabs<'a> {
-    pub fn new(active_tab: TabName, config: &'a Config) -> Self {
+    pub fn new(active_tab: TabName, config: &'a Config, draw_borders: bool) -> Self {
         let tab_names = config
             .tabs
             .names
@@ -58,7 +66,7 @@ impl<'a> AppTabs<'a> {
 
         let tabs = Tabs::new(tab_names)
             .divider("")
-            .block(config.as_tabs_block())
+            .block(config.as_tabs_block(draw_borders))
             .style(config.theme.tab_bar.inactive_style)
             .alignment(ratatui::prelude::Alignment::Center)
             .highlight_style(config.theme.tab_bar.active_style);
@@ -70,3 +78,61 @@ impl<'a> AppTabs<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use ratatui::{
+        buffer::Buffer,
+        layout::{Position, Rect},
+    };
+    use rstest::rstest;
+
+    use super::AppTabs;
+    use crate::{context::AppContext, tests::fixtures::app_context};
+
+    #[rstest]
+    fn click_in_tab_range_selects_tab(app_context: AppContext) {
+        let config = app_context.config;
+        let mut subject = AppTabs::new(config.tabs.names[0], config, config.theme.draw_borders);
+        let area = Rect::new(0, 0, 200, 3);
+        let mut buf = Buffer::empty(area);
+
+        subject.render(area, &mut buf);
+
+        for (idx, name) in config.tabs.names.iter().enumerate() {
+            let tab_area = subject.tabs.areas[idx];
+            assert!(tab_area.width > 0);
+            for x in tab_area.left()..tab_area.right() {
+                assert_eq!(subject.get_tab_at(Position::new(x, tab_area.y)), Some(*name));
+            }
+        }
+    }
+
+    #[rstest]
+    fn click_outside_of_tabs_selects_nothing(app_context: AppContext) {
+        let config = app_context.config;
+        let mut subject = AppTabs::new(config.tabs.names[0], config, config.theme.draw_borders);
+        let area = Rect::new(0, 0, 200, 3);
+        let mut buf = Buffer::empty(area);
+
+        subject.render(area, &mut buf);
+
+        assert_eq!(subject.get_tab_at(Position::new(0, 1)), None);
+        assert_eq!(subject.get_tab_at(Position::new(100, 0)), None);
+    }
+
+    #[rstest]
+    fn tabs_which_do_not_fit_are_not_clickable(app_context: AppContext) {
+        let config = app_context.config;
+        let mut subject = AppTabs::new(config.tabs.names[0], config, config.theme.draw_borders);
+        let area = Rect::new(0, 0, 30, 3);
+        let mut buf = Buffer::empty(area);
+
+        subject.render(area, &mut buf);
+
+        let last_idx = config.tabs.names.len() - 1;
+        assert_eq!(subject.tabs.areas[last_idx], Rect::default());
+        assert_eq!(subject.get_tab_at(Position::new(0, 0)), None);
+        assert_eq!(subject.get_tab_at(Position::new(1, 1)), Some(config.tabs.names[0]));
+    }
+}
@@ -1,8 +1,10 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, List, ListItem, Padding, StatefulWidget};
+use ratatui::widgets::{Block, Borders, List, ListItem, Padding, Paragraph, StatefulWidget, Wrap};
 use style::Styled;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use crate::config::Config;
+use crate::shared::favorites::Favorites;
 use crate::ui::dirstack::{Dir, DirStack, DirStackItem};
 
 #[derive(Debug)]
@@ -10,27 +12,82 @@ pub struct Browser<T: std::fmt::Debug + DirStackItem + Clone + Send> {
     state_type_marker: std::marker::PhantomData<T>,
     widths: Vec<u16>,
     config: &'static Config,
+    favorites: Favorites,
+    draw_borders: bool,
     border_style: Style,
     pub areas: [Rect; 3],
     filter_input_active: bool,
+    empty_message: Option<&'static str>,
+    root_name: &'static str,
 }
 
 impl<T: std::fmt::Debug + DirStackItem + Clone + Send> Browser<T> {
-    pub fn new(config: &'static Config) -> Self {
+    /// The `root_name` is shown as the first part of the breadcrumb
+    pub fn new(config: &'static Config, root_name: &'static str) -> Self {
         Self {
             state_type_marker: std::marker::PhantomData,
             widths: config.theme.column_widths.to_vec(),
             config,
+            favorites: Favorites::default(),
+            draw_borders: config.theme.draw_borders,
             border_style: config.as_border_style(),
             areas: [Rect::default(); 3],
             filter_input_active: false,
+            empty_message: None,
+            root_name,
         }
     }
 
+    pub fn set_draw_borders(&mut self, value: bool) -> &mut Self {
+        self.draw_borders = value;
+        self
+    }
+
+    pub fn set_favorites(&mut self, value: &Favorites) -> &mut Self {
+        self.favorites = value.clone();
+        self
+    }
+
     pub fn set_filter_input_active(&mut self, value: bool) -> &mut Self {
         self.filter_input_active = value;
         self
     }
+
+    /// Message shown in place of the items when the top level of the browser turned out to be empty, for example
+    /// because the MPD database has not been created yet
+    pub fn set_empty_message(&mut self, message: Option<&'static str>) -> &mut Self {
+        self.empty_message = message;
+        self
+    }
+}
+
+const BREADCRUMB_SEPARATOR: &str = " > ";
+
+/// Path to the current level of the browser, such as `Albums > The Wall`. When it does not fit into `max_width`
+/// columns it is truncated from the left so that the deepest levels stay visible.
+fn breadcrumb(root_name: &str, path: &[String], max_width: usize, ellipsis: &str) -> String {
+    let result = std::iter::once(root_name)
+        .chain(path.iter().map(String::as_str))
+        .collect::<Vec<_>>()
+        .join(BREADCRUMB_SEPARATOR);
+    if result.width() <= max_width {
+        return result;
+    }
+
+    let mut available = max_width.saturating_sub(ellipsis.width());
+    let start = result
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| match available.checked_sub(c.width().unwrap_or(0)) {
+            Some(rest) => {
+                available = rest;
+                true
+            }
+            None => false,
+        })
+        .last()
+        .map_or(result.len(), |(idx, _)| idx);
+    format!("{ellipsis}{}", &result[start..])
 }
 const MIDDLE_COLUMN_SYMBOLS: symbols::border::Set = symbols::border::Set {
     top_right: symbols::line::NORMAL.horizontal_down,
@@ -51,7 +108,7 @@ where
     type State = DirStack<T>;
 
     fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer, state: &mut Self::State) {
-        let scrollbar_margin = if self.config.theme.draw_borders {
+        let scrollbar_margin = if self.draw_borders {
             let scrollbar_track = self.config.theme.scrollbar.symbols[0];
             Margin {
                 vertical: 0,
@@ -63,9 +120,30 @@ where
                 horizontal: 0,
             }
         };
-        let previous = state.previous().to_list_items(self.config);
-        let current = state.current().to_list_items(self.config);
-        let preview = state.preview().cloned();
+        let area = if self.config.theme.show_breadcrumbs && area.height > 1 {
+            let [breadcrumb_area, area] = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(area);
+            let text = breadcrumb(
+                self.root_name,
+                state.path(),
+                breadcrumb_area.width.into(),
+                self.config.theme.symbols.ellipsis,
+            );
+            let breadcrumb = Paragraph::new(text).style(self.config.theme.borders_style);
+            ratatui::widgets::Widget::render(breadcrumb, breadcrumb_area, buf);
+            area
+        } else {
+            area
+        };
+        let is_top_level = state.path().is_empty();
+        let previous = state.previous().to_list_items(self.config, &self.favorites);
+        let mut current = state.current().to_list_items(self.config, &self.favorites);
+        if let Some(item) = state.current().state.get_hovered().and_then(|idx| current.get_mut(idx)) {
+            let style = Styled::style(item).patch(self.config.theme.hovered_item_style);
+            *item = item.clone().set_style(style);
+        }
+        let preview = state
+            .preview()
+            .map(|preview| preview.iter().skip(state.preview_offset()).cloned().collect::<Vec<_>>());
 
         let [previous_area, current_area, preview_area] = *Layout::horizontal([
             Constraint::Percentage(self.widths[0]),
@@ -91,7 +169,7 @@ where
             prev_state.set_viewport_len(Some(previous_area.height.into()));
 
             let mut previous = List::new(previous).style(self.config.as_text_style());
-            let mut block = if self.config.theme.draw_borders {
+            let mut block = if self.draw_borders {
                 Block::default()
                     .borders(Borders::RIGHT)
                     .border_style(self.border_style)
@@ -110,12 +188,14 @@ where
             self.areas[0] = inner_block;
             ratatui::widgets::StatefulWidget::render(previous, inner_block, buf, prev_state.as_render_state_ref());
             ratatui::widgets::Widget::render(block, previous_area, buf);
-            ratatui::widgets::StatefulWidget::render(
-                self.config.as_styled_scrollbar(),
-                previous_area.inner(scrollbar_margin),
-                buf,
-                prev_state.as_scrollbar_state_ref(),
-            );
+            if let Some(scrollbar) = self.config.as_styled_scrollbar(prev_state) {
+                ratatui::widgets::StatefulWidget::render(
+                    scrollbar,
+                    previous_area.inner(scrollbar_margin),
+                    buf,
+                    prev_state.as_scrollbar_state_ref(),
+                );
+            }
         }
         if self.widths[1] > 0 {
             let title = state
@@ -129,7 +209,7 @@ where
 
             let block = {
                 let mut b = Block::default();
-                if self.config.theme.draw_borders {
+                if self.draw_borders {
                     b = b
                         .borders(Borders::RIGHT)
                         .border_style(self.border_style)
@@ -146,14 +226,60 @@ where
 
             let inner_block = block.inner(current_area);
             ratatui::widgets::StatefulWidget::render(current, inner_block, buf, state.as_render_state_ref());
+            if let Some(message) = self.empty_message.filter(|_| items.is_empty() && is_top_level) {
+                let message = Paragraph::new(message)
+                    .wrap(Wrap { trim: true })
+                    .style(self.config.as_text_style());
+                ratatui::widgets::Widget::render(message, inner_block, buf);
+            }
             self.areas[1] = inner_block;
             ratatui::widgets::Widget::render(block, current_area, buf);
-            ratatui::widgets::StatefulWidget::render(
-                self.config.as_styled_scrollbar(),
-                current_area.inner(scrollbar_margin),
-                buf,
-                state.as_scrollbar_state_ref(),
-            );
+            if let Some(scrollbar) = self.config.as_styled_scrollbar(state) {
+                ratatui::widgets::StatefulWidget::render(
+                    scrollbar,
+                    current_area.inner(scrollbar_margin),
+                    buf,
+                    state.as_scrollbar_state_ref(),
+                );
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::breadcrumb;
+
+    #[test]
+    fn breadcrumb_at_top_level() {
+        assert_eq!(breadcrumb("Albums", &[], 80, "..."), "Albums");
+    }
+
+    #[test]
+    fn breadcrumb_matches_path() {
+        let path = vec!["Pink Floyd".to_owned(), "The Wall".to_owned()];
+
+        assert_eq!(
+            breadcrumb("Artists", &path, 80, "..."),
+            "Artists > Pink Floyd > The Wall"
+        );
+    }
+
+    #[test]
+    fn breadcrumb_truncated_from_left() {
+        let path = vec!["Pink Floyd".to_owned(), "The Wall".to_owned()];
+
+        assert_eq!(breadcrumb("Artists", &path, 20, "..."), "... Floyd > The Wall");
+    }
+
+    #[test]
+    fn breadcrumb_truncated_by_display_width() {
+        let path = vec!["坂本龍一".to_owned(), "音楽図鑑".to_owned()];
+
+        assert_eq!(breadcrumb("Artists", &path, 29, "..."), "Artists > 坂本龍一 > 音楽図鑑");
+        assert_eq!(breadcrumb("Artists", &path, 14, "..."), "... > 音楽図鑑");
+        assert_eq!(breadcrumb("Artists", &path, 11, "..."), "...音楽図鑑");
+        // A wide character which does not fit is left out completely
+        assert_eq!(breadcrumb("Artists", &path, 10, "..."), "...楽図鑑");
+    }
+}
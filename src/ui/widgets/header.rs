@@ -1,19 +1,30 @@
 use either::Either;
+use itertools::Itertools;
 use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
     prelude::{Constraint, Layout},
     style::Style,
-    text::Line,
+    text::{Line, Span},
     widgets::{Block, Widget},
 };
+use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    config::theme::properties::{Property, PropertyKind},
+    config::theme::{
+        properties::{Property, PropertyKind},
+        HeaderConfigRow, HeaderOverflow,
+    },
     context::AppContext,
     mpd::commands::{Song, Status},
 };
 
+/// Number of blank columns between the end and the start of the scrolled text
+const MARQUEE_GAP: usize = 3;
+
 pub struct Header<'a> {
     context: &'a AppContext,
+    marquee_frame: usize,
 }
 
 impl Widget for Header<'_> {
@@ -29,27 +40,8 @@ impl Widget for Header<'_> {
         let row_count = config.theme.header.rows.len();
 
         let layouts = Layout::vertical((0..row_count).map(|_| Constraint::Length(1))).split(area);
-        let song = self.context.find_current_song_in_queue().map(|(_, song)| song);
         for row in 0..row_count {
-            let [left, center, right] = *Layout::horizontal([
-                Constraint::Percentage(30),
-                Constraint::Percentage(40),
-                Constraint::Percentage(30),
-            ])
-            .split(layouts[row]) else {
-                return;
-            };
-            let template = PropertyTemplates(config.theme.header.rows[row].left);
-            let widget = template.format(song, &self.context.status).left_aligned();
-            widget.render(left, buf);
-
-            let template = PropertyTemplates(config.theme.header.rows[row].center);
-            let widget = template.format(song, &self.context.status).centered();
-            widget.render(center, buf);
-
-            let template = PropertyTemplates(config.theme.header.rows[row].right);
-            let widget = template.format(song, &self.context.status).right_aligned();
-            widget.render(right, buf);
+            self.render_row(&config.theme.header.rows[row], layouts[row], buf);
         }
     }
 }
@@ -70,6 +62,202 @@ impl<'a> PropertyTemplates<'a> {
 
 impl<'a> Header<'a> {
     pub fn new(context: &'a AppContext) -> Self {
-        Self { context }
+        Self {
+            context,
+            marquee_frame: 0,
+        }
+    }
+
+    /// Number of frames rendered since the marquee was last reset
+    pub fn marquee_frame(mut self, frame: usize) -> Self {
+        self.marquee_frame = frame;
+        self
+    }
+
+    /// Renders a single row of properties, also used for the status line in the bottom bar
+    pub fn render_row(&self, row: &HeaderConfigRow, area: Rect, buf: &mut Buffer) {
+        let [left, center, right] = *Layout::horizontal([
+            Constraint::Percentage(30),
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+        ])
+        .split(area) else {
+            return;
+        };
+        let song = self.context.find_current_song_in_queue().map(|(_, song)| song);
+
+        let template = PropertyTemplates(row.left);
+        let widget = self.fit(template.format(song, &self.context.status), left.width);
+        widget.left_aligned().render(left, buf);
+
+        let template = PropertyTemplates(row.center);
+        let widget = self.fit(template.format(song, &self.context.status), center.width);
+        widget.centered().render(center, buf);
+
+        let template = PropertyTemplates(row.right);
+        let widget = self.fit(template.format(song, &self.context.status), right.width);
+        widget.right_aligned().render(right, buf);
+    }
+
+    fn fit<'line>(&self, line: Line<'line>, width: u16) -> Line<'line> {
+        let width = width as usize;
+        let graphemes = line.styled_graphemes(Style::default()).collect_vec();
+        let text_width = graphemes.iter().map(|g| g.symbol.width()).sum::<usize>();
+        if text_width <= width {
+            return line;
+        }
+
+        match self.context.config.theme.header.overflow {
+            HeaderOverflow::Off => line,
+            HeaderOverflow::Ellipsis => {
+                let ellipsis = self.context.config.theme.symbols.ellipsis;
+                let ellipsis_style = graphemes.last().map(|g| g.style).unwrap_or_default();
+                let mut remaining = width.saturating_sub(ellipsis.width());
+                let mut spans = graphemes
+                    .into_iter()
+                    .map_while(|g| {
+                        remaining = remaining.checked_sub(g.symbol.width())?;
+                        Some(Span::styled(g.symbol.to_owned(), g.style))
+                    })
+                    .collect_vec();
+                spans.push(Span::styled(ellipsis, ellipsis_style));
+                Line::from(spans)
+            }
+            HeaderOverflow::Marquee => {
+                let offset = marquee_offset(self.marquee_frame, text_width, graphemes.len(), width);
+                let mut remaining = width;
+                let spans = graphemes
+                    .iter()
+                    .map(|g| Span::styled(g.symbol.to_owned(), g.style))
+                    .chain(std::iter::repeat(Span::raw(" ")).take(MARQUEE_GAP))
+                    .cycle()
+                    .skip(offset)
+                    .map_while(|span| {
+                        remaining = remaining.checked_sub(span.width())?;
+                        Some(span)
+                    })
+                    .collect_vec();
+                Line::from(spans)
+            }
+        }
+    }
+}
+
+/// Computes by how many graphemes the text should be scrolled in the given frame. Text whose width in columns fits
+/// into the area is never scrolled. Otherwise the offset wraps around after all `text_len` graphemes of the text and
+/// the gap after it have scrolled past.
+fn marquee_offset(frame: usize, text_width: usize, text_len: usize, area_width: usize) -> usize {
+    if text_width <= area_width {
+        0
+    } else {
+        frame % (text_len + MARQUEE_GAP)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use ratatui::{
+        buffer::{Buffer, Cell},
+        layout::Rect,
+        text::Line,
+    };
+    use rstest::rstest;
+    use test_case::test_case;
+
+    use super::{marquee_offset, Header, MARQUEE_GAP};
+    use crate::{
+        config::{
+            theme::{
+                properties::{Property, PropertyKind, PropertyKindOrText, StatusProperty},
+                HeaderConfigRow, HeaderOverflow,
+            },
+            Leak,
+        },
+        context::AppContext,
+        mpd::commands::Volume,
+        tests::fixtures::app_context,
+    };
+
+    #[test_case(0, 0; "first frame")]
+    #[test_case(5, 5; "scrolls by one column per frame")]
+    #[test_case(22, 22; "last offset before wrap")]
+    #[test_case(23, 0; "wraps after text and gap")]
+    #[test_case(24, 1; "continues after wrap")]
+    #[test_case(50, 4; "multiple wraps")]
+    fn marquee_offset_wraps_around(frame: usize, expected: usize) {
+        let text_width = 23 - MARQUEE_GAP;
+
+        assert_eq!(marquee_offset(frame, text_width, text_width, 10), expected);
+    }
+
+    #[test]
+    fn marquee_offset_is_zero_when_text_fits() {
+        for frame in 0..50 {
+            assert_eq!(marquee_offset(frame, 10, 10, 10), 0);
+        }
+    }
+
+    #[rstest]
+    #[case(HeaderOverflow::Ellipsis, "日本...")]
+    #[case(HeaderOverflow::Marquee, "日本語")]
+    fn wide_characters_are_fit_by_columns(
+        mut app_context: AppContext,
+        #[case] overflow: HeaderOverflow,
+        #[case] expected: &str,
+    ) {
+        let mut config = app_context.config.clone();
+        config.theme.header.overflow = overflow;
+        app_context.config = config.leak();
+
+        let line = Header::new(&app_context).fit(Line::from("日本語のタイトル"), 7);
+
+        assert_eq!(line.to_string(), expected);
+        assert!(line.width() <= 7);
+    }
+
+    #[rstest]
+    fn status_line_renders_current_state(mut app_context: AppContext) {
+        let property = |kind: PropertyKindOrText<'static, PropertyKind>| -> &'static Property<'static, PropertyKind> {
+            Box::leak(Box::new(Property {
+                kind,
+                style: None,
+                default: None,
+            }))
+        };
+        let status = |prop: StatusProperty| property(PropertyKindOrText::Property(PropertyKind::Status(prop)));
+        let row = HeaderConfigRow {
+            left: vec![
+                property(PropertyKindOrText::Text("vol ")),
+                status(StatusProperty::Volume),
+                property(PropertyKindOrText::Text(" repeat ")),
+                status(StatusProperty::Repeat),
+                property(PropertyKindOrText::Text(" consume ")),
+                status(StatusProperty::Consume),
+            ]
+            .leak(),
+            center: vec![
+                status(StatusProperty::Elapsed),
+                property(PropertyKindOrText::Text("/")),
+                status(StatusProperty::Duration),
+            ]
+            .leak(),
+            right: vec![status(StatusProperty::Output)].leak(),
+        };
+        app_context.status.volume = Volume::new(42);
+        app_context.status.repeat = true;
+        app_context.status.elapsed = Duration::from_secs(83);
+        app_context.status.duration = Duration::from_secs(215);
+        app_context.status.output = Some("Speakers".to_owned());
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+
+        Header::new(&app_context).render_row(&row, area, &mut buf);
+
+        let content = buf.content().iter().map(Cell::symbol).collect::<String>();
+        assert!(content.starts_with("vol 42 repeat On consume Off"), "{content}");
+        assert!(content.contains("1:23/3:35"), "{content}");
+        assert!(content.trim_end().ends_with("Speakers"), "{content}");
     }
 }
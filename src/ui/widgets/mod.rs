@@ -12,6 +12,7 @@ pub mod button;
 pub mod header;
 pub mod input;
 pub mod progress_bar;
+pub mod screensaver;
 pub mod tabs;
 pub mod volume;
 
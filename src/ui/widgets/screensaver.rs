@@ -0,0 +1,44 @@
+use ratatui::{
+    layout::{Constraint, Layout},
+    style::Modifier,
+    text::{Line, Text},
+    widgets::{Paragraph, Widget},
+};
+
+use crate::context::AppContext;
+
+/// Minimal screen showing only the dimmed current song, displayed after a period of inactivity
+pub struct Screensaver<'a> {
+    context: &'a AppContext,
+}
+
+impl<'a> Screensaver<'a> {
+    pub fn new(context: &'a AppContext) -> Self {
+        Self { context }
+    }
+}
+
+impl Widget for Screensaver<'_> {
+    fn render(self, area: ratatui::prelude::Rect, buf: &mut ratatui::prelude::Buffer) {
+        let style = self.context.config.as_text_style().add_modifier(Modifier::DIM);
+        let song = self.context.find_current_song_in_queue().map(|(_, song)| song);
+
+        let mut lines = vec![Line::from(self.context.status.state.as_ref()).style(style)];
+        if let Some(song) = song {
+            lines.insert(
+                0,
+                Line::from(song.title_str().to_owned()).style(style.add_modifier(Modifier::BOLD)),
+            );
+            lines.insert(1, Line::from(song.artist_str().to_owned()).style(style));
+        }
+
+        let [_, text_area, _] = Layout::vertical([
+            Constraint::Fill(1),
+            Constraint::Length(u16::try_from(lines.len()).unwrap_or(u16::MAX)),
+            Constraint::Fill(1),
+        ])
+        .areas(area);
+
+        Paragraph::new(Text::from(lines)).centered().render(text_area, buf);
+    }
+}
@@ -43,7 +43,7 @@ use super::get_line_offset;
 /// # use ratatui::style::{Style, Color};
 /// # use ratatui::text::{Line};
 /// # use ratatui::symbols::{DOT};
-/// let titles = ["Tab1", "Tab2", "Tab3", "Tab4"].iter().cloned().map(Line::from).collect();
+/// let titles: Vec<Line> = ["Tab1", "Tab2", "Tab3", "Tab4"].iter().cloned().map(Line::from).collect();
 /// Tabs::new(titles)
 ///     .block(Block::default().title("Tabs").borders(Borders::ALL))
 ///     .style(Style::default().fg(Color::White))
@@ -188,10 +188,8 @@ impl Widget for &mut Tabs<'_> {
             x = pos.0.saturating_add(1);
             let remaining_width = tabs_area.right().saturating_sub(x);
             if remaining_width == 0 || last_title {
-                if i < self.areas.len() - 2 {
-                    // make the rest of the areas empty since we ran out of space
-                    self.areas[i + 1..].iter_mut().for_each(|a| *a = Rect::default());
-                }
+                // make the rest of the areas empty since we ran out of space
+                self.areas[i + 1..].iter_mut().for_each(|a| *a = Rect::default());
                 break;
             }
             let pos = buf.set_span(x - 1, tabs_area.top(), &self.divider, self.divider.width() as u16);
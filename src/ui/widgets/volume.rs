@@ -52,6 +52,9 @@ impl<'a> Volume<'a> {
 
 impl Volume<'_> {
     pub fn get_str(value: u8) -> String {
+        if value == 0 {
+            return format!("{:<20}", "Volume: muted");
+        }
         let i = std::cmp::min((value / 13) as usize, CHARS.len());
         format!("Volume: {:<7} {:>3}%", CHARS[0..i].join(""), value)
     }
@@ -74,11 +77,10 @@ impl Widget for Volume<'_> {
 
         let left_offset = get_line_offset(20, area.width, self.alignment);
 
-        let i = self.value / 13;
         buf.set_string(
             area.left() + left_offset,
             area.top(),
-            format!("Volume: {:<7} {:>3}%", CHARS[0..i as usize].join(""), self.value),
+            Self::get_str(self.value),
             self.style,
         );
     }